@@ -0,0 +1,68 @@
+// Run with 'cargo bench'; criterion keeps the previous run around, so changes show up as
+// regressions or improvements against it.
+
+#[macro_use]
+extern crate criterion;
+extern crate bip_39;
+
+use bip_39::prelude::*;
+use criterion::{black_box, Criterion};
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn mnemonic_generation(c: &mut Criterion) {
+    c.bench_function("generate 24 word mnemonic", |b| {
+        b.iter(|| Config::generate_from(MnemonicListSize::TwentyFour, String::new()))
+    });
+}
+
+fn phrase_parsing(c: &mut Criterion) {
+    c.bench_function("parse 12 word mnemonic", |b| {
+        b.iter(|| Config::from_phrase(black_box(MNEMONIC.to_string()), String::new()))
+    });
+}
+
+fn seed_derivation(c: &mut Criterion) {
+    let config = Config::from_phrase(MNEMONIC, "TREZOR").unwrap();
+    c.bench_function("derive seed", |b| b.iter(|| black_box(&config).to_seed()));
+}
+
+fn child_key_derivation(c: &mut Criterion) {
+    let config = Config::from_phrase(MNEMONIC, "").unwrap();
+    let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+    let path: DerivationPath = "m/84'/0'/0'/0/0".parse().unwrap();
+
+    c.bench_function("derive root key", |b| {
+        let seed = config.to_seed();
+        b.iter(|| ExtendedPrivKey::new_master(black_box(seed.as_bytes()), Network::Mainnet))
+    });
+    c.bench_function("derive m/84'/0'/0'/0/0", |b| {
+        b.iter(|| root.derive_path(black_box(&path)))
+    });
+
+    // A wallet scan's worth of receive keys, one at a time and as a batch:
+    let chain = root
+        .derive_path(&"m/84'/0'/0'/0".parse().unwrap())
+        .unwrap()
+        .to_extended_pub_key();
+    c.bench_function("derive 1000 receive keys one by one", |b| {
+        b.iter(|| {
+            (0..1000)
+                .map(|index| chain.derive_child(ChildNumber::normal(index).unwrap()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+    });
+    c.bench_function("derive 1000 receive keys as a batch", |b| {
+        b.iter(|| chain.derive_children(black_box(0..1000)))
+    });
+}
+
+criterion_group!(
+    benches,
+    mnemonic_generation,
+    phrase_parsing,
+    seed_derivation,
+    child_key_derivation
+);
+criterion_main!(benches);
@@ -0,0 +1,261 @@
+// Packs each wordlist into a compact blob at build time, which 'Language::word_list' unpacks into
+// a '[&str; 2048]' the first time it's asked for.  Most lists are sorted, so neighbouring words
+// share most of their letters; "front coding" stores each word as the number of bytes it shares
+// with the one before, then the rest of it, then a newline.  That, and not embedding 2,048 string
+// pointers per list, keeps several hundred kilobytes out of multilingual (and wasm) builds.
+//
+// Only the wordlists picked with 'langs-*' features (plus the default language's) are embedded,
+// the rest are 'None'; with no 'langs-*' feature at all, every wordlist is.
+//
+// Each list is checked against what BIP-39 promises of it before it's embedded, so a bad edit to
+// one fails the build instead of making mnemonics other wallets can't read.
+
+extern crate unicode_normalization;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+const WORDLISTS: [&str; 10] = [
+    "chinese_simplified",
+    "chinese_traditional",
+    "czech",
+    "english",
+    "french",
+    "italian",
+    "japanese",
+    "korean",
+    "portuguese",
+    "spanish",
+];
+
+// The lists in code point order (the rest follow their language's own collation, or none):
+const SORTED: [&str; 4] = ["english", "italian", "korean", "portuguese"];
+// The lists whose words are told apart by their first four letters, accents aside:
+const UNIQUE_PREFIXES: [&str; 6] = [
+    "czech",
+    "english",
+    "french",
+    "italian",
+    "portuguese",
+    "spanish",
+];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR for build scripts");
+    let mut generated = String::new();
+    // The longest word in any embedded list, in bytes, so phrases fit a fixed-size buffer:
+    let mut max_word_len = 0;
+
+    // Cargo sets CARGO_FEATURE_<NAME> for each enabled feature, '-' turned into '_':
+    let enabled = |feature: &str| env::var_os(format!("CARGO_FEATURE_{}", feature)).is_some();
+    let subset = WORDLISTS
+        .iter()
+        .any(|name| enabled(&format!("LANGS_{}", name.to_uppercase())));
+
+    for name in WORDLISTS.iter() {
+        let upper = name.to_uppercase();
+        if subset && !enabled(&format!("LANGS_{}", upper)) && !enabled(&upper) {
+            generated.push_str(&format!("pub static {}: Option<&[u8]> = None;\n", upper));
+            continue;
+        }
+
+        let path = format!("wordlists/{}.txt", name);
+        println!("cargo:rerun-if-changed={}", path);
+
+        let contents = fs::read_to_string(&path).expect("Unable to read the wordlist.");
+        let words = contents.lines().collect::<Vec<&str>>();
+        validate(name, &path, &words);
+        max_word_len = words
+            .iter()
+            .map(|word| word.len())
+            .fold(max_word_len, usize::max);
+
+        let blob = Path::new(&out_dir).join(format!("{}.bin", name));
+        fs::write(&blob, front_code(&words)).expect("Unable to write the packed wordlist.");
+        generated.push_str(&format!(
+            "pub static {}: Option<&[u8]> = Some(include_bytes!({:?}));\n",
+            upper, blob
+        ));
+    }
+
+    generated.push_str(&format!(
+        "pub const MAX_WORD_LEN: usize = {};\n",
+        max_word_len
+    ));
+    fs::write(Path::new(&out_dir).join("wordlists.rs"), generated)
+        .expect("Unable to write the generated wordlists.");
+
+    if enabled("FFI") {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-env-changed=BIP39_HEADER");
+        let source = fs::read_to_string("src/ffi.rs").expect("Unable to read src/ffi.rs.");
+        let header = c_header(&source);
+        fs::write(Path::new(&out_dir).join("bip39.h"), &header)
+            .expect("Unable to write the C header.");
+        if let Some(path) = env::var_os("BIP39_HEADER") {
+            if let Some(dir) = Path::new(&path).parent() {
+                fs::create_dir_all(dir).expect("Unable to make the C header's directory.");
+            }
+            fs::write(&path, &header).expect("Unable to write the C header.");
+        }
+    }
+}
+
+fn validate(name: &str, path: &str, words: &[&str]) {
+    assert_eq!(words.len(), 2048, "{} must have 2,048 words", path);
+    for word in words {
+        assert!(
+            !word.is_empty() && !word.contains(char::is_whitespace),
+            "{} has a blank line or a word with spaces in it: {:?}",
+            path,
+            word
+        );
+        // BIP-39 normalizes mnemonics to NFKD before PBKDF2, so the words have to be in it too:
+        assert!(
+            word.nfkd().eq(word.chars()),
+            "{}: {:?} isn't NFKD normalized",
+            path,
+            word
+        );
+    }
+
+    let mut unique = words.to_vec();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), 2048, "{} has a word in it twice", path);
+
+    if SORTED.contains(&name) {
+        for pair in words.windows(2) {
+            assert!(pair[0] < pair[1], "{}: {:?} is out of order", path, pair[1]);
+        }
+    }
+
+    if UNIQUE_PREFIXES.contains(&name) {
+        let mut prefixes: Vec<String> = words
+            .iter()
+            .map(|word| {
+                word.chars()
+                    .filter(|&c| !is_combining_mark(c))
+                    .take(4)
+                    .collect()
+            })
+            .collect();
+        prefixes.sort_unstable();
+        for pair in prefixes.windows(2) {
+            assert!(
+                pair[0] != pair[1],
+                "{}: two words start with {:?}",
+                path,
+                pair[0]
+            );
+        }
+    }
+}
+
+fn front_code(words: &[&str]) -> Vec<u8> {
+    let mut packed = Vec::new();
+    let mut previous = "";
+    for word in words {
+        // Shared whole characters only, so the rest of the word is valid UTF-8 on its own:
+        let shared: usize = previous
+            .chars()
+            .zip(word.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum();
+        assert!(shared < 256 && !word.contains('\n'));
+        packed.push(shared as u8);
+        packed.extend(&word.as_bytes()[shared..]);
+        packed.push(b'\n');
+        previous = word;
+    }
+    packed
+}
+
+// ffi.rs's header: a declaration for each of its 'extern "C"' functions, under the comment above
+// it.  Only the handful of types ffi.rs uses are known, so a new one fails the build here rather
+// than making a header that doesn't match the library.
+fn c_header(source: &str) -> String {
+    let mut header = String::from(
+        "// Generated by build.rs from src/ffi.rs; see there.\n\n\
+         #ifndef BIP39_H\n#define BIP39_H\n\n\
+         #include <stdint.h>\n\n\
+         #ifdef __cplusplus\nextern \"C\" {\n#endif\n",
+    );
+    let mut comment = Vec::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("//") {
+            comment.push(line);
+            continue;
+        }
+        if line.starts_with("pub unsafe extern \"C\" fn ")
+            || line.starts_with("pub extern \"C\" fn ")
+        {
+            // (rustfmt breaks long signatures over several lines)
+            let mut signature = line.to_string();
+            while !signature.ends_with('{') {
+                signature.push_str(lines.next().expect("An unfinished signature.").trim());
+            }
+            let (name, rest) = signature
+                .split_once(" fn ")
+                .unwrap()
+                .1
+                .split_once('(')
+                .unwrap();
+            let (params, returns) = rest.rsplit_once(')').unwrap();
+            let params = params
+                .split(',')
+                .filter(|param| !param.trim().is_empty())
+                .map(|param| {
+                    let (name, rust) = param.split_once(':').unwrap();
+                    c_declaration(c_type(rust), name.trim())
+                })
+                .collect::<Vec<_>>();
+            let returns = returns.trim_end_matches('{').trim();
+            let returns = returns.strip_prefix("->").map_or("void", c_type);
+            let params = if params.is_empty() {
+                "void".to_string()
+            } else {
+                params.join(", ")
+            };
+
+            header.push('\n');
+            for line in &comment {
+                header.push_str(line);
+                header.push('\n');
+            }
+            header.push_str(&format!("{}({});\n", c_declaration(returns, name), params));
+        }
+        if !line.starts_with("#[") {
+            comment.clear();
+        }
+    }
+    header.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif\n");
+    header
+}
+
+fn c_type(rust: &str) -> &'static str {
+    match rust.trim() {
+        "*const c_char" => "const char *",
+        "*mut c_char" => "char *",
+        "*const u8" => "const uint8_t *",
+        "*mut u8" => "uint8_t *",
+        "i32" => "int32_t",
+        "u32" => "uint32_t",
+        other => panic!("src/ffi.rs: no C type for {:?}", other),
+    }
+}
+
+// "char *name", but "uint32_t name":
+fn c_declaration(c_type: &str, name: &str) -> String {
+    if c_type.ends_with('*') {
+        format!("{}{}", c_type, name)
+    } else {
+        format!("{} {}", c_type, name)
+    }
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use bip_39::bip38;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|encrypted: &str| {
+    let _ = bip38::decrypt(encrypted, "passphrase");
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use bip_39::bip32::DerivationPath;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|path: &str| {
+    if let Ok(parsed) = path.parse::<DerivationPath>() {
+        // Whatever parses must print back to something that parses to the same path.
+        assert_eq!(parsed.to_string().parse::<DerivationPath>(), Ok(parsed));
+    }
+});
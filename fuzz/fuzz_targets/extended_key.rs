@@ -0,0 +1,28 @@
+#![no_main]
+
+use bip_39::bip32::{ExtendedPrivKey, ExtendedPubKey};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Whatever decodes must encode back to something that decodes to the same key, from the raw
+    // 78 bytes...
+    if let Ok(key) = ExtendedPrivKey::from_bytes(data) {
+        let bytes = key.to_bytes();
+        let again = ExtendedPrivKey::from_bytes(&bytes).map(|key| key.to_bytes());
+        assert_eq!(again, Ok(bytes));
+    }
+    if let Ok(key) = ExtendedPubKey::from_bytes(data) {
+        assert_eq!(ExtendedPubKey::from_bytes(&key.to_bytes()), Ok(key));
+    }
+
+    // ...and from the Base58Check strings.
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(key) = text.parse::<ExtendedPrivKey>() {
+            let again = key.to_string().parse::<ExtendedPrivKey>().map(|key| key.to_bytes());
+            assert_eq!(again, Ok(key.to_bytes()));
+        }
+        if let Ok(key) = text.parse::<ExtendedPubKey>() {
+            assert_eq!(key.to_string().parse::<ExtendedPubKey>(), Ok(key));
+        }
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use bip_39::Config;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|phrase: &str| {
+    if let Ok(config) = Config::from_phrase(phrase.to_string(), String::new()) {
+        config.to_seed();
+    }
+});
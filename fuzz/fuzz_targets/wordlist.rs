@@ -0,0 +1,13 @@
+#![no_main]
+
+use bip_39::wordlist::Wordlist;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(list) = Wordlist::from_lines(text) {
+        // Every word a list accepts must be found again at its own index.
+        for (index, word) in list.words().iter().enumerate() {
+            assert_eq!(list.index_of(word), Some(index));
+        }
+    }
+});
@@ -0,0 +1,121 @@
+// A BIP-44 style account: m/purpose'/coin_type'/account', keyed by the script type it pays to.
+
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, KeyOrigin};
+use descriptor::{self, ScriptType};
+
+pub struct Account {
+    pub script_type: ScriptType,
+    pub origin: KeyOrigin,
+    pub xpub: ExtendedPubKey,
+}
+
+impl Account {
+    pub fn derive(
+        root: &ExtendedPrivKey,
+        script_type: ScriptType,
+        index: u32,
+    ) -> Result<Account, &'static str> {
+        let path = DerivationPath::from(vec![
+            ChildNumber::hardened(script_type.purpose())?,
+            ChildNumber::hardened(root.network.coin_type())?,
+            ChildNumber::hardened(index)?,
+        ]);
+
+        Ok(Account {
+            script_type,
+            xpub: root.derive_path(&path)?.to_extended_pub_key(),
+            origin: KeyOrigin::new(root.fingerprint(), path),
+        })
+    }
+
+    // Key 'index' on the external (receive, 0) and internal (change, 1) chains:
+    pub fn receive_key(&self, index: u32) -> Result<ExtendedPubKey, &'static str> {
+        self.chain_key(false, index)
+    }
+
+    pub fn change_key(&self, index: u32) -> Result<ExtendedPubKey, &'static str> {
+        self.chain_key(true, index)
+    }
+
+    // The whole receive or change chain's xpub, for deriving many of its keys:
+    pub fn chain_xpub(&self, change: bool) -> Result<ExtendedPubKey, &'static str> {
+        Ok(self.xpub.derive_child(ChildNumber::normal(if change { 1 } else { 0 })?)?)
+    }
+
+    fn chain_key(&self, change: bool, index: u32) -> Result<ExtendedPubKey, &'static str> {
+        Ok(self.chain_xpub(change)?.derive_child(ChildNumber::normal(index)?)?)
+    }
+
+    // The "[fingerprint/84h/0h/0h]xpub..." key expression used inside descriptors:
+    pub fn key_expression(&self) -> String {
+        // The '#' in "{:#}" asks for the alternate form, which writes hardened steps as 'h':
+        format!("{:#}{}", self.origin, self.xpub)
+    }
+
+    // The ranged descriptor, with checksum, for the receive (0) or change (1) chain:
+    pub fn descriptor(&self, change: bool) -> String {
+        self.ranged_descriptor(if change { "1" } else { "0" })
+    }
+
+    // Both chains in one BIP-389 multipath descriptor ("/<0;1>/*"), which Bitcoin Core and Sparrow
+    // expand into the receive and change descriptors themselves:
+    pub fn multipath_descriptor(&self) -> String {
+        self.ranged_descriptor("<0;1>")
+    }
+
+    fn ranged_descriptor(&self, chain: &str) -> String {
+        let key = format!("{}/{}/*", self.key_expression(), chain);
+        descriptor::with_checksum(&self.script_type.wrap(&key))
+            .expect("Descriptors built from keys only contain valid characters.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+    use Config;
+
+    // BIP-84's test mnemonic, account 0:
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const ACCOUNT_XPUB: &str = "[73c5da0a/84h/0h/0h]xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+
+    fn account() -> Account {
+        let config = Config::from_phrase(MNEMONIC, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+        Account::derive(&root, ScriptType::P2wpkh, 0).unwrap()
+    }
+
+    // BIP-389's form of the two chains, with its checksum worked out by BIP-380's reference code:
+    #[test]
+    fn multipath_descriptor() {
+        let account = account();
+        assert_eq!(
+            account.multipath_descriptor(),
+            format!("wpkh({}/<0;1>/*)#qf45pmyh", ACCOUNT_XPUB)
+        );
+        assert_eq!(
+            account.descriptor(false),
+            format!("wpkh({}/0/*)#afwvtk2s", ACCOUNT_XPUB)
+        );
+    }
+
+    // BIP-84's first receive and change keys, m/84'/0'/0'/0/0 and m/84'/0'/0'/1/0:
+    #[test]
+    fn receive_and_change_keys() {
+        let account = account();
+        assert_eq!(
+            hex::encode(account.receive_key(0).unwrap().public_key()),
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c"
+        );
+        assert_eq!(
+            hex::encode(account.change_key(0).unwrap().public_key()),
+            "03025324888e429ab8e3dbaf1f7802648b9cd01e9b418485c5fa4c1b9b5700e1a6"
+        );
+        assert_eq!(
+            account.chain_xpub(false).unwrap().derive_child(ChildNumber::normal(0).unwrap()).unwrap().to_string(),
+            account.receive_key(0).unwrap().to_string()
+        );
+        assert!(account.receive_key(1 << 31).is_err());
+    }
+}
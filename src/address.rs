@@ -0,0 +1,345 @@
+// Bitcoin addresses for public keys, on any of the networks in network.rs.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use serde_json::Value;
+
+use account::Account;
+use base58;
+use bech32::{self, Variant};
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey};
+use descriptor::ScriptType;
+use hash::hash160;
+use network::Network;
+use taproot;
+
+// Pay-to-public-key-hash, the original "1..." (or "m..."/"n..." off mainnet) address:
+pub fn p2pkh(public_key: &[u8], network: Network) -> String {
+    let mut data = Vec::with_capacity(21);
+    data.push(network.p2pkh_prefix());
+    data.extend(&hash160(public_key));
+    base58::encode_check(&data)
+}
+
+// Native segwit v0 pay-to-witness-public-key-hash, "bc1q..." (BIP-84):
+pub fn p2wpkh(public_key: &[u8], network: Network) -> Result<String, &'static str> {
+    segwit(network, 0, &hash160(public_key))
+}
+
+// The same witness program wrapped in pay-to-script-hash, "3..." (BIP-49), for software that
+// predates native segwit addresses:
+pub fn p2sh_p2wpkh(public_key: &[u8], network: Network) -> String {
+    // The redeem script is 'OP_0 <20 byte key hash>':
+    let mut redeem_script = vec![0x00, 0x14];
+    redeem_script.extend(&hash160(public_key));
+
+    let mut data = Vec::with_capacity(21);
+    data.push(network.p2sh_prefix());
+    data.extend(&hash160(&redeem_script));
+    base58::encode_check(&data)
+}
+
+// Taproot, "bc1p..." (BIP-86): a key path only output, for the (compressed) internal key.
+pub fn p2tr(public_key: &[u8], network: Network) -> Result<String, &'static str> {
+    if public_key.len() != 33 {
+        return Err("Taproot needs a compressed public key.");
+    }
+    let mut internal_key = [0; 32];
+    internal_key.copy_from_slice(&public_key[1..]);
+    segwit(network, 1, &taproot::taproot_tweak(&internal_key, None)?.0)
+}
+
+// The address a key pays to under each script type's descriptor:
+pub fn for_script_type(
+    script_type: ScriptType,
+    public_key: &[u8],
+    network: Network,
+) -> Result<String, &'static str> {
+    match script_type {
+        ScriptType::P2pkh => Ok(p2pkh(public_key, network)),
+        ScriptType::P2shP2wpkh => Ok(p2sh_p2wpkh(public_key, network)),
+        ScriptType::P2wpkh => p2wpkh(public_key, network),
+        ScriptType::P2tr => p2tr(public_key, network),
+    }
+}
+
+// One derived address, with what was used to get there, for listings and exporters:
+pub struct AddressRecord {
+    pub index: u32,
+    // From the master key, e.g. m/84'/0'/0'/0/5:
+    pub path: DerivationPath,
+    pub public_key: [u8; 33],
+    pub address: String,
+    pub script_type: ScriptType,
+}
+
+impl AddressRecord {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "index": self.index,
+            "path": self.path.to_string(),
+            "public_key": hex::encode(self.public_key),
+            "address": self.address,
+            "script_type": self.script_type.to_string(),
+        })
+    }
+}
+
+// Where 'ExtendedPubKey::contains_address' found an address: the chain and index below the xpub,
+// and the script type that pays to it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AddressMatch {
+    pub change: bool,
+    pub index: u32,
+    pub script_type: ScriptType,
+}
+
+impl fmt::Display for AddressMatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chain = if self.change { 1 } else { 0 };
+        write!(f, "{} at {}/{}", self.script_type, chain, self.index)
+    }
+}
+
+// The addresses at 'range' on an account's receive (or change) chain, in order:
+pub fn derive_addresses(
+    account: &Account,
+    change: bool,
+    range: Range<u32>,
+) -> Result<Vec<AddressRecord>, &'static str> {
+    let chain = account.chain_xpub(change)?;
+    range
+        .clone()
+        .zip(chain.derive_children(range)?)
+        .map(|(index, key)| record(account, change, index, &key))
+        .collect()
+}
+
+// The same, spread across threads for wallet scans that need tens of thousands of addresses;
+// rayon's indexed iterators keep the results in the range's order.
+#[cfg(feature = "parallel")]
+pub fn derive_addresses_parallel(
+    account: &Account,
+    change: bool,
+    range: Range<u32>,
+) -> Result<Vec<AddressRecord>, &'static str> {
+    let chain = account.chain_xpub(change)?;
+    range
+        .into_par_iter()
+        .map(|index| {
+            let key = chain.derive_child(ChildNumber::normal(index)?)?;
+            record(account, change, index, &key)
+        })
+        .collect()
+}
+
+// The addresses among 'known' (pasted from an old wallet's history, say) that a root key's standard
+// accounts pay to: accounts 0..'accounts' of every script type, the first 'gap_limit' keys of each
+// chain.  It's the most direct answer to "is this really my seed?", and stops looking once all of
+// 'known' has turned up.  Script types without addresses on the root's network are skipped.
+pub fn find_known(
+    root: &ExtendedPrivKey,
+    known: &[&str],
+    accounts: u32,
+    gap_limit: u32,
+) -> Result<Vec<AddressRecord>, &'static str> {
+    // Bech32 addresses can be written in upper case, as QR codes do, but derive in lower case:
+    let mut remaining: HashSet<String> = known
+        .iter()
+        .map(|address| match bech32::decode(address) {
+            Ok(_) => address.to_lowercase(),
+            Err(_) => address.to_string(),
+        })
+        .collect();
+    let mut found = Vec::new();
+
+    for &script_type in ScriptType::all().iter() {
+        for index in 0..accounts {
+            let account = Account::derive(root, script_type, index)?;
+            for &change in [false, true].iter() {
+                let records = match derive_addresses(&account, change, 0..gap_limit) {
+                    Ok(records) => records,
+                    Err(_) => continue,
+                };
+                for record in records {
+                    if remaining.remove(&record.address) {
+                        found.push(record);
+                        if remaining.is_empty() {
+                            return Ok(found);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn record(
+    account: &Account,
+    change: bool,
+    index: u32,
+    key: &ExtendedPubKey,
+) -> Result<AddressRecord, &'static str> {
+    let public_key = key.public_key();
+    Ok(AddressRecord {
+        index,
+        path: account
+            .origin
+            .path
+            .child(ChildNumber::normal(if change { 1 } else { 0 })?)
+            .child(ChildNumber::normal(index)?),
+        address: for_script_type(account.script_type, &public_key, key.network)?,
+        public_key,
+        script_type: account.script_type,
+    })
+}
+
+// Which network an address is for, going by its version byte or human readable part.  Testnet
+// and signet addresses look the same, so those come back as testnet.
+pub fn network_of(address: &str) -> Option<Network> {
+    if let Ok((hrp, _, _)) = bech32::decode(address) {
+        return Network::all()
+            .iter()
+            .find(|network| network.bech32_hrp() == Some(hrp.as_str()))
+            .cloned();
+    }
+
+    let data = base58::decode_check(address).ok()?;
+    if data.len() != 21 {
+        return None;
+    }
+    Network::all()
+        .iter()
+        .find(|network| network.p2pkh_prefix() == data[0] || network.p2sh_prefix() == data[0])
+        .cloned()
+}
+
+// The witness version and program a segwit address on 'network' pays to:
+pub fn decode_segwit(address: &str, network: Network) -> Result<(u8, Vec<u8>), &'static str> {
+    let (hrp, data, variant) = bech32::decode(address)?;
+    if Some(hrp.as_str()) != network.bech32_hrp() {
+        return Err("Address is for another network.");
+    }
+    let (&version, program) = data.split_first().ok_or("Invalid segwit address.")?;
+    let program = bech32::from_base32(program)?;
+
+    let valid = match version {
+        0 => variant == Variant::Bech32 && (program.len() == 20 || program.len() == 32),
+        1..=16 => variant == Variant::Bech32m && (2..=40).contains(&program.len()),
+        _ => false,
+    };
+    if !valid {
+        return Err("Invalid segwit address.");
+    }
+    Ok((version, program))
+}
+
+// The output script for a witness version and program: 'OP_n <program>':
+pub fn segwit_script(version: u8, program: &[u8]) -> Vec<u8> {
+    let mut script = vec![if version == 0 { 0x00 } else { 0x50 + version }];
+    script.push(program.len() as u8);
+    script.extend(program);
+    script
+}
+
+// Witness v0 programs use Bech32, everything from v1 (taproot) on uses Bech32m:
+fn segwit(network: Network, version: u8, program: &[u8]) -> Result<String, &'static str> {
+    let hrp = network
+        .bech32_hrp()
+        .ok_or("This network doesn't have segwit addresses.")?;
+
+    let mut data = vec![version];
+    data.extend(bech32::to_base32(program));
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    Ok(bech32::encode(hrp, &data, variant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Config;
+
+    // BIP-173's and BIP-350's addresses, with the output scripts they pay to:
+    #[test]
+    fn segwit_spec_vectors() {
+        let vectors = [
+            ("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", Network::Mainnet, "0014751e76e8199196d454941c45d1b3a323f1433bd6"),
+            ("tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7", Network::Testnet, "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"),
+            ("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0", Network::Mainnet, "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            ("tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c", Network::Signet, "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433"),
+        ];
+        for &(address, network, script) in vectors.iter() {
+            let (version, program) = decode_segwit(address, network).unwrap();
+            assert_eq!(hex::encode(segwit_script(version, &program)), script);
+            assert_eq!(segwit(network, version, &program).unwrap(), address.to_lowercase());
+        }
+
+        // A v0 program with Bech32m's checksum, and a v1 program with Bech32's:
+        for address in [
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+        ] {
+            assert_eq!(decode_segwit(address, Network::Mainnet), Err("Invalid segwit address."));
+        }
+        assert_eq!(
+            decode_segwit(vectors[0].0, Network::Testnet),
+            Err("Address is for another network.")
+        );
+    }
+
+    // BIP-84's first receive key pays to the same program on every network with segwit addresses,
+    // under each network's own human readable part:
+    #[test]
+    fn addresses_per_network() {
+        let public_key = hex::decode("0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c").unwrap();
+        let mainnet = p2wpkh(&public_key, Network::Mainnet).unwrap();
+        assert_eq!(mainnet, "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        let (_, program) = decode_segwit(&mainnet, Network::Mainnet).unwrap();
+
+        for &(network, hrp) in [(Network::Testnet, "tb1q"), (Network::Signet, "tb1q"), (Network::Regtest, "bcrt1q")].iter() {
+            let address = p2wpkh(&public_key, network).unwrap();
+            assert!(address.starts_with(hrp), "{}", address);
+            assert_eq!(decode_segwit(&address, network).unwrap(), (0, program.clone()));
+        }
+        assert_eq!(network_of(&p2wpkh(&public_key, Network::Regtest).unwrap()), Some(Network::Regtest));
+        // Signet's addresses are testnet's:
+        assert_eq!(network_of(&p2wpkh(&public_key, Network::Signet).unwrap()), Some(Network::Testnet));
+
+        assert!(p2pkh(&public_key, Network::Mainnet).starts_with('1'));
+        let testnet = p2pkh(&public_key, Network::Testnet);
+        assert!(testnet.starts_with('m') || testnet.starts_with('n'));
+        assert!(p2sh_p2wpkh(&public_key, Network::Regtest).starts_with('2'));
+    }
+
+    // The first receive addresses of BIP-44's, BIP-49's, BIP-84's and BIP-86's test mnemonic:
+    #[test]
+    fn addresses_per_script_type() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let config = Config::from_phrase(mnemonic, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+
+        for &(script_type, address) in [
+            (ScriptType::P2pkh, "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            (ScriptType::P2shP2wpkh, "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            (ScriptType::P2wpkh, "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"),
+            (ScriptType::P2tr, "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"),
+        ]
+        .iter()
+        {
+            let key = Account::derive(&root, script_type, 0).unwrap().receive_key(0).unwrap();
+            assert_eq!(
+                for_script_type(script_type, &key.public_key(), Network::Mainnet).unwrap(),
+                address
+            );
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Backups of the mnemonic as age (https://age-encryption.org) files, encrypted either to one or
+// more X25519 recipients ("age1...") or to a passphrase.
+
+use std::io::{Read, Write};
+use std::iter;
+
+use age::secrecy::SecretString;
+use age::{Decryptor, Encryptor, IdentityFile, Recipient};
+
+pub fn encrypt_to_recipients(mnemonic: &str, recipients: &[String]) -> Result<Vec<u8>, &'static str> {
+    let recipients = recipients
+        .iter()
+        .map(|recipient| recipient.parse::<age::x25519::Recipient>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "Invalid age recipient.")?;
+
+    let encryptor = Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn Recipient))
+        .map_err(|_| "At least one age recipient is required.")?;
+    encrypt(encryptor, mnemonic)
+}
+
+pub fn encrypt_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Vec<u8>, &'static str> {
+    let passphrase = SecretString::from(passphrase.to_string());
+    encrypt(Encryptor::with_user_passphrase(passphrase), mnemonic)
+}
+
+// 'identity_file' is the contents of an age identity file, as written by 'age-keygen'.
+pub fn decrypt_with_identities(backup: &[u8], identity_file: &str) -> Result<String, &'static str> {
+    let identities = IdentityFile::from_buffer(identity_file.as_bytes())
+        .map_err(|_| "Invalid age identity file.")?
+        .into_identities()
+        .map_err(|_| "Invalid age identity file.")?;
+
+    decrypt(backup, identities.iter().map(|i| i.as_ref()))
+}
+
+pub fn decrypt_with_passphrase(backup: &[u8], passphrase: &str) -> Result<String, &'static str> {
+    let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+    decrypt(backup, iter::once(&identity as &dyn age::Identity))
+}
+
+fn encrypt(encryptor: Encryptor, mnemonic: &str) -> Result<Vec<u8>, &'static str> {
+    let mut backup = Vec::new();
+    let mut writer = encryptor
+        .wrap_output(&mut backup)
+        .map_err(|_| "Unable to encrypt the backup.")?;
+    writer
+        .write_all(mnemonic.as_bytes())
+        .and_then(|_| writer.finish())
+        .map_err(|_| "Unable to encrypt the backup.")?;
+    Ok(backup)
+}
+
+fn decrypt<'a>(
+    backup: &[u8],
+    identities: impl Iterator<Item = &'a dyn age::Identity>,
+) -> Result<String, &'static str> {
+    let decryptor = Decryptor::new(backup).map_err(|_| "Not an age encrypted backup.")?;
+    let mut reader = decryptor
+        .decrypt(identities)
+        .map_err(|_| "Unable to decrypt the backup with the given key or passphrase.")?;
+
+    let mut mnemonic = String::new();
+    reader
+        .read_to_string(&mut mnemonic)
+        .map_err(|_| "The decrypted backup is not a mnemonic.")?;
+    Ok(mnemonic)
+}
+
+#[cfg(test)]
+mod tests {
+    use age::secrecy::ExposeSecret;
+    use age::x25519::Identity;
+
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn recipients_round_trip() {
+        let identity = Identity::generate();
+        let recipient = identity.to_public().to_string();
+        let backup = encrypt_to_recipients(MNEMONIC, &[recipient]).unwrap();
+        assert!(backup.starts_with(b"age-encryption.org/v1\n"));
+
+        // An identity file as 'age-keygen' writes it, comments and all:
+        let identity_file = format!(
+            "# created: 2024-01-01T00:00:00Z\n# public key: {}\n{}\n",
+            identity.to_public(),
+            identity.to_string().expose_secret()
+        );
+        assert_eq!(decrypt_with_identities(&backup, &identity_file), Ok(MNEMONIC.to_string()));
+
+        let stranger = Identity::generate().to_string();
+        assert_eq!(
+            decrypt_with_identities(&backup, stranger.expose_secret()),
+            Err("Unable to decrypt the backup with the given key or passphrase.")
+        );
+    }
+
+    #[test]
+    fn passphrase_round_trip() {
+        let backup = encrypt_with_passphrase(MNEMONIC, "correct horse").unwrap();
+        assert_eq!(decrypt_with_passphrase(&backup, "correct horse"), Ok(MNEMONIC.to_string()));
+        assert_eq!(
+            decrypt_with_passphrase(&backup, "battery staple"),
+            Err("Unable to decrypt the backup with the given key or passphrase.")
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            encrypt_to_recipients(MNEMONIC, &["age1nope".to_string()]),
+            Err("Invalid age recipient.")
+        );
+        assert_eq!(encrypt_to_recipients(MNEMONIC, &[]), Err("At least one age recipient is required."));
+        assert_eq!(decrypt_with_passphrase(b"not a backup", "correct horse"), Err("Not an age encrypted backup."));
+    }
+}
@@ -0,0 +1,285 @@
+// Argon2id (RFC 9106, version 0x13), the memory-hard password hash the vault (and a keystore, if
+// asked) derives its key with: guessing a vault's passphrase takes the full 'memory' for every
+// guess, which is what makes GPU and ASIC farms expensive.  It's built on RustCrypto's BLAKE2b.
+//
+// Memory is a matrix of 1 KiB blocks, 'lanes' rows of it, filled a column at a time in four
+// slices per pass.  Each new block mixes the one before it with an earlier one picked by a
+// pseudo-random index: for "id", the first half of the first pass picks those indexes
+// independently of the password (resisting side channels), and the rest from the data itself
+// (resisting time-memory trade-offs).
+
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+
+const VERSION: u32 = 0x13;
+// Argon2d is 0, Argon2i 1:
+const ARGON2ID: u32 = 2;
+const BLOCK_WORDS: usize = 128;
+const SLICES: usize = 4;
+
+type Block = [u64; BLOCK_WORDS];
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    // In KiB; at least 8 per lane:
+    pub memory: u32,
+    pub iterations: u32,
+    pub lanes: u32,
+}
+
+// 'secret' and 'associated' are the optional K and X inputs (empty for the vault), kept for the
+// RFC's test vector.
+pub fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated: &[u8],
+    params: &Params,
+    output: &mut [u8],
+) -> Result<(), &'static str> {
+    if params.lanes == 0 || params.iterations == 0 || params.memory < 8 * params.lanes {
+        return Err("Argon2 needs a lane, an iteration, and 8 KiB of memory per lane.");
+    }
+    if salt.len() < 8 || output.len() < 4 {
+        return Err("Argon2 needs a salt of 8 bytes and an output of 4 bytes at least.");
+    }
+
+    // H0, every parameter and input with its length:
+    let mut h0 = Blake2bVar::new(64).unwrap();
+    for value in [
+        params.lanes,
+        output.len() as u32,
+        params.memory,
+        params.iterations,
+        VERSION,
+        ARGON2ID,
+    ]
+    .iter()
+    {
+        h0.update(&value.to_le_bytes());
+    }
+    for input in [password, salt, secret, associated].iter() {
+        h0.update(&(input.len() as u32).to_le_bytes());
+        h0.update(input);
+    }
+    let mut seed = [0; 72];
+    h0.finalize_variable(&mut seed[..64]).unwrap();
+
+    // The memory rounded down to a whole number of segments (a lane's quarter) per lane:
+    let lanes = params.lanes as usize;
+    let segment_length = params.memory as usize / (SLICES * lanes);
+    let lane_length = segment_length * SLICES;
+    let mut memory = vec![[0u64; BLOCK_WORDS]; lane_length * lanes];
+
+    // Each lane's first two blocks, from H0, the block's index (0 or 1) and the lane:
+    for lane in 0..lanes {
+        for column in 0..2 {
+            seed[64..68].copy_from_slice(&(column as u32).to_le_bytes());
+            seed[68..].copy_from_slice(&(lane as u32).to_le_bytes());
+            let mut bytes = [0; 1024];
+            variable_hash(&seed, &mut bytes);
+            memory[lane * lane_length + column] = from_bytes(&bytes);
+        }
+    }
+
+    let total_blocks = (lane_length * lanes) as u64;
+    for pass in 0..params.iterations as usize {
+        for slice in 0..SLICES {
+            for lane in 0..lanes {
+                let independent = pass == 0 && slice < SLICES / 2;
+                let mut input = [0u64; BLOCK_WORDS];
+                input[..6].copy_from_slice(&[
+                    pass as u64,
+                    lane as u64,
+                    slice as u64,
+                    total_blocks,
+                    u64::from(params.iterations),
+                    u64::from(ARGON2ID),
+                ]);
+                let mut addresses = [0u64; BLOCK_WORDS];
+
+                let first = if pass == 0 && slice == 0 { 2 } else { 0 };
+                if independent && first != 0 {
+                    next_addresses(&mut input, &mut addresses);
+                }
+                for index in first..segment_length {
+                    let column = slice * segment_length + index;
+                    let current = lane * lane_length + column;
+                    let previous = if column == 0 {
+                        current + lane_length - 1
+                    } else {
+                        current - 1
+                    };
+
+                    let random = if independent {
+                        if index % BLOCK_WORDS == 0 {
+                            next_addresses(&mut input, &mut addresses);
+                        }
+                        addresses[index % BLOCK_WORDS]
+                    } else {
+                        memory[previous][0]
+                    };
+
+                    // The high half picks the lane (the first slice of all stays in its own), the
+                    // low half a block among those that are finished and may be referenced:
+                    let reference_lane = if pass == 0 && slice == 0 {
+                        lane
+                    } else {
+                        (random >> 32) as usize % lanes
+                    };
+                    let same_lane = reference_lane == lane;
+                    let finished = if pass == 0 {
+                        slice * segment_length
+                    } else {
+                        lane_length - segment_length
+                    };
+                    let area = if same_lane {
+                        finished + index - 1
+                    } else if index == 0 {
+                        finished - 1
+                    } else {
+                        finished
+                    };
+                    let low = random & 0xffff_ffff;
+                    let x = (low * low) >> 32;
+                    let y = (area as u64 * x) >> 32;
+                    let relative = area - 1 - y as usize;
+                    let start = if pass == 0 || slice == SLICES - 1 {
+                        0
+                    } else {
+                        (slice + 1) * segment_length
+                    };
+                    let reference = reference_lane * lane_length + (start + relative) % lane_length;
+
+                    let mixed = compress(&memory[previous], &memory[reference]);
+                    // (later passes XOR into what's there, as version 0x13 does)
+                    if pass == 0 {
+                        memory[current] = mixed;
+                    } else {
+                        for (word, new) in memory[current].iter_mut().zip(mixed.iter()) {
+                            *word ^= new;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // The last column, XORed across the lanes, hashed to the output length:
+    let mut last = memory[lane_length - 1];
+    for lane in 1..lanes {
+        for (word, other) in last.iter_mut().zip(memory[(lane + 1) * lane_length - 1].iter()) {
+            *word ^= other;
+        }
+    }
+    let mut bytes = [0; 1024];
+    for (chunk, word) in bytes.chunks_mut(8).zip(last.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    variable_hash(&bytes, output);
+    Ok(())
+}
+
+// The next 128 password-independent indexes: the counter in 'input' goes up by one, then
+// G(0, G(0, input)).
+fn next_addresses(input: &mut Block, addresses: &mut Block) {
+    input[6] += 1;
+    let zero = [0; BLOCK_WORDS];
+    *addresses = compress(&zero, &compress(&zero, input));
+}
+
+// H', BLAKE2b stretched to any length: whole hashes chained, 32 bytes of each kept, and the rest
+// of the length from the last.
+fn variable_hash(input: &[u8], output: &mut [u8]) {
+    let length = (output.len() as u32).to_le_bytes();
+    if output.len() <= 64 {
+        blake2b(&[&length, input], output);
+        return;
+    }
+
+    let mut v = [0; 64];
+    blake2b(&[&length, input], &mut v);
+    output[..32].copy_from_slice(&v[..32]);
+
+    let mut written = 32;
+    while output.len() - written > 64 {
+        let previous = v;
+        blake2b(&[&previous], &mut v);
+        output[written..written + 32].copy_from_slice(&v[..32]);
+        written += 32;
+    }
+    blake2b(&[&v], &mut output[written..]);
+}
+
+// BLAKE2b of the parts one after another, as long as 'output' (1 to 64 bytes):
+fn blake2b(parts: &[&[u8]], output: &mut [u8]) {
+    let mut hash = Blake2bVar::new(output.len()).unwrap();
+    for part in parts {
+        hash.update(part);
+    }
+    hash.finalize_variable(output).unwrap();
+}
+
+fn from_bytes(bytes: &[u8; 1024]) -> Block {
+    let mut block = [0; BLOCK_WORDS];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks(8)) {
+        let mut le = [0; 8];
+        le.copy_from_slice(chunk);
+        *word = u64::from_le_bytes(le);
+    }
+    block
+}
+
+// G: the permutation P over the XOR of the two blocks' rows, then its columns (sixteen words at
+// a time, seen as eight pairs), XORed back with what went in.
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = [0; BLOCK_WORDS];
+    for (word, (a, b)) in r.iter_mut().zip(x.iter().zip(y.iter())) {
+        *word = a ^ b;
+    }
+    let mut z = r;
+
+    for row in 0..8 {
+        let mut words = [0; 16];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = 16 * row + i;
+        }
+        permute(&mut z, &words);
+    }
+    for column in 0..8 {
+        let mut words = [0; 16];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = 2 * column + 16 * (i / 2) + i % 2;
+        }
+        permute(&mut z, &words);
+    }
+
+    for (word, original) in z.iter_mut().zip(r.iter()) {
+        *word ^= original;
+    }
+    z
+}
+
+// P: BLAKE2b's round function, with the multiplications Argon2 adds ('BlaMka'):
+fn permute(block: &mut Block, words: &[usize; 16]) {
+    let mix = |block: &mut Block, a: usize, b: usize, c: usize, d: usize| {
+        let (a, b, c, d) = (words[a], words[b], words[c], words[d]);
+        let multiply = |x: u64, y: u64| ((x & 0xffff_ffff) * (y & 0xffff_ffff)).wrapping_mul(2);
+        block[a] = block[a].wrapping_add(block[b]).wrapping_add(multiply(block[a], block[b]));
+        block[d] = (block[d] ^ block[a]).rotate_right(32);
+        block[c] = block[c].wrapping_add(block[d]).wrapping_add(multiply(block[c], block[d]));
+        block[b] = (block[b] ^ block[c]).rotate_right(24);
+        block[a] = block[a].wrapping_add(block[b]).wrapping_add(multiply(block[a], block[b]));
+        block[d] = (block[d] ^ block[a]).rotate_right(16);
+        block[c] = block[c].wrapping_add(block[d]).wrapping_add(multiply(block[c], block[d]));
+        block[b] = (block[b] ^ block[c]).rotate_right(63);
+    };
+    mix(block, 0, 4, 8, 12);
+    mix(block, 1, 5, 9, 13);
+    mix(block, 2, 6, 10, 14);
+    mix(block, 3, 7, 11, 15);
+    mix(block, 0, 5, 10, 15);
+    mix(block, 1, 6, 11, 12);
+    mix(block, 2, 7, 8, 13);
+    mix(block, 3, 4, 9, 14);
+}
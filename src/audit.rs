@@ -0,0 +1,78 @@
+// Audit events for key ceremonies: a hook that hears when a mnemonic is generated, a seed derived
+// or keys exported, to write into whatever log the ceremony keeps.  The events are redacted by
+// construction: none of them has a field that could hold a mnemonic, passphrase, seed or private
+// key, only what's safe to write down (word counts, languages, master fingerprints, formats).
+
+use std::sync::RwLock;
+
+use serde_json::Value;
+
+use language::Language;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    MnemonicGenerated {
+        language: Language,
+        words: usize,
+    },
+    // Identified by the master key's fingerprint, as wallets show it:
+    SeedDerived {
+        fingerprint: [u8; 4],
+    },
+    KeysExported {
+        format: &'static str,
+        fingerprint: [u8; 4],
+        account: u32,
+    },
+}
+
+impl Event {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Event::MnemonicGenerated { language, words } => json!({
+                "event": "mnemonic_generated",
+                "language": language.to_string(),
+                "words": words,
+            }),
+            Event::SeedDerived { fingerprint } => json!({
+                "event": "seed_derived",
+                "fingerprint": hex::encode(fingerprint),
+            }),
+            Event::KeysExported {
+                format,
+                fingerprint,
+                account,
+            } => json!({
+                "event": "keys_exported",
+                "format": format,
+                "fingerprint": hex::encode(fingerprint),
+                "account": account,
+            }),
+        }
+    }
+}
+
+type Hook = Box<dyn Fn(&Event) + Send + Sync>;
+
+// One hook for the whole program, like a logger:
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+pub fn set_hook(hook: Hook) {
+    *HOOK.write().unwrap() = Some(hook);
+}
+
+pub fn clear_hook() {
+    *HOOK.write().unwrap() = None;
+}
+
+// So events that cost something to put together (a fingerprint means deriving the master key)
+// are only put together for someone listening:
+pub fn has_hook() -> bool {
+    HOOK.read().unwrap().is_some()
+}
+
+pub fn emit(event: Event) {
+    if let Some(hook) = HOOK.read().unwrap().as_ref() {
+        hook(&event);
+    }
+}
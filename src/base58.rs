@@ -0,0 +1,93 @@
+// Base58Check: the payload followed by the first four bytes of its double SHA-256.
+
+use alloc_prelude::*;
+use error::Error;
+use hash::double_sha256;
+
+pub fn encode_check(data: &[u8]) -> String {
+    let mut payload = data.to_vec();
+    payload.extend(&double_sha256(data)[..4]);
+    bs58::encode(payload).into_string()
+}
+
+pub fn decode_check(encoded: &str) -> Result<Vec<u8>, Error> {
+    let mut payload = bs58::decode(encoded).into_vec()?;
+
+    if payload.len() < 4 {
+        return Err(Error::Other("Base58Check payload is too short."));
+    }
+
+    let checksum_pos = payload.len() - 4;
+    if double_sha256(&payload[..checksum_pos])[..4] != payload[checksum_pos..] {
+        return Err(Error::Other("Base58Check checksum mismatch."));
+    }
+
+    payload.truncate(checksum_pos);
+    Ok(payload)
+}
+
+// Wallet Import Format: a private key behind a network's WIF prefix, followed by a 1 if its address
+// is of the compressed public key (a "K" or "L" key on mainnet) rather than the old uncompressed one
+// (a "5" key).
+pub fn encode_wif(prefix: u8, secret_key: &[u8; 32], compressed: bool) -> String {
+    let mut data = Vec::with_capacity(34);
+    data.push(prefix);
+    data.extend(secret_key);
+    if compressed {
+        data.push(1);
+    }
+    encode_check(&data)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::convert::TryInto;
+    use test_vectors;
+
+    #[test]
+    fn spec_keys() {
+        // Every serialized key in BIP-32's vectors is 78 bytes behind its checksum:
+        for vector in test_vectors::BIP32.iter() {
+            for chain in vector.chains {
+                for &key in [chain.xprv, chain.xpub].iter() {
+                    let payload = decode_check(key).unwrap();
+                    assert_eq!(payload.len(), 78);
+                    assert_eq!(encode_check(&payload), key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wif() {
+        let secret_key = hex::decode(test_vectors::WIF_SECRET_KEY).unwrap();
+        let secret_key: [u8; 32] = secret_key[..].try_into().unwrap();
+        assert_eq!(
+            encode_wif(0x80, &secret_key, true),
+            test_vectors::WIF_COMPRESSED
+        );
+        assert_eq!(
+            encode_wif(0x80, &secret_key, false),
+            test_vectors::WIF_UNCOMPRESSED
+        );
+    }
+
+    #[test]
+    fn bad_encodings() {
+        let key = test_vectors::BIP32[0].chains[0].xprv;
+        let mut encoded = bs58::decode(key).into_vec().unwrap();
+        encoded[80] ^= 1;
+        assert_eq!(
+            decode_check(&bs58::encode(encoded).into_string()),
+            Err(Error::Other("Base58Check checksum mismatch."))
+        );
+
+        assert_eq!(
+            decode_check("111"),
+            Err(Error::Other("Base58Check payload is too short."))
+        );
+        // ('0' isn't in the alphabet:)
+        assert!(matches!(decode_check("x0"), Err(Error::Base58(_))));
+    }
+}
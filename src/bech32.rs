@@ -0,0 +1,189 @@
+// Bech32 (BIP-173) and its Bech32m variant (BIP-350), used by segwit addresses and by
+// bech32 encoded keys such as Nostr's npub/nsec.
+
+const CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+// The two only differ in the constant the checksum is XORed with:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(&self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+// 'data' is already in 5-bit groups (see 'to_base32'):
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> String {
+    let mut values = expand_hrp(hrp);
+    values.extend(data);
+    values.extend(&[0; 6]);
+    let checksum = polymod(&values) ^ variant.constant();
+
+    let mut encoded = format!("{}1", hrp);
+    for value in data {
+        encoded.push(CHARSET[*value as usize] as char);
+    }
+    for i in 0..6 {
+        encoded.push(CHARSET[((checksum >> (5 * (5 - i))) & 31) as usize] as char);
+    }
+    encoded
+}
+
+// The human readable part, the 5-bit data (without the checksum) and which checksum it had:
+pub fn decode(encoded: &str) -> Result<(String, Vec<u8>, Variant), &'static str> {
+    // Either case is fine, mixing them isn't:
+    if encoded.to_lowercase() != encoded && encoded.to_uppercase() != encoded {
+        return Err("Mixed case bech32 string.");
+    }
+    let encoded = encoded.to_lowercase();
+    let separator = encoded.rfind('1').ok_or("Missing bech32 separator.")?;
+    let (hrp, data) = (&encoded[..separator], &encoded[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 {
+        return Err("Invalid bech32 string.");
+    }
+
+    let mut values = Vec::with_capacity(data.len());
+    for c in data.bytes() {
+        let value = CHARSET.iter().position(|&x| x == c).ok_or("Invalid bech32 character.")?;
+        values.push(value as u8);
+    }
+
+    let mut checked = expand_hrp(hrp);
+    checked.extend(&values);
+    let variant = match polymod(&checked) {
+        c if c == Variant::Bech32.constant() => Variant::Bech32,
+        c if c == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err("Invalid bech32 checksum."),
+    };
+
+    values.truncate(values.len() - 6);
+    Ok((hrp.to_string(), values, variant))
+}
+
+// Regroup bytes into 5-bit values, zero padding the last one:
+pub fn to_base32(data: &[u8]) -> Vec<u8> {
+    let mut groups = Vec::with_capacity((data.len() * 8).div_ceil(5));
+    let mut accumulator: u32 = 0;
+    let mut bits = 0;
+
+    for byte in data {
+        accumulator = (accumulator << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            groups.push(((accumulator >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        groups.push(((accumulator << (5 - bits)) & 31) as u8);
+    }
+    groups
+}
+
+// And back, refusing padding that's more than 4 bits or isn't zero:
+pub fn from_base32(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = Vec::with_capacity(data.len() * 5 / 8);
+    let mut accumulator: u32 = 0;
+    let mut bits = 0;
+
+    for value in data {
+        accumulator = (accumulator << 5) | u32::from(*value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+        }
+    }
+    if bits >= 5 || (accumulator << (8 - bits)) as u8 != 0 {
+        return Err("Invalid bech32 padding.");
+    }
+    Ok(bytes)
+}
+
+fn expand_hrp(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 31));
+    values
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(*value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-173's and BIP-350's valid strings, each of which decodes with its own variant's
+    // checksum and encodes back the same (in lower case):
+    #[test]
+    fn spec_vectors() {
+        let vectors = [
+            ("A12UEL5L", Variant::Bech32),
+            ("a12uel5l", Variant::Bech32),
+            ("an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs", Variant::Bech32),
+            ("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", Variant::Bech32),
+            ("split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w", Variant::Bech32),
+            ("?1ezyfcl", Variant::Bech32),
+            ("A1LQFN3A", Variant::Bech32m),
+            ("a1lqfn3a", Variant::Bech32m),
+            ("an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6", Variant::Bech32m),
+            ("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Variant::Bech32m),
+            ("split1checkupstagehandshakeupstreamerranterredcaperredlc445v", Variant::Bech32m),
+            ("?1v759aa", Variant::Bech32m),
+        ];
+        for &(encoded, variant) in vectors.iter() {
+            let (hrp, data, decoded_variant) = decode(encoded).unwrap();
+            assert_eq!(decoded_variant, variant, "{}", encoded);
+            assert_eq!(encode(&hrp, &data, variant), encoded.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn invalid_strings() {
+        assert_eq!(decode("A12uEL5L"), Err("Mixed case bech32 string."));
+        assert_eq!(decode("pzry9x0s0muk"), Err("Missing bech32 separator."));
+        assert_eq!(decode("1pzry9x0s0muk"), Err("Invalid bech32 string."));
+        assert_eq!(decode("a1qpzry"), Err("Invalid bech32 string."));
+        assert_eq!(decode("x1b4n0q5v"), Err("Invalid bech32 character."));
+        assert_eq!(decode("a12uel5m"), Err("Invalid bech32 checksum."));
+    }
+
+    // BIP-173's P2WPKH example's key hash is its address's characters after "bc1q" (and before the
+    // checksum):
+    #[test]
+    fn base32_regrouping() {
+        let program = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        let groups = to_base32(&program);
+        let encoded: String = groups.iter().map(|&group| CHARSET[group as usize] as char).collect();
+        assert_eq!(encoded, "w508d6qejxtdg4y5r3zarvary0c5xw7k");
+        assert_eq!(from_base32(&groups), Ok(program));
+
+        // Seven bytes leave four bits of padding, which have to be zero, and never a whole group:
+        let mut groups = to_base32(&[0xff; 7]);
+        assert_eq!((groups.len(), groups[11]), (12, 16));
+        groups[11] = 17;
+        assert_eq!(from_base32(&groups), Err("Invalid bech32 padding."));
+        assert_eq!(from_base32(&[0]), Err("Invalid bech32 padding."));
+    }
+}
@@ -0,0 +1,95 @@
+// BIP-137 message signatures, as Bitcoin Core's 'signmessage' and most wallets make them: a
+// recoverable ECDSA signature over the double SHA-256 of the "Bitcoin Signed Message" preimage,
+// whose header byte says how to get from the recovered public key to the address.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+
+use address;
+use descriptor::ScriptType;
+use hash::double_sha256;
+use network::Network;
+use tx::push_varint;
+
+const MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+// Header bytes are one of these plus the recovery id (0 to 3):
+const P2PKH_UNCOMPRESSED: u8 = 27;
+const P2PKH: u8 = 31;
+const P2SH_P2WPKH: u8 = 35;
+const P2WPKH: u8 = 39;
+
+// The base64 signature proving 'secret_key' controls its 'script_type' address:
+pub fn sign(
+    secret_key: &[u8; 32],
+    script_type: ScriptType,
+    message: &[u8],
+) -> Result<String, &'static str> {
+    let header = match script_type {
+        ScriptType::P2pkh => P2PKH,
+        ScriptType::P2shP2wpkh => P2SH_P2WPKH,
+        ScriptType::P2wpkh => P2WPKH,
+        ScriptType::P2tr => return Err("BIP-137 doesn't cover taproot addresses."),
+    };
+
+    let signing_key = SigningKey::from_slice(secret_key).map_err(|_| "Invalid private key.")?;
+    // RFC 6979 nonces, so the same key and message always give the same signature (as Core's do):
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(&message_hash(message))
+        .map_err(|_| "Unable to sign the message.")?;
+
+    let mut data = vec![header + recovery_id.to_byte()];
+    data.extend(signature.to_bytes());
+    Ok(STANDARD.encode(data))
+}
+
+// Whether 'signature' was made by the key behind 'address' (on 'network').
+pub fn verify(
+    address: &str,
+    message: &[u8],
+    signature: &str,
+    network: Network,
+) -> Result<bool, &'static str> {
+    let data = STANDARD
+        .decode(signature.trim())
+        .map_err(|_| "Signatures are base64 encoded.")?;
+    if data.len() != 65 || !(27..=42).contains(&data[0]) {
+        return Err("Not a BIP-137 signature.");
+    }
+
+    let recovery_id = (data[0] - 27) % 4;
+    let kind = data[0] - recovery_id;
+    let signature = Signature::from_slice(&data[1..]).map_err(|_| "Invalid signature.")?;
+    let recovery_id = RecoveryId::from_byte(recovery_id).ok_or("Invalid signature.")?;
+    let public_key =
+        match VerifyingKey::recover_from_prehash(&message_hash(message), &signature, recovery_id) {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(false),
+        };
+
+    let compressed = public_key.to_encoded_point(true);
+    let compressed = compressed.as_bytes();
+    Ok(match kind {
+        P2PKH_UNCOMPRESSED => {
+            address::p2pkh(public_key.to_encoded_point(false).as_bytes(), network) == address
+        }
+        // Electrum (and others) sign segwit addresses with the P2PKH header, so take any of the
+        // compressed key's addresses there:
+        P2PKH => [ScriptType::P2pkh, ScriptType::P2shP2wpkh, ScriptType::P2wpkh]
+            .iter()
+            .any(|&script_type| {
+                address::for_script_type(script_type, compressed, network).as_deref() == Ok(address)
+            }),
+        P2SH_P2WPKH => address::p2sh_p2wpkh(compressed, network) == address,
+        _ => address::p2wpkh(compressed, network).as_deref() == Ok(address),
+    })
+}
+
+// SHA256d("\x18Bitcoin Signed Message:\n" || varint(message length) || message):
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = MAGIC.to_vec();
+    push_varint(&mut data, message.len() as u64);
+    data.extend(message);
+    double_sha256(&data)
+}
@@ -0,0 +1,908 @@
+// BIP-32 hierarchical deterministic keys: the master key from a seed, child key
+// derivation (CKDpriv/CKDpub), and the "m/44'/0'/0'" style derivation paths.
+
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+use k256::elliptic_curve::ops::MulByGenerator;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, PublicKey, Scalar, SecretKey};
+
+#[cfg(feature = "std")]
+use address::{self, AddressMatch};
+use alloc_prelude::*;
+use base58;
+#[cfg(feature = "std")]
+use descriptor::ScriptType;
+use error::Error;
+use fault;
+use hash::{hash160, HmacSha512};
+use network::Network;
+use slip10::{Curve, ExtendedKey};
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use zeroize::Zeroize;
+
+const HARDENED_OFFSET: u32 = 1 << 31;
+
+// A child index; indexes at or above 2^31 are "hardened".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    pub fn normal(index: u32) -> Result<ChildNumber, Error> {
+        if index >= HARDENED_OFFSET {
+            return Err(Error::ChildIndexOutOfRange);
+        }
+        Ok(ChildNumber(index))
+    }
+
+    pub fn hardened(index: u32) -> Result<ChildNumber, Error> {
+        if index >= HARDENED_OFFSET {
+            return Err(Error::ChildIndexOutOfRange);
+        }
+        Ok(ChildNumber(index | HARDENED_OFFSET))
+    }
+
+    pub fn is_hardened(&self) -> bool {
+        self.0 >= HARDENED_OFFSET
+    }
+
+    // The index without the hardened bit, as written in a derivation path:
+    pub fn index(&self) -> u32 {
+        self.0 & !HARDENED_OFFSET
+    }
+}
+
+// The raw 32-bit value as it appears in the serialized key:
+impl From<u32> for ChildNumber {
+    fn from(value: u32) -> ChildNumber {
+        ChildNumber(value)
+    }
+}
+
+impl From<ChildNumber> for u32 {
+    fn from(child: ChildNumber) -> u32 {
+        child.0
+    }
+}
+
+// Hardened indexes print with an apostrophe, or with 'h' using the alternate form ("{:#}"),
+// which is what output descriptors tend to use.
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.index())?;
+        if self.is_hardened() {
+            f.write_str(if f.alternate() { "h" } else { "'" })?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    pub fn master() -> DerivationPath {
+        DerivationPath(Vec::new())
+    }
+
+    pub fn children(&self) -> &[ChildNumber] {
+        &self.0
+    }
+
+    pub fn child(&self, child: ChildNumber) -> DerivationPath {
+        let mut children = self.0.clone();
+        children.push(child);
+        DerivationPath(children)
+    }
+
+    // A path relative to some other key, such as "0/5" from an account xpub (no leading 'm'):
+    pub fn relative(path: &str) -> Result<DerivationPath, Error> {
+        if path.is_empty() {
+            return Ok(DerivationPath::master());
+        }
+        path.split('/')
+            .map(parse_child)
+            .collect::<Result<Vec<_>, _>>()
+            .map(DerivationPath)
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(children: Vec<ChildNumber>) -> DerivationPath {
+        DerivationPath(children)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<DerivationPath, Error> {
+        let mut parts = path.split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::PathWithoutMaster);
+        }
+
+        parts
+            .map(parse_child)
+            .collect::<Result<Vec<_>, _>>()
+            .map(DerivationPath)
+    }
+}
+
+// One "/"-separated step: an index, with a ', h or H suffix if it's hardened.
+fn parse_child(part: &str) -> Result<ChildNumber, Error> {
+    let (digits, hardened) = match part.strip_suffix(|c| c == '\'' || c == 'h' || c == 'H') {
+        Some(digits) => (digits, true),
+        None => (part, false),
+    };
+    let index = digits
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidPathComponent)?;
+    if hardened {
+        ChildNumber::hardened(index)
+    } else {
+        ChildNumber::normal(index)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("m")?;
+        for child in &self.0 {
+            if f.alternate() {
+                write!(f, "/{:#}", child)?;
+            } else {
+                write!(f, "/{}", child)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Where a key came from: the master key's fingerprint and the path down from it.  It prints as
+// the "[73c5da0a/84'/0'/0']" prefix descriptors and PSBTs use (with 'h' in the alternate form).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyOrigin {
+    pub fingerprint: [u8; 4],
+    pub path: DerivationPath,
+}
+
+impl KeyOrigin {
+    pub fn new(fingerprint: [u8; 4], path: DerivationPath) -> KeyOrigin {
+        KeyOrigin { fingerprint, path }
+    }
+}
+
+impl FromStr for KeyOrigin {
+    type Err = Error;
+
+    fn from_str(origin: &str) -> Result<KeyOrigin, Error> {
+        let inner = origin
+            .strip_prefix('[')
+            .and_then(|origin| origin.strip_suffix(']'))
+            .ok_or(Error::UnbracketedKeyOrigin)?;
+        let (fingerprint, path) = match inner.find('/') {
+            Some(pos) => inner.split_at(pos),
+            None => (inner, ""),
+        };
+
+        let fingerprint = hex::decode(fingerprint)
+            .ok()
+            .filter(|fingerprint| fingerprint.len() == 4)
+            .ok_or(Error::InvalidFingerprint)?;
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&fingerprint);
+
+        Ok(KeyOrigin::new(bytes, format!("m{}", path).parse()?))
+    }
+}
+
+impl fmt::Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if f.alternate() {
+            format!("{:#}", self.path)
+        } else {
+            self.path.to_string()
+        };
+        write!(
+            f,
+            "[{}{}]",
+            hex::encode(self.fingerprint),
+            path.trim_start_matches('m')
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct ExtendedPrivKey {
+    pub network: Network,
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: ChildNumber,
+    pub chain_code: [u8; 32],
+    // Kept private; use 'secret_key_bytes' to get at it deliberately.
+    secret_key: SecretKey,
+}
+
+impl ExtendedPrivKey {
+    pub fn new_master(seed: &[u8], network: Network) -> Result<ExtendedPrivKey, Error> {
+        fault::twice(|| ExtendedPrivKey::compute_master(seed, network), same_key)
+    }
+
+    fn compute_master(seed: &[u8], network: Network) -> Result<ExtendedPrivKey, Error> {
+        // SLIP-0010 on secp256k1 is BIP-32's master key generation (same "Bitcoin seed" key):
+        let master = ExtendedKey::new_master(seed, Curve::Secp256k1)?;
+        let secret_key = SecretKey::from_slice(&master.secret_key_bytes())
+            .map_err(|_| Error::InvalidMasterKey)?;
+
+        Ok(ExtendedPrivKey {
+            network,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber(0),
+            chain_code: master.chain_code,
+            secret_key,
+        })
+    }
+
+    // A depth 0 key from a chain code and private key that came from somewhere other than a seed
+    // (BIP-85's XPRV application, for one):
+    pub fn from_parts(
+        network: Network,
+        chain_code: [u8; 32],
+        secret_key: &[u8; 32],
+    ) -> Result<ExtendedPrivKey, Error> {
+        Ok(ExtendedPrivKey {
+            network,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber(0),
+            chain_code,
+            secret_key: SecretKey::from_slice(secret_key).map_err(|_| Error::InvalidPrivateKey)?,
+        })
+    }
+
+    // CKDpriv from the BIP-32 spec:
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedPrivKey, Error> {
+        self.derive_child_of(&Parent::new(&self.chain_code, self.public_key()), child)
+    }
+
+    // The normal children 'indexes' (e.g. a run of address keys), working out what they all
+    // share about this parent just once:
+    pub fn derive_children(&self, indexes: Range<u32>) -> Result<Vec<ExtendedPrivKey>, Error> {
+        let parent = Parent::new(&self.chain_code, self.public_key());
+        indexes
+            .map(|index| self.derive_child_of(&parent, ChildNumber::normal(index)?))
+            .collect()
+    }
+
+    fn derive_child_of(
+        &self,
+        parent: &Parent,
+        child: ChildNumber,
+    ) -> Result<ExtendedPrivKey, Error> {
+        fault::twice(|| self.compute_child(parent, child), same_key)
+    }
+
+    fn compute_child(&self, parent: &Parent, child: ChildNumber) -> Result<ExtendedPrivKey, Error> {
+        let mut data = Vec::with_capacity(37);
+        if child.is_hardened() {
+            data.push(0);
+            data.extend(&self.secret_key_bytes());
+        } else {
+            data.extend(&parent.public_key);
+        }
+        data.extend(&u32::from(child).to_be_bytes());
+
+        let output = parent.mac.mac(&data);
+        let (il, ir) = output.split_at(32);
+
+        let tweak = parse_scalar(il)?;
+        let child_scalar = tweak + *self.secret_key.to_nonzero_scalar();
+        let secret_key =
+            SecretKey::from_bytes(&child_scalar.to_bytes()).map_err(|_| Error::InvalidChildKey)?;
+
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(ir);
+
+        Ok(ExtendedPrivKey {
+            network: self.network,
+            depth: self.depth.checked_add(1).ok_or(Error::MaxDepthExceeded)?,
+            parent_fingerprint: parent.fingerprint,
+            child_number: child,
+            chain_code,
+            secret_key,
+        })
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPrivKey, Error> {
+        let mut key = self.clone();
+        for child in path.children() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+
+    pub fn to_extended_pub_key(&self) -> ExtendedPubKey {
+        ExtendedPubKey {
+            network: self.network,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.secret_key.public_key(),
+        }
+    }
+
+    // The compressed (33 byte) public key:
+    pub fn public_key(&self) -> [u8; 33] {
+        compress(&self.secret_key.public_key())
+    }
+
+    pub fn secret_key_bytes(&self) -> [u8; 32] {
+        self.secret_key.to_bytes().into()
+    }
+
+    // The key alone in Wallet Import Format, to sweep or import into other wallets: with 'network''s
+    // prefix (0x80 for mainnet, 0xEF for testnet and regtest), usually the key's own, and flagged
+    // for its compressed public key, as every key from a BIP-32 wallet is, unless 'compressed' is
+    // false (for software that only knows the uncompressed addresses of old wallets).
+    pub fn to_wif(&self, network: Network, compressed: bool) -> String {
+        base58::encode_wif(network.wif_prefix(), &self.secret_key_bytes(), compressed)
+    }
+
+    // The HASH160 of the compressed public key, which BIP-32 calls the key's identifier:
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key())
+    }
+
+    // The identifier's first four bytes, as children record their parent's and descriptors and
+    // PSBTs their master's:
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key())
+    }
+
+    // The raw 78 bytes BIP-32 serializes a key to, before Base58Check (e.g. for hex dumps):
+    pub fn to_bytes(&self) -> [u8; 78] {
+        let mut key_data = [0; 33];
+        key_data[1..].copy_from_slice(&self.secret_key_bytes());
+        serialize(
+            self.network.xprv_version(),
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key_data,
+        )
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<ExtendedPrivKey, Error> {
+        let key = deserialize(data)?;
+        let network =
+            Network::from_xprv_version(key.version).ok_or(Error::NotExtendedPrivateKey)?;
+        if key.key_data[0] != 0 {
+            return Err(Error::InvalidPrivateKeyData);
+        }
+
+        Ok(ExtendedPrivKey {
+            network,
+            depth: key.depth,
+            parent_fingerprint: key.parent_fingerprint,
+            child_number: key.child_number,
+            chain_code: key.chain_code,
+            secret_key: SecretKey::from_slice(&key.key_data[1..])
+                .map_err(|_| Error::InvalidPrivateKeyData)?,
+        })
+    }
+
+    // The xprv written out (to a file, say) without a copy of it staying behind in memory, as the
+    // 'String' of 'to_string()' would until the allocator reuses it:
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut bytes = self.to_bytes();
+        let mut encoded = base58::encode_check(&bytes);
+        let written = writer.write_all(encoded.as_bytes());
+        bytes.zeroize();
+        encoded.zeroize();
+        written
+    }
+}
+
+// 'SecretKey' wipes itself when dropped; '{:?}' leaves it out, and the chain code with it (see
+// 'slip10::ExtendedKey'), where 'Display' is the xprv for whoever asks for it:
+impl fmt::Debug for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedPrivKey")
+            .field("network", &self.network)
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &hex::encode(self.parent_fingerprint))
+            .field("child_number", &self.child_number)
+            .field("chain_code", &"[redacted]")
+            .field("secret_key", &"[redacted]")
+            .finish()
+    }
+}
+
+// Base58Check encoded, starting with "xprv" (or "tprv" on testnet):
+impl fmt::Display for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&base58::encode_check(&self.to_bytes()))
+    }
+}
+
+// (with bs58's error behind 'Error::Base58' when it isn't base58 at all)
+impl FromStr for ExtendedPrivKey {
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<ExtendedPrivKey, Error> {
+        ExtendedPrivKey::from_bytes(&base58::decode_check(encoded)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPubKey {
+    pub network: Network,
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: ChildNumber,
+    pub chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+impl ExtendedPubKey {
+    // CKDpub from the BIP-32 spec; only non-hardened children can be derived from a public key.
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedPubKey, Error> {
+        self.derive_child_of(&Parent::new(&self.chain_code, self.public_key()), child)
+    }
+
+    // As with private keys, the children 'indexes' with the parent's share worked out once:
+    pub fn derive_children(&self, indexes: Range<u32>) -> Result<Vec<ExtendedPubKey>, Error> {
+        let parent = Parent::new(&self.chain_code, self.public_key());
+        indexes
+            .map(|index| self.derive_child_of(&parent, ChildNumber::normal(index)?))
+            .collect()
+    }
+
+    fn derive_child_of(
+        &self,
+        parent: &Parent,
+        child: ChildNumber,
+    ) -> Result<ExtendedPubKey, Error> {
+        if child.is_hardened() {
+            return Err(Error::HardenedFromPublicKey);
+        }
+
+        let mut data = Vec::with_capacity(37);
+        data.extend(&parent.public_key);
+        data.extend(&u32::from(child).to_be_bytes());
+
+        let output = parent.mac.mac(&data);
+        let (il, ir) = output.split_at(32);
+
+        let tweak = parse_scalar(il)?;
+        let point = ProjectivePoint::mul_by_generator(&tweak) + self.public_key.to_projective();
+        let public_key =
+            PublicKey::from_affine(point.to_affine()).map_err(|_| Error::InvalidChildKey)?;
+
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(ir);
+
+        Ok(ExtendedPubKey {
+            network: self.network,
+            depth: self.depth.checked_add(1).ok_or(Error::MaxDepthExceeded)?,
+            parent_fingerprint: parent.fingerprint,
+            child_number: child,
+            chain_code,
+            public_key,
+        })
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPubKey, Error> {
+        let mut key = self.clone();
+        for child in path.children() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+
+    pub fn public_key(&self) -> [u8; 33] {
+        compress(&self.public_key)
+    }
+
+    // Whether 'address' is one of this (account) xpub's, looking at the first 'gap_limit' keys of
+    // the receive and change chains under every script type this network has addresses for:
+    #[cfg(feature = "std")]
+    pub fn contains_address(
+        &self,
+        address: &str,
+        gap_limit: u32,
+    ) -> Result<Option<AddressMatch>, Error> {
+        for &change in [false, true].iter() {
+            let chain = self.derive_child(ChildNumber::normal(if change { 1 } else { 0 })?)?;
+            for (index, key) in (0..gap_limit).zip(chain.derive_children(0..gap_limit)?) {
+                let public_key = key.public_key();
+                for &script_type in ScriptType::all().iter() {
+                    // (Script types without addresses on this network just don't match.)
+                    let candidate = address::for_script_type(script_type, &public_key, key.network);
+                    if candidate.as_ref().map(String::as_str) == Ok(address) {
+                        return Ok(Some(AddressMatch {
+                            change,
+                            index,
+                            script_type,
+                        }));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key())
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key())
+    }
+
+    pub fn to_bytes(&self) -> [u8; 78] {
+        serialize(
+            self.network.xpub_version(),
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.public_key(),
+        )
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<ExtendedPubKey, Error> {
+        let key = deserialize(data)?;
+        let network = Network::from_xpub_version(key.version).ok_or(Error::NotExtendedPublicKey)?;
+
+        Ok(ExtendedPubKey {
+            network,
+            depth: key.depth,
+            parent_fingerprint: key.parent_fingerprint,
+            child_number: key.child_number,
+            chain_code: key.chain_code,
+            public_key: PublicKey::from_sec1_bytes(&key.key_data)
+                .map_err(|_| Error::InvalidPublicKeyData)?,
+        })
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(base58::encode_check(&self.to_bytes()).as_bytes())
+    }
+}
+
+// Base58Check encoded, starting with "xpub" (or "tpub"):
+impl fmt::Display for ExtendedPubKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&base58::encode_check(&self.to_bytes()))
+    }
+}
+
+impl FromStr for ExtendedPubKey {
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<ExtendedPubKey, Error> {
+        ExtendedPubKey::from_bytes(&base58::decode_check(encoded)?)
+    }
+}
+
+// What every child derivation needs from its parent: the HMAC keyed with its chain code, its
+// compressed public key (a scalar multiplication away, for private keys) and its fingerprint.
+struct Parent {
+    mac: HmacSha512,
+    public_key: [u8; 33],
+    fingerprint: [u8; 4],
+}
+
+impl Parent {
+    fn new(chain_code: &[u8; 32], public_key: [u8; 33]) -> Parent {
+        Parent {
+            mac: HmacSha512::new(chain_code),
+            fingerprint: fingerprint(&public_key),
+            public_key,
+        }
+    }
+}
+
+// For 'fault::twice': two results agree when they're the same error, or the same key down to
+// every serialized byte.
+fn same_key(
+    first: &Result<ExtendedPrivKey, Error>,
+    second: &Result<ExtendedPrivKey, Error>,
+) -> bool {
+    match (first, second) {
+        (Ok(first), Ok(second)) => first.to_bytes() == second.to_bytes(),
+        (Err(first), Err(second)) => first == second,
+        _ => false,
+    }
+}
+
+// The 78 byte layout shared by both key types:
+fn serialize(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: &[u8; 32],
+    key_data: &[u8; 33],
+) -> [u8; 78] {
+    let mut data = [0; 78];
+    data[..4].copy_from_slice(&version);
+    data[4] = depth;
+    data[5..9].copy_from_slice(&parent_fingerprint);
+    data[9..13].copy_from_slice(&u32::from(child_number).to_be_bytes());
+    data[13..45].copy_from_slice(chain_code);
+    data[45..].copy_from_slice(key_data);
+    data
+}
+
+// The fields of a serialized key, before they're checked for the key type at hand:
+struct Serialized {
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key_data: [u8; 33],
+}
+
+fn deserialize(data: &[u8]) -> Result<Serialized, Error> {
+    if data.len() != 78 {
+        return Err(Error::InvalidExtendedKeyLength(data.len()));
+    }
+
+    let mut key = Serialized {
+        version: [0; 4],
+        depth: data[4],
+        parent_fingerprint: [0; 4],
+        child_number: ChildNumber(u32::from_be_bytes([data[9], data[10], data[11], data[12]])),
+        chain_code: [0; 32],
+        key_data: [0; 33],
+    };
+    key.version.copy_from_slice(&data[..4]);
+    key.parent_fingerprint.copy_from_slice(&data[5..9]);
+    key.chain_code.copy_from_slice(&data[13..45]);
+    key.key_data.copy_from_slice(&data[45..]);
+
+    // A master key has no parent:
+    if key.depth == 0 && (key.parent_fingerprint != [0; 4] || key.child_number.0 != 0) {
+        return Err(Error::MasterKeyWithParent);
+    }
+    Ok(key)
+}
+
+// BIP-32 calls a derived key invalid if parse256(IL) >= n:
+fn parse_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+    let mut repr = k256::FieldBytes::default();
+    repr.copy_from_slice(bytes);
+    Option::from(Scalar::from_repr(repr)).ok_or(Error::InvalidChildKey)
+}
+
+fn compress(public_key: &PublicKey) -> [u8; 33] {
+    let mut compressed = [0; 33];
+    compressed.copy_from_slice(public_key.to_encoded_point(true).as_bytes());
+    compressed
+}
+
+fn fingerprint(public_key: &[u8; 33]) -> [u8; 4] {
+    let mut fingerprint = [0; 4];
+    fingerprint.copy_from_slice(&hash160(public_key)[..4]);
+    fingerprint
+}
+
+// BIP-32's vectors 1 to 3 (and 4, which is 3's leading zero case with hardened children) are
+// 'test_vectors::BIP32', and vector 5's keys 'test_vectors::BIP32_INVALID', all of them shared with
+// 'verify_implementation':
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use test_vectors;
+
+    #[test]
+    fn spec_vectors() {
+        for vector in test_vectors::BIP32.iter() {
+            let master =
+                ExtendedPrivKey::new_master(&hex::decode(vector.seed).unwrap(), Network::Mainnet)
+                    .unwrap();
+
+            for chain in vector.chains {
+                let path: DerivationPath = chain.path.parse().unwrap();
+                let key = master.derive_path(&path).unwrap();
+                assert_eq!(key.to_string(), chain.xprv, "{}", chain.path);
+                assert_eq!(
+                    key.to_extended_pub_key().to_string(),
+                    chain.xpub,
+                    "{}",
+                    chain.path
+                );
+            }
+        }
+    }
+
+    // The derivation_path fuzz target's property, on the inputs most likely to break it: whatever
+    // parses prints back to the same path.
+    #[test]
+    fn path_round_trips() {
+        for path in ["m", "m/0", "m/0'/1h/2H", "m/2147483647'", "m/+5", "m/007"] {
+            let parsed: DerivationPath = path.parse().unwrap();
+            assert_eq!(
+                parsed.to_string().parse::<DerivationPath>(),
+                Ok(parsed),
+                "{}",
+                path
+            );
+        }
+        for path in [
+            "",
+            "M/0",
+            "m/",
+            "m//0",
+            "m/2147483648",
+            "m/4294967296",
+            "m/0''",
+            "m/-1",
+            "m/0 ",
+            "m/١",
+        ] {
+            assert!(path.parse::<DerivationPath>().is_err(), "{}", path);
+        }
+    }
+
+    // The origins descriptors write ("[d34db33f/44'/0'/0']" in BIP-380's examples), which print
+    // back the same, with 'h' in the alternate form:
+    #[test]
+    fn key_origins() {
+        let origin: KeyOrigin = "[d34db33f/44'/0'/0']".parse().unwrap();
+        assert_eq!(origin.fingerprint, [0xd3, 0x4d, 0xb3, 0x3f]);
+        assert_eq!(origin.path, "m/44'/0'/0'".parse().unwrap());
+        assert_eq!(origin.to_string(), "[d34db33f/44'/0'/0']");
+        assert_eq!(format!("{:#}", origin), "[d34db33f/44h/0h/0h]");
+        assert_eq!("[d34db33f/44h/0h/0h]".parse(), Ok(origin));
+
+        // A master key's own origin has no path:
+        let master: KeyOrigin = "[3442193e]".parse().unwrap();
+        assert_eq!(master.path, DerivationPath::master());
+        assert_eq!(master.to_string(), "[3442193e]");
+        let seed = hex::decode(test_vectors::BIP32[0].seed).unwrap();
+        let key = ExtendedPrivKey::new_master(&seed, Network::Mainnet).unwrap();
+        assert_eq!(master.fingerprint, key.fingerprint());
+
+        for origin in [
+            "d34db33f/44'",
+            "[d34db33f/44'",
+            "[d34db3/44']",
+            "[d34db33g]",
+            "[d34db33f/x]",
+            "[d34db33f/]",
+        ] {
+            assert!(origin.parse::<KeyOrigin>().is_err(), "{}", origin);
+        }
+    }
+
+    // Any chain of BIP-32's vectors, parsed back in, gets to the ones below it by a relative path:
+    #[test]
+    fn relative_derivation() {
+        let chains = test_vectors::BIP32[0].chains;
+        let xprv: ExtendedPrivKey = chains[1].xprv.parse().unwrap();
+        let path = DerivationPath::relative("1/2'").unwrap();
+        assert_eq!(xprv.derive_path(&path).unwrap().to_string(), chains[3].xprv);
+
+        let xpub: ExtendedPubKey = chains[3].xpub.parse().unwrap();
+        let path = DerivationPath::relative("2/1000000000").unwrap();
+        assert_eq!(xpub.derive_path(&path).unwrap().to_string(), chains[5].xpub);
+        // but not through a hardened step:
+        assert!(xpub
+            .derive_path(&DerivationPath::relative("2h").unwrap())
+            .is_err());
+
+        assert_eq!(DerivationPath::relative(""), Ok(DerivationPath::master()));
+        for path in ["m/0", "/0", "0/", "0//1", "x"] {
+            assert!(DerivationPath::relative(path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn raw_serialization() {
+        for vector in test_vectors::BIP32.iter() {
+            for chain in vector.chains {
+                let xprv: ExtendedPrivKey = chain.xprv.parse().unwrap();
+                let bytes = xprv.to_bytes();
+                assert_eq!(
+                    ExtendedPrivKey::from_bytes(&bytes).unwrap().to_string(),
+                    chain.xprv
+                );
+                // The private key's bytes aren't an extended public key:
+                assert!(ExtendedPubKey::from_bytes(&bytes).is_err());
+
+                let xpub: ExtendedPubKey = chain.xpub.parse().unwrap();
+                let bytes = xpub.to_bytes();
+                assert_eq!(
+                    ExtendedPubKey::from_bytes(&bytes).unwrap().to_string(),
+                    chain.xpub
+                );
+                assert!(ExtendedPubKey::from_bytes(&bytes[..77]).is_err());
+            }
+        }
+    }
+
+    // A key's identifier is the same whether it's private or public, its fingerprint is the
+    // identifier's first four bytes, and that's what its children record as their parent's:
+    #[test]
+    fn identifiers() {
+        let chains = test_vectors::BIP32[0].chains;
+        let xprv: ExtendedPrivKey = chains[0].xprv.parse().unwrap();
+        let xpub: ExtendedPubKey = chains[0].xpub.parse().unwrap();
+        let child: ExtendedPrivKey = chains[1].xprv.parse().unwrap();
+        assert_eq!(xprv.identifier(), xpub.identifier());
+        assert_eq!(xprv.fingerprint()[..], xprv.identifier()[..4]);
+        assert_eq!(child.parent_fingerprint, xprv.fingerprint());
+        assert_eq!((child.depth, xprv.depth), (1, 0));
+    }
+
+    // BIP-32's vector 5, each key refused (as whichever kind its version says it is) for the
+    // reason the spec gives:
+    #[test]
+    fn spec_invalid_keys() {
+        for &(key, reason) in test_vectors::BIP32_INVALID.iter() {
+            let private = key.starts_with("xprv");
+            let expected = match reason {
+                "zero depth with non-zero parent fingerprint"
+                | "zero depth with non-zero index" => {
+                    "Master key with a parent fingerprint or child number."
+                }
+                "unknown extended key version" => "Not an extended public key.",
+                "invalid checksum" => "Base58Check checksum mismatch.",
+                _ if private => "Invalid extended private key data.",
+                _ => "Invalid extended public key data.",
+            };
+            let err = if private {
+                key.parse::<ExtendedPrivKey>().err()
+            } else {
+                key.parse::<ExtendedPubKey>().err()
+            };
+            assert_eq!(err.map(|err| err.message()), Some(expected), "{}", reason);
+
+            // and refused as the other kind too:
+            assert!(key.parse::<ExtendedPrivKey>().is_err());
+            assert!(key.parse::<ExtendedPubKey>().is_err());
+        }
+    }
+
+    #[test]
+    fn spec_vectors_public_derivation() {
+        // Wherever a chain steps to a normal child, the public parent has to get there on its own:
+        for vector in test_vectors::BIP32.iter() {
+            let master =
+                ExtendedPrivKey::new_master(&hex::decode(vector.seed).unwrap(), Network::Mainnet)
+                    .unwrap();
+
+            for pair in vector.chains.windows(2) {
+                let child: DerivationPath = pair[1].path.parse().unwrap();
+                let last = *child.children().last().unwrap();
+                if last.is_hardened() {
+                    continue;
+                }
+
+                let parent: DerivationPath = pair[0].path.parse().unwrap();
+                let parent = master.derive_path(&parent).unwrap().to_extended_pub_key();
+                assert_eq!(
+                    parent.derive_child(last).unwrap().to_string(),
+                    pair[1].xpub,
+                    "{}",
+                    pair[1].path
+                );
+            }
+        }
+    }
+}
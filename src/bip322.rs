@@ -0,0 +1,223 @@
+// BIP-322 "simple" message signatures, which cover the segwit addresses BIP-137 can't (and so
+// are what modern wallets ask for with bc1 addresses).  The message is committed to by a virtual
+// transaction ("to_spend") paying to the address, and the signature is the witness of a second
+// virtual transaction ("to_sign") spending it, as though it were a real spend.
+//
+// Simple signatures only exist for witness programs; this covers p2wpkh and key path p2tr.
+
+use std::convert::TryFrom;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{schnorr, SecretKey};
+
+use address;
+use descriptor::ScriptType;
+use hash::{double_sha256, hash160, sha256, tagged_hash};
+use network::Network;
+use taproot;
+use tx::{push_bytes, push_varint, read_bytes, read_varint};
+
+const SIGHASH_DEFAULT: u8 = 0x00;
+const SIGHASH_ALL: u8 = 0x01;
+
+// The base64 encoded witness proving 'secret_key' controls its 'script_type' address:
+pub fn sign(
+    secret_key: &[u8; 32],
+    script_type: ScriptType,
+    message: &[u8],
+) -> Result<String, &'static str> {
+    let key = SecretKey::from_slice(secret_key).map_err(|_| "Invalid private key.")?;
+    let public_key = key.public_key().to_encoded_point(true);
+    let public_key = public_key.as_bytes();
+
+    let witness = match script_type {
+        ScriptType::P2wpkh => {
+            let key_hash = hash160(public_key);
+            let to_spend = to_spend_txid(&address::segwit_script(0, &key_hash), message);
+            let digest = segwit_v0_sighash(&to_spend, &key_hash);
+
+            let signature: Signature = SigningKey::from(&key)
+                .sign_prehash(&digest)
+                .map_err(|_| "Unable to sign the message.")?;
+            let mut signature = signature.to_der().as_bytes().to_vec();
+            signature.push(SIGHASH_ALL);
+            vec![signature, public_key.to_vec()]
+        }
+        ScriptType::P2tr => {
+            let mut internal_key = [0; 32];
+            internal_key.copy_from_slice(&public_key[1..]);
+            let (output_key, _) = taproot::taproot_tweak(&internal_key, None)?;
+            let script = address::segwit_script(1, &output_key);
+            let digest = taproot_sighash(&to_spend_txid(&script, message), &script, SIGHASH_DEFAULT);
+
+            let tweaked = taproot::tweak_secret_key(secret_key, None)?;
+            let signing_key =
+                schnorr::SigningKey::from_bytes(&tweaked).map_err(|_| "Invalid private key.")?;
+            // BIP-340's "auxiliary randomness" is optional; zeros keep signatures reproducible.
+            let signature = signing_key
+                .sign_raw(&digest, &[0; 32])
+                .map_err(|_| "Unable to sign the message.")?;
+            vec![signature.to_bytes().to_vec()]
+        }
+        _ => return Err("BIP-322 simple signatures are for p2wpkh and p2tr addresses."),
+    };
+
+    let mut data = Vec::new();
+    push_varint(&mut data, witness.len() as u64);
+    for item in &witness {
+        push_bytes(&mut data, item);
+    }
+    Ok(STANDARD.encode(data))
+}
+
+// Whether 'signature' is a valid simple signature of 'message' for 'address' (on 'network').
+pub fn verify(
+    address: &str,
+    message: &[u8],
+    signature: &str,
+    network: Network,
+) -> Result<bool, &'static str> {
+    let (version, program) = address::decode_segwit(address, network)?;
+    let script = address::segwit_script(version, &program);
+    let to_spend = to_spend_txid(&script, message);
+
+    let data = STANDARD
+        .decode(signature.trim())
+        .map_err(|_| "Signatures are base64 encoded.")?;
+    let witness = parse_witness(&data)?;
+
+    match (version, program.len()) {
+        (0, 20) => {
+            if witness.len() != 2 || hash160(witness[1]) != program[..] {
+                return Ok(false);
+            }
+            let (signature, hash_type) = match witness[0].split_last() {
+                Some((&hash_type, signature)) => (signature, hash_type),
+                None => return Ok(false),
+            };
+            let (signature, public_key) = match (
+                Signature::from_der(signature),
+                VerifyingKey::from_sec1_bytes(witness[1]),
+            ) {
+                (Ok(signature), Ok(public_key)) => (signature, public_key),
+                _ => return Ok(false),
+            };
+
+            let digest = segwit_v0_sighash(&to_spend, &program);
+            Ok(hash_type == SIGHASH_ALL && public_key.verify_prehash(&digest, &signature).is_ok())
+        }
+        (1, 32) => {
+            // 64 bytes means SIGHASH_DEFAULT; a 65th byte gives the hash type explicitly.
+            let (signature, hash_type) = match witness.as_slice() {
+                [signature] if signature.len() == 64 => (*signature, SIGHASH_DEFAULT),
+                [signature] if signature.len() == 65 && signature[64] == SIGHASH_ALL => {
+                    (&signature[..64], SIGHASH_ALL)
+                }
+                _ => return Ok(false),
+            };
+            let (signature, output_key) = match (
+                schnorr::Signature::try_from(signature),
+                schnorr::VerifyingKey::from_bytes(&program),
+            ) {
+                (Ok(signature), Ok(output_key)) => (signature, output_key),
+                _ => return Ok(false),
+            };
+
+            let digest = taproot_sighash(&to_spend, &script, hash_type);
+            Ok(output_key.verify_raw(&digest, &signature).is_ok())
+        }
+        _ => Err("BIP-322 simple signatures are for p2wpkh and p2tr addresses."),
+    }
+}
+
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    tagged_hash("BIP0322-signed-message", message)
+}
+
+// The txid of the transaction "spending" nothing into 'script', with the message in its
+// scriptSig:
+fn to_spend_txid(script: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut tx = vec![0; 4]; // version 0
+    tx.push(1);
+    tx.extend(&[0; 32]);
+    tx.extend(&[0xFF; 4]);
+    let mut script_sig = vec![0x00, 0x20]; // OP_0 PUSH32
+    script_sig.extend(&message_hash(message));
+    push_bytes(&mut tx, &script_sig);
+    tx.extend(&[0; 4]); // sequence
+    tx.push(1);
+    tx.extend(&[0; 8]); // value
+    push_bytes(&mut tx, script);
+    tx.extend(&[0; 4]); // lock time
+    double_sha256(&tx)
+}
+
+// "to_sign"'s single input spends output 0 of "to_spend", and its single output is an empty
+// OP_RETURN:
+fn to_sign_prevout(to_spend: &[u8; 32]) -> Vec<u8> {
+    let mut prevout = to_spend.to_vec();
+    prevout.extend(&[0; 4]);
+    prevout
+}
+
+fn to_sign_output() -> Vec<u8> {
+    let mut output = vec![0; 8];
+    push_bytes(&mut output, &[0x6A]);
+    output
+}
+
+// BIP-143's signature hash for a p2wpkh input (SIGHASH_ALL):
+fn segwit_v0_sighash(to_spend: &[u8; 32], key_hash: &[u8]) -> [u8; 32] {
+    let prevout = to_sign_prevout(to_spend);
+    let mut script_code = vec![0x76, 0xA9, 0x14]; // OP_DUP OP_HASH160 PUSH20
+    script_code.extend(key_hash);
+    script_code.extend(&[0x88, 0xAC]); // OP_EQUALVERIFY OP_CHECKSIG
+
+    let mut preimage = vec![0; 4]; // version
+    preimage.extend(&double_sha256(&prevout));
+    preimage.extend(&double_sha256(&[0; 4])); // sequences
+    preimage.extend(&prevout);
+    push_bytes(&mut preimage, &script_code);
+    preimage.extend(&[0; 8]); // the spent amount
+    preimage.extend(&[0; 4]); // sequence
+    preimage.extend(&double_sha256(&to_sign_output()));
+    preimage.extend(&[0; 4]); // lock time
+    preimage.extend(&u32::from(SIGHASH_ALL).to_le_bytes());
+    double_sha256(&preimage)
+}
+
+// BIP-341's signature hash for a key path spend (without an annex), for SIGHASH_DEFAULT or
+// SIGHASH_ALL, which commit to the same things:
+fn taproot_sighash(to_spend: &[u8; 32], script: &[u8], hash_type: u8) -> [u8; 32] {
+    let mut spent_script = Vec::new();
+    push_bytes(&mut spent_script, script);
+
+    let mut message = vec![0x00, hash_type]; // epoch, hash type
+    message.extend(&[0; 4]); // version
+    message.extend(&[0; 4]); // lock time
+    message.extend(&sha256(&to_sign_prevout(to_spend)));
+    message.extend(&sha256(&[0; 8])); // amounts
+    message.extend(&sha256(&spent_script));
+    message.extend(&sha256(&[0; 4])); // sequences
+    message.extend(&sha256(&to_sign_output()));
+    message.push(0x00); // spend type: key path, no annex
+    message.extend(&[0; 4]); // input index
+    tagged_hash("TapSighash", &message)
+}
+
+fn parse_witness(data: &[u8]) -> Result<Vec<&[u8]>, &'static str> {
+    let mut data = data;
+    let count = read_varint(&mut data)?;
+    let mut witness = Vec::new();
+    for _ in 0..count {
+        witness.push(read_bytes(&mut data)?);
+    }
+    if !data.is_empty() {
+        return Err("Trailing data after the signature's witness.");
+    }
+    Ok(witness)
+}
@@ -0,0 +1,201 @@
+// BIP-38 passphrase-protected private keys (the non-EC-multiplied variant): a "6P..." string
+// that is safe to print or store where it might be found, as long as the passphrase isn't.
+//
+// The passphrase goes into scrypt in Unicode NFC, as the spec says, so that one typed with a
+// precomposed letter and one with a combining accent make the same key, here and in other wallets.
+
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes256;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::SecretKey;
+use scrypt::{scrypt, Params as ScryptParams};
+use unicode_normalization::UnicodeNormalization;
+
+use address;
+use base58;
+use hash::double_sha256;
+use network::Network;
+use zeroize::Zeroize;
+
+const PREFIX: [u8; 2] = [0x01, 0x42];
+const FLAG_COMPRESSED: u8 = 0xe0;
+const FLAG_UNCOMPRESSED: u8 = 0xc0;
+
+// 'compressed' selects which form of the public key (and so which address) the key belongs to.
+pub fn encrypt(
+    secret_key: &[u8; 32],
+    compressed: bool,
+    passphrase: &str,
+) -> Result<String, &'static str> {
+    let address_hash = get_address_hash(secret_key, compressed)?;
+    let (derived_half1, derived_half2) = derive_halves(passphrase, &address_hash);
+
+    let mut encrypted = *secret_key;
+    xor(&mut encrypted, &derived_half1);
+    let cipher = Aes256::new(&derived_half2.into());
+    cipher.encrypt_block((&mut encrypted[..16]).into());
+    cipher.encrypt_block((&mut encrypted[16..]).into());
+
+    let mut data = Vec::with_capacity(39);
+    data.extend(&PREFIX);
+    data.push(if compressed { FLAG_COMPRESSED } else { FLAG_UNCOMPRESSED });
+    data.extend(&address_hash);
+    data.extend(&encrypted);
+    Ok(base58::encode_check(&data))
+}
+
+// Returns the secret key and whether it belongs to a compressed public key.
+pub fn decrypt(encrypted: &str, passphrase: &str) -> Result<([u8; 32], bool), &'static str> {
+    let data = base58::decode_check(encrypted)?;
+    if data.len() != 39 || data[..2] != PREFIX {
+        return Err("Not a BIP-38 encrypted key (or an unsupported EC-multiplied one).");
+    }
+    let compressed = match data[2] {
+        FLAG_COMPRESSED => true,
+        FLAG_UNCOMPRESSED => false,
+        _ => return Err("Unsupported BIP-38 flag byte."),
+    };
+    let address_hash = &data[3..7];
+    let (derived_half1, derived_half2) = derive_halves(passphrase, address_hash);
+
+    let cipher = Aes256::new(&derived_half2.into());
+    let mut secret_key = [0; 32];
+    secret_key.copy_from_slice(&data[7..]);
+    cipher.decrypt_block((&mut secret_key[..16]).into());
+    cipher.decrypt_block((&mut secret_key[16..]).into());
+    xor(&mut secret_key, &derived_half1);
+
+    // The address hash doubles as the check that the passphrase was right:
+    match get_address_hash(&secret_key, compressed) {
+        Ok(hash) if hash[..] == *address_hash => Ok((secret_key, compressed)),
+        _ => Err("Wrong passphrase."),
+    }
+}
+
+// The first four bytes of the double SHA-256 of the key's P2PKH address (always the mainnet one;
+// "6P..." keys don't record a network):
+fn get_address_hash(secret_key: &[u8; 32], compressed: bool) -> Result<[u8; 4], &'static str> {
+    let secret_key = SecretKey::from_slice(secret_key).map_err(|_| "Invalid private key.")?;
+    let public_key = secret_key.public_key().to_encoded_point(compressed);
+    let address = address::p2pkh(public_key.as_bytes(), Network::Mainnet);
+
+    let mut address_hash = [0; 4];
+    address_hash.copy_from_slice(&double_sha256(address.as_bytes())[..4]);
+    Ok(address_hash)
+}
+
+fn derive_halves(passphrase: &str, address_hash: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut passphrase: String = passphrase.nfc().collect();
+    let mut derived = [0; 64];
+    let params = ScryptParams::new(14, 8, 8, derived.len()).unwrap();
+    scrypt(passphrase.as_bytes(), address_hash, &params, &mut derived).unwrap();
+
+    let mut half1 = [0; 32];
+    let mut half2 = [0; 32];
+    half1.copy_from_slice(&derived[..32]);
+    half2.copy_from_slice(&derived[32..]);
+    passphrase.zeroize();
+    derived.zeroize();
+    (half1, half2)
+}
+
+fn xor(block: &mut [u8; 32], mask: &[u8; 32]) {
+    for (byte, mask) in block.iter_mut().zip(mask.iter()) {
+        *byte ^= mask;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-38's own vectors, "no compression, no EC multiply" and "compression, no EC multiply":
+    const VECTORS: [(&str, &str, &str, bool); 4] = [
+        (
+            "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg",
+            "TestingOneTwoThree",
+            "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+            false,
+        ),
+        (
+            "6PRNFFkZc2NZ6dJqFfhRoFNMR9Lnyj7dYGrzdgXXVMXcxoKTePPX1dWByq",
+            "Satoshi",
+            "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+            false,
+        ),
+        (
+            "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+            "TestingOneTwoThree",
+            "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+            true,
+        ),
+        (
+            "6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7",
+            "Satoshi",
+            "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+            true,
+        ),
+    ];
+
+    fn secret(hex: &str) -> [u8; 32] {
+        let mut secret = [0; 32];
+        secret.copy_from_slice(&hex::decode(hex).unwrap());
+        secret
+    }
+
+    #[test]
+    fn spec_vectors() {
+        for &(encrypted, passphrase, key, compressed) in VECTORS.iter() {
+            assert_eq!(
+                encrypt(&secret(key), compressed, passphrase).unwrap(),
+                encrypted
+            );
+            assert_eq!(
+                decrypt(encrypted, passphrase).unwrap(),
+                (secret(key), compressed)
+            );
+        }
+    }
+
+    // The spec's Unicode vector: GREEK UPSILON WITH HOOK, COMBINING ACUTE ACCENT, NULL, DESERET
+    // CAPITAL LETTER LONG I and PILE OF POO, which NFC turns the first two of into U+03D3:
+    #[test]
+    fn unicode_passphrase() {
+        let passphrase = "\u{03D2}\u{0301}\u{0000}\u{10400}\u{1F4A9}";
+        let encrypted = "6PRW5o9FLp4gJDDVqJQKJFTpMvdsSGJxMYHtHaQBF3ooa8mwD69bapcDQn";
+        let key = secret("64eeab5f9be2a01a8365a579511eb3373c87c40da6d2a25f05bda68fe077b66e");
+        assert_eq!(encrypt(&key, false, passphrase).unwrap(), encrypted);
+        assert_eq!(decrypt(encrypted, passphrase).unwrap(), (key, false));
+        // (already composed, it's the same passphrase)
+        let composed = "\u{03D3}\u{0000}\u{10400}\u{1F4A9}";
+        assert_eq!(decrypt(encrypted, composed).unwrap(), (key, false));
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let key = secret(VECTORS[2].2);
+        let encrypted = encrypt(&key, true, "correct horse").unwrap();
+        assert_eq!(decrypt(&encrypted, "correct horse").unwrap(), (key, true));
+        assert_eq!(
+            decrypt(&encrypted, "battery staple").unwrap_err(),
+            "Wrong passphrase."
+        );
+    }
+
+    // What the bip38 fuzz target feeds in: anything at all fails cleanly, before scrypt for
+    // most of it.
+    #[test]
+    fn malformed_input() {
+        let valid = VECTORS[0].0;
+        for encrypted in [
+            "",
+            "6P",
+            &valid[..valid.len() - 1],
+            &valid.replace('z', "0"),
+            "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ",
+            "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGh",
+        ] {
+            assert!(decrypt(encrypted, "passphrase").is_err(), "{}", encrypted);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+// BIP-47 reusable payment codes: the version 1 "PM8T..." code for m/47'/coin_type'/account'.
+
+use base58;
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+
+// The Base58Check version byte that makes payment codes start with "P":
+const VERSION_BYTE: u8 = 0x47;
+
+pub fn payment_code(root: &ExtendedPrivKey, account: u32) -> Result<String, &'static str> {
+    let path = DerivationPath::from(vec![
+        ChildNumber::hardened(47)?,
+        ChildNumber::hardened(root.network.coin_type())?,
+        ChildNumber::hardened(account)?,
+    ]);
+    let key = root.derive_path(&path)?;
+
+    // version | features (none) | public key | chain code | 13 reserved zero bytes:
+    let mut data = Vec::with_capacity(81);
+    data.push(VERSION_BYTE);
+    data.push(1);
+    data.push(0);
+    data.extend(&key.public_key());
+    data.extend(&key.chain_code);
+    data.extend(&[0; 13]);
+    Ok(base58::encode_check(&data))
+}
@@ -0,0 +1,159 @@
+// BIP-85: deterministic entropy for other wallets and applications, derived from the root key
+// so that one backup covers all of them.  Each application has its own path under
+// m/83696968'; the private key found there is run through HMAC-SHA512 to give 64 bytes of
+// entropy, which the application then uses (or stretches with the SHAKE256 based DRNG).
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Shake256, Shake256Reader};
+
+use base58;
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+use error::Error;
+use hash::hmac_sha512;
+use language::Language;
+use mnemonic::Mnemonic;
+use MnemonicListSize;
+
+const PURPOSE: u32 = 83696968;
+
+// Application numbers:
+const BIP39: u32 = 39;
+const WIF: u32 = 2;
+const XPRV: u32 = 32;
+const HEX: u32 = 128169;
+const PWD_BASE64: u32 = 707764;
+const PWD_BASE85: u32 = 707785;
+
+// Python's 'base64.b85encode' alphabet (RFC 1924's), which the base85 passwords use:
+const BASE85_ALPHABET: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+// The 64 bytes of entropy for an application path, e.g. m/83696968'/2'/0':
+pub fn derive_entropy(root: &ExtendedPrivKey, path: &DerivationPath) -> Result<[u8; 64], Error> {
+    let key = root.derive_path(path)?;
+    Ok(hmac_sha512(b"bip-entropy-from-k", &key.secret_key_bytes()))
+}
+
+// m/83696968'/<application>'/<further hardened indexes>':
+pub fn application_path(application: u32, indexes: &[u32]) -> Result<DerivationPath, Error> {
+    let mut children = vec![ChildNumber::hardened(PURPOSE)?, ChildNumber::hardened(application)?];
+    for index in indexes {
+        children.push(ChildNumber::hardened(*index)?);
+    }
+    Ok(DerivationPath::from(children))
+}
+
+// A child mnemonic, a wallet of its own that needs no backup but this one's: the first 16 to 32
+// bytes of the entropy (as many as 'word_count' words take), in 'language''s words.  The language
+// is part of the path too, so the same index in another language is another wallet, not the same
+// one in translation.
+pub fn mnemonic(
+    root: &ExtendedPrivKey,
+    language: Language,
+    word_count: MnemonicListSize,
+    index: u32,
+) -> Result<Mnemonic, Error> {
+    let words = word_count as u32;
+    let path = application_path(BIP39, &[language_number(language)?, words, index])?;
+    let entropy = derive_entropy(root, &path)?;
+    Mnemonic::from_entropy_bytes(&entropy[..(words * 4 / 3) as usize], language)
+}
+
+// BIP-85's numbers for the wordlists, which aren't the order they're listed in anywhere else:
+fn language_number(language: Language) -> Result<u32, Error> {
+    Ok(match language {
+        Language::English => 0,
+        Language::Japanese => 1,
+        Language::Korean => 2,
+        Language::Spanish => 3,
+        Language::ChineseSimplified => 4,
+        Language::ChineseTraditional => 5,
+        Language::French => 6,
+        Language::Italian => 7,
+        Language::Czech => 8,
+        Language::Portuguese => 9,
+        Language::Custom(_) => return Err(Error::Other("BIP-85 has no number for a custom wordlist.")),
+    })
+}
+
+// A private key in Wallet Import Format, for the root key's network:
+pub fn wif(root: &ExtendedPrivKey, index: u32) -> Result<String, Error> {
+    let entropy = derive_entropy(root, &application_path(WIF, &[index])?)?;
+    let mut secret_key = [0; 32];
+    secret_key.copy_from_slice(&entropy[..32]);
+    Ok(base58::encode_wif(root.network.wif_prefix(), &secret_key, true))
+}
+
+// A fresh master key: the chain code is the first half of the entropy and the key the second.
+pub fn xprv(root: &ExtendedPrivKey, index: u32) -> Result<ExtendedPrivKey, Error> {
+    let entropy = derive_entropy(root, &application_path(XPRV, &[index])?)?;
+    let mut chain_code = [0; 32];
+    let mut secret_key = [0; 32];
+    chain_code.copy_from_slice(&entropy[..32]);
+    secret_key.copy_from_slice(&entropy[32..]);
+    ExtendedPrivKey::from_parts(root.network, chain_code, &secret_key)
+}
+
+// 'num_bytes' (16 to 64) of raw entropy as hex:
+pub fn hex(root: &ExtendedPrivKey, num_bytes: u32, index: u32) -> Result<String, Error> {
+    if !(16..=64).contains(&num_bytes) {
+        return Err(Error::Other("BIP-85 hex entropy must be between 16 and 64 bytes."));
+    }
+    let entropy = derive_entropy(root, &application_path(HEX, &[num_bytes, index])?)?;
+    Ok(hex::encode(&entropy[..num_bytes as usize]))
+}
+
+// A 'length' (20 to 86) character password: the start of the base64 encoded entropy.
+pub fn password_base64(root: &ExtendedPrivKey, length: u32, index: u32) -> Result<String, Error> {
+    if !(20..=86).contains(&length) {
+        return Err(Error::Other("BIP-85 base64 passwords must be between 20 and 86 characters."));
+    }
+    let entropy = derive_entropy(root, &application_path(PWD_BASE64, &[length, index])?)?;
+    let mut password = STANDARD.encode(&entropy[..]);
+    password.truncate(length as usize);
+    Ok(password)
+}
+
+// A 'length' (10 to 80) character password: the start of the base85 encoded entropy, for sites
+// that want symbols in their passwords.
+pub fn password_base85(root: &ExtendedPrivKey, length: u32, index: u32) -> Result<String, Error> {
+    if !(10..=80).contains(&length) {
+        return Err(Error::Other("BIP-85 base85 passwords must be between 10 and 80 characters."));
+    }
+    let entropy = derive_entropy(root, &application_path(PWD_BASE85, &[length, index])?)?;
+    let mut password = base85(&entropy);
+    password.truncate(length as usize);
+    Ok(password)
+}
+
+// Every 4 bytes (as a big endian number) become 5 base 85 digits, most significant first;
+// 64 bytes divide evenly, so there's no padding to deal with:
+fn base85(bytes: &[u8; 64]) -> String {
+    let mut encoded = String::with_capacity(80);
+    for chunk in bytes.chunks(4) {
+        let mut value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let mut digits = [0; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = BASE85_ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+        encoded.extend(digits.iter().map(|&digit| digit as char));
+    }
+    encoded
+}
+
+// BIP85-DRNG-SHAKE256: an endless stream of bytes seeded with an application's entropy, for
+// applications that need more than 64 bytes.
+pub struct Drng(Shake256Reader);
+
+impl Drng {
+    pub fn new(entropy: &[u8; 64]) -> Drng {
+        Drng(Shake256::default().chain(entropy).finalize_xof())
+    }
+
+    pub fn read(&mut self, output: &mut [u8]) {
+        self.0.read(output);
+    }
+}
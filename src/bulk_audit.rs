@@ -0,0 +1,94 @@
+// Auditing a stash of old backups to find the real wallet: a file of mnemonics, one to a line,
+// each checked against the wordlists and, when it's valid, reduced to its master fingerprint (and
+// optionally its first receive address) to compare with what the wallet software shows.  PBKDF2
+// is the slow part, so with the 'parallel' feature the lines are spread across threads; the
+// results come back in file order either way.
+//
+// Blank lines and lines starting with '#' are skipped, so backups can be annotated.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use account::Account;
+use address;
+use descriptor::ScriptType;
+use error::Error;
+use language::Language;
+use mnemonic::Mnemonic;
+use network::Network;
+use {normalize_mnemonic, Seed};
+
+pub struct LineAudit {
+    // Counting from 1, as editors do:
+    pub line: usize,
+    pub result: Result<AuditedWallet, &'static str>,
+}
+
+pub struct AuditedWallet {
+    pub language: Language,
+    pub fingerprint: [u8; 4],
+    // The first receive address of account 0, for the script type asked for:
+    pub address: Option<String>,
+}
+
+pub fn audit(
+    text: &str,
+    passphrase: &str,
+    network: Network,
+    script_type: Option<ScriptType>,
+) -> Vec<LineAudit> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    let check = |&(line, phrase): &(usize, &str)| LineAudit {
+        line,
+        result: audit_phrase(phrase, passphrase, network, script_type),
+    };
+
+    #[cfg(feature = "parallel")]
+    return lines.par_iter().map(check).collect();
+    #[cfg(not(feature = "parallel"))]
+    return lines.iter().map(check).collect();
+}
+
+fn audit_phrase(
+    phrase: &str,
+    passphrase: &str,
+    network: Network,
+    script_type: Option<ScriptType>,
+) -> Result<AuditedWallet, &'static str> {
+    let mnemonic = parse_any(&normalize_mnemonic(phrase))?;
+    let root = Seed::new(&mnemonic, passphrase).to_root_key(network)?;
+    let address = match script_type {
+        Some(script_type) => {
+            let account = Account::derive(&root, script_type, 0)?;
+            let first = address::derive_addresses(&account, false, 0..1)?.remove(0);
+            Some(first.address)
+        }
+        None => None,
+    };
+
+    Ok(AuditedWallet {
+        language: mnemonic.language(),
+        fingerprint: root.fingerprint(),
+        address,
+    })
+}
+
+// Old backups aren't labelled with their language: the build's default wordlist first, as the
+// likeliest, then the others, and the default's error if none of them have all the words.
+fn parse_any(phrase: &str) -> Result<Mnemonic, Error> {
+    let compiled = Language::compiled();
+    let error = match Mnemonic::parse(phrase, compiled) {
+        Ok(mnemonic) => return Ok(mnemonic),
+        Err(err) => err,
+    };
+    Language::available()
+        .into_iter()
+        .filter(|&language| language != compiled)
+        .find_map(|language| Mnemonic::parse(phrase, language).ok())
+        .ok_or(error)
+}
@@ -0,0 +1,80 @@
+// Decoy (duress) wallets: the same mnemonic with a different passphrase is a completely separate
+// wallet.  Funding the empty passphrase wallet with a little and hiding the real funds behind a
+// passphrase only works if the wallets can be told apart later, so this summarizes each one by
+// its master fingerprint and first receive address.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use account::Account;
+use address;
+use bip32::ExtendedPrivKey;
+use descriptor::ScriptType;
+use mnemonic::Mnemonic;
+use network::Network;
+use Seed;
+
+pub struct Wallet {
+    pub fingerprint: [u8; 4],
+    // The legacy address at m/44'/0'/0'/0/0:
+    pub first_address: String,
+    // The wallet itself, for checking against more than the above:
+    pub seed: Seed,
+    pub root_key: ExtendedPrivKey,
+}
+
+// One seed per passphrase, in order: every hidden wallet a mnemonic opens, in one go (and with
+// the 'parallel' feature, the PBKDF2 runs side by side).
+pub fn derive_seeds(mnemonic: &Mnemonic, passphrases: &[&str]) -> Vec<Seed> {
+    #[cfg(feature = "parallel")]
+    return passphrases.par_iter().map(|&passphrase| mnemonic.to_seed(passphrase)).collect();
+    #[cfg(not(feature = "parallel"))]
+    return passphrases.iter().map(|&passphrase| mnemonic.to_seed(passphrase)).collect();
+}
+
+// One Wallet per passphrase, in order, always starting with the empty passphrase:
+pub fn wallets(mnemonic: &str, passphrases: &[String]) -> Result<Vec<Wallet>, &'static str> {
+    let mnemonic = mnemonic.parse::<Mnemonic>()?;
+    let mut all = vec![""];
+    all.extend(passphrases.iter().map(String::as_str));
+
+    derive_seeds(&mnemonic, &all)
+        .into_iter()
+        .map(|seed| {
+            let root = seed.to_root_key(Network::Mainnet)?;
+            let account = Account::derive(&root, ScriptType::P2pkh, 0)?;
+            let key = account.receive_key(0)?;
+
+            Ok(Wallet {
+                fingerprint: root.fingerprint(),
+                first_address: address::p2pkh(&key.public_key(), root.network),
+                seed,
+                root_key: root,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_vectors::BIP39;
+
+    #[test]
+    fn wallet_per_passphrase() {
+        let (_, mnemonic, _, root_key) = BIP39[0];
+        let wallets = wallets(mnemonic, &["TREZOR".to_string()]).unwrap();
+        assert_eq!(wallets.len(), 2);
+
+        // The empty passphrase's wallet, whose first BIP-44 address every wallet app shows:
+        assert_eq!(wallets[0].fingerprint, [0x73, 0xc5, 0xda, 0x0a]);
+        assert_eq!(wallets[0].first_address, "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA");
+
+        // and the Trezor vector's, behind "TREZOR":
+        assert_eq!(wallets[1].root_key.to_string(), root_key);
+        assert_eq!(wallets[1].seed.to_hex(), BIP39[0].2);
+        assert_ne!(wallets[1].first_address, wallets[0].first_address);
+
+        assert!(super::wallets("abandon abandon", &[]).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+// Output script descriptors (BIP-380 and friends) for the standard single-key script types.
+
+use std::fmt;
+use std::str::FromStr;
+
+// The characters a descriptor may contain, in the order the checksum algorithm ranks them:
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptType {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2tr,
+}
+
+impl ScriptType {
+    pub fn all() -> [ScriptType; 4] {
+        [
+            ScriptType::P2pkh,
+            ScriptType::P2shP2wpkh,
+            ScriptType::P2wpkh,
+            ScriptType::P2tr,
+        ]
+    }
+
+    // The BIP-44/49/84/86 purpose level each script type appears under:
+    pub fn purpose(&self) -> u32 {
+        match self {
+            ScriptType::P2pkh => 44,
+            ScriptType::P2shP2wpkh => 49,
+            ScriptType::P2wpkh => 84,
+            ScriptType::P2tr => 86,
+        }
+    }
+
+    // Wrap a key expression in this script type's descriptor function(s):
+    pub fn wrap(&self, key: &str) -> String {
+        match self {
+            ScriptType::P2pkh => format!("pkh({})", key),
+            ScriptType::P2shP2wpkh => format!("sh(wpkh({}))", key),
+            ScriptType::P2wpkh => format!("wpkh({})", key),
+            ScriptType::P2tr => format!("tr({})", key),
+        }
+    }
+}
+
+impl FromStr for ScriptType {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<ScriptType, &'static str> {
+        match name.to_lowercase().as_str() {
+            "p2pkh" => Ok(ScriptType::P2pkh),
+            "p2sh-p2wpkh" => Ok(ScriptType::P2shP2wpkh),
+            "p2wpkh" => Ok(ScriptType::P2wpkh),
+            "p2tr" => Ok(ScriptType::P2tr),
+            _ => Err("Unknown script type."),
+        }
+    }
+}
+
+impl fmt::Display for ScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ScriptType::P2pkh => "p2pkh",
+            ScriptType::P2shP2wpkh => "p2sh-p2wpkh",
+            ScriptType::P2wpkh => "p2wpkh",
+            ScriptType::P2tr => "p2tr",
+        })
+    }
+}
+
+// Appends the eight character "#checksum" Bitcoin Core expects on imported descriptors.
+pub fn with_checksum(descriptor: &str) -> Result<String, &'static str> {
+    Ok(format!("{}#{}", descriptor, checksum(descriptor)?))
+}
+
+pub fn checksum(descriptor: &str) -> Result<String, &'static str> {
+    let mut symbols = Vec::with_capacity(descriptor.len() * 2);
+    let mut groups = Vec::with_capacity(3);
+
+    for c in descriptor.chars() {
+        let value = INPUT_CHARSET
+            .find(c)
+            .ok_or("Invalid character in descriptor.")? as u64;
+        symbols.push(value & 31);
+        groups.push(value >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => {}
+    }
+    symbols.extend(&[0; 8]);
+
+    let checksum = polymod(&symbols) ^ 1;
+    Ok((0..8)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+fn polymod(symbols: &[u64]) -> u64 {
+    const GENERATOR: [u64; 5] = [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+    ];
+
+    let mut chk = 1;
+    for value in symbols {
+        let top = chk >> 35;
+        chk = ((chk & 0x7ffffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bitcoin Core's own checksum cases (descriptor_tests.cpp):
+    #[test]
+    fn core_checksums() {
+        assert_eq!(
+            checksum("sh(multi(2,[00000000/111'/222]xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc,xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L/0))"),
+            Ok("ggrsrxfy".to_string())
+        );
+        assert_eq!(
+            checksum("sh(multi(2,[00000000/111'/222]xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL,xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y/0))"),
+            Ok("tjg09x5t".to_string())
+        );
+    }
+    #[test]
+    fn with_checksum_appended() {
+        let descriptor = "raw(deadbeef)";
+        let checked = with_checksum(descriptor).unwrap();
+        assert_eq!(checked.len(), descriptor.len() + 9);
+        assert_eq!(&checked[descriptor.len() + 1..], checksum(descriptor).unwrap());
+
+        // Only the characters Core's checksum covers:
+        assert_eq!(checksum("raw(dead\u{e9})"), Err("Invalid character in descriptor."));
+    }
+
+    #[test]
+    fn script_type_names() {
+        for script_type in ScriptType::all().iter() {
+            assert_eq!(script_type.to_string().parse(), Ok(*script_type));
+        }
+        assert_eq!("P2WPKH".parse(), Ok(ScriptType::P2wpkh));
+        assert_eq!(ScriptType::P2shP2wpkh.wrap("KEY"), "sh(wpkh(KEY))");
+    }
+}
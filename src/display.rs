@@ -0,0 +1,156 @@
+// How a mnemonic is laid out for people copying it down: backup cards and steel plates come with
+// their own columns and rows, so the words can be wrapped into lines, split into groups, and
+// numbered ("1. abandon  2. ability") to match.  The default is the plain single line, which is
+// also the form to give other software.
+//
+// Paged, the phrase is never on screen all at once: it's shown a word (or a line) at a time, for
+// writing down where someone could be looking over a shoulder.
+
+pub struct MnemonicFormat {
+    // Between words, e.g. " " or " - ":
+    pub separator: String,
+    // Start a new line after this many words:
+    pub words_per_line: Option<usize>,
+    // Within a line, leave a wider gap (three spaces) after every this many words:
+    pub group_size: Option<usize>,
+    // Put each word's position in front of it; wrapped lines are padded into columns:
+    pub numbered: bool,
+    // Bold and underline the last word, which carries the checksum (terminals only: it's done
+    // with ANSI escapes, which would end up in files and copied text as they are):
+    pub highlight_checksum: bool,
+    // Show one page at a time (see 'pages'), each erased before the next:
+    pub paged: bool,
+}
+
+impl Default for MnemonicFormat {
+    fn default() -> MnemonicFormat {
+        MnemonicFormat {
+            separator: " ".to_string(),
+            words_per_line: None,
+            group_size: None,
+            numbered: false,
+            highlight_checksum: false,
+            paged: false,
+        }
+    }
+}
+
+impl MnemonicFormat {
+    pub fn render(&self, mnemonic: &str) -> String {
+        let words: Vec<&str> = mnemonic.split_whitespace().collect();
+        let per_line = self.words_per_line.filter(|&n| n > 0).unwrap_or(words.len().max(1));
+
+        let mut entries: Vec<String> = if self.numbered {
+            let digits = words.len().to_string().len();
+            (1..)
+                .zip(&words)
+                .map(|(n, word)| format!("{:>width$}. {}", n, word, width = digits))
+                .collect()
+        } else {
+            words.iter().map(|word| word.to_string()).collect()
+        };
+        // Numbered words only line up in columns if every entry takes the same width:
+        let width = match (self.numbered, self.words_per_line) {
+            (true, Some(_)) => entries.iter().map(|entry| width(entry)).max().unwrap_or(0),
+            _ => 0,
+        };
+
+        // (The last word ends its line, so it never needs padding that the escapes would upset.)
+        if self.highlight_checksum {
+            if let (Some(entry), Some(word)) = (entries.last_mut(), words.last()) {
+                let start = entry.len() - word.len();
+                entry.replace_range(start.., &format!("\x1B[1;4m{}\x1B[0m", word));
+            }
+        }
+
+        entries
+            .chunks(per_line)
+            .map(|line| {
+                let mut rendered = String::new();
+                for (i, entry) in line.iter().enumerate() {
+                    if i > 0 {
+                        rendered += match self.group_size {
+                            Some(size) if size > 0 && i % size == 0 => "   ",
+                            _ => &self.separator,
+                        };
+                    }
+                    rendered += entry;
+                    if i + 1 < line.len() {
+                        let padding = width.saturating_sub(self::width(entry));
+                        rendered += &" ".repeat(padding);
+                    }
+                }
+                rendered
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // The pages of a paged display: a word each, or a line of 'words_per_line' words.  They're
+    // always numbered, since a word on its own doesn't say where in the phrase it goes.
+    pub fn pages(&self, mnemonic: &str) -> Vec<String> {
+        let format = MnemonicFormat {
+            separator: self.separator.clone(),
+            words_per_line: Some(self.words_per_line.unwrap_or(1)),
+            group_size: self.group_size,
+            numbered: true,
+            highlight_checksum: self.highlight_checksum,
+            paged: false,
+        };
+        format.render(mnemonic).lines().map(str::to_string).collect()
+    }
+}
+
+// How many terminal columns 'text' takes.  Chinese, Japanese and Korean characters (and
+// full-width forms) are drawn two columns wide, so counting characters would leave the columns
+// of a CJK mnemonic ragged.  The wordlists are also decomposed (NFD): accents, kana voicing marks
+// and Hangul vowels and final consonants are separate characters that take no column of their
+// own.
+pub fn width(text: &str) -> usize {
+    text.chars()
+        .map(|c| {
+            if is_combining(c) {
+                0
+            } else if is_wide(c) {
+                2
+            } else {
+                1
+            }
+        })
+        .sum()
+}
+
+// 'text' padded with spaces out to 'columns' wide:
+pub fn pad(text: &str, columns: usize) -> String {
+    format!("{}{}", text, " ".repeat(columns.saturating_sub(width(text))))
+}
+
+fn is_combining(c: char) -> bool {
+    matches!(c as u32,
+        0x0300..=0x036F             // combining accents
+        | 0x1160..=0x11FF           // Hangul medial vowels and final consonants
+        | 0x3099..=0x309A)          // kana voicing marks
+}
+
+// The East Asian Wide and Fullwidth blocks (Unicode's UAX #11), which cover every wordlist:
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F             // Hangul Jamo
+        | 0x2E80..=0x303E           // CJK radicals, punctuation
+        | 0x3041..=0x33FF           // kana, CJK symbols
+        | 0x3400..=0x4DBF           // CJK extension A
+        | 0x4E00..=0x9FFF           // CJK unified ideographs
+        | 0xA000..=0xA4CF           // Yi
+        | 0xAC00..=0xD7A3           // Hangul syllables
+        | 0xF900..=0xFAFF           // CJK compatibility ideographs
+        | 0xFE30..=0xFE4F           // CJK compatibility forms
+        | 0xFF00..=0xFF60           // full-width forms
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD)        // CJK extensions B and on
+}
+
+// How many of the last word's 11 bits are checksum (one for every three words), for explaining
+// why changing any other word makes it wrong:
+pub fn checksum_bits(word_count: usize) -> usize {
+    word_count / 3
+}
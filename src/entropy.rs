@@ -0,0 +1,331 @@
+// Entropy of one of the five lengths BIP-39 allows, enforced by the compiler rather than checked
+// at runtime: only 'Entropy<16>', '<20>', '<24>', '<28>' and '<32>' have constructors, so
+// 'Mnemonic::from_entropy' can't be handed 17 bytes.
+//
+// 'Rolls' is entropy made by hand, for those who'd rather not trust any computer's random
+// numbers: dice rolls or coin flips, taken as they're entered, until there are enough of them.
+//
+//   let mut rolls = Rolls::new(Source::Dice, Conditioning::Sha256, MnemonicListSize::TwentyFour);
+//   rolls.add("3 6 1 4 2 ...")?;
+//   println!("{} rolls to go", rolls.remaining());
+//   let mnemonic = Mnemonic::from_entropy_bytes(&rolls.entropy().unwrap(), language)?;
+//
+// 'EntropySource' is where 'Config::generate_using' gets a new mnemonic's entropy: the system's
+// random numbers ('OsRandom', what 'generate_from' uses), those mixed with material of one's own
+// ('Mixed'), or rolls.
+
+use core::fmt;
+use core::str::{self, FromStr};
+
+use zeroize::Zeroize;
+
+use alloc_prelude::*;
+use fault;
+use hash::sha256;
+use language::Language;
+use {write_mnemonic_from_entropy, MnemonicListSize, MAX_PHRASE_LEN};
+
+pub struct Entropy<const N: usize>([u8; N]);
+
+impl<const N: usize> Entropy<N> {
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    // Three words for every 4 bytes:
+    pub fn word_count(&self) -> MnemonicListSize {
+        MnemonicListSize::from(N * 3 / 4).unwrap()
+    }
+
+    // The phrase for this entropy, written into 'buffer' instead of allocated, for callers that
+    // keep their secrets off the heap:
+    pub fn write_phrase<'a>(
+        &self,
+        language: Language,
+        buffer: &'a mut [u8; MAX_PHRASE_LEN],
+    ) -> &'a str {
+        let (phrase, len) = fault::twice(
+            || {
+                let mut phrase = [0; MAX_PHRASE_LEN];
+                let len = write_mnemonic_from_entropy(&self.0, language, &mut phrase).len();
+                (phrase, len)
+            },
+            PartialEq::eq,
+        );
+        buffer.copy_from_slice(&phrase);
+        // Only ever whole words:
+        str::from_utf8(&buffer[..len]).unwrap()
+    }
+}
+
+// One constructor set per allowed length; a macro, since const generics can't (yet) say "N is one
+// of these" in a where clause:
+macro_rules! entropy_lengths {
+    ($($bytes:literal),*) => {$(
+        #[cfg(feature = "std")]
+        impl Entropy<$bytes> {
+            pub fn random() -> Entropy<$bytes> {
+                Entropy(random())
+            }
+        }
+
+        impl From<[u8; $bytes]> for Entropy<$bytes> {
+            fn from(bytes: [u8; $bytes]) -> Entropy<$bytes> {
+                Entropy(bytes)
+            }
+        }
+    )*};
+}
+
+entropy_lengths!(16, 20, 24, 28, 32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    // Rolls of a six-sided die, 1 to 6:
+    Dice,
+    // Flips of a coin, h or t (or 1 or 0):
+    Coin,
+}
+
+// How the rolls become bits.  Either way, what matters is that nobody else sees them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Conditioning {
+    // The rolls as digits (flips as 1 and 0), hashed with SHA-256, and the hash cut to the
+    // entropy's length, once the rolls would hold that many bits from a fair die (log2(6), about
+    // 2.58, a roll) or coin (1 a flip).  A somewhat loaded die is still fine, so long as there are
+    // a few rolls more to make up for it.  (Coldcard does the same.)
+    Sha256,
+    // The rolls in pairs: two different outcomes are a bit (whether the first is higher), and two
+    // the same are thrown away.  However loaded the die, the bits come out even, so long as the
+    // rolls don't depend on each other; it takes more of them, about 2.4 a bit for a die and 4 for
+    // a coin.
+    VonNeumann,
+}
+
+impl FromStr for Conditioning {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<Conditioning, &'static str> {
+        match name.to_lowercase().as_str() {
+            "sha256" => Ok(Conditioning::Sha256),
+            "von-neumann" => Ok(Conditioning::VonNeumann),
+            _ => Err("Unknown conditioning."),
+        }
+    }
+}
+
+impl fmt::Display for Conditioning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Conditioning::Sha256 => "sha256",
+            Conditioning::VonNeumann => "von-neumann",
+        })
+    }
+}
+
+pub struct Rolls {
+    source: Source,
+    conditioning: Conditioning,
+    // The entropy's length in bits, 128 for 12 words up to 256 for 24:
+    bits: usize,
+    // Every roll so far, 1 to 6 (or a flip, 1 or 0):
+    outcomes: Vec<u8>,
+}
+
+impl Rolls {
+    pub fn new(source: Source, conditioning: Conditioning, word_count: MnemonicListSize) -> Rolls {
+        Rolls {
+            source,
+            conditioning,
+            bits: word_count as usize * 32 / 3,
+            outcomes: Vec::new(),
+        }
+    }
+
+    // The rolls in 'text', with or without spaces and commas between them: all of them or, if
+    // one of them isn't a roll, none.
+    pub fn add(&mut self, text: &str) -> Result<(), &'static str> {
+        let mut outcomes = Vec::new();
+        for roll in text.chars().filter(|&c| !c.is_whitespace() && c != ',') {
+            let outcome = match (self.source, roll.to_ascii_lowercase()) {
+                (Source::Dice, '1'..='6') => roll as u8 - b'0',
+                (Source::Coin, 'h' | '1') => 1,
+                (Source::Coin, 't' | '0') => 0,
+                (source, _) => {
+                    outcomes.zeroize();
+                    return Err(match source {
+                        Source::Dice => "Dice rolls are 1 to 6.",
+                        Source::Coin => "Coin flips are h or t (or 1 or 0).",
+                    });
+                }
+            };
+            outcomes.push(outcome);
+        }
+        self.outcomes.extend_from_slice(&outcomes);
+        outcomes.zeroize();
+        Ok(())
+    }
+
+    pub fn count(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    // How many more rolls it takes: exactly, for 'Sha256', and for 'VonNeumann' about how many
+    // a fair die (or coin) would, since thrown away pairs can't be known ahead.
+    pub fn remaining(&self) -> usize {
+        match self.conditioning {
+            Conditioning::Sha256 => {
+                // (A die's log2(6) bits a roll in millionths, rounded up, as 'core' has no
+                // 'f64::log2'.)
+                let needed = match self.source {
+                    Source::Dice => (self.bits * 1_000_000).div_ceil(2_584_963),
+                    Source::Coin => self.bits,
+                };
+                needed.saturating_sub(self.outcomes.len())
+            }
+            Conditioning::VonNeumann => {
+                let missing = self.bits.saturating_sub(self.debiased_bits().len());
+                if missing == 0 {
+                    return 0;
+                }
+                // A pair makes a bit five times in six for a die, and half the time for a coin
+                // (less the roll already waiting for its pair):
+                let pairs = match self.source {
+                    Source::Dice => (missing * 6).div_ceil(5),
+                    Source::Coin => missing * 2,
+                };
+                pairs * 2 - self.outcomes.len() % 2
+            }
+        }
+    }
+
+    // Once 'remaining' is down to 0, the entropy, for 'Mnemonic::from_entropy_bytes':
+    pub fn entropy(&self) -> Option<Vec<u8>> {
+        if self.remaining() > 0 {
+            return None;
+        }
+        let entropy = match self.conditioning {
+            Conditioning::Sha256 => {
+                let mut digits: Vec<u8> = self.outcomes.iter().map(|&roll| b'0' + roll).collect();
+                let mut hash = sha256(&digits);
+                digits.zeroize();
+                let entropy = hash[..self.bits / 8].to_vec();
+                hash.zeroize();
+                entropy
+            }
+            Conditioning::VonNeumann => {
+                let mut bits = self.debiased_bits();
+                let entropy = bits[..self.bits]
+                    .chunks(8)
+                    .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+                    .collect();
+                bits.zeroize();
+                entropy
+            }
+        };
+        Some(entropy)
+    }
+
+    fn debiased_bits(&self) -> Vec<bool> {
+        self.outcomes
+            .chunks_exact(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| pair[0] > pair[1])
+            .collect()
+    }
+}
+
+impl Drop for Rolls {
+    fn drop(&mut self) {
+        self.outcomes.zeroize();
+    }
+}
+
+pub trait EntropySource {
+    // Fills all of 'entropy' (16, 20, 24, 28 or 32 bytes), or fails:
+    fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str>;
+}
+
+// The system's random numbers, by way of 'getrandom' (the kernel's, or in a browser,
+// crypto.getRandomValues).  This, and everything built on it, needs the 'std' feature; without
+// it, entropy comes from an 'EntropySource' of one's own (a hardware RNG, say).
+#[cfg(feature = "std")]
+pub struct OsRandom;
+
+#[cfg(feature = "std")]
+impl EntropySource for OsRandom {
+    fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+        getrandom::getrandom(entropy).map_err(|_| "The system's random number generator failed.")
+    }
+}
+
+// The same for salts, nonces and the like, where there's no error to return: without random
+// numbers there's nothing to do, so this panics (as 'rand::random' did).
+#[cfg(feature = "std")]
+pub fn fill_random(bytes: &mut [u8]) {
+    OsRandom.fill(bytes).unwrap()
+}
+
+#[cfg(feature = "std")]
+pub fn random<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    fill_random(&mut bytes);
+    bytes
+}
+
+// The system's random numbers with material of one's own (keys mashed at random, a hardware RNG's
+// file, dice rolls): the SHA-256 of 32 random bytes and then the material, cut to length.  Whoever
+// knows (or chose) the system's numbers still has the material to guess, and poor material is no
+// worse than none.  Hashing, rather than XOR, takes material of any length and any quality.
+#[cfg(feature = "std")]
+pub struct Mixed {
+    material: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl Mixed {
+    pub fn new(material: &[u8]) -> Mixed {
+        Mixed {
+            material: material.to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl EntropySource for Mixed {
+    fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+        let mut random = [0; 32];
+        OsRandom.fill(&mut random)?;
+        let mut input = Vec::with_capacity(32 + self.material.len());
+        input.extend_from_slice(&random);
+        random.zeroize();
+        input.extend_from_slice(&self.material);
+        let mut hash = sha256(&input);
+        input.zeroize();
+        entropy.copy_from_slice(&hash[..entropy.len()]);
+        hash.zeroize();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Mixed {
+    fn drop(&mut self) {
+        self.material.zeroize();
+    }
+}
+
+// Rolls on their own, once there are enough of them for as many words as they were rolled for:
+impl EntropySource for Rolls {
+    fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+        let mut rolled = self.entropy().ok_or("There aren't enough rolls yet.")?;
+        let result = if rolled.len() == entropy.len() {
+            entropy.copy_from_slice(&rolled);
+            Ok(())
+        } else {
+            Err("The rolls were for another number of words.")
+        };
+        rolled.zeroize();
+        result
+    }
+}
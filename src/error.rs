@@ -0,0 +1,274 @@
+// The ways the mnemonic pipeline fails, as an enum library users can match on (to say which word
+// to check again, or to offer suggestions only when the checksum is what's off) rather than
+// English text to compare.  The messages are still the ones 'i18n' translates, and the unknown
+// word itself is left out of them, since error messages end up in logs and the word is a secret.
+//
+//   match mnemonic.parse::<Mnemonic>() {
+//       Err(Error::UnknownWord { index, .. }) => println!("Check word {}.", index + 1),
+//       ...
+//   }
+//
+// Key derivation (bip32, slip10, bip85), derivation paths and extended keys fail with variants of
+// their own too, as does a wrong password for a keystore.  What's particular to one format (a
+// SLIP-39 share's padding, say) is 'Error::Other', with its message; the modules that still fail
+// with '&'static str' messages come through as that too, and either converts to the other with
+// '?'.  Text that wasn't hex or base58 to begin with keeps the decoder's own error, as 'source()',
+// for error reporters (anyhow, eyre) that show the whole chain of causes.
+//
+// The unknown word is kept out of 'Debug' as well, and wiped when the error goes.
+
+use core::error;
+use core::fmt;
+
+use alloc_prelude::*;
+use zeroize::Zeroize;
+
+#[derive(Clone, PartialEq)]
+pub enum Error {
+    // A phrase of other than 12, 15, 18, 21 or 24 words:
+    InvalidWordCount(usize),
+    // A word that isn't in the wordlist, at 'index' (counting from 0):
+    UnknownWord { word: String, index: usize },
+    // The same, when 'UnknownWords::Suggest' found no word close enough to swap in:
+    NoCloseWord { word: String, index: usize },
+    // The last word doesn't carry the others' checksum:
+    ChecksumMismatch,
+    // Entropy of other than 16, 20, 24, 28 or 32 bytes:
+    InvalidEntropyLength(usize),
+    // A seed of other than 64 bytes:
+    InvalidSeedLength(usize),
+    // A wordlist this build was compiled without:
+    LanguageNotEmbedded,
+    // Text that isn't hex (a seed, say):
+    Hex(hex::FromHexError),
+    // Text that isn't base58 (an extended key, say):
+    Base58(bs58::decode::Error),
+    // A derivation path that doesn't start from the master key, "m":
+    PathWithoutMaster,
+    // A path step that isn't an index, with or without a hardened suffix:
+    InvalidPathComponent,
+    // An index of 2^31 or more, which leaves no room for the hardened bit:
+    ChildIndexOutOfRange,
+    // A key origin that isn't in '[' and ']':
+    UnbracketedKeyOrigin,
+    // A key origin whose fingerprint isn't 4 bytes of hex:
+    InvalidFingerprint,
+    // A normal child asked of an extended public key:
+    HardenedFromPublicKey,
+    // A normal child asked of an ed25519 key, which SLIP-0010 only derives hardened:
+    HardenedOnly,
+    // A child past depth 255, the most a serialized key can say:
+    MaxDepthExceeded,
+    // One of the (astronomically unlikely) indexes whose key is zero or past the curve's order:
+    InvalidChildKey,
+    // The same, for the master key a seed makes:
+    InvalidMasterKey,
+    // A private key of zero or past the curve's order:
+    InvalidPrivateKey,
+    // A SLIP-0010 seed of other than 16 to 64 bytes:
+    SeedLengthOutOfRange(usize),
+    // A serialized extended key of other than 78 bytes:
+    InvalidExtendedKeyLength(usize),
+    // Version bytes of no known network's xprv:
+    NotExtendedPrivateKey,
+    // Or xpub:
+    NotExtendedPublicKey,
+    // An xprv whose key isn't a zero byte and a valid private key:
+    InvalidPrivateKeyData,
+    // An xpub whose key isn't a compressed point on the curve:
+    InvalidPublicKeyData,
+    // A depth 0 key with a parent's fingerprint or a child number, which only children have:
+    MasterKeyWithParent,
+    // Encrypted data (a keystore's) whose tag doesn't match: the password is wrong, or the data
+    // was changed:
+    WrongPassword,
+    Other(&'static str),
+}
+
+// (hex's error leaves 'Eq' out, but its 'PartialEq' is an equivalence all the same)
+impl Eq for Error {}
+
+impl Error {
+    // The English message, as 'i18n::localize' looks it up:
+    pub fn message(&self) -> &'static str {
+        match self {
+            Error::InvalidWordCount(_) => "Invalid mnemonic word list size.",
+            Error::UnknownWord { .. } => "A mnemonic word isn't in the word list.",
+            Error::NoCloseWord { .. } => {
+                "A mnemonic word isn't in the word list, nor close to one."
+            }
+            Error::ChecksumMismatch => "Invalid mnemonic checksum.",
+            Error::InvalidEntropyLength(_) => "Entropy must be 16, 20, 24, 28, or 32 bytes.",
+            Error::InvalidSeedLength(_) => "A seed must be 64 bytes.",
+            Error::LanguageNotEmbedded => "That wordlist isn't built in.",
+            Error::Hex(_) => "Invalid hex string.",
+            Error::Base58(_) => "Invalid base58 string.",
+            Error::PathWithoutMaster => "Derivation path must start with 'm'.",
+            Error::InvalidPathComponent => "Invalid derivation path component.",
+            Error::ChildIndexOutOfRange => "Child index is out of range.",
+            Error::UnbracketedKeyOrigin => "Key origin must be enclosed in '[' and ']'.",
+            Error::InvalidFingerprint => "Key origin fingerprint must be 8 hex characters.",
+            Error::HardenedFromPublicKey => "Cannot derive a hardened child from a public key.",
+            Error::HardenedOnly => "Ed25519 keys only support hardened derivation.",
+            Error::MaxDepthExceeded => "Maximum derivation depth exceeded.",
+            Error::InvalidChildKey => "Invalid child key.",
+            Error::InvalidMasterKey => "Invalid master key.",
+            Error::InvalidPrivateKey => "Invalid private key.",
+            Error::SeedLengthOutOfRange(_) => "Seed must be between 16 and 64 bytes.",
+            Error::InvalidExtendedKeyLength(_) => "Extended keys are 78 bytes long.",
+            Error::NotExtendedPrivateKey => "Not an extended private key.",
+            Error::NotExtendedPublicKey => "Not an extended public key.",
+            Error::InvalidPrivateKeyData => "Invalid extended private key data.",
+            Error::InvalidPublicKeyData => "Invalid extended public key data.",
+            Error::MasterKeyWithParent => "Master key with a parent fingerprint or child number.",
+            Error::WrongPassword => "Wrong password, or the encrypted data is corrupt.",
+            Error::Other(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+// The derived Debug, but for the unknown word, which is the user's (perhaps only mistyped) secret:
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownWord { index, .. } => f
+                .debug_struct("UnknownWord")
+                .field("word", &"[redacted]")
+                .field("index", index)
+                .finish(),
+            Error::NoCloseWord { index, .. } => f
+                .debug_struct("NoCloseWord")
+                .field("word", &"[redacted]")
+                .field("index", index)
+                .finish(),
+            Error::InvalidWordCount(count) => {
+                f.debug_tuple("InvalidWordCount").field(count).finish()
+            }
+            Error::InvalidEntropyLength(length) => {
+                f.debug_tuple("InvalidEntropyLength").field(length).finish()
+            }
+            Error::InvalidSeedLength(length) => {
+                f.debug_tuple("InvalidSeedLength").field(length).finish()
+            }
+            Error::SeedLengthOutOfRange(length) => {
+                f.debug_tuple("SeedLengthOutOfRange").field(length).finish()
+            }
+            Error::InvalidExtendedKeyLength(length) => f
+                .debug_tuple("InvalidExtendedKeyLength")
+                .field(length)
+                .finish(),
+            Error::Hex(err) => f.debug_tuple("Hex").field(err).finish(),
+            Error::Base58(err) => f.debug_tuple("Base58").field(err).finish(),
+            Error::Other(message) => f.debug_tuple("Other").field(message).finish(),
+            Error::ChecksumMismatch => f.write_str("ChecksumMismatch"),
+            Error::LanguageNotEmbedded => f.write_str("LanguageNotEmbedded"),
+            Error::PathWithoutMaster => f.write_str("PathWithoutMaster"),
+            Error::InvalidPathComponent => f.write_str("InvalidPathComponent"),
+            Error::ChildIndexOutOfRange => f.write_str("ChildIndexOutOfRange"),
+            Error::UnbracketedKeyOrigin => f.write_str("UnbracketedKeyOrigin"),
+            Error::InvalidFingerprint => f.write_str("InvalidFingerprint"),
+            Error::HardenedFromPublicKey => f.write_str("HardenedFromPublicKey"),
+            Error::HardenedOnly => f.write_str("HardenedOnly"),
+            Error::MaxDepthExceeded => f.write_str("MaxDepthExceeded"),
+            Error::InvalidChildKey => f.write_str("InvalidChildKey"),
+            Error::InvalidMasterKey => f.write_str("InvalidMasterKey"),
+            Error::InvalidPrivateKey => f.write_str("InvalidPrivateKey"),
+            Error::NotExtendedPrivateKey => f.write_str("NotExtendedPrivateKey"),
+            Error::NotExtendedPublicKey => f.write_str("NotExtendedPublicKey"),
+            Error::InvalidPrivateKeyData => f.write_str("InvalidPrivateKeyData"),
+            Error::InvalidPublicKeyData => f.write_str("InvalidPublicKeyData"),
+            Error::MasterKeyWithParent => f.write_str("MasterKeyWithParent"),
+            Error::WrongPassword => f.write_str("WrongPassword"),
+        }
+    }
+}
+
+impl Drop for Error {
+    fn drop(&mut self) {
+        if let Error::UnknownWord { word, .. } | Error::NoCloseWord { word, .. } = self {
+            word.zeroize();
+        }
+    }
+}
+
+// (the decoders' errors are only 'Error's themselves with std)
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Error::Hex(err) => Some(err),
+            #[cfg(feature = "std")]
+            Error::Base58(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<&'static str> for Error {
+    fn from(message: &'static str) -> Error {
+        Error::Other(message)
+    }
+}
+
+impl From<hex::FromHexError> for Error {
+    fn from(err: hex::FromHexError) -> Error {
+        Error::Hex(err)
+    }
+}
+
+impl From<bs58::decode::Error> for Error {
+    fn from(err: bs58::decode::Error) -> Error {
+        Error::Base58(err)
+    }
+}
+
+// (the details go, the message stays)
+impl From<Error> for &'static str {
+    fn from(err: Error) -> &'static str {
+        err.message()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use bip32::ExtendedPrivKey;
+    use std::error::Error as _;
+    use Seed;
+
+    #[test]
+    fn decoders_errors_are_the_source() {
+        // ('0' isn't in base58's alphabet)
+        let err = "xprv0".parse::<ExtendedPrivKey>().unwrap_err();
+        assert_eq!(
+            err,
+            Error::Base58(bs58::decode::Error::InvalidCharacter {
+                character: '0',
+                index: 4
+            })
+        );
+        assert_eq!(err.to_string(), "Invalid base58 string.");
+        let source = err.source().unwrap().to_string();
+        assert!(source.contains("invalid character '0'"), "{}", source);
+
+        let err = "0g".parse::<Seed>().unwrap_err();
+        assert_eq!(
+            err,
+            Error::Hex(hex::FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            "Invalid character 'g' at position 1"
+        );
+        assert!(Error::ChecksumMismatch.source().is_none());
+        assert!(Error::Other("Base58Check checksum mismatch.")
+            .source()
+            .is_none());
+    }
+}
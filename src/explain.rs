@@ -0,0 +1,73 @@
+// A step by step trace of the whole pipeline, mnemonic to root key, with every intermediate value
+// written out: the words' 11 bit indices, where the entropy ends and the checksum starts, what
+// goes into PBKDF2, and how the master key's HMAC output splits in two.  It's meant for learning
+// on throwaway mnemonics; everything it prints is as secret as the mnemonic itself.
+
+use std::fmt::Write;
+
+use bip32::ExtendedPrivKey;
+use display;
+use hash::{hmac_sha512, sha256};
+use language::Language;
+use network::Network;
+use {get_salt, word_index, Config};
+
+pub fn trace(config: &Config, language: Language) -> Result<String, &'static str> {
+    let mut out = String::new();
+    let words: Vec<&str> = config.mnemonic.split(' ').collect();
+
+    // 1) Every word is an index into the 2,048 word list, i.e. 11 bits:
+    let heading = "1) Each word's position in the word list";
+    writeln!(out, "{} ({}), as 11 bits:", heading, language.native_name()).unwrap();
+    let mut bits = String::new();
+    let column = words.iter().map(|word| display::width(word)).max().unwrap_or(0);
+    for (i, word) in words.iter().enumerate() {
+        let index = word_index(word, language).ok_or("A mnemonic word isn't in the word list.")?;
+        let word = display::pad(word, column);
+        writeln!(out, "   {:>2}. {} {:>4}  {:011b}", i + 1, word, index, index).unwrap();
+        write!(bits, "{:011b}", index).unwrap();
+    }
+
+    // 2) Of those bits, one in 33 is checksum: ENT / 32 of them, at the end.
+    let checksum_len = bits.len() / 33;
+    let (entropy_bits, checksum_bits) = bits.split_at(bits.len() - checksum_len);
+    let entropy: Vec<u8> = entropy_bits
+        .as_bytes()
+        .chunks(8)
+        .map(|byte| u8::from_str_radix(std::str::from_utf8(byte).unwrap(), 2).unwrap())
+        .collect();
+    let hash = sha256(&entropy);
+    let expected = format!("{:08b}", hash[0]);
+    writeln!(out, "\n2) The {} bits split into entropy and checksum:", bits.len()).unwrap();
+    writeln!(out, "   entropy ({} bits): {}", entropy_bits.len(), hex::encode(&entropy)).unwrap();
+    writeln!(out, "   SHA-256(entropy): {}", hex::encode(hash)).unwrap();
+    writeln!(
+        out,
+        "   checksum: the first {} bits of that hash, {}; the mnemonic's last {} bits are {} ({})",
+        checksum_len,
+        &expected[..checksum_len],
+        checksum_len,
+        checksum_bits,
+        if checksum_bits == &expected[..checksum_len] { "valid" } else { "INVALID" }
+    )
+    .unwrap();
+
+    // 3) The seed doesn't come from the entropy, but from the words themselves:
+    let seed = config.to_seed();
+    writeln!(out, "\n3) PBKDF2-HMAC-SHA512, 2,048 rounds, 64 bytes out:").unwrap();
+    writeln!(out, "   password: \"{}\"", config.mnemonic).unwrap();
+    writeln!(out, "   salt: \"{}\"", get_salt(config.passphrase.clone())).unwrap();
+    writeln!(out, "   seed: {}", seed).unwrap();
+
+    // 4) The master key is HMAC-SHA512 of the seed, keyed with "Bitcoin seed":
+    let mac = hmac_sha512(b"Bitcoin seed", seed.as_bytes());
+    writeln!(out, "\n4) HMAC-SHA512(key \"Bitcoin seed\", seed) = {}", hex::encode(&mac[..]))
+        .unwrap();
+    writeln!(out, "   left 32 bytes, the master private key: {}", hex::encode(&mac[..32])).unwrap();
+    writeln!(out, "   right 32 bytes, the master chain code: {}", hex::encode(&mac[32..])).unwrap();
+
+    let root = ExtendedPrivKey::new_master(seed.as_bytes(), Network::Mainnet)?;
+    writeln!(out, "\n5) Both, with depth, parent and version bytes, Base58Check encoded:").unwrap();
+    write!(out, "   {}", root).unwrap();
+    Ok(out)
+}
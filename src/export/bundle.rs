@@ -0,0 +1,73 @@
+// Every account xpub for accounts 0..count of the chosen script types in one document, for users
+// who set up several accounts ahead of time and want to hand them all to a coordinator at once.
+
+use account::Account;
+use bip32::ExtendedPrivKey;
+use descriptor::ScriptType;
+use schema;
+
+pub fn accounts(
+    root: &ExtendedPrivKey,
+    script_types: &[ScriptType],
+    count: u32,
+) -> Result<String, &'static str> {
+    let mut accounts = Vec::new();
+
+    for script_type in script_types.iter() {
+        for index in 0..count {
+            let account = Account::derive(root, *script_type, index)?;
+            accounts.push(json!({
+                "script_type": script_type.to_string(),
+                "account": index,
+                "path": account.origin.path.to_string(),
+                "fingerprint": hex::encode(account.xpub.fingerprint()),
+                "xpub": account.xpub.to_string(),
+                "receive": account.descriptor(false),
+                "change": account.descriptor(true),
+                "multipath": account.multipath_descriptor(),
+            }));
+        }
+    }
+
+    let output = schema::versioned(json!({
+        "master_fingerprint": hex::encode(root.fingerprint()),
+        "accounts": accounts,
+    }));
+    Ok(serde_json::to_string_pretty(&output).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+    use Config;
+
+    // BIP-84's and BIP-86's test mnemonic, whose account 0 keys are the specs':
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn accounts_of_two_script_types() {
+        let config = Config::from_phrase(MNEMONIC, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+
+        let output = accounts(&root, &[ScriptType::P2wpkh, ScriptType::P2tr], 2).unwrap();
+        let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["schema_version"], json!(schema::VERSION));
+        assert_eq!(output["master_fingerprint"], "73c5da0a");
+
+        let accounts = output["accounts"].as_array().unwrap();
+        assert_eq!(accounts.len(), 4);
+        let paths: Vec<_> = accounts.iter().map(|account| account["path"].as_str().unwrap()).collect();
+        assert_eq!(paths, ["m/84'/0'/0'", "m/84'/0'/1'", "m/86'/0'/0'", "m/86'/0'/1'"]);
+
+        assert_eq!(accounts[0]["script_type"], ScriptType::P2wpkh.to_string());
+        assert_eq!(accounts[0]["account"], 0);
+        assert_eq!(accounts[0]["xpub"], "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V");
+        assert!(accounts[0]["receive"].as_str().unwrap().ends_with("/0/*)#afwvtk2s"));
+        assert!(accounts[0]["change"].as_str().unwrap().ends_with("/1/*)#vatdkr6g"));
+        assert!(accounts[0]["multipath"].as_str().unwrap().contains("/<0;1>/*)#"));
+
+        assert_eq!(accounts[2]["xpub"], "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ");
+        assert!(accounts[2]["receive"].as_str().unwrap().starts_with("tr([73c5da0a/86h/0h/0h]"));
+    }
+}
@@ -0,0 +1,127 @@
+// The "Generic JSON" file a Coldcard writes as coldcard-export.json, which many coordinators
+// accept when adding a device or cosigner: the master fingerprint and one entry per BIP-44/49/84/86
+// account, with its first receive address.
+
+use account::Account;
+use address;
+use base58;
+use bip32::ExtendedPrivKey;
+use descriptor::ScriptType;
+use network::Network;
+use serde_json::{Map, Value};
+
+pub fn generic_json(root: &ExtendedPrivKey, account: u32) -> Result<String, &'static str> {
+    let mut output = Map::new();
+    output.insert("chain".to_string(), json!("BTC"));
+    // Coldcard writes fingerprints in upper case:
+    output.insert(
+        "xfp".to_string(),
+        json!(hex::encode_upper(root.fingerprint())),
+    );
+    output.insert("account".to_string(), json!(account));
+    output.insert(
+        "xpub".to_string(),
+        json!(root.to_extended_pub_key().to_string()),
+    );
+
+    for script_type in ScriptType::all().iter() {
+        let account = Account::derive(root, *script_type, account)?;
+        let first = account.receive_key(0)?;
+        let mut entry = json!({
+            "name": script_type.to_string(),
+            "deriv": account.origin.path.to_string(),
+            "xfp": hex::encode_upper(account.xpub.fingerprint()),
+            "xpub": account.xpub.to_string(),
+            "desc": account.descriptor(false),
+            "first": address::for_script_type(*script_type, &first.public_key(), first.network)?,
+        });
+        if let Some(slip132) = slip132_pub(&account) {
+            entry["_pub"] = json!(slip132);
+        }
+        output.insert(format!("bip{}", script_type.purpose()), entry);
+    }
+
+    Ok(serde_json::to_string_pretty(&Value::Object(output)).unwrap())
+}
+
+// The account xpub under SLIP-132's version bytes for its script type (ypub for p2sh-p2wpkh, zpub
+// for p2wpkh, and upub and vpub on the test networks), which Coldcard adds as "_pub" for wallets
+// that still go by them.  Legacy and taproot accounts have none.
+fn slip132_pub(account: &Account) -> Option<String> {
+    let testnet = match account.xpub.network {
+        Network::Mainnet => false,
+        Network::Testnet | Network::Signet | Network::Regtest => true,
+        _ => return None,
+    };
+    let version = match (account.script_type, testnet) {
+        (ScriptType::P2shP2wpkh, false) => [0x04, 0x9d, 0x7c, 0xb2],
+        (ScriptType::P2shP2wpkh, true) => [0x04, 0x4a, 0x52, 0x62],
+        (ScriptType::P2wpkh, false) => [0x04, 0xb2, 0x47, 0x46],
+        (ScriptType::P2wpkh, true) => [0x04, 0x5f, 0x1c, 0xf6],
+        _ => return None,
+    };
+
+    let mut bytes = account.xpub.to_bytes();
+    bytes[..4].copy_from_slice(&version);
+    Some(base58::encode_check(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Config;
+
+    // A Coldcard's export for BIP-84's test mnemonic: the spec's keys and first addresses for
+    // BIP-44, 49, 84 and 86, and the ypub and zpub alongside.
+    #[test]
+    fn golden() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let config = Config::from_phrase(mnemonic, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+
+        let output: Value = serde_json::from_str(&generic_json(&root, 0).unwrap()).unwrap();
+        assert_eq!(
+            output,
+            json!({
+                "chain": "BTC",
+                "xfp": "73C5DA0A",
+                "account": 0,
+                "xpub": "xpub661MyMwAqRbcFkPHucMnrGNzDwb6teAX1RbKQmqtEF8kK3Z7LZ59qafCjB9eCRLiTVG3uxBxgKvRgbubRhqSKXnGGb1aoaqLrpMBDrVxga8",
+                "bip44": {
+                    "name": "p2pkh",
+                    "deriv": "m/44'/0'/0'",
+                    "xfp": "6CC9F252",
+                    "xpub": "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj",
+                    "desc": "pkh([73c5da0a/44h/0h/0h]xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj/0/*)#5l2aanww",
+                    "first": "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+                },
+                "bip49": {
+                    "name": "p2sh-p2wpkh",
+                    "deriv": "m/49'/0'/0'",
+                    "xfp": "3A161284",
+                    "xpub": "xpub6C6nQwHaWbSrzs5tZ1q7m5R9cPK9eYpNMFesiXsYrgc1P8bvLLAet9JfHjYXKjToD8cBRswJXXbbFpXgwsswVPAZzKMa1jUp2kVkGVUaJa7",
+                    "_pub": "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP",
+                    "desc": "sh(wpkh([73c5da0a/49h/0h/0h]xpub6C6nQwHaWbSrzs5tZ1q7m5R9cPK9eYpNMFesiXsYrgc1P8bvLLAet9JfHjYXKjToD8cBRswJXXbbFpXgwsswVPAZzKMa1jUp2kVkGVUaJa7/0/*))#vu666hnq",
+                    "first": "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+                },
+                "bip84": {
+                    "name": "p2wpkh",
+                    "deriv": "m/84'/0'/0'",
+                    "xfp": "FD13AAC9",
+                    "xpub": "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V",
+                    "_pub": "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+                    "desc": "wpkh([73c5da0a/84h/0h/0h]xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#afwvtk2s",
+                    "first": "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                },
+                "bip86": {
+                    "name": "p2tr",
+                    "deriv": "m/86'/0'/0'",
+                    "xfp": "A7BEA80D",
+                    "xpub": "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ",
+                    "desc": "tr([73c5da0a/86h/0h/0h]xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ/0/*)#se42yddx",
+                    "first": "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                },
+            })
+        );
+    }
+}
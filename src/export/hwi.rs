@@ -0,0 +1,133 @@
+// The JSON shapes printed by HWI's 'getdescriptors' and 'getkeypool' commands, so tooling
+// built around a hardware wallet can consume this crate's software wallet just the same.
+
+use account::Account;
+use bip32::ExtendedPrivKey;
+use descriptor::ScriptType;
+use error::Error;
+
+// HWI's default keypool range:
+pub const DEFAULT_RANGE: (u32, u32) = (0, 1000);
+
+// {"receive": [...], "internal": [...]} covering every standard script type for the account.
+pub fn descriptors(root: &ExtendedPrivKey, account: u32) -> Result<String, Error> {
+    let mut receive = Vec::new();
+    let mut internal = Vec::new();
+
+    for script_type in ScriptType::all().iter() {
+        let account = Account::derive(root, *script_type, account)?;
+        receive.push(account.descriptor(false));
+        internal.push(account.descriptor(true));
+    }
+
+    let output = json!({
+        "receive": receive,
+        "internal": internal,
+    });
+    Ok(serde_json::to_string_pretty(&output).unwrap())
+}
+
+// The array of import requests, ready to hand to Bitcoin Core's 'importdescriptors'.  Like
+// 'getkeypool', which covers only native segwit unless told '--addr-type' or '--all', this is
+// for the script types given (the command line's default being just p2wpkh).
+pub fn keypool(
+    root: &ExtendedPrivKey,
+    script_types: &[ScriptType],
+    account: u32,
+    range: (u32, u32),
+) -> Result<String, Error> {
+    let mut imports = Vec::new();
+
+    for script_type in script_types.iter() {
+        let account = Account::derive(root, *script_type, account)?;
+        for &change in [false, true].iter() {
+            imports.push(json!({
+                "desc": account.descriptor(change),
+                "range": [range.0, range.1],
+                "timestamp": "now",
+                "internal": change,
+                "keypool": true,
+                "active": true,
+                "watchonly": true,
+            }));
+        }
+    }
+
+    Ok(serde_json::to_string_pretty(&imports).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+    use Config;
+
+    // BIP-84's test mnemonic, whose account 0 key is the spec's (as an xpub rather than a zpub,
+    // which is how descriptors write it):
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const ACCOUNT_XPUB: &str = "[73c5da0a/84h/0h/0h]xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+
+    fn root() -> ExtendedPrivKey {
+        let config = Config::from_phrase(MNEMONIC, "").unwrap();
+        config.to_seed().to_root_key(Network::Mainnet).unwrap()
+    }
+
+    #[test]
+    fn keypool_of_one_script_type() {
+        let output = keypool(&root(), &[ScriptType::P2wpkh], 0, DEFAULT_RANGE).unwrap();
+        let imports: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(
+            imports,
+            json!([
+                {
+                    "desc": format!("wpkh({}/0/*)#afwvtk2s", ACCOUNT_XPUB),
+                    "range": [0, 1000],
+                    "timestamp": "now",
+                    "internal": false,
+                    "keypool": true,
+                    "active": true,
+                    "watchonly": true,
+                },
+                {
+                    "desc": format!("wpkh({}/1/*)#vatdkr6g", ACCOUNT_XPUB),
+                    "range": [0, 1000],
+                    "timestamp": "now",
+                    "internal": true,
+                    "keypool": true,
+                    "active": true,
+                    "watchonly": true,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn keypool_of_every_script_type() {
+        let output = keypool(&root(), &ScriptType::all(), 0, (5, 10)).unwrap();
+        let imports: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let imports = imports.as_array().unwrap();
+
+        assert_eq!(imports.len(), 8);
+        assert!(imports[0]["desc"].as_str().unwrap().starts_with("pkh([73c5da0a/44h/0h/0h]"));
+        assert!(imports[7]["desc"].as_str().unwrap().starts_with("tr([73c5da0a/86h/0h/0h]"));
+        assert!(imports.iter().all(|import| import["range"] == json!([5, 10])));
+    }
+
+    #[test]
+    fn descriptors_of_every_script_type() {
+        let output: serde_json::Value =
+            serde_json::from_str(&descriptors(&root(), 0).unwrap()).unwrap();
+
+        assert_eq!(output["receive"].as_array().unwrap().len(), 4);
+        assert_eq!(output["internal"].as_array().unwrap().len(), 4);
+        assert_eq!(
+            output["receive"][2],
+            format!("wpkh({}/0/*)#afwvtk2s", ACCOUNT_XPUB)
+        );
+        assert_eq!(
+            output["internal"][2],
+            format!("wpkh({}/1/*)#vatdkr6g", ACCOUNT_XPUB)
+        );
+    }
+}
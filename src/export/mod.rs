@@ -0,0 +1,299 @@
+// Watch-only wallet exports in the file formats other wallet software already understands.
+//
+// Each format is an 'Exporter' in a 'Registry', which is all the command line tool dispatches on:
+// a new format (from this crate or a downstream one) is one more 'register' call, with no match
+// arms to add anywhere.
+
+pub mod bundle;
+pub mod coldcard;
+pub mod hwi;
+pub mod sparrow;
+pub mod xpub;
+
+use account::Account;
+use audit;
+use bip32::{DerivationPath, ExtendedPrivKey};
+use descriptor::ScriptType;
+use ur;
+use wallet_policy::WalletPolicy;
+
+// Everything a format might need; each one reads what it cares about.
+pub struct ExportRequest<'a> {
+    pub root: &'a ExtendedPrivKey,
+    // The BIP-44 account index:
+    pub account: u32,
+    // Formats describing one script type take the first; 'descriptor', 'bundle' and 'hwi-keypool'
+    // take them all.
+    pub script_types: &'a [ScriptType],
+    // For 'bundle', how many accounts (counting from 0):
+    pub accounts: u32,
+    // For 'xpub', the key to export, at any depth; without one, it's the account's:
+    pub path: Option<&'a DerivationPath>,
+}
+
+pub trait Exporter {
+    // What '--format' calls it, e.g. "hwi-descriptors":
+    fn name(&self) -> &'static str;
+    // One line, for '--help':
+    fn description(&self) -> &'static str;
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str>;
+}
+
+pub struct Registry {
+    exporters: Vec<Box<dyn Exporter>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry {
+            exporters: Vec::new(),
+        }
+    }
+
+    // The formats this crate ships:
+    pub fn builtin() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Bip388));
+        registry.register(Box::new(Bundle));
+        registry.register(Box::new(Coldcard));
+        registry.register(Box::new(Descriptor));
+        registry.register(Box::new(HwiDescriptors));
+        registry.register(Box::new(HwiKeypool));
+        registry.register(Box::new(Sparrow));
+        registry.register(Box::new(UrAccount));
+        registry.register(Box::new(UrHdkey));
+        registry.register(Box::new(Xpub));
+        registry
+    }
+
+    // A later exporter with the same name replaces the earlier one:
+    pub fn register(&mut self, exporter: Box<dyn Exporter>) {
+        self.exporters.retain(|existing| existing.name() != exporter.name());
+        self.exporters.push(exporter);
+    }
+
+    // The named format's export, reported to the audit hook when it succeeds:
+    pub fn export(&self, name: &str, request: &ExportRequest) -> Result<String, &'static str> {
+        let exporter = self.get(name).ok_or("Unknown export format.")?;
+        let output = exporter.export(request)?;
+        audit::emit(audit::Event::KeysExported {
+            format: exporter.name(),
+            fingerprint: request.root.fingerprint(),
+            account: request.account,
+        });
+        Ok(output)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Exporter> {
+        self.exporters
+            .iter()
+            .find(|exporter| exporter.name() == name)
+            .map(|exporter| exporter.as_ref())
+    }
+
+    pub fn exporters(&self) -> impl Iterator<Item = &dyn Exporter> {
+        self.exporters.iter().map(|exporter| exporter.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        Registry::builtin()
+    }
+}
+
+struct Bip388;
+
+impl Exporter for Bip388 {
+    fn name(&self) -> &'static str {
+        "bip388"
+    }
+
+    fn description(&self) -> &'static str {
+        "A BIP-388 wallet policy, for registering the account with a hardware signer"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        match request.script_types {
+            [script_type] => {
+                let account = Account::derive(request.root, *script_type, request.account)?;
+                let name = format!("{} account {}", script_type, request.account);
+                let policy = WalletPolicy::single_sig(&name, &account);
+                Ok(serde_json::to_string_pretty(&policy.to_json()).unwrap())
+            }
+            _ => Err("A single-sig wallet policy has exactly one script type."),
+        }
+    }
+}
+
+struct Bundle;
+
+impl Exporter for Bundle {
+    fn name(&self) -> &'static str {
+        "bundle"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every account xpub, path, and fingerprint for accounts 0..N in one JSON document"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        bundle::accounts(request.root, request.script_types, request.accounts)
+    }
+}
+
+struct Coldcard;
+
+impl Exporter for Coldcard {
+    fn name(&self) -> &'static str {
+        "coldcard"
+    }
+
+    fn description(&self) -> &'static str {
+        "The \"Generic JSON\" coldcard-export.json file"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        coldcard::generic_json(request.root, request.account)
+    }
+}
+
+struct Descriptor;
+
+impl Exporter for Descriptor {
+    fn name(&self) -> &'static str {
+        "descriptor"
+    }
+
+    fn description(&self) -> &'static str {
+        "One multipath descriptor (\"<0;1>/*\", receive and change together) per script type"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        let descriptors = request
+            .script_types
+            .iter()
+            .map(|script_type| {
+                Account::derive(request.root, *script_type, request.account)
+                    .map(|account| account.multipath_descriptor())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(descriptors.join("\n"))
+    }
+}
+
+struct HwiDescriptors;
+
+impl Exporter for HwiDescriptors {
+    fn name(&self) -> &'static str {
+        "hwi-descriptors"
+    }
+
+    fn description(&self) -> &'static str {
+        "The output of HWI's 'getdescriptors'"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        Ok(hwi::descriptors(request.root, request.account)?)
+    }
+}
+
+struct HwiKeypool;
+
+impl Exporter for HwiKeypool {
+    fn name(&self) -> &'static str {
+        "hwi-keypool"
+    }
+
+    fn description(&self) -> &'static str {
+        "The output of HWI's 'getkeypool'"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        Ok(hwi::keypool(
+            request.root,
+            request.script_types,
+            request.account,
+            hwi::DEFAULT_RANGE,
+        )?)
+    }
+}
+
+struct Sparrow;
+
+impl Exporter for Sparrow {
+    fn name(&self) -> &'static str {
+        "sparrow"
+    }
+
+    fn description(&self) -> &'static str {
+        "A watch-only wallet file for Sparrow's \"Import Wallet\""
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        match request.script_types {
+            [script_type] => sparrow::wallet(request.root, *script_type, request.account),
+            _ => Err("A Sparrow wallet has exactly one script type."),
+        }
+    }
+}
+
+struct UrAccount;
+
+impl Exporter for UrAccount {
+    fn name(&self) -> &'static str {
+        "ur-account"
+    }
+
+    fn description(&self) -> &'static str {
+        "A ur:crypto-account of every script type's account xpub, for Keystone and Passport"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        ur::crypto_account(request.root, request.account)
+    }
+}
+
+struct UrHdkey;
+
+impl Exporter for UrHdkey {
+    fn name(&self) -> &'static str {
+        "ur-hdkey"
+    }
+
+    fn description(&self) -> &'static str {
+        "A ur:crypto-hdkey of the script type's account xpub, with its origin"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        match request.script_types {
+            [script_type] => {
+                ur::crypto_hdkey(&Account::derive(request.root, *script_type, request.account)?)
+            }
+            _ => Err("A crypto-hdkey has exactly one script type."),
+        }
+    }
+}
+
+struct Xpub;
+
+impl Exporter for Xpub {
+    fn name(&self) -> &'static str {
+        "xpub"
+    }
+
+    fn description(&self) -> &'static str {
+        "The xpub at --path (any depth), or the script type's account xpub, with its origin"
+    }
+
+    fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+        match (request.path, request.script_types) {
+            (Some(path), _) => xpub::at_path(request.root, path),
+            (None, [script_type]) => {
+                let account = Account::derive(request.root, *script_type, request.account)?;
+                xpub::at_path(request.root, &account.origin.path)
+            }
+            _ => Err("Give one script type, or a --path."),
+        }
+    }
+}
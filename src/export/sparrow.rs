@@ -0,0 +1,81 @@
+// The wallet JSON Sparrow reads through File > Import Wallet: a single-signature policy with one
+// watch-only keystore carrying the master fingerprint, derivation and account xpub.
+
+use account::Account;
+use bip32::ExtendedPrivKey;
+use descriptor::ScriptType;
+
+pub fn wallet(
+    root: &ExtendedPrivKey,
+    script_type: ScriptType,
+    account: u32,
+) -> Result<String, &'static str> {
+    Ok(account_wallet(&Account::derive(root, script_type, account)?))
+}
+
+// The same file from the account alone, for watch-only wallets:
+pub fn account_wallet(account: &Account) -> String {
+    // Sparrow spells its script types in upper snake case:
+    let script_type = match account.script_type {
+        ScriptType::P2pkh => "P2PKH",
+        ScriptType::P2shP2wpkh => "P2SH_P2WPKH",
+        ScriptType::P2wpkh => "P2WPKH",
+        ScriptType::P2tr => "P2TR",
+    };
+
+    let output = json!({
+        "label": "bip-39",
+        "network": "MAINNET",
+        "policyType": "SINGLE",
+        "scriptType": script_type,
+        "keystores": [{
+            "label": "bip-39",
+            "source": "SW_WATCH",
+            "walletModel": "SPARROW",
+            "keyDerivation": {
+                "masterFingerprint": hex::encode(account.origin.fingerprint),
+                "derivationPath": account.origin.path.to_string(),
+            },
+            "extendedPublicKey": account.xpub.to_string(),
+        }],
+    });
+    serde_json::to_string_pretty(&output).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+    use Config;
+
+    // What Sparrow imports for BIP-84's test mnemonic (its "abandon ... about" native segwit
+    // wallet, account 0):
+    #[test]
+    fn golden_p2wpkh() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let config = Config::from_phrase(mnemonic, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(&wallet(&root, ScriptType::P2wpkh, 0).unwrap()).unwrap();
+        assert_eq!(
+            output,
+            json!({
+                "label": "bip-39",
+                "network": "MAINNET",
+                "policyType": "SINGLE",
+                "scriptType": "P2WPKH",
+                "keystores": [{
+                    "label": "bip-39",
+                    "source": "SW_WATCH",
+                    "walletModel": "SPARROW",
+                    "keyDerivation": {
+                        "masterFingerprint": "73c5da0a",
+                        "derivationPath": "m/84'/0'/0'",
+                    },
+                    "extendedPublicKey": "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V",
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,29 @@
+// One extended public key at whatever depth an integration wants it: the purpose level
+// (m/84'), an account's receive chain (m/84'/0'/0'/0), or the root itself (m).  The depth,
+// parent fingerprint, and child number come out of the derivation, so the xpub is the one any
+// other BIP-32 wallet derives at that path; they're in the document as well, for checking.
+
+use bip32::{DerivationPath, ExtendedPrivKey, KeyOrigin};
+use schema;
+
+pub fn at_path(root: &ExtendedPrivKey, path: &DerivationPath) -> Result<String, &'static str> {
+    let xpub = root.derive_path(path)?.to_extended_pub_key();
+    let origin = KeyOrigin::new(root.fingerprint(), path.clone());
+    let child_number = if xpub.depth == 0 {
+        None
+    } else {
+        Some(xpub.child_number.to_string())
+    };
+
+    let output = schema::versioned(json!({
+        "master_fingerprint": hex::encode(root.fingerprint()),
+        "path": path.to_string(),
+        "depth": xpub.depth,
+        "parent_fingerprint": hex::encode(xpub.parent_fingerprint),
+        "child_number": child_number,
+        "xpub": xpub.to_string(),
+        // The '#' in "{:#}" writes hardened steps as 'h', as descriptors do:
+        "key_expression": format!("{:#}{}", origin, xpub),
+    }));
+    Ok(serde_json::to_string_pretty(&output).unwrap())
+}
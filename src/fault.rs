@@ -0,0 +1,63 @@
+// For signing devices, where a well-timed voltage or clock glitch can make one computation skip
+// an instruction and come out wrong (a checksum that passes when it shouldn't, or a key that
+// isn't the one the words make): with the 'double-check' feature, every checksum and derivation
+// is computed twice and the two results compared before either is used, aborting if they differ.
+// One glitch can't land on both the same way.
+//
+// It's a build option rather than a runtime switch, so there's no flag in memory for a glitch to
+// turn off.  Without the feature, 'twice' computes once.
+
+#[cfg(feature = "double-check")]
+use core::hint::black_box;
+#[cfg(all(feature = "double-check", feature = "std"))]
+use std::process;
+
+// 'same' says whether two results agree; 'PartialEq::eq' for anything that has it.
+#[cfg(feature = "double-check")]
+pub fn twice<T>(mut compute: impl FnMut() -> T, same: impl Fn(&T, &T) -> bool) -> T {
+    let first = compute();
+    // (through 'black_box', so the optimizer can't notice it's the same computation and reuse
+    // the first result)
+    let second = black_box(&mut compute)();
+    if !black_box(same(&first, &second)) {
+        mismatch()
+    }
+    first
+}
+
+#[cfg(not(feature = "double-check"))]
+pub fn twice<T>(mut compute: impl FnMut() -> T, _same: impl Fn(&T, &T) -> bool) -> T {
+    compute()
+}
+
+// The same for a result written into the caller's 'output': the second computation goes into a
+// buffer of its own, which is wiped once the two have been compared.
+#[cfg(feature = "double-check")]
+pub fn twice_into(output: &mut [u8; 64], mut compute: impl FnMut(&mut [u8; 64])) {
+    compute(output);
+    let mut second = [0; 64];
+    black_box(&mut compute)(&mut second);
+    let same = black_box(output[..] == second[..]);
+    black_box(&mut second).fill(0);
+    if !same {
+        mismatch()
+    }
+}
+
+// An abort rather than a panic, so nothing unwinds through code the glitch may have landed in;
+// without std, it's the firmware's panic handler (which should reset the device) that decides.
+#[cfg(feature = "double-check")]
+fn mismatch() -> ! {
+    #[cfg(feature = "std")]
+    {
+        eprintln!("A computation came out differently the second time; aborting.");
+        process::abort()
+    }
+    #[cfg(not(feature = "std"))]
+    panic!("A computation came out differently the second time.")
+}
+
+#[cfg(not(feature = "double-check"))]
+pub fn twice_into(output: &mut [u8; 64], mut compute: impl FnMut(&mut [u8; 64])) {
+    compute(output)
+}
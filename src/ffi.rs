@@ -0,0 +1,167 @@
+// A C interface, for apps that call in from Swift, Kotlin (through JNI) or C itself, behind the
+// 'ffi' feature.  build.rs writes its header, bip39.h, from the signatures below (to OUT_DIR, and
+// to $BIP39_HEADER if that's set):
+//
+//   BIP39_HEADER=include/bip39.h cargo build --release --no-default-features -F ffi
+//
+// The library is then target/release/libbip_39.so (or .dylib), or for iOS, a static library:
+//
+//   cargo rustc --release --lib --no-default-features -F ffi --crate-type staticlib
+//
+// Strings go in as NUL-terminated UTF-8, and come out the same way: a returned 'char *' is the
+// caller's, to give back to bip39_string_free (which wipes it, since it's a mnemonic or a key)
+// and never to free().  Seeds are written into the caller's 64 bytes instead.  A function that
+// fails returns NULL or -1, and bip39_last_error says why (until the thread's next failure).
+//
+// The functions are 'unsafe' for Rust callers, who have the library itself: it's up to the caller
+// that each pointer is NULL (where allowed) or valid for what it's said to point to.  (That's the
+// safety section for all of them, hence no '# Safety' on each.)
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use zeroize::Zeroize;
+
+use language::Language;
+use network::Network;
+use {Config, Error, MnemonicListSize, Seed};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail(err: Error) {
+    // (the crate's messages have no NULs in them)
+    let message = CString::new(err.to_string()).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+unsafe fn read_str<'a>(string: *const c_char) -> Result<&'a str, Error> {
+    if string.is_null() {
+        return Err(Error::Other("A required string was NULL."));
+    }
+    Ok(CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| "A string wasn't UTF-8.")?)
+}
+
+fn into_c_string(mut string: String) -> *mut c_char {
+    let owned = CString::new(string.as_bytes()).map(CString::into_raw);
+    string.zeroize();
+    owned.unwrap_or(ptr::null_mut())
+}
+
+// A new mnemonic of 12, 15, 18, 21 or 24 words, in 'language' ("english", "japanese", ...), or
+// the default wordlist's when that's NULL; NULL on failure.  Free it with bip39_string_free.
+#[no_mangle]
+pub unsafe extern "C" fn bip39_generate_mnemonic(
+    words: u32,
+    language: *const c_char,
+) -> *mut c_char {
+    let generate = || -> Result<String, Error> {
+        let language = if language.is_null() {
+            Language::compiled()
+        } else {
+            read_str(language)?.parse()?
+        };
+        let size = MnemonicListSize::from(words as usize)?;
+        Ok(Config::generate_in(language, size, "")
+            .mnemonic
+            .to_string())
+    };
+    generate().map(into_c_string).unwrap_or_else(|err| {
+        fail(err);
+        ptr::null_mut()
+    })
+}
+
+// 0 if 'phrase' is a valid mnemonic (in any embedded wordlist), or -1, with bip39_last_error
+// saying what's wrong with it (a word count, an unknown word, the checksum).
+#[no_mangle]
+pub unsafe extern "C" fn bip39_validate(phrase: *const c_char) -> i32 {
+    match read_str(phrase).and_then(|phrase| Config::from_phrase(phrase, "")) {
+        Ok(_) => 0,
+        Err(err) => {
+            fail(err);
+            -1
+        }
+    }
+}
+
+// Writes the mnemonic's 64-byte BIP-39 seed into 'seed'; 'passphrase' may be NULL for none.
+// 0, or -1 if the phrase isn't a valid mnemonic.
+#[no_mangle]
+pub unsafe extern "C" fn bip39_mnemonic_to_seed(
+    phrase: *const c_char,
+    passphrase: *const c_char,
+    seed: *mut u8,
+) -> i32 {
+    let derive = || -> Result<(), Error> {
+        let passphrase = if passphrase.is_null() {
+            ""
+        } else {
+            read_str(passphrase)?
+        };
+        if seed.is_null() {
+            return Err(Error::Other("The seed buffer was NULL."));
+        }
+        let config = Config::from_phrase(read_str(phrase)?, passphrase)?;
+        config.derive_seed_into(&mut *(seed as *mut [u8; 64]));
+        Ok(())
+    };
+    match derive() {
+        Ok(()) => 0,
+        Err(err) => {
+            fail(err);
+            -1
+        }
+    }
+}
+
+// The BIP-32 root key of a 64-byte seed, with 'network''s prefix ("testnet" for a tprv, say, or
+// NULL for mainnet's xprv).  NULL on failure.  Free it with bip39_string_free.
+#[no_mangle]
+pub unsafe extern "C" fn bip39_seed_to_xprv(
+    seed: *const u8,
+    network: *const c_char,
+) -> *mut c_char {
+    let derive = || -> Result<String, Error> {
+        let network = if network.is_null() {
+            Network::Mainnet
+        } else {
+            read_str(network)?.parse()?
+        };
+        if seed.is_null() {
+            return Err(Error::Other("The seed was NULL."));
+        }
+        let seed = Seed::from_bytes(&*(seed as *const [u8; 64]))?;
+        Ok(seed.to_root_key(network)?.to_string())
+    };
+    derive().map(into_c_string).unwrap_or_else(|err| {
+        fail(err);
+        ptr::null_mut()
+    })
+}
+
+// Wipes and frees a string this library returned; NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn bip39_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        let mut bytes = CString::from_raw(string).into_bytes();
+        bytes.zeroize();
+    }
+}
+
+// Why this thread's last failing call failed, or NULL if none has.  The string is the library's,
+// and good until the thread's next failure.
+#[no_mangle]
+pub extern "C" fn bip39_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
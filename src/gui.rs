@@ -0,0 +1,317 @@
+// A small native window ('--features gui', then 'cargo run --bin bip-39-gui') for whoever would
+// rather not use a terminal: the same generate, recover and verify steps as the command line
+// tool, on the same library, and just as offline (nothing here opens a connection).
+//
+//   Generate  a new mnemonic, in any embedded language, of 12 to 24 words
+//   Recover   a mnemonic and passphrase back to the wallet, shown the way '--preview' shows it:
+//             the master fingerprint, the account xpub and its first address, none of which can
+//             spend from it
+//   Verify    whether a written down phrase is a valid mnemonic, and if not, why not (in the
+//             phrase's language) and which words were likely meant
+//
+// egui's built in fonts have no Chinese, Japanese or Korean characters, so those wordlists aren't
+// offered for new mnemonics, and errors about phrases in them are in English.  (Reading such a
+// phrase still works: it's pasted in, and only the words themselves would show as boxes.)
+//
+// The phrase and passphrase fields are wiped when they're cleared, when another tab is picked, and
+// when the window closes.  (egui keeps some state of its own for a text field being edited, its
+// undo history among it; closing the window is the way to be rid of that too.)
+
+extern crate bip_39;
+extern crate eframe;
+extern crate hex;
+extern crate zeroize;
+
+use bip_39::i18n;
+use bip_39::language::Language;
+use bip_39::mnemonic::{Mnemonic, ParseOptions};
+use bip_39::network::Network;
+use bip_39::preview;
+use bip_39::{normalize_mnemonic, Config, MnemonicListSize};
+use eframe::egui;
+use zeroize::Zeroize;
+
+const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+fn main() -> eframe::Result {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([640.0, 480.0]),
+        ..Default::default()
+    };
+    eframe::run_native("bip-39", options, Box::new(|_| Ok(Box::new(App::new()))))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Generate,
+    Recover,
+    Verify,
+}
+
+struct App {
+    tab: Tab,
+    // The language for new mnemonics (phrases being read are in whichever list takes them):
+    language: Language,
+    word_count: usize,
+    // The generated mnemonic's words, numbered as a backup card has them:
+    generated: Option<Config>,
+    phrase: String,
+    passphrase: String,
+    // What the last Recover or Verify came to, as lines of text:
+    report: Vec<String>,
+}
+
+impl App {
+    fn new() -> App {
+        // The compiled in language, unless it's one that can't be shown:
+        let languages = drawable_languages();
+        let language = match languages.first() {
+            Some(&first) if !drawable(Language::compiled()) => first,
+            _ => Language::compiled(),
+        };
+        App {
+            tab: Tab::Generate,
+            language,
+            word_count: 24,
+            generated: None,
+            phrase: String::new(),
+            passphrase: String::new(),
+            report: Vec::new(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.generated = None;
+        self.phrase.zeroize();
+        self.passphrase.zeroize();
+        self.report.iter_mut().for_each(Zeroize::zeroize);
+        self.report.clear();
+    }
+
+    fn generate(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("language")
+                .selected_text(self.language.native_name())
+                .show_ui(ui, |ui| {
+                    for language in drawable_languages() {
+                        ui.selectable_value(&mut self.language, language, language.native_name());
+                    }
+                });
+            egui::ComboBox::from_label("words")
+                .selected_text(self.word_count.to_string())
+                .show_ui(ui, |ui| {
+                    for count in WORD_COUNTS {
+                        ui.selectable_value(&mut self.word_count, count, count.to_string());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Generate").clicked() {
+                // (the counts offered are all MnemonicListSize lengths)
+                let size = MnemonicListSize::from(self.word_count).unwrap();
+                self.generated = Some(Config::generate_in(self.language, size, ""));
+            }
+            if ui.button("Clear").clicked() {
+                self.clear();
+            }
+        });
+        ui.separator();
+
+        if let Some(config) = &self.generated {
+            ui.label("Write these words down, in order, and keep them offline:");
+            egui::Grid::new("words").num_columns(4).show(ui, |ui| {
+                for (index, word) in config.mnemonic.split(' ').enumerate() {
+                    ui.monospace(format!("{:>2}. {}", index + 1, word));
+                    if index % 4 == 3 {
+                        ui.end_row();
+                    }
+                }
+            });
+        }
+    }
+
+    fn phrase_fields(&mut self, ui: &mut egui::Ui, passphrase: bool) {
+        ui.label("Mnemonic:");
+        ui.add(
+            egui::TextEdit::multiline(&mut self.phrase)
+                .desired_rows(3)
+                .desired_width(f32::INFINITY),
+        );
+        if passphrase {
+            ui.horizontal(|ui| {
+                ui.label("Passphrase (if any):");
+                ui.add(egui::TextEdit::singleline(&mut self.passphrase).password(true));
+            });
+        }
+    }
+
+    fn recover(&mut self, ui: &mut egui::Ui) {
+        self.phrase_fields(ui, true);
+        ui.horizontal(|ui| {
+            if ui.button("Recover").clicked() {
+                self.report = recovered(&self.phrase, &self.passphrase);
+            }
+            if ui.button("Clear").clicked() {
+                self.clear();
+            }
+        });
+        self.show_report(ui);
+    }
+
+    fn verify(&mut self, ui: &mut egui::Ui) {
+        self.phrase_fields(ui, false);
+        ui.horizontal(|ui| {
+            if ui.button("Verify").clicked() {
+                self.report = verified(&self.phrase);
+            }
+            if ui.button("Clear").clicked() {
+                self.clear();
+            }
+        });
+        self.show_report(ui);
+    }
+
+    fn show_report(&self, ui: &mut egui::Ui) {
+        ui.separator();
+        for line in &self.report {
+            ui.monospace(line);
+        }
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::TopBottomPanel::top("tabs").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let tab = self.tab;
+                ui.selectable_value(&mut self.tab, Tab::Generate, "Generate");
+                ui.selectable_value(&mut self.tab, Tab::Recover, "Recover");
+                ui.selectable_value(&mut self.tab, Tab::Verify, "Verify");
+                // (what one tab showed isn't left for the next)
+                if self.tab != tab {
+                    self.clear();
+                }
+            });
+        });
+        egui::CentralPanel::default().show(ctx, |ui| match self.tab {
+            Tab::Generate => self.generate(ui),
+            Tab::Recover => self.recover(ui),
+            Tab::Verify => self.verify(ui),
+        });
+    }
+}
+
+impl Drop for App {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+fn drawable(language: Language) -> bool {
+    !matches!(
+        language,
+        Language::ChineseSimplified
+            | Language::ChineseTraditional
+            | Language::Japanese
+            | Language::Korean
+    )
+}
+
+// The embedded languages whose words the window can show:
+fn drawable_languages() -> Vec<Language> {
+    Language::available()
+        .into_iter()
+        .filter(|&language| drawable(language))
+        .collect()
+}
+
+// The phrase as the command line tool reads it: pasted in oddities cleaned out, in whichever
+// embedded language takes it, or else the one with the most of its words, for the error:
+fn parse(phrase: &str) -> Result<Mnemonic, (Language, bip_39::Error)> {
+    let mut phrase = normalize_mnemonic(phrase);
+    let language = Language::detect(&phrase).unwrap_or_else(|_| Language::closest(&phrase));
+    let parsed = Mnemonic::parse(&phrase, language).map_err(|err| (language, err));
+    phrase.zeroize();
+    parsed
+}
+
+// What's wrong with the phrase, in its language, with the words that were likely meant:
+fn problems(phrase: &str, language: Language, err: &bip_39::Error) -> Vec<String> {
+    let language = if drawable(language) {
+        language
+    } else {
+        Language::English
+    };
+    let mut lines = vec![format!(
+        "Not valid: {}",
+        i18n::localize(err.message(), language)
+    )];
+    let mut phrase = normalize_mnemonic(phrase);
+    if let Ok(lenient) = Mnemonic::parse_with(&phrase, language, &ParseOptions::lenient()) {
+        for correction in lenient.corrections() {
+            lines.push(format!(
+                "word {}: '{}' is likely '{}'",
+                correction.position + 1,
+                correction.written,
+                correction.word
+            ));
+        }
+    }
+    phrase.zeroize();
+    lines
+}
+
+fn recovered(phrase: &str, passphrase: &str) -> Vec<String> {
+    let mnemonic = match parse(phrase) {
+        Ok(mnemonic) => mnemonic,
+        Err((language, err)) => return problems(phrase, language, &err),
+    };
+    let shown = mnemonic
+        .to_seed(passphrase)
+        .to_root_key(Network::Mainnet)
+        .map_err(|err| err.message())
+        .and_then(|root| preview::preview(&root));
+    match shown {
+        Ok(shown) => vec![
+            format!("master fingerprint: {}", hex::encode(shown.fingerprint)),
+            format!("account xpub: {}", shown.account.key_expression()),
+            format!("first address: {}", shown.first_address),
+        ],
+        Err(err) => vec![format!("Error: {}", err)],
+    }
+}
+
+fn verified(phrase: &str) -> Vec<String> {
+    match parse(phrase) {
+        Ok(mnemonic) => vec![format!(
+            "Valid: a {} word mnemonic, in {}",
+            mnemonic.words().len(),
+            mnemonic.language().native_name()
+        )],
+        Err((language, err)) => problems(phrase, language, &err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                          abandon abandon about";
+
+    #[test]
+    fn verify_and_recover() {
+        assert_eq!(verified(PHRASE), ["Valid: a 12 word mnemonic, in English"]);
+        let typo = PHRASE.replace("about", "abuot");
+        let report = verified(&typo);
+        assert_eq!(
+            report[0],
+            "Not valid: A mnemonic word isn't in the word list."
+        );
+        assert_eq!(report[1], "word 12: 'abuot' is likely 'about'");
+
+        let report = recovered(PHRASE, "");
+        assert_eq!(report[0], "master fingerprint: 73c5da0a");
+        assert!(report[2].ends_with("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"));
+    }
+}
@@ -0,0 +1,196 @@
+// Thin wrappers around the hashing primitives the rest of the crate needs, so the individual
+// modules don't each have to juggle a hash library's state.  All of them are RustCrypto's
+// maintained crates: SHA-256 and SHA-512 ('sha2'), HMAC ('hmac'), RIPEMD-160, SHA-1 and BLAKE2b.
+// Only PBKDF2-HMAC-SHA512's loop is this module's own, for the progress reports and the salt in
+// parts the 'pbkdf2' crate has no room for.  (The 'paranoid' feature derives seeds and root keys
+// again with ring, which makes it the second opinion.)
+
+use core::iter;
+
+#[cfg(feature = "std")]
+use blake2::digest::consts::U20;
+#[cfg(feature = "std")]
+use blake2::Blake2b;
+use hmac::{Hmac, Mac};
+use ripemd::Ripemd160;
+#[cfg(feature = "std")]
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+use zeroize::Zeroize;
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+// BIP-340's tagged hashes, SHA256(SHA256(tag) || SHA256(tag) || data), which keep the hashes
+// taproot and BIP-322 use for different purposes from ever colliding:
+#[cfg(feature = "std")]
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag_hash = sha256(tag.as_bytes());
+    Sha256::new()
+        .chain_update(tag_hash)
+        .chain_update(tag_hash)
+        .chain_update(data)
+        .finalize()
+        .into()
+}
+
+pub fn double_sha256(data: &[u8]) -> [u8; 32] {
+    sha256(&sha256(data))
+}
+
+// Bitcoin's "HASH160" is RIPEMD-160 over SHA-256; it's what key fingerprints and
+// legacy addresses are made from.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(sha256(data)).into()
+}
+
+// Only for OpenPGP key fingerprints, which are still SHA-1:
+#[cfg(feature = "std")]
+pub fn sha1(data: &[u8]) -> [u8; 20] {
+    Sha1::digest(data).into()
+}
+
+// BLAKE2b cut to 20 bytes, which Tezos hashes public keys into addresses with:
+#[cfg(feature = "std")]
+pub fn blake2b_160(data: &[u8]) -> [u8; 20] {
+    Blake2b::<U20>::digest(data).into()
+}
+
+pub fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    HmacSha512::new(key).mac(data)
+}
+
+// HMAC-SHA512 with the key's padded blocks already hashed in: 'hmac' keys a MAC by compressing
+// them into an inner and an outer SHA-512 state, and each message here starts from a copy of
+// those, which saves two of the (usually four) SHA-512 compressions a one-off HMAC costs.  Worth
+// keeping around whenever one key MACs many messages, such as a parent chain code deriving a
+// batch of children, or PBKDF2's rounds.  (The states are as good as the key, but neither 'hmac'
+// nor 'sha2' wipes them when they go.)
+#[derive(Clone)]
+pub struct HmacSha512(Hmac<Sha512>);
+
+impl HmacSha512 {
+    pub fn new(key: &[u8]) -> HmacSha512 {
+        // (HMAC takes keys of any length, hashing long ones down first)
+        HmacSha512(Hmac::new_from_slice(key).unwrap())
+    }
+
+    pub fn mac(&self, data: &[u8]) -> [u8; 64] {
+        self.mac_parts(iter::once(data))
+    }
+
+    // The MAC of the parts one after another, without first copying them together:
+    pub fn mac_parts<'a>(&self, parts: impl IntoIterator<Item = &'a [u8]>) -> [u8; 64] {
+        let mut mac = self.0.clone();
+        for part in parts {
+            mac.update(part);
+        }
+        let mut output = [0; 64];
+        output.copy_from_slice(&mac.finalize().into_bytes());
+        output
+    }
+}
+
+// PBKDF2-HMAC-SHA512, filling 'output': one 64 byte block is all BIP-39 ever asks for, and other
+// lengths are for the schemes that stretch words to 32 or 96 bytes.  'progress' hears (rounds
+// done, total rounds) every PROGRESS_INTERVAL rounds and once more at the end, counting every
+// block's rounds.  The salt comes in parts ("mnemonic" and the passphrase, for BIP-39) that are
+// MACed in turn, so the passphrase is never copied onto the heap to join them, and the blocks are
+// accumulated in the caller's 'output' rather than returned, so no copy of them is left behind on
+// the stack.
+pub const PROGRESS_INTERVAL: u32 = 64;
+
+pub fn pbkdf2_hmac_sha512(
+    password: &[u8],
+    salt: &[&[u8]],
+    rounds: u32,
+    progress: &mut dyn FnMut(u32, u32),
+    output: &mut [u8],
+) {
+    let mac = HmacSha512::new(password);
+    let blocks = output.len().div_ceil(64) as u32;
+    let total = rounds * blocks;
+
+    // (the last block is cut short when the length isn't a multiple of 64)
+    for (done, chunk) in (0..).step_by(rounds as usize).zip(output.chunks_mut(64)) {
+        // U1 = HMAC(password, salt || INT(block)), blocks counting from 1:
+        let index = (done / rounds + 1).to_be_bytes();
+        let mut block = mac.mac_parts(salt.iter().cloned().chain(iter::once(&index[..])));
+        chunk.copy_from_slice(&block[..chunk.len()]);
+
+        // Ui = HMAC(password, Ui-1), XORed into the output:
+        for round in 2..=rounds {
+            block = mac.mac(&block);
+            for (byte, u) in chunk.iter_mut().zip(block.iter()) {
+                *byte ^= u;
+            }
+
+            if (done + round) % PROGRESS_INTERVAL == 0 && done + round != total {
+                progress(done + round, total);
+            }
+        }
+        // (the last U is the block's final input to the XOR, and no less secret)
+        block.zeroize();
+    }
+
+    progress(total, total);
+}
+
+// HMAC-SHA256 and PBKDF2-HMAC-SHA256, for SLIP-39's share digests and the rounds that encrypt its
+// master secret.  Those are a few thousand rounds over 16 to 32 bytes, so the plain way will do.
+#[cfg(feature = "std")]
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(feature = "std")]
+pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], rounds: u32, output: &mut [u8]) {
+    pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, rounds, output);
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    // The first Trezor BIP-39 vector's seed is PBKDF2-HMAC-SHA512 of the mnemonic, salted with
+    // "mnemonic" and the passphrase, over 2048 rounds:
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const SEED: &str = "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04";
+
+    #[test]
+    fn pbkdf2_progress() {
+        let mut reports = Vec::new();
+        let mut seed = [0; 64];
+        pbkdf2_hmac_sha512(
+            MNEMONIC.as_bytes(),
+            &[b"mnemonic", b"TREZOR"],
+            2048,
+            &mut |done, total| reports.push((done, total)),
+            &mut seed,
+        );
+        assert_eq!(hex::encode(seed), SEED);
+
+        // Every PROGRESS_INTERVAL rounds, then once at the end (which 2048 also falls on):
+        let expected: Vec<(u32, u32)> = (1..=2048 / PROGRESS_INTERVAL)
+            .map(|step| (step * PROGRESS_INTERVAL, 2048))
+            .collect();
+        assert_eq!(reports, expected);
+
+        // Two blocks count as twice the rounds, and the first block is the same as before:
+        let mut last = (0, 0);
+        let mut long = [0; 100];
+        pbkdf2_hmac_sha512(
+            MNEMONIC.as_bytes(),
+            &[b"mnemonicTREZOR"],
+            2048,
+            &mut |done, total| last = (done, total),
+            &mut long,
+        );
+        assert_eq!(hex::encode(&long[..64]), SEED);
+        assert_eq!(last, (4096, 4096));
+    }
+}
@@ -0,0 +1,81 @@
+// Mnemonic validation errors in the languages of the wordlists, so someone recovering a Spanish
+// mnemonic is told what's wrong with it in Spanish.  They're looked up by their English text
+// ('Error::message', or any of the '&'static str' messages); a message that hasn't been translated
+// comes back as it went in.
+//
+//   Err(err) => eprintln!("Error: {}", i18n::localize(err.message(), Language::Spanish)),
+
+use language::Language;
+
+// Each row in 'Language::all()' order, English first:
+const MESSAGES: [[&str; 10]; 5] = [
+    [
+        "Invalid mnemonic word list size.",
+        "助记词的单词数量无效。",
+        "助記詞的單詞數量無效。",
+        "Neplatný počet slov mnemotechnické fráze.",
+        "Nombre de mots de la phrase mnémonique invalide.",
+        "Numero di parole della frase mnemonica non valido.",
+        "ニーモニックの単語数が正しくありません。",
+        "니모닉 단어 개수가 올바르지 않습니다.",
+        "Número de palavras do mnemônico inválido.",
+        "Número de palabras de la frase mnemotécnica no válido.",
+    ],
+    [
+        "That wordlist isn't built in.",
+        "此版本未内置该词表。",
+        "此版本未內建該詞表。",
+        "Tento seznam slov není součástí sestavení.",
+        "Cette liste de mots n'est pas intégrée.",
+        "Questo elenco di parole non è incluso.",
+        "その単語リストは組み込まれていません。",
+        "해당 단어 목록이 포함되어 있지 않습니다.",
+        "Essa lista de palavras não está incluída.",
+        "Esa lista de palabras no está incluida.",
+    ],
+    [
+        "A mnemonic word isn't in the word list.",
+        "助记词中有单词不在词表中。",
+        "助記詞中有單詞不在詞表中。",
+        "Jedno ze slov není v seznamu slov.",
+        "Un mot de la phrase mnémonique n'est pas dans la liste de mots.",
+        "Una parola della frase mnemonica non è nell'elenco di parole.",
+        "単語リストにない単語が含まれています。",
+        "단어 목록에 없는 단어가 있습니다.",
+        "Uma palavra do mnemônico não está na lista de palavras.",
+        "Una palabra de la frase mnemotécnica no está en la lista de palabras.",
+    ],
+    [
+        "A mnemonic word isn't in the word list, nor close to one.",
+        "助记词中有单词不在词表中，也没有相近的单词。",
+        "助記詞中有單詞不在詞表中，也沒有相近的單詞。",
+        "Jedno ze slov není v seznamu slov, ani se žádnému nepodobá.",
+        "Un mot de la phrase mnémonique n'est pas dans la liste de mots, ni proche d'aucun.",
+        "Una parola della frase mnemonica non è nell'elenco di parole, né simile ad alcuna.",
+        "単語リストにない単語が含まれており、近い単語もありません。",
+        "단어 목록에 없는 단어가 있으며, 비슷한 단어도 없습니다.",
+        "Uma palavra do mnemônico não está na lista de palavras, nem se parece com nenhuma.",
+        "Una palabra de la frase mnemotécnica no está en la lista de palabras, ni se parece a ninguna.",
+    ],
+    [
+        "Invalid mnemonic checksum.",
+        "助记词校验和无效。",
+        "助記詞校驗和無效。",
+        "Neplatný kontrolní součet mnemotechnické fráze.",
+        "Somme de contrôle de la phrase mnémonique invalide.",
+        "Checksum della frase mnemonica non valido.",
+        "ニーモニックのチェックサムが正しくありません。",
+        "니모닉 체크섬이 올바르지 않습니다.",
+        "Checksum do mnemônico inválido.",
+        "Suma de verificación de la frase mnemotécnica no válida.",
+    ],
+];
+
+pub fn localize(message: &str, language: Language) -> &str {
+    // (a custom list's speakers get English)
+    let slot = Language::all().iter().position(|&other| other == language).unwrap_or(0);
+    MESSAGES
+        .iter()
+        .find(|row| row[0] == message)
+        .map_or(message, |row| row[slot])
+}
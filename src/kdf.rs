@@ -0,0 +1,120 @@
+// The one step where BIP-39 and its look-alikes part ways: turning the words and passphrase into a
+// seed.  Electrum's seeds, for one, are PBKDF2 just the same but salted with "electrum" instead of
+// "mnemonic"; everything after the seed (BIP-32, accounts, addresses) is shared, so a different
+// 'Kdf' passed to 'Config::to_seed_with' is all it takes.
+
+use alloc::borrow::Cow;
+
+use zeroize::Zeroize;
+
+use alloc_prelude::*;
+use hash;
+use nfkd;
+
+pub trait Kdf {
+    // 'mnemonic' is the words joined by single spaces; 'progress' hears (rounds done, total
+    // rounds) for whatever "rounds" mean to the implementation.
+    fn derive(
+        &self,
+        mnemonic: &str,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u32, u32),
+    ) -> [u8; 64];
+
+    // The same, written into 'seed'; implementations that can skip the copy override it.
+    fn derive_into(
+        &self,
+        mnemonic: &str,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u32, u32),
+        seed: &mut [u8; 64],
+    ) {
+        *seed = self.derive(mnemonic, passphrase, progress);
+    }
+}
+
+// The spec's own: 2,048 rounds of PBKDF2-HMAC-SHA512, salted with "mnemonic" + passphrase.
+pub struct Bip39;
+
+impl Kdf for Bip39 {
+    fn derive(
+        &self,
+        mnemonic: &str,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u32, u32),
+    ) -> [u8; 64] {
+        let mut seed = [0; 64];
+        self.derive_into(mnemonic, passphrase, progress, &mut seed);
+        seed
+    }
+
+    fn derive_into(
+        &self,
+        mnemonic: &str,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u32, u32),
+        seed: &mut [u8; 64],
+    ) {
+        // ('get_salt', in its two parts, without a String to hold the passphrase unless it has to
+        // be normalized)
+        let (mnemonic, passphrase) = (nfkd(mnemonic), nfkd(passphrase));
+        let salt = [&b"mnemonic"[..], passphrase.as_bytes()];
+        hash::pbkdf2_hmac_sha512(mnemonic.as_bytes(), &salt, 2048, progress, seed);
+        // (normalized copies are as secret as what they were normalized from)
+        for text in [mnemonic, passphrase] {
+            if let Cow::Owned(mut text) = text {
+                text.zeroize();
+            }
+        }
+    }
+}
+
+// The PBKDF2-HMAC-SHA512 underneath 'Bip39', with its parameters out in the open for schemes
+// that salt the words differently or stretch them to some other length (32 or 96 bytes, say).
+// 'Pbkdf2::default()' is BIP-39's own; 'Config::to_seed' and the 'Kdf' trait stay at 64 bytes.
+pub struct Pbkdf2 {
+    // Put in front of the passphrase, "mnemonic" for BIP-39:
+    pub salt_prefix: String,
+    pub rounds: u32,
+    // In bytes:
+    pub length: usize,
+}
+
+impl Default for Pbkdf2 {
+    fn default() -> Pbkdf2 {
+        Pbkdf2 {
+            salt_prefix: "mnemonic".to_string(),
+            rounds: 2048,
+            length: 64,
+        }
+    }
+}
+
+impl Pbkdf2 {
+    pub fn derive(&self, mnemonic: &str, passphrase: &str) -> Result<Vec<u8>, &'static str> {
+        let mut output = vec![0; self.length];
+        self.derive_into(mnemonic, passphrase, &mut output)?;
+        Ok(output)
+    }
+
+    // Fills 'output', whatever its length ('self.length' is for 'derive'):
+    pub fn derive_into(
+        &self,
+        mnemonic: &str,
+        passphrase: &str,
+        output: &mut [u8],
+    ) -> Result<(), &'static str> {
+        if self.rounds == 0 {
+            return Err("PBKDF2 needs at least one round.");
+        }
+        let salt = [self.salt_prefix.as_bytes(), passphrase.as_bytes()];
+        hash::pbkdf2_hmac_sha512(
+            mnemonic.as_bytes(),
+            &salt,
+            self.rounds,
+            &mut |_, _| {},
+            output,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+// A keystore file: a mnemonic (or the seed from one) encrypted with a password, as a JSON document
+// that says how, in the spirit of Ethereum's keystores, so it can sit in a password manager or a
+// cloud drive and still mean nothing without the password:
+//
+//   {
+//     "header": {
+//       "version": 1,
+//       "contents": "mnemonic",
+//       "kdf": { "name": "scrypt", "log_n": 17, "r": 8, "p": 1, "salt": "<hex>" },
+//       "cipher": { "name": "aes-256-gcm", "nonce": "<hex>" }
+//     },
+//     "ciphertext": "<hex>",
+//     "tag": "<hex>"
+//   }
+//
+// The key comes from scrypt or Argon2id (with "memory", "iterations" and "lanes" in place of
+// scrypt's numbers), and the header, as written, is AES-256-GCM's associated data: altering it
+// (to weaker parameters, or "seed" for "mnemonic") fails the tag just as a wrong password does.
+// Unlike the vault's format it needs no optional features, and any JSON tool can read its header.
+//
+//   let file = keystore::export(&Contents::Mnemonic(mnemonic), "password")?;
+//   let Contents::Mnemonic(mnemonic) = keystore::import(&file, "password")? else { ... };
+//
+// The BIP-39 passphrase isn't in a mnemonic keystore, as it isn't in an age backup or a vault.  A
+// seed keystore has it worked in already, for wallets that only ever needed the seed.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce, Tag};
+use scrypt::{scrypt, Params as ScryptParams};
+use serde_json::Value;
+use zeroize::Zeroize;
+
+use argon2::{self, Params};
+use entropy;
+use error::Error;
+use mnemonic::Mnemonic;
+use Seed;
+
+const VERSION: u64 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+// The most a keystore may ask for, as for vaults, so a doctored header can't exhaust the machine
+// opening it: 4 GiB, and 16 scrypt threads or 64 Argon2 lanes.
+const MAX_MEMORY: u64 = 4 << 30;
+const MAX_PARALLELISM: u32 = 16;
+const MAX_LANES: u32 = 64;
+
+pub enum Contents {
+    Mnemonic(Mnemonic),
+    Seed(Seed),
+}
+
+// How the key is derived from the password:
+#[derive(Clone, Debug)]
+pub enum KeyDerivation {
+    // N = 2^log_n; scrypt takes 128 * r * N bytes of memory:
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    Argon2id(Params),
+}
+
+impl KeyDerivation {
+    // scrypt's 128 MiB (Ethereum's keystores take twice that, which is slow to open on a phone):
+    pub fn scrypt() -> KeyDerivation {
+        KeyDerivation::Scrypt {
+            log_n: 17,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    // The vault's defaults:
+    pub fn argon2id() -> KeyDerivation {
+        KeyDerivation::Argon2id(Params {
+            memory: 64 * 1024,
+            iterations: 3,
+            lanes: 4,
+        })
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        let fits = match *self {
+            KeyDerivation::Scrypt { log_n, r, p } => {
+                log_n > 0
+                    && log_n < 32
+                    && r > 0
+                    && (1..=MAX_PARALLELISM).contains(&p)
+                    && 128 * r as u64 * (1 << log_n) <= MAX_MEMORY
+            }
+            KeyDerivation::Argon2id(ref params) => {
+                params.memory as u64 * 1024 <= MAX_MEMORY && params.lanes <= MAX_LANES
+            }
+        };
+        if !fits {
+            return Err(Error::Other("The keystore's key derivation asks for more than 4 GiB, or isn't valid."));
+        }
+        Ok(())
+    }
+
+    fn derive(&self, password: &str, salt: &[u8], key: &mut [u8; 32]) -> Result<(), Error> {
+        self.check()?;
+        match *self {
+            KeyDerivation::Scrypt { log_n, r, p } => ScryptParams::new(log_n, r, p, key.len())
+                .ok()
+                .and_then(|params| scrypt(password.as_bytes(), salt, &params, key).ok())
+                .ok_or(Error::Other("Invalid scrypt parameters.")),
+            KeyDerivation::Argon2id(ref params) => {
+                Ok(argon2::argon2id(password.as_bytes(), salt, &[], &[], params, key)?)
+            }
+        }
+    }
+
+    fn to_json(&self, salt: &[u8]) -> Value {
+        match *self {
+            KeyDerivation::Scrypt { log_n, r, p } => json!({
+                "name": "scrypt",
+                "log_n": log_n,
+                "r": r,
+                "p": p,
+                "salt": hex::encode(salt),
+            }),
+            KeyDerivation::Argon2id(ref params) => json!({
+                "name": "argon2id",
+                "memory": params.memory,
+                "iterations": params.iterations,
+                "lanes": params.lanes,
+                "salt": hex::encode(salt),
+            }),
+        }
+    }
+
+    fn from_json(kdf: &Value) -> Result<(KeyDerivation, Vec<u8>), Error> {
+        let number = |name: &str| {
+            kdf[name]
+                .as_u64()
+                .and_then(|value| u32::try_from(value).ok())
+                .ok_or(Error::Other("The keystore's key derivation is missing a parameter."))
+        };
+        let derivation = match kdf["name"].as_str() {
+            Some("scrypt") => KeyDerivation::Scrypt {
+                log_n: u8::try_from(number("log_n")?).map_err(|_| Error::Other("Invalid scrypt parameters."))?,
+                r: number("r")?,
+                p: number("p")?,
+            },
+            Some("argon2id") => KeyDerivation::Argon2id(Params {
+                memory: number("memory")?,
+                iterations: number("iterations")?,
+                lanes: number("lanes")?,
+            }),
+            _ => return Err(Error::Other("Unsupported keystore key derivation.")),
+        };
+        Ok((derivation, hex_field(kdf, "salt")?))
+    }
+}
+
+// "scrypt" or "argon2id", with their defaults:
+impl FromStr for KeyDerivation {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<KeyDerivation, Error> {
+        match name.to_lowercase().as_str() {
+            "scrypt" => Ok(KeyDerivation::scrypt()),
+            "argon2id" => Ok(KeyDerivation::argon2id()),
+            _ => Err(Error::Other("Unknown key derivation.")),
+        }
+    }
+}
+
+impl fmt::Display for KeyDerivation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            KeyDerivation::Scrypt { .. } => "scrypt",
+            KeyDerivation::Argon2id(_) => "argon2id",
+        })
+    }
+}
+
+pub fn export(contents: &Contents, password: &str) -> Result<String, Error> {
+    export_with(contents, password, &KeyDerivation::scrypt())
+}
+
+pub fn export_with(
+    contents: &Contents,
+    password: &str,
+    derivation: &KeyDerivation,
+) -> Result<String, Error> {
+    let (name, mut plaintext) = match contents {
+        Contents::Mnemonic(mnemonic) => ("mnemonic", mnemonic.phrase().into_bytes()),
+        Contents::Seed(seed) => ("seed", seed.as_bytes().to_vec()),
+    };
+    let salt = entropy::random::<SALT_LEN>();
+    let nonce = entropy::random::<NONCE_LEN>();
+    let header = json!({
+        "version": VERSION,
+        "contents": name,
+        "kdf": derivation.to_json(&salt),
+        "cipher": { "name": "aes-256-gcm", "nonce": hex::encode(nonce) },
+    });
+
+    let mut key = [0; 32];
+    if let Err(err) = derivation.derive(password, &salt, &mut key) {
+        plaintext.zeroize();
+        return Err(err);
+    }
+    let cipher = Aes256Gcm::new_from_slice(&key).unwrap();
+    key.zeroize();
+    // (in place, so from here on 'plaintext' holds the ciphertext)
+    let aad = header.to_string();
+    let tag = cipher
+        .encrypt_in_place_detached(&Nonce::from(nonce), aad.as_bytes(), &mut plaintext)
+        .unwrap();
+    let ciphertext = plaintext;
+
+    let document = json!({
+        "header": header,
+        "ciphertext": hex::encode(&ciphertext),
+        "tag": hex::encode(tag),
+    });
+    Ok(serde_json::to_string_pretty(&document).unwrap())
+}
+
+// A mnemonic keystore's phrase is parsed in whichever available wordlist it's in (and checked, so
+// a keystore written by something else with a bad checksum is caught here).
+pub fn import(file: &str, password: &str) -> Result<Contents, Error> {
+    let document: Value = serde_json::from_str(file).map_err(|_| Error::Other("Not a keystore file."))?;
+    let header = &document["header"];
+    if !header.is_object() {
+        return Err(Error::Other("Not a keystore file."));
+    }
+    if header["version"].as_u64() != Some(VERSION) {
+        return Err(Error::Other("Unsupported keystore version."));
+    }
+    if header["cipher"]["name"].as_str() != Some("aes-256-gcm") {
+        return Err(Error::Other("Unsupported keystore cipher."));
+    }
+    let (derivation, salt) = KeyDerivation::from_json(&header["kdf"])?;
+    let nonce = hex_field(&header["cipher"], "nonce")?;
+    let ciphertext = hex_field(&document, "ciphertext")?;
+    let tag = hex_field(&document, "tag")?;
+    let (nonce, tag) = match (
+        <[u8; NONCE_LEN]>::try_from(&nonce[..]),
+        <[u8; TAG_LEN]>::try_from(&tag[..]),
+    ) {
+        (Ok(nonce), Ok(tag)) => (nonce, tag),
+        _ => return Err(Error::Other("Not a keystore file.")),
+    };
+
+    let mut key = [0; 32];
+    derivation.derive(password, &salt, &mut key)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).unwrap();
+    key.zeroize();
+    // (decrypted in place, and wiped if the tag doesn't match: aes-gcm decrypts either way)
+    let mut plaintext = ciphertext;
+    let decrypted = cipher.decrypt_in_place_detached(
+        &Nonce::from(nonce),
+        header.to_string().as_bytes(),
+        &mut plaintext,
+        &Tag::from(tag),
+    );
+    if decrypted.is_err() {
+        plaintext.zeroize();
+        return Err(Error::WrongPassword);
+    }
+
+    let contents = match header["contents"].as_str() {
+        Some("mnemonic") => std::str::from_utf8(&plaintext)
+            .map_err(|_| Error::Other("The decrypted keystore is not a mnemonic."))
+            .and_then(|phrase| phrase.parse::<Mnemonic>())
+            .map(Contents::Mnemonic),
+        Some("seed") => Seed::from_bytes(&plaintext).map(Contents::Seed),
+        _ => Err(Error::Other("Unsupported keystore contents.")),
+    };
+    plaintext.zeroize();
+    contents
+}
+
+fn hex_field(object: &Value, name: &str) -> Result<Vec<u8>, Error> {
+    object[name]
+        .as_str()
+        .and_then(|text| hex::decode(text).ok())
+        .ok_or(Error::Other("Not a keystore file."))
+}
@@ -0,0 +1,501 @@
+// The ten BIP-39 wordlists.  Every one of them is built in unless 'langs-*' features pick a
+// subset; the language features only choose the default ('Language::compiled()'), so a program
+// can still offer the others ('Language::available()') at runtime.
+
+use alloc::collections::BTreeSet;
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter;
+use core::str::FromStr;
+
+use once_cell::race::OnceBox;
+
+use alloc_prelude::*;
+use error::Error;
+use mnemonic::Mnemonic;
+use wordlist::Wordlist;
+use wordlists;
+use {edit_distance, nfkd};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    Czech,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Portuguese,
+    Spanish,
+    // A list read with 'Wordlist::from_lines'.  It's never one of 'all' or 'available', and its
+    // name is just "custom", so it doesn't come back from parsing one:
+    Custom(&'static Wordlist),
+}
+
+impl Language {
+    pub fn all() -> [Language; 10] {
+        [
+            Language::English,
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+            Language::Czech,
+            Language::French,
+            Language::Italian,
+            Language::Japanese,
+            Language::Korean,
+            Language::Portuguese,
+            Language::Spanish,
+        ]
+    }
+
+    // The wordlist feature this was built with, and English without one (or, when 'langs-*'
+    // features left English out too, the first wordlist that is embedded):
+    pub fn compiled() -> Language {
+        // 'cfg!' macro is for compile time boolean evaluation:
+        // 'feature' is used by cargo for conditional building:
+        if cfg!(feature = "chinese_traditional") {
+            Language::ChineseTraditional
+        } else if cfg!(feature = "czech") {
+            Language::Czech
+        } else if cfg!(feature = "japanese") {
+            Language::Japanese
+        } else if cfg!(feature = "korean") {
+            Language::Korean
+        } else if cfg!(feature = "spanish") {
+            Language::Spanish
+        } else if cfg!(feature = "chinese_simplified") {
+            Language::ChineseSimplified
+        } else if cfg!(feature = "french") {
+            Language::French
+        } else if cfg!(feature = "italian") {
+            Language::Italian
+        } else if cfg!(feature = "portuguese") {
+            Language::Portuguese
+        } else if Language::English.is_embedded() {
+            Language::English
+        } else {
+            Language::available()[0]
+        }
+    }
+
+    // What a speaker would look for in a menu:
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::ChineseSimplified => "简体中文",
+            Language::ChineseTraditional => "繁體中文",
+            Language::Czech => "Čeština",
+            Language::French => "Français",
+            Language::Italian => "Italiano",
+            Language::Japanese => "日本語",
+            Language::Korean => "한국어",
+            Language::Portuguese => "Português",
+            Language::Spanish => "Español",
+            Language::Custom(_) => "Custom",
+        }
+    }
+
+    // Panics for a wordlist the 'langs-*' features left out; check 'is_embedded' (or stick to
+    // 'available') first.
+    pub fn word_list(&self) -> &'static [&'static str; 2048] {
+        self.embedded_word_list()
+            .expect("That wordlist isn't built in; enable its 'langs-*' feature.")
+    }
+
+    pub fn is_embedded(&self) -> bool {
+        self.embedded_word_list().is_some()
+    }
+
+    // The languages whose wordlists this build embeds, in 'all' order:
+    pub fn available() -> Vec<Language> {
+        Language::all()
+            .iter()
+            .cloned()
+            .filter(Language::is_embedded)
+            .collect()
+    }
+
+    // Unpacked on first use, and kept for the life of the program.  (A custom list's words are
+    // as much at hand as an embedded one's, which is what 'is_embedded' asks.)
+    fn embedded_word_list(&self) -> Option<&'static [&'static str; 2048]> {
+        static UNPACKED: [OnceBox<[&str; 2048]>; 10] = [const { OnceBox::new() }; 10];
+
+        if let Language::Custom(list) = self {
+            return Some(list.words());
+        }
+        let slot = Language::all()
+            .iter()
+            .position(|language| language == self)
+            .unwrap();
+        let packed = self.packed_word_list()?;
+        Some(UNPACKED[slot].get_or_init(|| Box::new(unpack(packed))))
+    }
+
+    fn packed_word_list(&self) -> Option<&'static [u8]> {
+        match self {
+            Language::English => wordlists::ENGLISH,
+            Language::ChineseSimplified => wordlists::CHINESE_SIMPLIFIED,
+            Language::ChineseTraditional => wordlists::CHINESE_TRADITIONAL,
+            Language::Czech => wordlists::CZECH,
+            Language::French => wordlists::FRENCH,
+            Language::Italian => wordlists::ITALIAN,
+            Language::Japanese => wordlists::JAPANESE,
+            Language::Korean => wordlists::KOREAN,
+            Language::Portuguese => wordlists::PORTUGUESE,
+            Language::Spanish => wordlists::SPANISH,
+            Language::Custom(_) => None,
+        }
+    }
+
+    // The one word starting with 'prefix', or 'prefix' itself if it's a whole word.  English
+    // words are unique by their first four letters, which is all many steel backups keep.
+    pub fn complete(&self, prefix: &str) -> Option<&'static str> {
+        if prefix.is_empty() {
+            return None;
+        }
+        let words = Wordlist::of(*self);
+        if let Some(index) = words.index_of(prefix) {
+            return words.get(index);
+        }
+
+        match words.words_starting_with(prefix)[..] {
+            [word] => Some(word),
+            _ => None,
+        }
+    }
+
+    // What 'written' may have been meant as, closest first: the word itself if it is one, or else
+    // the words it's the start of (in list order), then those a typo or two away from it, for
+    // offering a choice rather than guessing.  Empty when nothing is that close.
+    pub fn suggest(&self, written: &str) -> Vec<&'static str> {
+        let written = nfkd(written);
+        let words = Wordlist::of(*self);
+        if written.is_empty() {
+            return Vec::new();
+        }
+        if let Some(index) = words.index_of(&written) {
+            return words.get(index).into_iter().collect();
+        }
+
+        let mut found = words.words_starting_with(&written);
+        let mut near: Vec<(usize, &'static str)> = words
+            .words()
+            .iter()
+            .filter(|word| !word.starts_with(&*written))
+            .map(|&word| (edit_distance(&written, word), word))
+            .filter(|&(distance, _)| distance <= 2)
+            .collect();
+        // Typos seldom hit the first letter, so of the words as near as each other, those starting
+        // the same way go first (and otherwise, a stable sort keeps them in list order):
+        let first = written.chars().next();
+        near.sort_by_key(|&(distance, word)| (distance, word.chars().next() != first));
+        found.extend(near.into_iter().map(|(_, word)| word));
+        found
+    }
+
+    // Every word containing 'text' (or, with 'prefix', starting with it), with its 0-based index,
+    // for looking up a half-remembered or half-legible word:
+    pub fn search(&self, text: &str, prefix: bool) -> Vec<(usize, &'static str)> {
+        self.word_list()
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| {
+                if prefix {
+                    word.starts_with(text)
+                } else {
+                    word.contains(text)
+                }
+            })
+            .map(|(index, &word)| (index, word))
+            .collect()
+    }
+
+    // The fewest leading characters that tell every word of the list apart (4 for English, which
+    // is what steel backups rely on); a word shorter than that is told apart by being whole.
+    pub fn unique_prefix_length(&self) -> usize {
+        let words = self.word_list();
+        (1..)
+            .find(|&length| {
+                let mut seen = BTreeSet::new();
+                words.iter().all(|word| seen.insert(prefix(word, length)))
+            })
+            .expect("The words of a list are all different.")
+    }
+
+    // The shortest beginning of 'word' that 'complete' turns back into it, for showing how much
+    // has to be typed (or stamped):
+    pub fn shortest_prefix(&self, word: &str) -> Option<&'static str> {
+        let words = Wordlist::of(*self);
+        let word = words.get(words.index_of(word)?)?;
+        (1..=word.chars().count())
+            .map(|length| prefix(word, length))
+            .find(|&prefix| self.complete(prefix) == Some(word))
+    }
+
+    // The words this list has in common with 'other''s, which is why one word can't always say
+    // which language a mnemonic is in (English and French share 100 of them):
+    pub fn shared_words(&self, other: Language) -> Vec<&'static str> {
+        let theirs: BTreeSet<&str> = other.word_list().iter().cloned().collect();
+        self.word_list()
+            .iter()
+            .cloned()
+            .filter(|word| theirs.contains(word))
+            .collect()
+    }
+
+    // Every embedded list with 'word' in it, in 'all' order:
+    pub fn containing(word: &str) -> Vec<Language> {
+        Language::available()
+            .into_iter()
+            .filter(|&language| Wordlist::of(language).index_of(word).is_some())
+            .collect()
+    }
+
+    // The embedded list a mnemonic is written in, so whoever is recovering one needn't say: the
+    // list with every one of its words that also takes their checksum, since one word, or a few,
+    // can be in two lists.  Should two take it all (a phrase of shared words alone), the compiled
+    // in language wins, then the first in 'all' order.  When none does, the error is the one from
+    // the 'closest' list: which of its words to check, or that the checksum is off.
+    pub fn detect(phrase: &str) -> Result<Language, Error> {
+        let compiled = Language::compiled();
+        let others = Language::available()
+            .into_iter()
+            .filter(|&language| language != compiled);
+        for language in iter::once(compiled).chain(others) {
+            if Mnemonic::parse(phrase, language).is_ok() {
+                return Ok(language);
+            }
+        }
+        // (which fails, as every list has)
+        Mnemonic::parse(phrase, Language::closest(phrase)).map(|mnemonic| mnemonic.language())
+    }
+
+    // The embedded list with the most of the phrase's words in it (on a tie, the compiled in
+    // language, then 'all' order), to suggest corrections from when a word is mistyped:
+    pub fn closest(phrase: &str) -> Language {
+        let phrase = nfkd(phrase);
+        let in_list = |language: Language| {
+            let words = Wordlist::of(language);
+            phrase
+                .split_whitespace()
+                .filter(|word| words.index_of(word).is_some())
+                .count()
+        };
+        let compiled = Language::compiled();
+        Language::available()
+            .into_iter()
+            .fold(compiled, |closest, language| {
+                if in_list(language) > in_list(closest) {
+                    language
+                } else {
+                    closest
+                }
+            })
+    }
+}
+
+// The first 'length' characters (not bytes) of 'word', or all of it:
+fn prefix(word: &str, length: usize) -> &str {
+    match word.char_indices().nth(length) {
+        Some((end, _)) => &word[..end],
+        None => word,
+    }
+}
+
+// Undoes build.rs's front coding: each word is a count of bytes shared with the word before, the
+// rest of its bytes, and a newline.  The words are copied into one string that's never freed, so
+// they can be handed out as '&'static str' like the lists used to be.
+fn unpack(packed: &[u8]) -> [&'static str; 2048] {
+    let mut text = Vec::with_capacity(packed.len() * 2);
+    // Where the word before starts in 'text':
+    let mut previous = 0;
+    let mut rest = packed;
+    while let Some((&shared, tail)) = rest.split_first() {
+        let end = tail.iter().position(|&byte| byte == b'\n').unwrap();
+        let start = text.len();
+        text.extend_from_within(previous..previous + shared as usize);
+        text.extend_from_slice(&tail[..end]);
+        text.push(b'\n');
+        previous = start;
+        rest = &tail[end + 1..];
+    }
+
+    let text: &'static str = Box::leak(String::from_utf8(text).unwrap().into_boxed_str());
+    let words: Vec<&'static str> = text.lines().collect();
+    <[&str; 2048]>::try_from(words).expect("A packed wordlist must have 2,048 words.")
+}
+
+// The same names as the cargo features, e.g. "chinese_simplified":
+impl FromStr for Language {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<Language, &'static str> {
+        Language::all()
+            .iter()
+            .find(|language| language.to_string() == name.to_lowercase())
+            .cloned()
+            .ok_or("Unknown wordlist language.")
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Language::English => "english",
+            Language::ChineseSimplified => "chinese_simplified",
+            Language::ChineseTraditional => "chinese_traditional",
+            Language::Czech => "czech",
+            Language::French => "french",
+            Language::Italian => "italian",
+            Language::Japanese => "japanese",
+            Language::Korean => "korean",
+            Language::Portuguese => "portuguese",
+            Language::Spanish => "spanish",
+            Language::Custom(_) => "custom",
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    // The text files build.rs embeds the lists from, in 'Language::all' order:
+    const TEXT: [&str; 10] = [
+        include_str!("../wordlists/english.txt"),
+        include_str!("../wordlists/chinese_simplified.txt"),
+        include_str!("../wordlists/chinese_traditional.txt"),
+        include_str!("../wordlists/czech.txt"),
+        include_str!("../wordlists/french.txt"),
+        include_str!("../wordlists/italian.txt"),
+        include_str!("../wordlists/japanese.txt"),
+        include_str!("../wordlists/korean.txt"),
+        include_str!("../wordlists/portuguese.txt"),
+        include_str!("../wordlists/spanish.txt"),
+    ];
+
+    #[test]
+    fn embedded_lists_match_the_text_files() {
+        for (language, text) in Language::all().iter().zip(TEXT.iter()) {
+            if language.is_embedded() {
+                assert!(
+                    language.word_list().iter().cloned().eq(text.lines()),
+                    "{}",
+                    language
+                );
+            }
+        }
+    }
+
+    // The 'langs-*' features picked, in 'Language::all' order:
+    const PICKED: [bool; 10] = [
+        cfg!(feature = "langs-english"),
+        cfg!(feature = "langs-chinese_simplified"),
+        cfg!(feature = "langs-chinese_traditional"),
+        cfg!(feature = "langs-czech"),
+        cfg!(feature = "langs-french"),
+        cfg!(feature = "langs-italian"),
+        cfg!(feature = "langs-japanese"),
+        cfg!(feature = "langs-korean"),
+        cfg!(feature = "langs-portuguese"),
+        cfg!(feature = "langs-spanish"),
+    ];
+
+    #[test]
+    fn embedded_subset() {
+        let subset = PICKED.contains(&true);
+        for (language, &picked) in Language::all().iter().zip(PICKED.iter()) {
+            if !subset || picked {
+                assert!(language.is_embedded(), "{}", language);
+            } else if language.is_embedded() {
+                // (a subset still holds the default language's list, picked or not)
+                assert_eq!(*language, Language::compiled());
+            }
+            assert_eq!(
+                Language::available().contains(language),
+                language.is_embedded()
+            );
+        }
+        assert!(Language::compiled().is_embedded());
+        assert!(!Language::available().is_empty());
+        if !subset {
+            assert_eq!(&Language::available()[..], &Language::all()[..]);
+        }
+    }
+
+    // Each word is the number of bytes it shares with the one before, the rest of it, and a
+    // newline.  That's less than the string pointers the lists used to be embedded as, and less
+    // than the text too wherever words are longer than a character (Chinese's aren't).
+    #[test]
+    fn front_coded_lists() {
+        if let Some(packed) = Language::English.packed_word_list() {
+            assert_eq!(&packed[..20], b"\x00abandon\n\x02ility\n\x02le\n");
+        }
+        for (language, text) in Language::all().iter().zip(TEXT.iter()) {
+            if let Some(packed) = language.packed_word_list() {
+                assert!(packed.len() < 2048 * core::mem::size_of::<&str>());
+                if text.lines().any(|word| word.chars().count() > 1) {
+                    assert!(packed.len() < text.len(), "{}", language);
+                }
+                assert!(unpack(packed).iter().cloned().eq(text.lines()));
+            }
+        }
+    }
+
+    // What build.rs checks of each list before embedding it, and the longest word it found:
+    #[test]
+    fn embedded_lists_are_valid() {
+        use unicode_normalization::char::is_combining_mark;
+
+        let sorted = [
+            Language::English,
+            Language::Italian,
+            Language::Korean,
+            Language::Portuguese,
+        ];
+        let unique_prefixes = [
+            Language::Czech,
+            Language::English,
+            Language::French,
+            Language::Italian,
+            Language::Portuguese,
+            Language::Spanish,
+        ];
+        let mut longest = 0;
+
+        for language in Language::available() {
+            let words = language.word_list();
+            assert!(words
+                .iter()
+                .all(|word| !word.is_empty() && !word.contains(char::is_whitespace)));
+            assert!(words.iter().all(|word| nfkd(word) == *word), "{}", language);
+            assert_eq!(words.iter().collect::<BTreeSet<_>>().len(), 2048);
+            if sorted.contains(&language) {
+                assert!(
+                    words.windows(2).all(|pair| pair[0] < pair[1]),
+                    "{}",
+                    language
+                );
+            }
+            if unique_prefixes.contains(&language) {
+                let prefixes: BTreeSet<String> = words
+                    .iter()
+                    .map(|word| {
+                        word.chars()
+                            .filter(|&c| !is_combining_mark(c))
+                            .take(4)
+                            .collect()
+                    })
+                    .collect();
+                assert_eq!(prefixes.len(), 2048, "{}", language);
+            }
+            longest = words
+                .iter()
+                .map(|word| word.len())
+                .fold(longest, usize::max);
+        }
+        assert_eq!(wordlists::MAX_WORD_LEN, longest);
+    }
+}
@@ -1,13 +1,242 @@
-extern crate crypto;
+// Without the (default) 'std' feature, only the core builds, on 'core' and 'alloc' alone, for a
+// signing device's firmware: entropy to mnemonic ('Mnemonic::from_entropy', or
+// 'Config::generate_using' with entropy from the device's own 'EntropySource'), mnemonic to seed,
+// and BIP-32 (and SLIP-0010) keys.
+//
+//   bip-39 = { version = "0.1", default-features = false, features = ["langs-english"] }
+//
+// The rest (the binary, prompts, files, JSON, the system's random numbers, and every module built
+// on them) is behind 'std', which the other features all turn on.
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use crypto::digest::Digest;
-use crypto::hmac::Hmac;
-use crypto::mac::Mac;
-use crypto::pbkdf2;
-use crypto::sha2::{Sha256, Sha512};
+#[cfg(feature = "std")]
+extern crate aes;
+#[cfg(feature = "std")]
+extern crate aes_gcm;
+#[cfg(feature = "age")]
+extern crate age;
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate base64;
+#[cfg(feature = "std")]
+extern crate blake2;
+extern crate bs58;
+#[cfg(feature = "vault")]
+extern crate chacha20poly1305;
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(feature = "std")]
+extern crate curve25519_dalek;
+extern crate ed25519_dalek;
+#[cfg(feature = "std")]
+extern crate getrandom;
+extern crate hex;
+extern crate hmac;
+extern crate k256;
+#[cfg(feature = "keyring")]
+extern crate keyring;
+#[cfg(all(feature = "cli", unix))]
+extern crate libc;
+#[cfg(all(feature = "camera", target_os = "linux"))]
+extern crate linuxvideo;
+extern crate once_cell;
+extern crate p256;
+#[cfg(feature = "std")]
+extern crate pbkdf2;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "paranoid")]
+extern crate ring;
+extern crate ripemd;
+#[cfg(all(feature = "cli", not(unix)))]
+extern crate rpassword;
+#[cfg(feature = "camera")]
+extern crate rqrr;
+#[cfg(feature = "std")]
+extern crate scrypt;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate serde_json;
+extern crate sha1;
+extern crate sha2;
+#[cfg(feature = "std")]
+extern crate sha3;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+extern crate unicode_normalization;
+#[cfg(feature = "wasm-bindgen")]
+extern crate wasm_bindgen;
+extern crate zeroize;
+#[cfg(feature = "std")]
+extern crate zxcvbn;
+
+// Debug spans and events for the 'tracing' feature, and nothing at all without it.  Only ever
+// give them public fields (languages, word counts, flags); 'secret_filter' is the backstop for
+// anyone who doesn't.
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        tracing::debug!($($arg)*);
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {};
+}
+
+// The core, which builds without std:
+mod base58;
+pub mod bip32;
+pub mod entropy;
+pub mod error;
+mod fault;
+mod hash;
+pub mod kdf;
+pub mod language;
+pub mod mnemonic;
+pub mod network;
+#[cfg(feature = "serde")]
+pub mod serde_secret;
+pub mod slip10;
+pub mod wordlist;
+
+macro_rules! with_std {
+    ($($item:item)*) => {$(
+        #[cfg(feature = "std")]
+        $item
+    )*};
+}
+
+with_std! {
+    pub mod account;
+    pub mod address;
+    #[cfg(feature = "age")]
+    pub mod age_backup;
+    mod argon2;
+    pub mod audit;
+    mod bech32;
+    pub mod bip137;
+    pub mod bip322;
+    pub mod bip38;
+    pub mod bip47;
+    pub mod bip85;
+    pub mod bulk_audit;
+    pub mod decoy;
+    pub mod descriptor;
+    pub mod display;
+    pub mod explain;
+    pub mod export;
+    #[cfg(feature = "ffi")]
+    pub mod ffi;
+    pub mod i18n;
+    pub mod keystore;
+    pub mod lightning;
+    pub mod message;
+    pub mod multisig;
+    pub mod nostr;
+    #[cfg(feature = "keyring")]
+    pub mod os_keyring;
+    #[cfg(feature = "paranoid")]
+    pub mod paranoid;
+    pub mod pgp;
+    pub mod prelude;
+    pub mod preview;
+    #[cfg(feature = "cli")]
+    pub mod prompt;
+    pub mod qr;
+    pub mod quiz;
+    pub mod recover;
+    #[cfg(feature = "camera")]
+    pub mod scan;
+    pub mod schema;
+    #[cfg(feature = "tracing")]
+    pub mod secret_filter;
+    pub mod seed_xor;
+    pub mod seedqr;
+    pub mod session;
+    pub mod slip39;
+    pub mod ssh;
+    pub mod strength;
+    pub mod taproot;
+    pub mod test_vectors;
+    pub mod tezos;
+    mod tx;
+    pub mod ur;
+    #[cfg(feature = "vault")]
+    pub mod vault;
+    pub mod vectors;
+    pub mod wallet_policy;
+    #[cfg(feature = "wasm-bindgen")]
+    pub mod wasm;
+    pub mod watch_only;
+    pub mod weak;
+}
+
+// What std's prelude has and core's doesn't, for the core modules to 'use alloc_prelude::*' so
+// they build either way:
+mod alloc_prelude {
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+}
+
+// The packed wordlists build.rs generates from wordlists/*.txt ('None' for any left out), and the
+// length of their longest word:
+mod wordlists {
+    include!(concat!(env!("OUT_DIR"), "/wordlists.rs"));
+}
+
+use alloc::borrow::Cow;
+use alloc_prelude::*;
+use bip32::ExtendedPrivKey;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "parallel")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+use entropy::EntropySource;
+#[cfg(feature = "std")]
+use entropy::OsRandom;
+// (so it's 'bip_39::Error')
+pub use error::Error;
+use kdf::Kdf;
+use language::Language;
+use mnemonic::Mnemonic;
+use network::Network;
+#[cfg(feature = "std")]
+pub use quiz::confirmation_challenge;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use slip10::Curve;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+pub use strength::estimate_passphrase_strength;
+#[cfg(feature = "std")]
+pub use test_vectors::verify_implementation;
+use unicode_normalization::UnicodeNormalization;
+use wordlist::Wordlist;
+use zeroize::{Zeroize, Zeroizing};
 use MnemonicListSize::*;
 
 // Enforce the list sizes by way of an enum.
+#[derive(Clone, Copy)]
 pub enum MnemonicListSize {
     Twelve = 12,
     Fifteen = 15,
@@ -18,14 +247,14 @@ pub enum MnemonicListSize {
 
 // "impl" blocks define methods:
 impl MnemonicListSize {
-    pub fn from(size: usize) -> Result<MnemonicListSize, &'static str> {
+    pub fn from(size: usize) -> Result<MnemonicListSize, Error> {
         let mnemonic_list_size = match size {
             12 => Twelve,
             15 => Fifteen,
             18 => Eighteen,
             21 => TwentyOne,
             24 => TwentyFour,
-            _ => return Err("Invalid mnemonic word list size."),
+            _ => return Err(Error::InvalidWordCount(size)),
         };
 
         // The lack of semicolon below is how "expression" code returns its result:
@@ -34,102 +263,645 @@ impl MnemonicListSize {
 }
 
 // When used as a library in external rust code, the Config is the way to safely invoke functionality here.
-// 'Config' is a "named-field" struct; while its types are both String, structs can contain mixed types.
+// 'Config' is a "named-field" struct; structs can contain mixed types, as this one does (a Phrase,
+// which keeps the words off the heap, and a String).
 pub struct Config {
     // Any guesses as to what 'pub' in the struct definition as well as below means?
-    pub mnemonic: String,
-    // Note: no 'pub' on 'salt', thus forcing construction to be done from its impl, below.
-    salt: String,
+    pub mnemonic: Phrase,
+    // Note: no 'pub' on 'passphrase', thus forcing construction to be done from its impl, below.
+    passphrase: String,
 }
 
+// The mnemonic and passphrase go in as anything that reads as a '&str' (a 'String', say, or a
+// 'Zeroizing<String>'), and are copied into the Config, whose copies are wiped with it; the
+// caller's own are still the caller's to wipe.
 impl Config {
-    pub fn generate_from(word_list_size: MnemonicListSize, passphrase: String) -> Config {
-        Config {
+    #[cfg(feature = "std")]
+    pub fn generate_from<P: AsRef<str>>(word_list_size: MnemonicListSize, passphrase: P) -> Config {
+        Config::generate_in(Language::compiled(), word_list_size, passphrase)
+    }
+
+    // The same, with words from 'language' rather than the compiled in wordlist:
+    #[cfg(feature = "std")]
+    pub fn generate_in<P: AsRef<str>>(
+        language: Language,
+        word_list_size: MnemonicListSize,
+        passphrase: P,
+    ) -> Config {
+        // (the system's random numbers don't run out)
+        Config::generate_using(&mut OsRandom, language, word_list_size, passphrase).unwrap()
+    }
+
+    // The same, with the entropy from 'source' (e.g. 'entropy::Mixed', to have more than the
+    // system's random numbers to go on) rather than the system's random numbers alone:
+    pub fn generate_using<P: AsRef<str>>(
+        source: &mut dyn EntropySource,
+        language: Language,
+        word_list_size: MnemonicListSize,
+        passphrase: P,
+    ) -> Result<Config, Error> {
+        trace_span!("generate", language = %language, word_count = word_list_size as usize);
+        let mnemonic = get_mnemonic(word_list_size, language, source)?;
+        #[cfg(feature = "std")]
+        audit::emit(audit::Event::MnemonicGenerated {
+            language,
+            words: word_list_size as usize,
+        });
+        Ok(Config {
             // Discuss get_mnemonic function:
-            mnemonic: get_mnemonic(word_list_size),
-            salt: get_salt(passphrase),
-        }
+            mnemonic,
+            passphrase: passphrase.as_ref().to_string(),
+        })
     }
 
-    pub fn new(mnemonic: String, word_list_size: MnemonicListSize, passphrase: String) -> Config {
+    // The word count comes from the phrase itself, so it has to be one of the MnemonicListSize
+    // lengths; the words have to be in a wordlist, and the last of them has to carry the others'
+    // checksum, since PBKDF2 makes a seed out of any words at all (typos included).
+    pub fn from_phrase<M: AsRef<str>, P: AsRef<str>>(
+        mnemonic: M,
+        passphrase: P,
+    ) -> Result<Config, Error> {
+        Config::read_phrase(mnemonic.as_ref(), passphrase.as_ref(), None)
+    }
+
+    // The same, with the words checked against 'language' only (a 'Language::Custom' list, say,
+    // which 'from_phrase' doesn't know about):
+    pub fn from_phrase_in<M: AsRef<str>, P: AsRef<str>>(
+        mnemonic: M,
+        passphrase: P,
+        language: Language,
+    ) -> Result<Config, Error> {
+        Config::read_phrase(mnemonic.as_ref(), passphrase.as_ref(), Some(language))
+    }
+
+    fn read_phrase(
+        mnemonic: &str,
+        passphrase: &str,
+        language: Option<Language>,
+    ) -> Result<Config, Error> {
+        // Japanese wallets separate words with the ideographic space (U+3000), and NFKD, which
+        // BIP-39 applies before PBKDF2, turns that into a plain one; so does any other spacing.
+        // NFKD also takes accents typed as one character ("é") apart, as the wordlists have them:
+        // (the joined copy is wiped once it's in the Config's Phrase, or the phrase didn't pass)
+        let mnemonic = Zeroizing::new(join_words(&nfkd(mnemonic)));
+        let word_count = mnemonic.split(' ').count();
+        trace_event!(word_count, "read a phrase");
+        MnemonicListSize::from(word_count)?;
+        match language {
+            Some(language) => Mnemonic::parse(&mnemonic, language)?,
+            // (any embedded wordlist will do; see 'Mnemonic::from_str')
+            None => mnemonic.parse::<Mnemonic>()?,
+        };
+
+        Ok(Config {
+            // A valid phrase's words are all in a wordlist, so it fits:
+            mnemonic: Phrase::copy_of(&mnemonic).expect("A valid phrase is too long."),
+            passphrase: passphrase.to_string(),
+        })
+    }
+
+    #[deprecated(note = "the phrase already says how many words it has; use Config::from_phrase")]
+    pub fn new(
+        mut mnemonic: String,
+        word_list_size: MnemonicListSize,
+        mut passphrase: String,
+    ) -> Config {
+        let config = Config::from_phrase(&mnemonic, &passphrase).expect("Invalid mnemonic.");
+        // (these were handed over, so they're wiped here, with the Config holding copies)
+        mnemonic.zeroize();
+        passphrase.zeroize();
         // Note that 'word_list_size' of enum type MnemoncListSize can be converted to a 'usize' here:
-        assert_eq!(mnemonic.split(' ').count(), word_list_size as usize);
+        assert_eq!(config.mnemonic.split(' ').count(), word_list_size as usize);
+        config
+    }
 
-        Config {
-            // Handy way to create your struct if variable name(s) match(es):
-            mnemonic,
-            salt: get_salt(passphrase),
+    // The start of the pipeline, e.g. 'config.to_seed().to_root_key(Network::Mainnet)?':
+    pub fn to_seed(&self) -> Seed {
+        self.to_seed_with_progress(&mut |_, _| {})
+    }
+
+    // Same as 'to_seed', but 'progress' hears (PBKDF2 rounds done, total rounds) along the way,
+    // so GUIs and TUIs have something to drive a progress bar with.
+    pub fn to_seed_with_progress(&self, progress: &mut dyn FnMut(u32, u32)) -> Seed {
+        let seed = self.to_seed_using(&kdf::Bip39, progress);
+        // (only BIP-39's own derivation has a second implementation to check it against)
+        #[cfg(feature = "paranoid")]
+        paranoid::check_seed(&self.mnemonic, &self.passphrase, &seed.0);
+        seed
+    }
+
+    // The seed by some other derivation than BIP-39's (see the 'kdf' module):
+    pub fn to_seed_with(&self, kdf: &dyn Kdf) -> Seed {
+        self.to_seed_using(kdf, &mut |_, _| {})
+    }
+
+    fn to_seed_using(&self, kdf: &dyn Kdf, progress: &mut dyn FnMut(u32, u32)) -> Seed {
+        trace_span!("derive");
+        // (with 'double-check', 'progress' runs through the rounds twice)
+        let mut derived = fault::twice(
+            || kdf.derive(&self.mnemonic, &self.passphrase, progress),
+            |first, second| first[..] == second[..],
+        );
+        trace_event!("derived the seed");
+        report_seed(&derived);
+        let seed = Seed(derived);
+        // (the Seed has a copy of its own)
+        derived.zeroize();
+        seed
+    }
+
+    // The BIP-39 seed written straight into the caller's buffer, for embedded callers and for
+    // keeping down the copies of it in memory: nothing is allocated, and no 'Seed' is made to
+    // hold another copy.  The checks 'to_seed' has ('paranoid', 'double-check') still apply.
+    pub fn derive_seed_into(&self, seed: &mut [u8; 64]) {
+        derive_seed_into(&self.mnemonic, &self.passphrase, seed)
+    }
+}
+
+// 'derive_seed_into' for a phrase already checked, whether a Config's or a Mnemonic's:
+fn derive_seed_into(mnemonic: &str, passphrase: &str, seed: &mut [u8; 64]) {
+    trace_span!("derive");
+    fault::twice_into(seed, |output| {
+        kdf::Bip39.derive_into(mnemonic, passphrase, &mut |_, _| {}, output)
+    });
+    #[cfg(feature = "paranoid")]
+    paranoid::check_seed(mnemonic, passphrase, seed);
+    trace_event!("derived the seed");
+    report_seed(seed);
+}
+
+// The mnemonic and passphrase are wiped when the Config goes, rather than left in freed memory
+// for whatever gets the heap next (a core dump, say); the Phrase wipes itself, and the passphrase
+// is wiped here:
+impl Drop for Config {
+    fn drop(&mut self) {
+        self.passphrase.zeroize();
+    }
+}
+
+// ...and never printed with '{:?}', so a Config inside a struct that derives Debug is safe to log:
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("mnemonic", &"[redacted]")
+            .field("passphrase", &"[redacted]")
+            .finish()
+    }
+}
+
+// A phrase in a buffer of 'MAX_PHRASE_LEN' bytes rather than a String, so a Config can be
+// generated without going to the heap (and a phrase isn't left behind in memory the heap frees).
+// It reads as a '&str', and is wiped when it goes:
+#[derive(Clone)]
+pub struct Phrase {
+    bytes: [u8; MAX_PHRASE_LEN],
+    len: usize,
+}
+
+impl Phrase {
+    // The words for 'entropy', one of the MnemonicListSize lengths:
+    fn of_entropy(entropy: &[u8], language: Language) -> Phrase {
+        let mut phrase = Phrase {
+            bytes: [0; MAX_PHRASE_LEN],
+            len: 0,
+        };
+        phrase.len = write_mnemonic_from_entropy(entropy, language, &mut phrase.bytes).len();
+        phrase
+    }
+
+    // None for text too long to be a phrase in any wordlist:
+    fn copy_of(text: &str) -> Option<Phrase> {
+        let mut phrase = Phrase {
+            bytes: [0; MAX_PHRASE_LEN],
+            len: text.len(),
+        };
+        phrase
+            .bytes
+            .get_mut(..text.len())?
+            .copy_from_slice(text.as_bytes());
+        Some(phrase)
+    }
+
+    pub fn as_str(&self) -> &str {
+        // (only ever copied from a '&str', or whole words)
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl core::ops::Deref for Phrase {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Phrase {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Phrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// (kept out of '{:?}', as the Config holding it is)
+impl fmt::Debug for Phrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Phrase([redacted])")
+    }
+}
+
+impl PartialEq for Phrase {
+    fn eq(&self, other: &Phrase) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for Phrase {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Phrase {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Phrase {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Phrase> for String {
+    fn eq(&self, other: &Phrase) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Drop for Phrase {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+// Tells the audit hook, if there is one, which wallet a seed was just derived for:
+#[cfg(feature = "std")]
+fn report_seed(seed: &[u8; 64]) {
+    if audit::has_hook() {
+        if let Ok(root) = ExtendedPrivKey::new_master(seed, Network::Mainnet) {
+            audit::emit(audit::Event::SeedDerived {
+                fingerprint: root.fingerprint(),
+            });
+        }
+    }
+}
+
+// (there's no audit hook without std)
+#[cfg(not(feature = "std"))]
+fn report_seed(_seed: &[u8; 64]) {}
+
+// The 64 byte seed, kept as bytes rather than hex so it can go straight on to BIP-32.
+pub struct Seed([u8; 64]);
+
+impl Seed {
+    // For a mnemonic already parsed (and so already checked), without going through a Config:
+    // The phrase is put together on the stack, and the seed derived straight into the Seed.
+    pub fn new<P: AsRef<str>>(mnemonic: &Mnemonic, passphrase: P) -> Seed {
+        let mut phrase = [0; MAX_PHRASE_LEN];
+        let mut seed = Seed([0; 64]);
+        derive_seed_into(
+            mnemonic.write_phrase(&mut phrase),
+            passphrase.as_ref(),
+            &mut seed.0,
+        );
+        phrase.zeroize();
+        seed
+    }
+
+    // Seed bytes kept apart from their mnemonic (in a keystore, say), checked for their length:
+    pub fn from_bytes(bytes: &[u8]) -> Result<Seed, Error> {
+        let mut seed = [0; 64];
+        if bytes.len() != seed.len() {
+            return Err(Error::InvalidSeedLength(bytes.len()));
+        }
+        seed.copy_from_slice(bytes);
+        Ok(Seed(seed))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 64] {
+        &self.0
+    }
+
+    // The same as 'to_string()', when it reads better to say so:
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.0[..])
+    }
+
+    pub fn to_root_key(&self, network: Network) -> Result<ExtendedPrivKey, Error> {
+        let root = ExtendedPrivKey::new_master(&self.0, network)?;
+        #[cfg(feature = "paranoid")]
+        paranoid::check_root_key(&self.0, &root);
+        Ok(root)
+    }
+
+    // The SLIP-0010 master key for chains on other curves (ed25519, P-256):
+    pub fn to_slip10_key(&self, curve: Curve) -> Result<slip10::ExtendedKey, Error> {
+        slip10::ExtendedKey::new_master(&self.0, curve)
+    }
+
+    // The seed written straight to a file, a pipe or a device, in 'encoding', with no copy of its
+    // text left behind (as 'to_hex()' would leave one, to the caller):
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W, encoding: Encoding) -> io::Result<()> {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+
+        let mut text = match encoding {
+            Encoding::Hex => hex::encode(&self.0[..]),
+            Encoding::Base64 => STANDARD.encode(&self.0[..]),
+            Encoding::Binary => return writer.write_all(&self.0),
+        };
+        let written = writer.write_all(text.as_bytes());
+        text.zeroize();
+        written
+    }
+}
+
+// How 'Seed::write_to' writes the 64 bytes: as hex (what 'Display' shows), base64, or the bytes
+// themselves.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Hex,
+    Base64,
+    Binary,
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Encoding {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<Encoding, &'static str> {
+        match name.to_lowercase().as_str() {
+            "hex" => Ok(Encoding::Hex),
+            "base64" => Ok(Encoding::Base64),
+            "binary" => Ok(Encoding::Binary),
+            _ => Err("Unknown encoding (hex, base64 or binary)."),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Encoding::Hex => "hex",
+            Encoding::Base64 => "base64",
+            Encoding::Binary => "binary",
+        })
+    }
+}
+
+// The same goes for the seed (its hex, from 'Display', is the caller's to wipe):
+impl Drop for Seed {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Seed([redacted])")
+    }
+}
+
+// Seeds are conventionally shown as hex:
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+// And read back from it, with hex's own error behind 'Error::Hex' when it isn't hex:
+impl FromStr for Seed {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Seed, Error> {
+        let mut bytes = hex::decode(text)?;
+        let seed = Seed::from_bytes(&bytes);
+        bytes.zeroize();
+        seed
+    }
+}
+
+// So a Seed goes wherever seed bytes do, 'generate_root_key(&seed)' included:
+impl AsRef<[u8]> for Seed {
+    fn as_ref(&self) -> &[u8] {
+        // The '..' means use the entire byte array "slice":
+        &self.0[..]
+    }
+}
+
+// PBKDF2's 2,048 rounds dominate audits and recovery searches over many mnemonics/passphrases,
+// so spread them across threads; the seeds come back in the same order as the configs.
+#[cfg(feature = "parallel")]
+pub fn derive_seeds(configs: &[Config]) -> Vec<Seed> {
+    derive_seeds_with_progress(configs, &|_, _| {})
+}
+
+// 'progress' hears (seeds done, total seeds) as each one completes, from whichever thread
+// finished it.
+#[cfg(feature = "parallel")]
+pub fn derive_seeds_with_progress(
+    configs: &[Config],
+    progress: &(dyn Fn(usize, usize) + Sync),
+) -> Vec<Seed> {
+    let done = AtomicUsize::new(0);
+    configs
+        .par_iter()
+        .map(|config| {
+            let seed = config.to_seed();
+            progress(done.fetch_add(1, Ordering::SeqCst) + 1, configs.len());
+            seed
+        })
+        .collect()
+}
+
+// Mnemonics pasted out of PDFs, password managers and chat apps pick up characters nobody can
+// see: zero-width spaces and joiners, byte order marks, soft hyphens, full-width Latin letters
+// (from CJK input methods), and odd spaces.  None of those belong in a mnemonic, so clean them
+// out before the words are looked up: one lowercase word per single space.
+pub fn normalize_mnemonic(input: &str) -> String {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'
+            )
+        })
+        .map(|c| match c {
+            // Full-width '！' through '～' sit 0xFEE0 above their ASCII counterparts:
+            '\u{FF01}'..='\u{FF5E}' => core::char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+
+    join_words(&cleaned.to_lowercase())
+}
+
+// The words, one plain space apart; splitting on any whitespace covers no-break and ideographic
+// spaces too.
+fn join_words(mnemonic: &str) -> String {
+    mnemonic.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// The 2,048 words of 'language', in order, for frontends doing their own autocomplete or
+// validation ('Language::compiled()' is the one the CLI uses):
+pub fn word_list(language: Language) -> &'static [&'static str; 2048] {
+    language.word_list()
+}
+
+// A word's 0-based position in the list, i.e. the 11 bits it stands for; steel plates and SeedQR
+// codes store these rather than the words.
+pub fn word_index(word: &str, language: Language) -> Option<usize> {
+    Wordlist::of(language).index_of(word)
+}
+
+// The Trezor reference vectors BIP-39 links to, for checking another implementation against this
+// one's data ('verify_implementation()' checks this one):
+#[cfg(feature = "std")]
+pub fn test_vectors() -> impl Iterator<Item = test_vectors::Bip39Vector> {
+    test_vectors::bip39()
+}
+
+#[deprecated(note = "the hex only has to be decoded again; use config.to_seed(), a Seed")]
 pub fn generate_seed(config: Config) -> String {
-    let mut output = [0; 64];
-    let mut mac = Hmac::new(Sha512::new(), config.mnemonic.as_bytes());
-    pbkdf2::pbkdf2(&mut mac, config.salt.as_bytes(), 2048, &mut output);
-    // The '..' means use the entire byte array "slice":
-    hex::encode(&output[..])
-}
-
-// Refer to BIP-32 requirements (Serialization format):
-pub fn generate_root_key(seed: &[u8]) -> String {
-    assert!(seed.len() == 64);
-    let mut output = [0; 64];
-    let mut mac = Hmac::new(Sha512::new(), b"Bitcoin seed");
-    mac.input(seed);
-    mac.raw_result(&mut output);
-    // 'il' is the "master secret key" and 'ir' is the "master chain code"
-    let (il, ir) = output.split_at(32);
-
-    // Serialize the master key:
-    let mut data = Vec::with_capacity(82);
-    // Initial four bytes are for "mainnet private key":
-    data.extend(&[4, 136, 173, 228, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
-    data.extend(ir);
-    data.extend(&[0]);
-    data.extend(il);
-
-    // Double hash the data to get the last four bytes:
-    let mut checksum_digest = [0; 32];
-    let mut hasher = Sha256::new();
-    hasher.input(&data);
-    hasher.result(&mut checksum_digest);
-    hasher.reset();
-    hasher.input(&checksum_digest);
-    hasher.result(&mut checksum_digest);
-    data.extend(&checksum_digest[..4]);
+    config.to_seed().to_hex()
+}
 
+// Refer to BIP-32 requirements (Serialization format); the 'bip32' module does the work:
+pub fn generate_root_key<S: AsRef<[u8]> + ?Sized>(seed: &S) -> Result<String, Error> {
+    let seed = seed.as_ref();
+    if seed.len() != 64 {
+        return Err(Error::InvalidSeedLength(seed.len()));
+    }
     // Base58 will encode the master key to start with "xprv":
-    bs58::encode(data).into_string()
+    Ok(ExtendedPrivKey::new_master(seed, Network::Mainnet)?.to_string())
+}
+
+// The master xprv and its xpub together; the xpub is the secret key's secp256k1 point with the
+// same chain code, serialized with "xpub"'s version bytes (0x0488B21E), and wallets that watch
+// take it instead of the xprv:
+pub fn generate_root_keys<S: AsRef<[u8]> + ?Sized>(seed: &S) -> Result<(String, String), Error> {
+    let seed = seed.as_ref();
+    if seed.len() != 64 {
+        return Err(Error::InvalidSeedLength(seed.len()));
+    }
+    let root = ExtendedPrivKey::new_master(seed, Network::Mainnet)?;
+    Ok((root.to_string(), root.to_extended_pub_key().to_string()))
 }
 
 // The spec calls for the salt to be prefixed by 'mnemonic'.
 // The 'passphrase' is entirely optional.
+#[cfg(feature = "std")]
 fn get_salt(passphrase: String) -> String {
     //String::from("mnemonic") + passphrase.as_str()
     //"mnemonic".to_string() + &passphrase
-    format!("mnemonic{}", passphrase)
+    format!("mnemonic{}", nfkd(&passphrase))
+}
+
+// BIP-39 hashes the words and the passphrase in Unicode's NFKD form, so that "é" typed as one
+// character or as "e" and a combining accent (and "㍍" or "メートル") make the same seed as in
+// any other wallet.  Text already in that form, which includes anything ASCII and every word of
+// the wordlists, comes back as it is, with nothing allocated.
+fn nfkd(text: &str) -> Cow<'_, str> {
+    if unicode_normalization::is_nfkd(text) {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.nfkd().collect())
+    }
+}
+
+// Edit distance counting characters rather than bytes, where swapping two neighbours is one edit
+// like the others (the "optimal string alignment" take on Damerau-Levenshtein), for suggesting
+// words in place of typos:
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // d[i][j] is the distance between the first i characters of 'a' and the first j of 'b':
+    let mut d: Vec<Vec<usize>> = (0..=a.len()).map(|i| vec![i; b.len() + 1]).collect();
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
 }
 
-fn get_mnemonic(word_list_size: MnemonicListSize) -> String {
-    // Create 32 bytes of random values (rust's arrays must be defined at compile time):
-    let rand_seq = rand::random::<[u8; 32]>();
+fn get_mnemonic(
+    word_list_size: MnemonicListSize,
+    language: Language,
+    source: &mut dyn EntropySource,
+) -> Result<Phrase, Error> {
+    // Room for 32 bytes of entropy (rust's arrays must be defined at compile time):
+    let mut rand_seq = [0; 32];
 
     // Given the word_list_size, we'll define our 'entropy' as a slice of the above 'rand_seq':
-    let entropy = get_entropy(&word_list_size, &rand_seq);
+    let entropy = get_entropy(&word_list_size, &mut rand_seq);
+    source.fill(entropy)?;
+
+    let mnemonic = fault::twice(|| Phrase::of_entropy(entropy, language), PartialEq::eq);
+    rand_seq.zeroize();
+    Ok(mnemonic)
+}
+
+// The longest a phrase can be, in bytes: 24 of the longest words this build embeds (a Korean one
+// is 33 bytes, its letters decomposed as the lists are) and the spaces between them.
+pub const MAX_PHRASE_LEN: usize = 24 * wordlists::MAX_WORD_LEN + 23;
 
+// 'entropy' must be 16, 20, 24, 28, or 32 bytes long (one of the MnemonicListSize lengths):
+fn get_mnemonic_from_entropy(entropy: &[u8], language: Language) -> String {
+    // Put together on the stack and copied to the heap once, at its final size; a String grown
+    // word by word would leave the start of the phrase behind in freed memory at every
+    // reallocation.
+    let mut buffer = [0; MAX_PHRASE_LEN];
+    write_mnemonic_from_entropy(entropy, language, &mut buffer).to_string()
+}
+
+// The same, into 'buffer' rather than onto the heap:
+fn write_mnemonic_from_entropy<'a>(
+    entropy: &[u8],
+    language: Language,
+    buffer: &'a mut [u8; MAX_PHRASE_LEN],
+) -> &'a str {
     let entropy_bits_len = entropy.len() * 8;
-    let mut terms = String::new();
+    let mut terms_len = 0;
 
     let mut bits_consumed = 0;
     let mut term_index: usize = 0;
     // Declare the 2,048 word list:
-    let word_list = get_word_list();
+    let word_list = language.word_list();
 
     // Accumulate the mnemonic terms from the entropy:
     while bits_consumed < entropy_bits_len {
         let remaining_term_bits = 11 - (bits_consumed % 11);
         let high_pos = 8 - (bits_consumed % 8);
+        #[allow(clippy::implicit_saturating_sub)]
         let low_bound = if remaining_term_bits >= high_pos {
             0 // drain the remaining bits
         } else {
@@ -148,8 +920,7 @@ fn get_mnemonic(word_list_size: MnemonicListSize) -> String {
         // If we've accumulated 11 bits, it's time to get the mnemonic from the word list and add it to the terms:
         if bits_consumed % 11 == 0 {
             //print!("{:011b}", term_index);
-            // You can treat Vector access as you would for an array:
-            append_term(word_list[term_index], &mut terms);
+            append_term(word_list[term_index], buffer, &mut terms_len);
             term_index = 0
         }
     }
@@ -157,39 +928,37 @@ fn get_mnemonic(word_list_size: MnemonicListSize) -> String {
     let checksum_byte = get_checksum_byte(entropy);
     //println!("\nchecksum_byte: {:08b}", checksum_byte);
 
-    // Declaration by way of match:
-    let checksum_low_bound = match word_list_size {
-        Twelve => 4,
-        Fifteen => 3,
-        Eighteen => 2,
-        TwentyOne => 1,
-        TwentyFour => 0,
-    };
+    // The checksum is one bit per 32 bits of entropy, taken from the top of the 'checksum_byte'
+    // (i.e. 4 bits for 12 words, down to all 8 for 24 words):
+    let checksum_low_bound = 8 - entropy.len() / 4;
 
     // Assign the final term's bits to the 'term_index' using the 'checksum_byte':
     shift(8, checksum_low_bound, &mut term_index, checksum_byte);
 
-    append_term(word_list[term_index], &mut terms);
+    append_term(word_list[term_index], buffer, &mut terms_len);
 
-    terms
+    // Whole words went in, so it's all still UTF-8:
+    core::str::from_utf8(&buffer[..terms_len]).unwrap()
 }
 
-fn append_term(term: &str, mnemonic: &mut String) {
-    if mnemonic.is_empty() == false {
-        *mnemonic += " "
+fn append_term(term: &str, buffer: &mut [u8], len: &mut usize) {
+    if *len > 0 {
+        buffer[*len] = b' ';
+        *len += 1;
     }
-    *mnemonic += term
+    buffer[*len..*len + term.len()].copy_from_slice(term.as_bytes());
+    *len += term.len();
 }
 
 // The 'a here is a "lifetime"; the function's output needs to live as long as the input here:
-fn get_entropy<'a>(word_list_size: &MnemonicListSize, rand_seq: &'a [u8]) -> &'a [u8] {
+fn get_entropy<'a>(word_list_size: &MnemonicListSize, rand_seq: &'a mut [u8]) -> &'a mut [u8] {
     match word_list_size {
         // The "..#" is a "slice" of the input data:
-        Twelve => &rand_seq[..16],
-        Fifteen => &rand_seq[..20],
-        Eighteen => &rand_seq[..24],
-        TwentyOne => &rand_seq[..28],
-        TwentyFour => &rand_seq[..32],
+        Twelve => &mut rand_seq[..16],
+        Fifteen => &mut rand_seq[..20],
+        Eighteen => &mut rand_seq[..24],
+        TwentyOne => &mut rand_seq[..28],
+        TwentyFour => &mut rand_seq[..32],
     }
 }
 
@@ -207,40 +976,73 @@ fn shift(high_pos: usize, low_bound: usize, term_index: &mut usize, byte: u8) {
     }
 }
 
-fn get_word_list() -> Vec<&'static str> {
-    // 'cfg!' macro is for compile time boolean evaluation:
-    // 'feature' is used by cargo for conditional building:
-    let contents = if cfg!(feature = "chinese_traditional") {
-        include_str!("../wordlists/chinese_traditional.txt")
-    } else if cfg!(feature = "czech") {
-        include_str!("../wordlists/czech.txt")
-    } else if cfg!(feature = "japanese") {
-        include_str!("../wordlists/japanese.txt")
-    } else if cfg!(feature = "korean") {
-        include_str!("../wordlists/korean.txt")
-    } else if cfg!(feature = "spanish") {
-        include_str!("../wordlists/spanish.txt")
-    } else if cfg!(feature = "chinese_simplified") {
-        include_str!("../wordlists/chinese_simplified.txt")
-    } else if cfg!(feature = "french") {
-        include_str!("../wordlists/french.txt")
-    } else if cfg!(feature = "italian") {
-        include_str!("../wordlists/italian.txt")
-    } else if cfg!(feature = "portuguese") {
-        include_str!("../wordlists/portuguese.txt")
-    } else {
-        include_str!("../wordlists/english.txt")
-    };
-
-    // get an iterator from our word list of its lines and collect them into a Vector:
-    contents.lines().collect::<Vec<&str>>()
-}
-
 fn get_checksum_byte(entropy: &[u8]) -> u8 {
     let mut hasher = Sha256::new();
-    hasher.input(entropy);
+    hasher.update(entropy);
     // Arrays may be created initialized w/ a value and the length:
     let mut digest_out = [0; 32];
-    hasher.result(&mut digest_out);
+    digest_out.copy_from_slice(&hasher.finalize());
     digest_out[0]
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
+// The core as a signing device builds it, without std ('cargo test --lib --no-default-features
+// -F langs-english'): Trezor's first vector from a device's own entropy to its root key, and
+// SLIP-0010's first ed25519 vector.
+#[cfg(all(test, not(feature = "std")))]
+mod core_tests {
+    use super::*;
+    use bip32::DerivationPath;
+    use slip10::ExtendedKey;
+
+    struct Zeros;
+
+    impl EntropySource for Zeros {
+        fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+            entropy.iter_mut().for_each(|byte| *byte = 0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn entropy_to_root_key() {
+        let config =
+            Config::generate_using(&mut Zeros, Language::English, Twelve, "TREZOR").unwrap();
+        assert_eq!(
+            config.mnemonic,
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+        let seed = config.to_seed();
+        assert_eq!(
+            seed.to_hex(),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+        assert_eq!(
+            seed.to_root_key(Network::Mainnet).unwrap().to_string(),
+            "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF"
+        );
+    }
+
+    #[test]
+    fn slip10_ed25519() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedKey::new_master(&seed, Curve::Ed25519).unwrap();
+        assert_eq!(
+            hex::encode(master.chain_code),
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+        );
+        assert_eq!(
+            hex::encode(master.secret_key_bytes()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        let child = master
+            .derive_path(&"m/0'".parse::<DerivationPath>().unwrap())
+            .unwrap();
+        assert_eq!(
+            hex::encode(child.secret_key_bytes()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+}
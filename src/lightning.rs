@@ -0,0 +1,118 @@
+// The keys an LND node derives from its wallet's root key: every key family lives at
+// m/1017'/coin_type'/family'/0/index, and the node's identity (its "node id" public key) is
+// family 6, index 0.  This lets node operators check which keys a backup actually covers.
+//
+// Core Lightning doesn't use BIP-32 for these; it expands its hsm_secret with HKDF instead, so
+// its keys can't be derived here.
+
+use std::fmt;
+
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+
+const PURPOSE: u32 = 1017;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyFamily {
+    MultiSig = 0,
+    RevocationBase = 1,
+    HtlcBase = 2,
+    PaymentBase = 3,
+    DelayBase = 4,
+    RevocationRoot = 5,
+    NodeKey = 6,
+    StaticBackup = 7,
+    TowerSession = 8,
+    TowerId = 9,
+}
+
+impl KeyFamily {
+    pub fn all() -> [KeyFamily; 10] {
+        [
+            KeyFamily::MultiSig,
+            KeyFamily::RevocationBase,
+            KeyFamily::HtlcBase,
+            KeyFamily::PaymentBase,
+            KeyFamily::DelayBase,
+            KeyFamily::RevocationRoot,
+            KeyFamily::NodeKey,
+            KeyFamily::StaticBackup,
+            KeyFamily::TowerSession,
+            KeyFamily::TowerId,
+        ]
+    }
+
+    pub fn path(&self, root: &ExtendedPrivKey, index: u32) -> Result<DerivationPath, &'static str> {
+        Ok(DerivationPath::from(vec![
+            ChildNumber::hardened(PURPOSE)?,
+            ChildNumber::hardened(root.network.coin_type())?,
+            ChildNumber::hardened(*self as u32)?,
+            ChildNumber::normal(0)?,
+            ChildNumber::normal(index)?,
+        ]))
+    }
+
+    pub fn derive(&self, root: &ExtendedPrivKey, index: u32) -> Result<ExtendedPrivKey, &'static str> {
+        Ok(root.derive_path(&self.path(root, index)?)?)
+    }
+}
+
+// LND's names for the families:
+impl fmt::Display for KeyFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            KeyFamily::MultiSig => "multisig",
+            KeyFamily::RevocationBase => "revocation base",
+            KeyFamily::HtlcBase => "htlc base",
+            KeyFamily::PaymentBase => "payment base",
+            KeyFamily::DelayBase => "delay base",
+            KeyFamily::RevocationRoot => "revocation root",
+            KeyFamily::NodeKey => "node key",
+            KeyFamily::StaticBackup => "static backup",
+            KeyFamily::TowerSession => "tower session",
+            KeyFamily::TowerId => "tower id",
+        })
+    }
+}
+
+// The node's public identity, as shown by 'lncli getinfo':
+pub fn node_id(root: &ExtendedPrivKey) -> Result<[u8; 33], &'static str> {
+    Ok(KeyFamily::NodeKey.derive(root, 0)?.public_key())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+    use Config;
+
+    fn root(network: Network) -> ExtendedPrivKey {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let config = Config::from_phrase(mnemonic, "").unwrap();
+        config.to_seed().to_root_key(network).unwrap()
+    }
+
+    // The keys at LND's paths for the test mnemonic, worked out with a separate BIP-32
+    // implementation:
+    #[test]
+    fn node_id_and_families() {
+        assert_eq!(
+            hex::encode(node_id(&root(Network::Mainnet)).unwrap()),
+            "03e2ed64c913bd000c21be4a48214d89edc26f550deefc795c57b6ed7c4f9a7728"
+        );
+        // Testnet nodes are under coin type 1:
+        assert_eq!(
+            hex::encode(node_id(&root(Network::Testnet)).unwrap()),
+            "02b47d1dfb8d4aba27484cf6008c0d85b512d9acf2d888826f741a5b1ee709f3cd"
+        );
+
+        let root = root(Network::Mainnet);
+        assert_eq!(KeyFamily::MultiSig.path(&root, 3).unwrap().to_string(), "m/1017'/0'/0'/0/3");
+        assert_eq!(
+            hex::encode(KeyFamily::MultiSig.derive(&root, 3).unwrap().public_key()),
+            "0374efd316ce00d7c2ad49ec848433e8fec2ac70303ae809758f3ddd80584fbedb"
+        );
+        for (number, family) in KeyFamily::all().iter().enumerate() {
+            assert_eq!(*family as usize, number);
+        }
+    }
+}
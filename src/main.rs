@@ -1,11 +1,814 @@
 extern crate bip_39;
+extern crate clap;
 extern crate hex;
-extern crate rpassword;
+extern crate serde_json;
 
-use bip_39::Config;
+use bip_39::address;
+use bip_39::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey};
+use bip_39::bip38;
+use bip_39::bip47;
+use bip_39::bip85;
+use bip_39::bulk_audit;
+use bip_39::decoy;
+use bip_39::descriptor::ScriptType;
+use bip_39::display::{self, MnemonicFormat};
+use bip_39::entropy::{self, Conditioning, EntropySource, Mixed, OsRandom, Rolls, Source};
+use bip_39::explain;
+use bip_39::export::{ExportRequest, Registry};
+use bip_39::i18n;
+use bip_39::keystore::KeyDerivation;
+use bip_39::language::Language;
+use bip_39::lightning::{self, KeyFamily};
+use bip_39::message::{self, SignatureFormat};
+use bip_39::mnemonic::{Checksum, Mnemonic, MnemonicGenerator, ParseOptions};
+use bip_39::multisig::{Cosigner, Multisig, MultisigFormat};
+use bip_39::network::Network;
+use bip_39::nostr;
+use bip_39::pgp::PgpKey;
+use bip_39::preview;
+use bip_39::prompt::{self, PassphrasePolicy, PromptError};
+use bip_39::qr::QrCode;
+use bip_39::recover::{Checkpoint, Search, Target};
+use bip_39::schema;
+use bip_39::seed_xor;
+use bip_39::seedqr;
+use bip_39::slip39::{self, Group, Share};
+use bip_39::ssh::SshKey;
+use bip_39::strength::PassphraseStrength;
+use bip_39::tezos::TezosKey;
+use bip_39::vectors;
+use bip_39::wallet_policy::WalletPolicy;
+use bip_39::watch_only::WatchOnlyWallet;
+use bip_39::weak;
 use bip_39::MnemonicListSize;
-use std::io;
+use bip_39::{Config, Encoding, Seed};
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Parser, Subcommand};
+use std::convert::TryFrom;
+use std::io::{self, BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+// Prompts and notes go to stdout along with the results, unless '--json' or '--quiet' keeps it
+// for the results alone:
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if output() == Output::Text {
+            println!($($arg)*)
+        } else {
+            eprintln!($($arg)*)
+        }
+    };
+}
+
+// Without a subcommand, the interactive walk-through below runs as it always has.
+#[derive(Parser)]
+#[command(about = "Create or recreate a BIP-39 wallet seed.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// What to put between the words of a printed mnemonic (one space, or two when numbered).
+    #[arg(long, global = true)]
+    separator: Option<String>,
+    /// Print a mnemonic this many words to a line, to match a backup card's rows.
+    #[arg(long, global = true)]
+    words_per_line: Option<usize>,
+    /// Leave a wider gap after every this many words within a line.
+    #[arg(long, global = true)]
+    group_size: Option<usize>,
+    /// Number the words ("1. abandon  2. ability ..."), for pre-numbered backup cards.
+    #[arg(long, global = true)]
+    numbered: bool,
+    /// Show a mnemonic one word (or one line, with --words-per-line) per press of Enter, erasing
+    /// each before the next, so the whole phrase is never on screen at once.
+    #[arg(long, global = true)]
+    paged: bool,
+    /// Don't ask for a new mnemonic to be typed back in before printing its seed and root key.
+    #[arg(long)]
+    skip_backup_check: bool,
+    /// Check a new mnemonic's backup by asking for this many of its words, picked at random,
+    /// rather than having the whole phrase typed back in.
+    #[arg(long, conflicts_with = "skip_backup_check")]
+    quiz: Option<usize>,
+    /// Print the mnemonic, entropy, seed, master fingerprint, xprv and xpub as one JSON document
+    /// on stdout, with the prompts on stderr.
+    #[arg(long, conflicts_with_all = ["preview", "unsafe_explain"])]
+    json: bool,
+    /// Print only the results on stdout, with the prompts and notes on stderr.
+    #[arg(long, conflicts_with = "json")]
+    quiet: bool,
+    /// Clear the screen this many seconds after showing secrets, unless Enter is pressed; 0 leaves
+    /// them up.
+    #[arg(long, global = true, default_value_t = 60)]
+    clear_after: u64,
+    /// Walk through every step from entropy to root key, on a throwaway mnemonic.
+    #[arg(long)]
+    explain: bool,
+    /// The same walk-through on the wizard's real mnemonic and passphrase, printing all of it.
+    #[arg(long, conflicts_with = "explain")]
+    unsafe_explain: bool,
+    /// Recreate a wallet but show only what can't spend from it (the master fingerprint, account
+    /// xpub, and first address), for checking a backup on a machine that isn't fully trusted.
+    #[arg(long, conflicts_with = "unsafe_explain")]
+    preview: bool,
+    /// Print secrets even when neither standard output nor standard error is a terminal.
+    #[arg(long, global = true)]
+    allow_redirect: bool,
+    /// Append a JSON line (with no secrets in it) to this file whenever a mnemonic is generated,
+    /// a seed derived, or keys exported, for key ceremony records.
+    #[arg(long, global = true)]
+    audit_log: Option<PathBuf>,
+    /// Go ahead with a mnemonic that's known or made up by hand (a test vector, one word repeated,
+    /// ...), after a warning, rather than refusing it.
+    #[arg(long, global = true)]
+    force: bool,
+    /// Start from a BIP-32 root xprv (asked for, like a passphrase) instead of a mnemonic, for
+    /// wallets backed up as one.
+    #[arg(long, global = true)]
+    xprv: bool,
+    /// Show an asterisk for each character typed at passphrase and password prompts.
+    #[arg(long, global = true)]
+    asterisks: bool,
+    /// The wordlist for new mnemonics (rather than asking), for reading existing ones, and for
+    /// the errors about them: english, japanese, spanish, chinese_simplified, ...
+    #[arg(long, global = true)]
+    language: Option<Language>,
+    /// The network root keys are made for (tprv/tpub on testnet, and the coin type accounts are
+    /// derived under): mainnet, testnet, signet, regtest, liquid, litecoin, dogecoin, dash, ...
+    #[arg(long, global = true)]
+    network: Option<Network>,
+    /// Go without a BIP-39 passphrase, without being asked for one.
+    #[arg(long, global = true)]
+    no_passphrase: bool,
+    /// Refuse BIP-39 passphrases shorter than this many characters.
+    #[arg(long, global = true, default_value_t = 0)]
+    min_passphrase_length: usize,
+    /// Ask for the BIP-39 passphrase once, without a confirmation.
+    #[arg(long, global = true)]
+    no_confirm_passphrase: bool,
+    /// Refuse an empty BIP-39 passphrase at the prompt, so that going without one takes
+    /// --no-passphrase.
+    #[arg(long, global = true)]
+    require_passphrase: bool,
+    /// Give up after this many refused or mismatched passphrase entries.
+    #[arg(long, global = true)]
+    passphrase_attempts: Option<usize>,
+    /// Go ahead with a new mnemonic's passphrase even if it's weak (scores under 3 of 4), with a
+    /// warning, rather than refusing it.
+    #[arg(long, global = true)]
+    allow_weak_passphrase: bool,
+    /// Read the BIP-39 passphrase from this file's first line instead of asking for it.
+    #[arg(long, global = true, conflicts_with = "no_passphrase")]
+    passphrase_file: Option<PathBuf>,
+    /// Write the seed and root key, wherever they'd be printed (by seed, recover, generate, the
+    /// walk-through, ...), to this file, created readable by its owner alone.
+    #[arg(long, global = true)]
+    output: Option<PathBuf>,
+    /// How the seed is written: hex, base64, or binary (its 64 bytes; for the seed command only).
+    #[arg(long, global = true, default_value_t = Encoding::Hex)]
+    encoding: Encoding,
+    /// Write a new mnemonic to this file, created readable by its owner alone, rather than showing
+    /// it (--paged is the way to see it without the whole phrase on screen at once).
+    #[arg(long, global = true)]
+    mnemonic_file: Option<PathBuf>,
+    /// Derive every seed and root key a second time with an independent implementation, and
+    /// abort if the two differ.
+    #[cfg(feature = "paranoid")]
+    #[arg(long, global = true)]
+    paranoid: bool,
+    /// Read the mnemonic from a SeedQR or crypto-seed UR held up to this camera (/dev/video0 if
+    /// none is given) rather than typing it in.
+    #[cfg(feature = "camera")]
+    #[arg(
+        long,
+        global = true,
+        num_args = 0..=1,
+        default_missing_value = "/dev/video0",
+        value_name = "DEVICE"
+    )]
+    scan: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a new mnemonic and print it with its seed and root key; what isn't given on the
+    /// command line is asked for.
+    Generate {
+        /// 12, 15, 18, 21, or 24.
+        #[arg(long, conflicts_with = "entropy")]
+        words: Option<usize>,
+        /// The entropy in hex (16, 20, 24, 28 or 32 bytes), from a hardware RNG or dice, rather
+        /// than the system's random numbers.
+        #[arg(long)]
+        entropy: Option<String>,
+        /// Make the entropy from dice rolls typed in as they're rolled, rather than the system's
+        /// random numbers.
+        #[arg(long, conflicts_with = "entropy")]
+        from_dice: bool,
+        /// The same with coin flips.
+        #[arg(long, conflicts_with_all = ["entropy", "from_dice"])]
+        from_coins: bool,
+        /// How the rolls become entropy: sha256 (hashed, once they hold enough bits) or
+        /// von-neumann (pairs of rolls, taking out a loaded die's bias, at the cost of more rolls).
+        #[arg(long, default_value = "sha256")]
+        conditioning: Conditioning,
+        /// Mix this file's contents (a hardware RNG's output, say) into the system's random
+        /// numbers, so they aren't all there is to go on.
+        #[arg(long, conflicts_with_all = ["entropy", "from_dice", "from_coins"])]
+        mix_file: Option<PathBuf>,
+        /// Mix keys typed at random into the system's random numbers.
+        #[arg(long, conflicts_with_all = ["entropy", "from_dice", "from_coins"])]
+        mix_keyboard: bool,
+        /// Generate this many mnemonics in one run, for provisioning devices: each is written with
+        /// its seed and keys as a line of --batch-format, in --language's words.  Only the
+        /// passphrase, which they share, is asked for.
+        #[arg(
+            long,
+            requires = "words",
+            conflicts_with_all = ["entropy", "from_dice", "from_coins", "mix_keyboard"]
+        )]
+        count: Option<usize>,
+        /// How --count's lines are written: jsonl (a JSON document to a line) or csv (after a
+        /// header line).
+        #[arg(long, value_parser = ["jsonl", "csv"], default_value = "jsonl", requires = "count")]
+        batch_format: String,
+    },
+    /// Recreate the seed and root key of an existing mnemonic (read from standard input).
+    Recover,
+    /// Print only the hex seed of an existing mnemonic, for scripts.
+    Seed,
+    /// Check an existing mnemonic's words and checksum, without deriving anything from it.
+    Validate,
+    /// List the last words that give the first 11, 14, 17, 20 or 23 words a valid checksum.
+    Complete,
+    /// Search for a mnemonic that's only partly legible, read from standard input as a pattern:
+    /// '?' for a lost word, 'ca*le' or 's?ke' for a smudged one, 'cable|castle' for either, and
+    /// a word short if one was lost from an unknown place.  Lists the candidates with a valid
+    /// checksum, or with --fingerprint or --address, the ones that are that wallet.
+    RecoverSearch {
+        /// The wallet's master key fingerprint, in hex (e.g. 73c5da0a).
+        #[arg(long, conflicts_with = "address")]
+        fingerprint: Option<String>,
+        /// An address the wallet used: any script type's, in account 0.
+        #[arg(long)]
+        address: Option<String>,
+        /// How many receive and change addresses to look through for --address.
+        #[arg(long, default_value_t = 20, requires = "address")]
+        gap_limit: u32,
+        /// A file to keep a --fingerprint or --address search's progress in: written every 30
+        /// seconds, and read back to pick up where a stopped search left off.  Matches are printed
+        /// as they're found, since the file doesn't hold them.
+        #[arg(long)]
+        checkpoint: Option<PathBuf>,
+    },
+    /// Split an existing mnemonic into SLIP-39 shares, so that it takes a threshold's worth of
+    /// them to put it back together.
+    Split {
+        /// A group of shares, as T-of-N ("3-of-5": five shares, any three of which will do);
+        /// repeat it for more groups.
+        #[arg(long = "group", required = true)]
+        groups: Vec<Group>,
+        /// How many of the groups it takes.
+        #[arg(long, default_value_t = 1)]
+        group_threshold: u8,
+    },
+    /// Put a mnemonic back together from SLIP-39 shares, read one to a line.
+    Combine,
+    /// Encode an existing mnemonic as a SeedQR, for signing devices that scan their seeds.
+    SeedQr {
+        /// The Compact SeedQR: the entropy's bytes, rather than each word's index as digits.
+        #[arg(long)]
+        compact: bool,
+        /// Draw the QR code in the terminal, not just its payload.
+        #[arg(long)]
+        qr: bool,
+    },
+    /// Write an existing mnemonic's entropy in another wordlist's words.  That's a different
+    /// wallet: BIP-39 seeds come from the words, not the entropy.
+    Translate {
+        /// The wordlist to write it in: english, japanese, spanish, chinese_simplified, ...
+        #[arg(long)]
+        to: Language,
+    },
+    /// Split a mnemonic into Seed XOR parts (as Coldcard does), or put one back together.
+    SeedXor {
+        #[command(subcommand)]
+        action: SeedXorAction,
+    },
+    /// Export account xpubs from an existing mnemonic for watch-only tooling.
+    Export {
+        #[arg(long, value_parser = export_formats())]
+        format: String,
+        /// The BIP-44 account index.
+        #[arg(long, default_value_t = 0)]
+        account: u32,
+        /// The script type, for formats describing a single one: p2pkh, p2sh-p2wpkh, p2wpkh, or p2tr.
+        /// The bundle, descriptor and hwi-keypool formats take it repeatedly.
+        #[arg(long, default_value = "p2wpkh")]
+        script_type: Vec<ScriptType>,
+        /// For the bundle format, how many accounts (counting from 0) to include.
+        #[arg(long, default_value_t = 1)]
+        accounts: u32,
+        /// For the xpub format, the path of the key to export, at any depth (e.g. m/84'/0' or
+        /// m/84'/0'/0'/0) rather than the account's.
+        #[arg(long)]
+        path: Option<DerivationPath>,
+    },
+    /// Derive a key and its address from an existing mnemonic, or onwards from an xprv or xpub you
+    /// already hold, such as an account key.
+    Derive {
+        /// The extended key to start from, rather than the mnemonic.
+        #[arg(long)]
+        key: Option<String>,
+        /// From the mnemonic, a full path (e.g. m/84'/0'/0'/0/5); from --key, a path relative to it
+        /// (e.g. 0/5 for an account's sixth receive address).
+        #[arg(long)]
+        path: String,
+        /// The script type to show the derived key's address for.
+        #[arg(long, default_value = "p2wpkh")]
+        script_type: ScriptType,
+        /// List the addresses of this many of the key's children (counting from 0) instead, e.g.
+        /// --path m/84'/0'/0'/0 --count 20 for an account's first 20 receive addresses.
+        #[arg(long, conflicts_with = "wif")]
+        count: Option<u32>,
+        /// Also show the derived private key in Wallet Import Format, to import into other
+        /// wallets.
+        #[arg(long)]
+        wif: bool,
+        /// Flag the WIF for the key's uncompressed public key (a "5" key), for old software.
+        #[arg(long, requires = "wif")]
+        uncompressed: bool,
+    },
+    /// Check, offline, whether an address belongs to an account xpub.
+    ContainsAddress {
+        #[arg(long)]
+        xpub: String,
+        #[arg(long)]
+        address: String,
+        /// How many keys of the receive and change chains to look through.
+        #[arg(long, default_value_t = 20)]
+        gap_limit: u32,
+    },
+    /// Check a file of mnemonics, one per line, and show each valid one's master fingerprint.
+    BulkAudit {
+        #[arg(long)]
+        file: PathBuf,
+        /// Also show each one's first receive address of this script type.
+        #[arg(long)]
+        script_type: Option<ScriptType>,
+    },
+    /// Generate test vectors (entropy, mnemonic, seed and root key in every language) as JSON,
+    /// as fixtures for other implementations.  They're printed for anyone to see: never fund them.
+    Vectors {
+        /// Entropy in hex, 16 to 32 bytes; may be repeated.
+        #[arg(long)]
+        entropy: Vec<String>,
+        /// How many random entropies to add.
+        #[arg(long, default_value_t = 0)]
+        count: usize,
+        /// The word count random entropies are for.
+        #[arg(long, default_value_t = 12)]
+        words: usize,
+        /// The passphrase for every seed.
+        #[arg(long, default_value = "TREZOR")]
+        passphrase: String,
+    },
+    /// Check this build against the BIP-39 reference test vectors (each one's entropy to words,
+    /// words to seed, and seed to root key) before trusting it with funds.
+    SelfTest,
+    /// Print the wordlist with each word's number, look a number up, or search for words.
+    Wordlist {
+        /// A word's number to look up, or the letters to search for; without one, the whole list
+        /// is printed.
+        query: Option<String>,
+        /// Only match words starting with the letters, rather than containing them.
+        #[arg(long)]
+        prefix: bool,
+        /// Number the words from 0 (the 11-bit values SeedQR and the like store) rather than 1.
+        #[arg(long)]
+        zero_based: bool,
+    },
+    /// Check a mnemonic against addresses from your wallet's history: "is this really my seed?"
+    CheckAddresses {
+        /// An address you've received to or sent change to (repeatable); without any, they're
+        /// asked for after the mnemonic.
+        #[arg(long)]
+        address: Vec<String>,
+        /// How many accounts of each script type to look through.
+        #[arg(long, default_value_t = 1)]
+        accounts: u32,
+        /// How many keys of each receive and change chain to look through.
+        #[arg(long, default_value_t = 20)]
+        gap_limit: u32,
+    },
+    /// Show addresses or export descriptors from an account xpub alone; no mnemonic involved.
+    WatchOnly {
+        #[arg(long)]
+        xpub: String,
+        #[arg(long, default_value = "p2wpkh")]
+        script_type: ScriptType,
+        /// The xpub's origin from its export, e.g. "[d34db33f/84h/0h/0h]", for descriptors that
+        /// name the master fingerprint.
+        #[arg(long)]
+        origin: Option<String>,
+        #[command(subcommand)]
+        action: WatchOnlyAction,
+    },
+    /// Build a multisig wallet's descriptors from the other cosigners' keys and this mnemonic's own.
+    Multisig {
+        /// How many of the keys it takes to spend.
+        #[arg(long)]
+        threshold: usize,
+        /// Another cosigner's key with its origin, e.g. "[d34db33f/48h/0h/0h/2h]xpub..."; repeat
+        /// for each of them.
+        #[arg(long, required = true)]
+        cosigner: Vec<String>,
+        /// The BIP-48 account index for this mnemonic's own key.
+        #[arg(long, default_value_t = 0)]
+        account: u32,
+        /// descriptors, a bip388 wallet policy, or a coldcard setup file (Coldcard and Specter).
+        #[arg(long, default_value = "descriptors")]
+        format: MultisigFormat,
+        /// The wallet's name, for formats that register one.
+        #[arg(long, default_value = "Multisig")]
+        name: String,
+    },
+    /// Sign a message with the key at a derivation path of an existing mnemonic.
+    Sign {
+        /// For example m/84'/0'/0'/0/0.
+        #[arg(long)]
+        path: DerivationPath,
+        /// Which of the key's addresses the signature is for.
+        #[arg(long, default_value = "p2wpkh")]
+        script_type: ScriptType,
+        /// bip137 or bip322; by default BIP-137 (what most wallets check), or BIP-322 for p2tr.
+        #[arg(long)]
+        format: Option<SignatureFormat>,
+        /// The message to sign; without it (or --file), it's asked for as a single line.
+        #[arg(long)]
+        message: Option<String>,
+        #[arg(long, conflicts_with = "message")]
+        file: Option<PathBuf>,
+    },
+    /// Verify a BIP-137 or BIP-322 signed message; no mnemonic needed.
+    VerifyMessage {
+        #[arg(long)]
+        address: String,
+        /// Base64, as signing wallets give it.
+        #[arg(long)]
+        signature: String,
+        /// The signed message; without it (or --file), it's read from standard input.
+        #[arg(long)]
+        message: Option<String>,
+        #[arg(long, conflicts_with = "message")]
+        file: Option<PathBuf>,
+    },
+    /// Encrypt a derived private key with BIP-38, or decrypt one.
+    Bip38 {
+        #[command(subcommand)]
+        action: Bip38Action,
+    },
+    /// Derive keys or entropy for other wallets and applications with BIP-85.
+    Bip85 {
+        #[command(subcommand)]
+        application: Bip85Application,
+    },
+    /// Print the BIP-47 reusable payment code of an existing mnemonic.
+    PaymentCode {
+        #[arg(long, default_value_t = 0)]
+        account: u32,
+    },
+    /// List the LND node id and key family public keys an existing mnemonic's root key covers.
+    Lightning {
+        /// The key index to show within each family.
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+    /// Derive an SSH ed25519 key (SLIP-0013) for an identity from an existing mnemonic.
+    Ssh {
+        /// For example ssh://git@github.com; each identity gets its own key.
+        #[arg(long)]
+        identity: String,
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+        /// Write the private key here (and the public key next to it, with ".pub") instead of
+        /// printing both.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Derive an OpenPGP key (ed25519 with a cv25519 subkey) from an existing mnemonic.
+    Pgp {
+        /// For example "Alice <alice@example.com>".
+        #[arg(long)]
+        user_id: String,
+        /// The key's creation time, in seconds since 1970; the same time restores the same key.
+        #[arg(long)]
+        created: u32,
+        /// Write the private key block here instead of printing it.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Compare the wallets one mnemonic opens with different passphrases, to set up a decoy wallet
+    /// or check hidden wallets against your records.
+    Decoy {
+        /// A name for each passphrase's wallet, in the order they're entered (e.g. --label
+        /// savings --label cold); the rest are numbered.
+        #[arg(long)]
+        label: Vec<String>,
+        /// Also show each wallet's seed and root key.
+        #[arg(long)]
+        keys: bool,
+    },
+    /// Derive a Nostr identity (NIP-06) from an existing mnemonic.
+    Nostr {
+        #[arg(long, default_value_t = 0)]
+        account: u32,
+    },
+    /// Derive a Tezos key (tz1 address) from an existing mnemonic, at m/44'/1729'/account'/0'.
+    Tezos {
+        #[arg(long, default_value_t = 0)]
+        account: u32,
+        /// For a 2017 fundraiser wallet, the email address it was issued with (its password is
+        /// asked for); its key comes from the seed itself, with no derivation path.
+        #[arg(long, conflicts_with = "account")]
+        fundraiser: Option<String>,
+    },
+    /// Hold an existing mnemonic's root key in memory and answer JSON requests for xpubs,
+    /// addresses and message verification from local processes over a Unix socket.
+    #[cfg(unix)]
+    Daemon {
+        /// Defaults to bip-39.sock in $XDG_RUNTIME_DIR; the directory must be private to you.
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+    /// Keep an encrypted mnemonic in the platform keyring (hot storage for test wallets only).
+    #[cfg(feature = "keyring")]
+    Keyring {
+        #[command(subcommand)]
+        action: KeyringAction,
+        /// The name the entry is stored under.
+        #[arg(long, default_value = "default")]
+        label: String,
+    },
+    /// Write or recover an age encrypted backup of a mnemonic.
+    #[cfg(feature = "age")]
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Write or open a passphrase-encrypted vault file (Argon2id and ChaCha20-Poly1305).
+    #[cfg(feature = "vault")]
+    Vault {
+        #[command(subcommand)]
+        action: VaultAction,
+    },
+    /// Write or read a password-encrypted JSON keystore file (scrypt or Argon2id, and AES-256-GCM).
+    Keystore {
+        #[command(subcommand)]
+        action: KeystoreAction,
+    },
+}
+
+impl Command {
+    // Whether a successful run leaves a mnemonic or private key on the screen:
+    fn shows_secrets(&self) -> bool {
+        match self {
+            // (the listed addresses are all there is with --count)
+            Command::Generate { .. } | Command::Recover | Command::Seed => true,
+            Command::Derive { key, count, .. } => {
+                let private = key
+                    .as_ref()
+                    .is_none_or(|key| key.parse::<ExtendedPrivKey>().is_ok());
+                count.is_none() && private
+            }
+            Command::Bip38 { action } => matches!(action, Bip38Action::Decrypt { .. }),
+            Command::Bip85 { .. } | Command::Nostr { .. } | Command::Tezos { .. } => true,
+            Command::Decoy { keys, .. } => *keys,
+            Command::Split { .. } | Command::Combine | Command::SeedXor { .. } => true,
+            Command::SeedQr { .. } | Command::Translate { .. } | Command::RecoverSearch { .. } => {
+                true
+            }
+            Command::Ssh { output, .. } | Command::Pgp { output, .. } => output.is_none(),
+            #[cfg(feature = "keyring")]
+            Command::Keyring { action, .. } => matches!(action, KeyringAction::Load),
+            #[cfg(feature = "age")]
+            Command::Backup { action } => matches!(action, BackupAction::Recover { .. }),
+            #[cfg(feature = "vault")]
+            Command::Vault { action } => matches!(action, VaultAction::Open { .. }),
+            Command::Keystore { action } => matches!(action, KeystoreAction::Import { .. }),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum WatchOnlyAction {
+    /// List receive (or change) addresses with their paths, as JSON.
+    Addresses {
+        #[arg(long)]
+        change: bool,
+        #[arg(long, default_value_t = 0)]
+        start: u32,
+        #[arg(long, default_value_t = 20)]
+        count: u32,
+    },
+    /// Print the receive and change descriptors, and the multipath one covering both.
+    Descriptors,
+    /// Print a Sparrow wallet import file.
+    Sparrow,
+}
+
+#[derive(Subcommand)]
+enum SeedXorAction {
+    /// Split an existing mnemonic into parts, mnemonics of their own, that it takes all of.
+    Split {
+        #[arg(long, default_value_t = 3)]
+        parts: usize,
+    },
+    /// Put a mnemonic back together from all of its parts, read one to a line.
+    Combine,
+}
+
+#[derive(Subcommand)]
+enum Bip38Action {
+    /// Encrypt the private key at a derivation path of an existing mnemonic.
+    Encrypt {
+        /// For example m/44'/0'/0'/0/0.
+        #[arg(long)]
+        path: DerivationPath,
+        /// Tie the key to its uncompressed public key (and legacy address).
+        #[arg(long)]
+        uncompressed: bool,
+    },
+    /// Decrypt a "6P..." key.
+    Decrypt { key: String },
+}
+
+#[derive(Subcommand)]
+enum Bip85Application {
+    /// A child mnemonic, in --language's words.
+    Mnemonic {
+        /// 12, 15, 18, 21 or 24.
+        #[arg(long, default_value_t = 12)]
+        words: usize,
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+    /// A private key in Wallet Import Format.
+    Wif {
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+    /// A fresh master extended private key.
+    Xprv {
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+    /// Raw entropy, as hex.
+    Hex {
+        /// Between 16 and 64.
+        #[arg(long, default_value_t = 32)]
+        num_bytes: u32,
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+    /// A site password (base64, or base85 with symbols).
+    Password {
+        /// 20 to 86 characters for base64, 10 to 80 for base85.
+        #[arg(long, default_value_t = 21)]
+        length: u32,
+        #[arg(long)]
+        base85: bool,
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+    },
+}
+
+#[cfg(feature = "age")]
+#[derive(Subcommand)]
+enum BackupAction {
+    /// Encrypt an existing mnemonic into an .age file.
+    Export {
+        /// An age recipient ("age1..."); may be repeated.  Without any, a passphrase is asked for.
+        #[arg(long)]
+        recipient: Vec<String>,
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Decrypt an .age backup and recreate its seed and root key.
+    Recover {
+        #[arg(long)]
+        input: PathBuf,
+        /// An age identity file; without one, the backup's passphrase is asked for.
+        #[arg(long)]
+        identity: Option<PathBuf>,
+    },
+}
+
+#[cfg(feature = "vault")]
+#[derive(Subcommand)]
+enum VaultAction {
+    /// Encrypt an existing mnemonic into a vault file.
+    Create {
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Decrypt a vault file and recreate its seed and root key.
+    Open {
+        #[arg(long)]
+        input: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeystoreAction {
+    /// Encrypt an existing mnemonic (or, with --seed, its seed) into a keystore file.
+    Export {
+        #[arg(long)]
+        output: PathBuf,
+        /// Keep the seed, with the BIP-39 passphrase worked in, rather than the mnemonic.
+        #[arg(long)]
+        seed: bool,
+        /// How the key is derived from the password: scrypt or argon2id.
+        #[arg(long, default_value = "scrypt")]
+        kdf: KeyDerivation,
+    },
+    /// Decrypt a keystore file and recreate its seed and root key.
+    Import {
+        #[arg(long)]
+        input: PathBuf,
+    },
+}
+
+#[cfg(feature = "keyring")]
+#[derive(Clone, Copy, Subcommand)]
+enum KeyringAction {
+    /// Encrypt a mnemonic and store it.
+    Store,
+    /// Decrypt and print a stored mnemonic.
+    Load,
+    /// Remove a stored mnemonic.
+    Delete,
+}
+
+// The '--format' names (and help) come from the exporter registry, so a format added there shows
+// up here on its own:
+fn export_formats() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        Registry::builtin()
+            .exporters()
+            .map(|exporter| PossibleValue::new(exporter.name()).help(exporter.description()))
+            .collect::<Vec<_>>(),
+    )
+}
+
+// '--force', for 'get_mnemonic_terms' wherever it's called from:
+static FORCE: AtomicBool = AtomicBool::new(false);
+// '--xprv', for 'get_existing_root_key' and everything that needs the mnemonic itself:
+static FROM_XPRV: AtomicBool = AtomicBool::new(false);
+// '--language', for 'language' and 'get_language':
+static LANGUAGE: OnceLock<Option<Language>> = OnceLock::new();
+// The wizard's menu choice, for 'language' once 'get_language' has asked:
+static CHOSEN_LANGUAGE: OnceLock<Language> = OnceLock::new();
+// '--network', for 'network' and 'get_existing_xprv':
+static NETWORK: OnceLock<Option<Network>> = OnceLock::new();
+// '--asterisks', for 'read_secret':
+static ASTERISKS: AtomicBool = AtomicBool::new(false);
+// The passphrase options, for 'get_passphrase' ('None' for '--no-passphrase'):
+static PASSPHRASE_POLICY: OnceLock<Option<PassphrasePolicy>> = OnceLock::new();
+// '--passphrase-file', for 'get_passphrase':
+static PASSPHRASE_FILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+// '--allow-weak-passphrase':
+static ALLOW_WEAK_PASSPHRASE: AtomicBool = AtomicBool::new(false);
+// '--json' or '--quiet', for 'output':
+static OUTPUT: OnceLock<Output> = OnceLock::new();
+// '--output', for 'secret_output':
+static OUTPUT_FILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+// '--encoding', for 'encoding':
+static ENCODING: OnceLock<Encoding> = OnceLock::new();
+// '--mnemonic-file', for 'show_new_mnemonic':
+static MNEMONIC_FILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+// '--scan', for 'get_mnemonic_terms':
+#[cfg(feature = "camera")]
+static SCAN: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// How a new mnemonic's backup is checked before anything derived from it is printed:
+// '--skip-backup-check', the whole phrase typed back in, or '--quiz''s words.
+#[derive(Clone, Copy)]
+enum BackupCheck {
+    Skip,
+    Retype,
+    Quiz(usize),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Output {
+    Text,
+    Quiet,
+    Json,
+}
 
 // The idea for BIP-39 is to create a deterministic wallet seed by way of
 // randomly generated words and an optional salt.
@@ -13,60 +816,2719 @@ use std::process;
 // list of previously generated mnemonic values (and optional salt) or to
 // create everything from scratch.
 fn main() {
+    let cli = Cli::parse();
+    let default_separator = if cli.numbered { "  " } else { " " };
+    let format = MnemonicFormat {
+        separator: cli
+            .separator
+            .unwrap_or_else(|| default_separator.to_string()),
+        words_per_line: cli.words_per_line,
+        group_size: cli.group_size,
+        numbered: cli.numbered,
+        highlight_checksum: io::stdout().is_terminal(),
+        paged: cli.paged,
+    };
+    if cli.explain {
+        return explain();
+    }
+    // Raw bytes would run into the text around them anywhere but the seed command's own output:
+    if cli.encoding == Encoding::Binary && (cli.json || !matches!(cli.command, Some(Command::Seed)))
+    {
+        eprintln!("Error: --encoding binary is only for the seed command, without --json.");
+        process::exit(1)
+    }
+    // (the JSON document would have it anyway)
+    if cli.json && cli.mnemonic_file.is_some() {
+        eprintln!("Error: --mnemonic-file can't be used with --json.");
+        process::exit(1)
+    }
+    let clear_after = cli.clear_after;
+    let backup_check = match (cli.skip_backup_check, cli.quiz) {
+        (true, _) => BackupCheck::Skip,
+        (false, Some(words)) => BackupCheck::Quiz(words),
+        (false, None) => BackupCheck::Retype,
+    };
+    // (The wizard, run without a subcommand, always does, unless it's previewing.)
+    let shows_secrets = cli
+        .command
+        .as_ref()
+        .map_or(!cli.preview, Command::shows_secrets);
+    // ('--output' takes the seed and root key off the screen, and '--mnemonic-file' a new mnemonic)
+    let shows_secrets = shows_secrets
+        && match &cli.command {
+            Some(Command::Recover | Command::Seed) => cli.output.is_none(),
+            Some(Command::Generate { count: None, .. }) | None => {
+                cli.output.is_none() || cli.mnemonic_file.is_none()
+            }
+            _ => true,
+        };
+    if shows_secrets && !cli.allow_redirect {
+        check_redirection();
+    }
+    if let Some(path) = cli.audit_log {
+        start_audit_log(path);
+    }
+    FORCE.store(cli.force, Ordering::Relaxed);
+    FROM_XPRV.store(cli.xprv, Ordering::Relaxed);
+    ASTERISKS.store(cli.asterisks, Ordering::Relaxed);
+    if let Some(language) = cli.language.filter(|language| !language.is_embedded()) {
+        eprintln!("Error: The {} wordlist isn't built in.", language);
+        process::exit(1)
+    }
+    let _ = LANGUAGE.set(cli.language);
+    let _ = NETWORK.set(cli.network);
+    let policy = PassphrasePolicy {
+        min_length: cli.min_passphrase_length,
+        confirm: !cli.no_confirm_passphrase,
+        allow_empty: !cli.require_passphrase,
+        max_attempts: cli.passphrase_attempts,
+    };
+    let _ = PASSPHRASE_POLICY.set(if cli.no_passphrase {
+        None
+    } else {
+        Some(policy)
+    });
+    let _ = PASSPHRASE_FILE.set(cli.passphrase_file);
+    let _ = OUTPUT_FILE.set(cli.output);
+    let _ = ENCODING.set(cli.encoding);
+    let _ = MNEMONIC_FILE.set(cli.mnemonic_file);
+    ALLOW_WEAK_PASSPHRASE.store(cli.allow_weak_passphrase, Ordering::Relaxed);
+    let _ = OUTPUT.set(if cli.json {
+        Output::Json
+    } else if cli.quiet {
+        Output::Quiet
+    } else {
+        Output::Text
+    });
+    prompt::prompt_on_stderr(output() != Output::Text);
+    #[cfg(feature = "paranoid")]
+    if cli.paranoid {
+        bip_39::paranoid::enable();
+    }
+    #[cfg(feature = "camera")]
+    let _ = SCAN.set(cli.scan);
+
+    match cli.command {
+        Some(Command::Generate {
+            words,
+            entropy,
+            from_dice,
+            from_coins,
+            conditioning,
+            mix_file,
+            mix_keyboard,
+            count,
+            batch_format,
+        }) => {
+            if let Some(count) = count {
+                let mix = get_mix(mix_file.as_deref(), false);
+                // ('requires' saw to '--words')
+                return generate_batch(count, words.unwrap(), mix, &batch_format);
+            }
+            let rolls = match (from_dice, from_coins) {
+                (true, _) => Some((Source::Dice, conditioning)),
+                (_, true) => Some((Source::Coin, conditioning)),
+                _ => None,
+            };
+            let mix = get_mix(mix_file.as_deref(), mix_keyboard);
+            generate(words, entropy.as_deref(), rolls, mix, &format, backup_check)
+        }
+        Some(Command::Recover) => recover(),
+        Some(Command::Seed) => seed(),
+        Some(Command::Validate) => validate(),
+        Some(Command::Complete) => complete(),
+        Some(Command::RecoverSearch {
+            fingerprint,
+            address,
+            gap_limit,
+            checkpoint,
+        }) => recover_search(
+            fingerprint.as_deref(),
+            address,
+            gap_limit,
+            checkpoint.as_deref(),
+        ),
+        Some(Command::Split {
+            groups,
+            group_threshold,
+        }) => split(&groups, group_threshold),
+        Some(Command::Combine) => combine(&format),
+        Some(Command::SeedQr { compact, qr }) => seed_qr(compact, qr),
+        Some(Command::Translate { to }) => translate(to, &format),
+        Some(Command::SeedXor { action }) => seed_xor(action, &format),
+        Some(Command::Export {
+            format,
+            account,
+            script_type,
+            accounts,
+            path,
+        }) => export(&format, account, &script_type, accounts, path.as_ref()),
+        Some(Command::Derive {
+            key,
+            path,
+            script_type,
+            count,
+            wif,
+            uncompressed,
+        }) => derive(
+            key.as_deref(),
+            &path,
+            script_type,
+            count,
+            wif.then_some(!uncompressed),
+        ),
+        Some(Command::ContainsAddress {
+            xpub,
+            address,
+            gap_limit,
+        }) => contains_address(&xpub, &address, gap_limit),
+        Some(Command::BulkAudit { file, script_type }) => bulk_audit(&file, script_type),
+        Some(Command::SelfTest) => self_test(),
+        Some(Command::Vectors {
+            entropy,
+            count,
+            words,
+            passphrase,
+        }) => vectors(&entropy, count, words, &passphrase),
+        Some(Command::Wordlist {
+            query,
+            prefix,
+            zero_based,
+        }) => wordlist(query.as_deref(), prefix, zero_based, language()),
+        Some(Command::CheckAddresses {
+            address,
+            accounts,
+            gap_limit,
+        }) => check_addresses(address, accounts, gap_limit),
+        Some(Command::WatchOnly {
+            xpub,
+            script_type,
+            origin,
+            action,
+        }) => watch_only(&xpub, script_type, origin.as_deref(), action),
+        Some(Command::Multisig {
+            threshold,
+            cosigner,
+            account,
+            format,
+            name,
+        }) => multisig(threshold, &cosigner, account, format, &name),
+        Some(Command::Sign {
+            path,
+            script_type,
+            format,
+            message,
+            file,
+        }) => sign(&path, script_type, format, message, file.as_deref()),
+        Some(Command::VerifyMessage {
+            address,
+            signature,
+            message,
+            file,
+        }) => verify_message(&address, &signature, message, file.as_deref()),
+        Some(Command::Bip38 { action }) => bip38(action),
+        Some(Command::Bip85 { application }) => bip85(application),
+        Some(Command::PaymentCode { account }) => payment_code(account),
+        Some(Command::Lightning { index }) => lightning_keys(index),
+        Some(Command::Ssh {
+            identity,
+            index,
+            output,
+        }) => ssh(&identity, index, output.as_deref()),
+        Some(Command::Pgp {
+            user_id,
+            created,
+            output,
+        }) => pgp(&user_id, created, output.as_deref()),
+        Some(Command::Decoy { label, keys }) => decoy(&label, keys),
+        Some(Command::Nostr { account }) => nostr(account),
+        Some(Command::Tezos {
+            account,
+            fundraiser,
+        }) => tezos(account, fundraiser),
+        #[cfg(unix)]
+        Some(Command::Daemon { socket }) => daemon(socket),
+        #[cfg(feature = "keyring")]
+        Some(Command::Keyring { action, label }) => keyring(action, &label, &format),
+        #[cfg(feature = "age")]
+        Some(Command::Backup { action }) => backup(action, &format),
+        #[cfg(feature = "vault")]
+        Some(Command::Vault { action }) => vault(action, &format),
+        Some(Command::Keystore { action }) => keystore(action, &format),
+        None => wizard(&format, backup_check, cli.unsafe_explain, cli.preview),
+    }
+
+    if shows_secrets {
+        clear_screen_after(clear_after);
+    }
+}
+
+// Each event goes in as its own line, stamped with the Unix time, and the file is opened for every
+// one of them so nothing sits in a buffer if the program is killed.
+fn start_audit_log(path: PathBuf) {
+    use bip_39::audit;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    audit::set_hook(Box::new(move |event| {
+        let mut record = schema::versioned(event.to_json());
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs());
+        record["time"] = serde_json::json!(time.unwrap_or(0));
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", record));
+        if written.is_err() {
+            eprintln!("Error: Unable to write to the audit log.");
+            process::exit(1)
+        }
+    }));
+}
+
+// Seeds and keys that end up in log files and CI output are a common way to lose a wallet.  With
+// the secrets going somewhere other than a terminal, someone watching stderr still gets warned;
+// with both redirected, nobody would see a warning, so it takes '--allow-redirect' to go on.
+fn check_redirection() {
+    match Redirection::of(io::stdout().is_terminal(), io::stderr().is_terminal()) {
+        Redirection::None => {}
+        Redirection::Watched => {
+            eprintln!(
+                "WARNING: secret output (a mnemonic, seed, or private key) is going to a file or"
+            );
+            eprintln!("WARNING: pipe, not the screen.  Whatever can read it can take the wallet.");
+        }
+        Redirection::Unwatched => {
+            eprintln!(
+                "Error: Secrets would go to a file or pipe with nobody watching; \
+                 pass --allow-redirect to allow it."
+            );
+            process::exit(1)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Redirection {
+    None,
+    // Standard output goes elsewhere, but standard error is still a terminal:
+    Watched,
+    Unwatched,
+}
+
+impl Redirection {
+    fn of(stdout_is_terminal: bool, stderr_is_terminal: bool) -> Redirection {
+        match (stdout_is_terminal, stderr_is_terminal) {
+            (true, _) => Redirection::None,
+            (false, true) => Redirection::Watched,
+            (false, false) => Redirection::Unwatched,
+        }
+    }
+}
+
+// Secrets left on screen outlive the session they were meant for; count down so anyone still
+// there can keep them (Enter), and otherwise clear the screen and its scrollback.  Only for
+// terminals: redirected output is somewhere the countdown can't reach anyway.
+fn clear_screen_after(seconds: u64) {
+    use std::io::Write;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    if seconds == 0 || !io::stdout().is_terminal() {
+        return;
+    }
+
+    let (pressed, keypress) = mpsc::channel();
+    thread::spawn(move || {
+        let mut line = String::new();
+        if io::stdin()
+            .read_line(&mut line)
+            .map(|read| read > 0)
+            .unwrap_or(false)
+        {
+            let _ = pressed.send(());
+        }
+    });
+
+    for remaining in (1..=seconds).rev() {
+        print!(
+            "\rClearing the screen in {:>3}s; press Enter to keep it. ",
+            remaining
+        );
+        io::stdout().flush().unwrap();
+        if keypress.recv_timeout(Duration::from_secs(1)).is_ok() {
+            return;
+        }
+    }
+    print!("\x1B[2J\x1B[3J\x1B[H");
+    io::stdout().flush().unwrap();
+}
+
+fn wizard(format: &MnemonicFormat, backup_check: BackupCheck, unsafe_explain: bool, preview: bool) {
     // 1) Instruct user that they can (A): create a seed from scratch or (B): recreate a seed.
     // 2) In either case, ask for and capture how many mnemonic words they intend to use: 12, 15, 18, 21, or 24.
-    // 3) Ask for and capture if they're creating a new seed.
+    // 3) Ask for and capture if they're creating a new seed, and if so, in which language.
     // 4) Ask for and capture the optional passphrase.
     // 5) Ask for and capture passphrase confirmation from #4; retry if confirmation does not match original.
     // 6) Acquire mnemonic terms - if:
     //		'A' path (creating from scratch), generate mnemonic terms based on count captured in #2.
     //		'B' path (recreating), ask for and capture mnemonic terms.
+    //		'A' path, have the new terms typed back in from the written backup before going on.
     // 7) Create 'Config' struct using terms, terms count, and passphrase.
     // 8) Generate seed using Config from #7.
     // 9) Generate BIP-32 "root key" using seed from #8.
 
-    // Stuff ending w/ '!' is a macro:
-    // "A macro invocation is shorthand for an "expanded" syntactic form." - rust docs
-    println!("You may create a new seed from scratch or recreate one from a previously generated mnemonic list.");
-    println!("In either case, what size mnemonic word list should the seed be derived from? [12, 15, 18, 21, or 24]");
+    // (an xprv can't be typed in here: the wizard makes and shows mnemonics)
+    require_mnemonic();
+
+    // Stuff ending w/ '!' is a macro:
+    // "A macro invocation is shorthand for an "expanded" syntactic form." - rust docs
+    say!("You may create a new seed from scratch or recreate one from a previously generated mnemonic list.");
+    say!("In either case, what size mnemonic word list should the seed be derived from? [12, 15, 18, 21, or 24]");
+
+    // 'let' declares variables:
+    let mnemonic_list_size = get_mnemonic_list_size();
+
+    let is_new_seed = get_is_new_seed();
+    // A new mnemonic that's never shown would be a wallet nobody could recreate:
+    if is_new_seed && preview {
+        eprintln!(
+            "Error: --preview never shows the mnemonic; it's for recreating an existing one."
+        );
+        process::exit(1)
+    }
+
+    // Only new mnemonics need words picked; existing ones are taken as typed:
+    let language = if is_new_seed {
+        get_language()
+    } else {
+        language()
+    };
+
+    let passphrase = get_passphrase();
+
+    // Just another way to declare a local variable; notice no use of parenthesis:
+    let config = if is_new_seed {
+        print_passphrase_strength(&passphrase);
+        // Our first struct - 'Config'
+        let config = Config::generate_in(language, mnemonic_list_size, passphrase);
+        show_new_mnemonic(&config.mnemonic, language, format, backup_check);
+        config
+    } else {
+        // (the words say how many there are, whatever size was asked for above)
+        Config::from_phrase(get_mnemonic_terms(), passphrase).unwrap_or_else(|err| {
+            eprintln!("Error: {}", i18n::localize(err.message(), language));
+            process::exit(1)
+        })
+    };
+
+    if unsafe_explain {
+        match explain::trace(&config, language) {
+            Ok(trace) => println!("{}\n", trace),
+            Err(err) => eprintln!("Error: {}", err),
+        }
+    }
+
+    if preview {
+        return print_preview(config);
+    }
+    print_seed_and_root_key(config)
+}
+
+// The wizard's new seed, asking only for what the command line doesn't already say: the word
+// count (or the entropy itself), '--language', and the passphrase options.
+fn generate(
+    words: Option<usize>,
+    entropy: Option<&str>,
+    rolls: Option<(Source, Conditioning)>,
+    mix: Option<Vec<u8>>,
+    format: &MnemonicFormat,
+    backup_check: BackupCheck,
+) {
+    use bip_39::audit;
+
+    // Where the new mnemonic's bits come from; given entropy says how many words it makes:
+    enum Bits {
+        Given(Vec<u8>),
+        Random(MnemonicListSize),
+    }
+
+    require_mnemonic();
+    let exit = |err: &str| -> ! {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    };
+    let bits = match (entropy, words) {
+        (Some(hex), _) => {
+            Bits::Given(hex::decode(hex).unwrap_or_else(|_| exit("Entropy must be hex.")))
+        }
+        (None, Some(words)) => {
+            Bits::Random(MnemonicListSize::from(words).unwrap_or_else(|err| exit(err.message())))
+        }
+        (None, None) => {
+            say!("What size mnemonic word list should the seed be derived from? [12, 15, 18, 21, or 24]");
+            Bits::Random(get_mnemonic_list_size())
+        }
+    };
+    // '--from-dice' (or '--from-coins') rolls for the bits instead:
+    let bits = match (bits, rolls) {
+        (Bits::Random(size), Some((source, conditioning))) => {
+            Bits::Given(get_rolls(source, conditioning, size))
+        }
+        (bits, _) => bits,
+    };
+    let language = get_language();
+    let passphrase = get_passphrase();
+    print_passphrase_strength(&passphrase);
+
+    let config = match bits {
+        Bits::Given(entropy) => {
+            let mnemonic = Mnemonic::from_entropy_bytes(&entropy, language)
+                .unwrap_or_else(|err| exit(i18n::localize(err.message(), language)));
+            // (entropy from outside can be anything, a test vector's included)
+            check_weakness(&mnemonic.phrase());
+            audit::emit(audit::Event::MnemonicGenerated {
+                language,
+                words: mnemonic.words().len(),
+            });
+            Config::from_phrase(mnemonic.phrase(), passphrase)
+                .unwrap_or_else(|err| exit(err.message()))
+        }
+        Bits::Random(size) => match mix {
+            Some(material) => {
+                Config::generate_using(&mut Mixed::new(&material), language, size, passphrase)
+                    .unwrap_or_else(|err| exit(err.message()))
+            }
+            None => Config::generate_in(language, size, passphrase),
+        },
+    };
+    show_new_mnemonic(&config.mnemonic, language, format, backup_check);
+    print_seed_and_root_key(config)
+}
+
+// '--count': new mnemonics a line at a time, written as each one's made rather than all at the
+// end, so a provisioning script can read them off as they come.  Standard output is all lines, so
+// the passphrase prompt goes to standard error, and there's no backup check (each mnemonic goes
+// to a device, not a sheet of paper).
+fn generate_batch(count: usize, words: usize, mix: Option<Vec<u8>>, batch_format: &str) {
+    require_mnemonic();
+    let size = MnemonicListSize::from(words).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    });
+    let language = language();
+    prompt::prompt_on_stderr(true);
+    let passphrase = get_passphrase();
+    if !passphrase.is_empty() {
+        check_passphrase_strength(&bip_39::estimate_passphrase_strength(&passphrase));
+    }
+
+    let mut mixed;
+    let source: &mut dyn EntropySource = match mix {
+        Some(material) => {
+            mixed = Mixed::new(&material);
+            &mut mixed
+        }
+        None => &mut OsRandom,
+    };
+    let csv = batch_format == "csv";
+    if csv {
+        println!("index,mnemonic,language,seed,fingerprint,xprv,xpub");
+    }
+    for (index, mnemonic) in MnemonicGenerator::new(source, language, size)
+        .take(count)
+        .enumerate()
+    {
+        let mnemonic = mnemonic.unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        });
+        let seed = mnemonic.to_seed(passphrase.clone());
+        let root_key = seed.to_root_key(network()).unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        });
+        let xpub = root_key.to_extended_pub_key();
+        let fingerprint = hex::encode(root_key.fingerprint());
+        if csv {
+            // (none of the fields has a comma or a quote in it to escape)
+            println!(
+                "{},{},{},{},{},{},{}",
+                index, mnemonic, language, seed, fingerprint, root_key, xpub
+            );
+        } else {
+            let document = serde_json::json!({
+                "index": index,
+                "mnemonic": mnemonic.phrase(),
+                "language": language.to_string(),
+                "seed": seed.to_hex(),
+                "network": network().to_string(),
+                "fingerprint": fingerprint,
+                "xprv": root_key.to_string(),
+                "xpub": xpub.to_string(),
+            });
+            println!("{}", schema::versioned(document));
+        }
+    }
+}
+
+// '--mix-file' and '--mix-keyboard''s material, both together if both are given:
+fn get_mix(file: Option<&Path>, keyboard: bool) -> Option<Vec<u8>> {
+    if file.is_none() && !keyboard {
+        return None;
+    }
+    let mut material = Vec::new();
+    if let Some(path) = file {
+        material = std::fs::read(path).unwrap_or_else(|_| {
+            eprintln!("Error: Unable to read {}.", path.display());
+            process::exit(1)
+        });
+    }
+    if keyboard {
+        let typed = read_secret("Type keys at random for a while, then press Enter: ");
+        material.extend_from_slice(typed.as_bytes());
+    }
+    Some(material)
+}
+
+// Rolls typed in a line at a time (as many to a line as is handy), counted down until there are
+// enough of them:
+fn get_rolls(source: Source, conditioning: Conditioning, size: MnemonicListSize) -> Vec<u8> {
+    let mut rolls = Rolls::new(source, conditioning, size);
+    let name = match source {
+        Source::Dice => {
+            say!("Roll a die, and enter what it shows (1 to 6) each time:");
+            "rolls"
+        }
+        Source::Coin => {
+            say!("Flip a coin, and enter how it lands (h or t) each time:");
+            "flips"
+        }
+    };
+    while rolls.remaining() > 0 {
+        match conditioning {
+            Conditioning::Sha256 => {
+                say!(
+                    "{} {} so far, {} more to go:",
+                    rolls.count(),
+                    name,
+                    rolls.remaining()
+                )
+            }
+            // (pairs of the same roll don't count, so it can only be guessed)
+            Conditioning::VonNeumann => {
+                say!(
+                    "{} {} so far, about {} more to go:",
+                    rolls.count(),
+                    name,
+                    rolls.remaining()
+                )
+            }
+        }
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            eprintln!("Error: The input ended before there were enough {}.", name);
+            process::exit(1)
+        }
+        if let Err(err) = rolls.add(&line) {
+            say!("{} That line wasn't counted; please enter it again.", err);
+        }
+    }
+    rolls.entropy().unwrap()
+}
+
+// The wizard's other path, an existing mnemonic; the words say how many there are.
+fn recover() {
+    let passphrase = get_passphrase();
+    let terms = get_mnemonic_terms();
+    let language = phrase_language(&terms);
+    let config = Config::from_phrase(terms, passphrase).unwrap_or_else(|err| {
+        eprintln!("Error: {}", i18n::localize(err.message(), language));
+        process::exit(1)
+    });
+    print_seed_and_root_key(config)
+}
+
+// The seed alone, as the last line of the output:
+fn seed() {
+    use std::io::Write;
+
+    let seed = get_existing_seed("seed");
+    let mut out = secret_output();
+    let written = if output() == Output::Json {
+        let document = serde_json::json!({ "seed": encoded_seed(&seed) });
+        writeln!(out, "{}", schema::versioned(document))
+    } else {
+        // (no newline after raw bytes, which would only be one more byte to strip off)
+        seed.write_to(&mut out, encoding())
+            .and_then(|()| match encoding() {
+                Encoding::Binary => Ok(()),
+                _ => writeln!(out),
+            })
+    };
+    finish_secret_output(out, written);
+}
+
+// Nothing is derived, so no passphrase is asked for: only whether the words make a mnemonic, and
+// if not, which word to check.
+fn validate() {
+    use bip_39::Error;
+
+    let terms = get_mnemonic_terms();
+    let err = match terms.parse::<Mnemonic>() {
+        Ok(mnemonic) => {
+            println!(
+                "Valid: {} words from the {} wordlist.",
+                mnemonic.words().len(),
+                mnemonic.language()
+            );
+            return;
+        }
+        Err(err) => err,
+    };
+    let message = i18n::localize(err.message(), phrase_language(&terms));
+    match err {
+        Error::UnknownWord { index, .. } | Error::NoCloseWord { index, .. } => {
+            eprintln!("Error: {} (word {})", message, index + 1)
+        }
+        _ => eprintln!("Error: {}", message),
+    }
+    process::exit(1)
+}
+
+// For a phrase drawn by hand, one word short: any of these finishes it, so the last one can be
+// drawn (or picked) from them.
+fn complete() {
+    use bip_39::Error;
+
+    say!("In one line, separated by spaces, please enter all but the last of your mnemonic terms:");
+    let mut terms = String::new();
+    io::stdin()
+        .read_line(&mut terms)
+        .expect("Failed to read mnemonic terms");
+    let terms = bip_39::normalize_mnemonic(&terms);
+    let partial: Vec<&str> = terms.split(' ').filter(|word| !word.is_empty()).collect();
+
+    let words = Mnemonic::valid_last_words(&partial, language()).unwrap_or_else(|err| {
+        let message = i18n::localize(err.message(), language());
+        match err {
+            Error::UnknownWord { index, .. } => {
+                eprintln!("Error: {} (word {})", message, index + 1)
+            }
+            _ => eprintln!("Error: {}", message),
+        }
+        process::exit(1)
+    });
+    if output() == Output::Json {
+        println!(
+            "{}",
+            schema::versioned(serde_json::json!({ "words": words }))
+        );
+        return;
+    }
+    say!(
+        "Any of these {} words makes word {} with a valid checksum:",
+        words.len(),
+        partial.len() + 1
+    );
+    for word in words {
+        println!("{}", word);
+    }
+}
+
+// Every candidate is a mnemonic, so they're printed only when there's no target to narrow them
+// down to the one wallet with.
+fn recover_search(
+    fingerprint: Option<&str>,
+    address: Option<String>,
+    gap_limit: u32,
+    checkpoint_file: Option<&Path>,
+) {
+    use bip_39::Error;
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    say!(
+        "In one line, separated by spaces, please enter the words you have, with '?' for any you \
+          don't:"
+    );
+    let mut terms = String::new();
+    io::stdin()
+        .read_line(&mut terms)
+        .expect("Failed to read mnemonic terms");
+    let terms = bip_39::normalize_mnemonic(&terms);
+    let language = phrase_language(&terms);
+    let search = Search::new(&terms, language).unwrap_or_else(|err| {
+        let message = i18n::localize(err.message(), language);
+        match err {
+            Error::UnknownWord { index, .. } | Error::NoCloseWord { index, .. } => {
+                eprintln!("Error: {} (word {})", message, index + 1)
+            }
+            _ => eprintln!("Error: {}", message),
+        }
+        process::exit(1)
+    });
+    let target = match (fingerprint, address) {
+        (Some(fingerprint), _) => {
+            let fingerprint = hex::decode(fingerprint)
+                .ok()
+                .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+                .unwrap_or_else(|| {
+                    eprintln!("Error: A fingerprint is 8 hex digits.");
+                    process::exit(1)
+                });
+            Some(Target::Fingerprint(fingerprint))
+        }
+        (None, Some(address)) => Some(Target::Address { address, gap_limit }),
+        (None, None) => None,
+    };
+    if checkpoint_file.is_some() && target.is_none() {
+        eprintln!("Error: --checkpoint is for searches with --fingerprint or --address.");
+        process::exit(1)
+    }
+    // A checkpoint file that's there already is the search to carry on with:
+    let resume = checkpoint_file
+        .and_then(|file| fs::read_to_string(file).ok())
+        .map(|text| {
+            text.parse::<Checkpoint>().unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1)
+            })
+        });
+    eprintln!(
+        "{} combinations of words to go through.",
+        search.combinations()
+    );
+
+    if let Some(checkpoint) = &resume {
+        eprintln!(
+            "Carrying on from the checkpoint, {} candidates in.",
+            checkpoint.checked
+        );
+    }
+
+    // A wallet search's matches are printed as they turn up (but all together in JSON):
+    let printing_matches = target.is_some() && output() != Output::Json;
+    let found: Vec<Mnemonic> = match &target {
+        None => search.candidates().collect(),
+        Some(target) => {
+            let passphrase = get_passphrase();
+            let mut last_written = Instant::now();
+            let found = search.find_from(
+                target,
+                &passphrase,
+                resume.as_ref(),
+                &mut |checkpoint, matches| {
+                    if printing_matches {
+                        for mnemonic in matches {
+                            eprintln!();
+                            println!("{}", mnemonic.phrase());
+                        }
+                    }
+                    eprint!("\rChecked {} candidates...", checkpoint.checked);
+                    if let Some(file) = checkpoint_file {
+                        if last_written.elapsed() >= Duration::from_secs(30) {
+                            write_checkpoint(file, checkpoint);
+                            last_written = Instant::now();
+                        }
+                    }
+                },
+            );
+            eprintln!();
+            let found = found.unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1)
+            });
+            // Finished, so there's nothing to carry on with:
+            if let Some(file) = checkpoint_file {
+                let _ = fs::remove_file(file);
+            }
+            found
+        }
+    };
+
+    let phrases: Vec<String> = found.iter().map(Mnemonic::phrase).collect();
+    if output() == Output::Json {
+        println!(
+            "{}",
+            schema::versioned(serde_json::json!({ "mnemonics": phrases }))
+        );
+    } else if !printing_matches {
+        for phrase in &phrases {
+            println!("{}", phrase);
+        }
+    }
+    let found_before = resume.map_or(0, |checkpoint| checkpoint.found as usize);
+    if found_before > 0 {
+        say!(
+            "({} found before the checkpoint were printed by the search that found them.)",
+            found_before
+        );
+    }
+    match (&target, found_before + phrases.len()) {
+        (Some(_), 0) => {
+            eprintln!("None of them is that wallet (with that passphrase).");
+            process::exit(1)
+        }
+        (Some(_), 1) => say!("That's the wallet."),
+        (Some(_), count) => say!("{} of them are that wallet.", count),
+        (None, count) => say!("{} candidates have a valid checksum.", count),
+    }
+}
+
+// Written beside the file and renamed over it, so a search stopped mid-write leaves the last
+// checkpoint whole:
+fn write_checkpoint(file: &Path, checkpoint: &Checkpoint) {
+    use std::fs;
+
+    let mut partial = file.as_os_str().to_owned();
+    partial.push(".partial");
+    let written =
+        fs::write(&partial, checkpoint.to_string()).and_then(|_| fs::rename(&partial, file));
+    if written.is_err() {
+        eprintln!("\nWarning: Unable to write the checkpoint file.");
+    }
+}
+
+// The shares hold the mnemonic's entropy, so 'combine' gives back the same words (in the same
+// '--language') and, with the same BIP-39 passphrase, the same wallet.  The SLIP-39 passphrase is
+// left empty: the BIP-39 one is still there for that.
+fn split(groups: &[Group], group_threshold: u8) {
+    let mnemonic = get_mnemonic_terms()
+        .parse::<Mnemonic>()
+        .unwrap_or_else(|err| {
+            eprintln!("Error: {}", i18n::localize(err.message(), language()));
+            process::exit(1)
+        });
+    let split =
+        slip39::split(&mnemonic.entropy(), "", group_threshold, groups, 1).unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        });
+
+    if output() == Output::Json {
+        let groups: Vec<serde_json::Value> = split
+            .iter()
+            .zip(groups)
+            .map(|(shares, group)| {
+                let shares: Vec<String> = shares.iter().map(|share| share.to_mnemonic()).collect();
+                serde_json::json!({ "threshold": group.threshold, "shares": shares })
+            })
+            .collect();
+        let document = serde_json::json!({ "group_threshold": group_threshold, "groups": groups });
+        println!("{}", schema::versioned(document));
+        return;
+    }
+    say!(
+        "It takes {} of the {} groups, each with its own threshold:",
+        group_threshold,
+        groups.len()
+    );
+    for (index, (shares, group)) in split.iter().zip(groups).enumerate() {
+        println!(
+            "group {} ({} of its {} shares):",
+            index + 1,
+            group.threshold,
+            group.count
+        );
+        for (number, share) in shares.iter().enumerate() {
+            println!("  share {}: {}", number + 1, share.to_mnemonic());
+        }
+    }
+}
+
+fn combine(format: &MnemonicFormat) {
+    say!("Please enter the shares one to a line, and an empty line after the last:");
+    let mut shares = Vec::new();
+    for line in io::stdin().lock().lines() {
+        let line = line.expect("Failed to read a share");
+        if line.trim().is_empty() {
+            break;
+        }
+        shares.push(line.parse::<Share>().unwrap_or_else(|err| {
+            eprintln!("Error: {} (share {})", err, shares.len() + 1);
+            process::exit(1)
+        }));
+    }
+
+    let entropy = slip39::combine(&shares, "").unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    });
+    let mnemonic = Mnemonic::from_entropy_bytes(&entropy, language()).unwrap_or_else(|err| {
+        eprintln!("Error: {}", i18n::localize(err.message(), language()));
+        process::exit(1)
+    });
+    if output() == Output::Json {
+        let document = serde_json::json!({ "mnemonic": mnemonic.phrase() });
+        println!("{}", schema::versioned(document));
+    } else {
+        print_mnemonic(&mnemonic.phrase(), format);
+    }
+}
+
+fn seed_qr(compact: bool, qr: bool) {
+    let mnemonic = get_mnemonic_terms()
+        .parse::<Mnemonic>()
+        .unwrap_or_else(|err| {
+            eprintln!("Error: {}", i18n::localize(err.message(), language()));
+            process::exit(1)
+        });
+    let (name, payload, code) = if compact {
+        let bytes = seedqr::compact(&mnemonic);
+        ("compact_seedqr", hex::encode(&bytes), QrCode::bytes(&bytes))
+    } else {
+        let digits = seedqr::standard(&mnemonic);
+        let code = QrCode::numeric(&digits);
+        ("seedqr", digits, code)
+    };
+
+    if output() == Output::Json {
+        println!(
+            "{}",
+            schema::versioned(serde_json::json!({ name: payload }))
+        );
+        return;
+    }
+    if compact {
+        println!("your Compact SeedQR (as hex): {}", payload);
+    } else {
+        println!("your SeedQR: {}", payload);
+    }
+    if qr {
+        // (every mnemonic fits a version 3 code, the largest 24 words need)
+        print!("{}", code.expect("The SeedQR didn't fit.").to_terminal());
+    }
+}
+
+// The words of one list for another's, index for index.  Whoever restores the new phrase gets
+// another wallet, since PBKDF2 hashes the words, so that's said alongside it (on standard error,
+// to stay out of what's piped on).
+fn translate(to: Language, format: &MnemonicFormat) {
+    let terms = get_mnemonic_terms();
+    let mnemonic = terms.parse::<Mnemonic>().unwrap_or_else(|err| {
+        eprintln!(
+            "Error: {}",
+            i18n::localize(err.message(), phrase_language(&terms))
+        );
+        process::exit(1)
+    });
+    let translated = mnemonic.translate(to).unwrap_or_else(|err| {
+        eprintln!(
+            "Error: {}",
+            i18n::localize(err.message(), mnemonic.language())
+        );
+        process::exit(1)
+    });
+
+    if output() == Output::Json {
+        let document = serde_json::json!({
+            "mnemonic": translated.phrase(),
+            "language": to.to_string(),
+            "from": mnemonic.language().to_string(),
+        });
+        println!("{}", schema::versioned(document));
+    } else {
+        print_mnemonic(&translated.phrase(), format);
+    }
+    eprintln!(
+        "Warning: The {} words restore a different wallet from the {} ones; keep those for any \
+         funds already sent to it.",
+        to,
+        mnemonic.language()
+    );
+}
+
+fn seed_xor(action: SeedXorAction, format: &MnemonicFormat) {
+    match action {
+        SeedXorAction::Split { parts } => {
+            let mnemonic = get_mnemonic_terms()
+                .parse::<Mnemonic>()
+                .unwrap_or_else(|err| {
+                    eprintln!("Error: {}", i18n::localize(err.message(), language()));
+                    process::exit(1)
+                });
+            let parts = seed_xor::split(&mnemonic, parts).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1)
+            });
+            if output() == Output::Json {
+                let parts: Vec<String> = parts.iter().map(Mnemonic::phrase).collect();
+                println!(
+                    "{}",
+                    schema::versioned(serde_json::json!({ "parts": parts }))
+                );
+                return;
+            }
+            say!("It takes all {} parts, in any order:", parts.len());
+            for (number, part) in parts.iter().enumerate() {
+                println!("part {}: {}", number + 1, part.phrase());
+            }
+        }
+        SeedXorAction::Combine => {
+            say!("Please enter the parts one to a line, and an empty line after the last:");
+            let mut parts = Vec::new();
+            for line in io::stdin().lock().lines() {
+                let line = line.expect("Failed to read a part");
+                if line.trim().is_empty() {
+                    break;
+                }
+                let part = Mnemonic::parse(&bip_39::normalize_mnemonic(&line), language());
+                parts.push(part.unwrap_or_else(|err| {
+                    let message = i18n::localize(err.message(), language());
+                    eprintln!("Error: {} (part {})", message, parts.len() + 1);
+                    process::exit(1)
+                }));
+            }
+
+            let mnemonic = seed_xor::combine(&parts).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1)
+            });
+            if output() == Output::Json {
+                let document = serde_json::json!({ "mnemonic": mnemonic.phrase() });
+                println!("{}", schema::versioned(document));
+            } else {
+                print_mnemonic(&mnemonic.phrase(), format);
+            }
+        }
+    }
+}
+
+// The walk-through is only ever on a mnemonic made up for it (with no passphrase), which is
+// thrown away afterwards; '--unsafe-explain' is for tracing a real one.
+fn explain() {
+    println!("What size mnemonic should the example use? [12, 15, 18, 21, or 24]");
+    let mnemonic_list_size = get_mnemonic_list_size();
+    let language = get_language();
+    let config = Config::generate_in(language, mnemonic_list_size, String::new());
+
+    println!("A throwaway mnemonic, just for this walk-through (don't use it for a wallet):");
+    println!("{}\n", config.mnemonic);
+    match explain::trace(&config, language) {
+        Ok(trace) => println!("{}", trace),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+// On one line as it always was, or under the heading when the format wraps it.  People reading
+// it get a note on the checksum word too; the plain form stays just the words.
+fn print_mnemonic(mnemonic: &str, format: &MnemonicFormat) {
+    if format.paged {
+        return page_mnemonic(mnemonic, format);
+    }
+
+    let rendered = format.render(mnemonic);
+    if rendered.contains('\n') {
+        println!("your BIP-39 mnemonic:\n{}", rendered);
+    } else {
+        println!("your BIP-39 mnemonic: {}", rendered);
+    }
+
+    if format.highlight_checksum {
+        let words: Vec<&str> = mnemonic.split_whitespace().collect();
+        println!(
+            "(the last word, \"{}\", ends in a {} bit checksum of the others, so changing any of them \
+             makes it wrong)",
+            words.last().unwrap_or(&""),
+            display::checksum_bits(words.len())
+        );
+    }
+}
+
+// Enter's echo moves the cursor down a line, so that's the line to go back up to and clear before
+// the next page goes in the same place:
+fn page_mnemonic(mnemonic: &str, format: &MnemonicFormat) {
+    use std::io::Write;
+
+    let pages = format.pages(mnemonic);
+    println!("your BIP-39 mnemonic, a page at a time (press Enter for the next):");
+    for (i, page) in pages.iter().enumerate() {
+        print!("{}   ({} of {})", page, i + 1, pages.len());
+        io::stdout().flush().unwrap();
+        let mut response = String::new();
+        io::stdin()
+            .read_line(&mut response)
+            .expect("Failed to read response.");
+        print!("\x1B[1A\x1B[2K");
+    }
+    println!("(that was the last of {} pages)", pages.len());
+}
+
+// A new mnemonic is shown (and its backup checked) before anything derived from it; with '--json'
+// it's in the document instead, for a script rather than someone with a pen.
+fn show_new_mnemonic(
+    mnemonic: &str,
+    language: Language,
+    format: &MnemonicFormat,
+    backup_check: BackupCheck,
+) {
+    // (nobody wrote it down, so there's no backup to check)
+    if let Some(path) = MNEMONIC_FILE.get().cloned().flatten() {
+        use std::io::Write;
+
+        let written = create_private_file(&path)
+            .and_then(|mut file| writeln!(file, "{}", format.render(mnemonic)));
+        if written.is_err() {
+            eprintln!("Error: Unable to write the mnemonic to {}.", path.display());
+            process::exit(1)
+        }
+        say!("your BIP-39 mnemonic is in {}", path.display());
+        return;
+    }
+    if output() == Output::Json {
+        return;
+    }
+    print_mnemonic(mnemonic, format);
+    match backup_check {
+        BackupCheck::Skip => {}
+        BackupCheck::Retype => check_written_backup(mnemonic, language),
+        BackupCheck::Quiz(words) => quiz_written_backup(mnemonic, language, words),
+    }
+}
+
+// The mnemonic has to come back from paper, not from the screen:
+fn clear_for_backup_check() {
+    say!("Write the mnemonic down, then press Enter; the screen will be cleared.");
+    let mut response = String::new();
+    io::stdin()
+        .read_line(&mut response)
+        .expect("Failed to read response.");
+    print!("\x1B[2J\x1B[H");
+}
+
+// A backup that can't reproduce the wallet is worse than none, so the new mnemonic has to come
+// back from paper before the seed is printed: the screen's cleared first so it can't just be
+// copied again.  Words may be cut to any prefix that's still unambiguous (four letters in English).
+fn check_written_backup(mnemonic: &str, language: Language) {
+    clear_for_backup_check();
+
+    let words: Vec<&str> = mnemonic.split(' ').collect();
+    loop {
+        say!("Type the mnemonic back in from your backup, in one line:");
+        let mut typed = String::new();
+        let read = io::stdin()
+            .read_line(&mut typed)
+            .expect("Failed to read mnemonic terms.");
+        if read == 0 {
+            eprintln!("Abort.");
+            process::exit(1)
+        }
+
+        let typed = bip_39::normalize_mnemonic(&typed);
+        let typed: Vec<&str> = typed.split(' ').filter(|word| !word.is_empty()).collect();
+        if typed.len() != words.len() {
+            eprintln!(
+                "Error: That's {} words rather than {}.  Please try again.",
+                typed.len(),
+                words.len()
+            );
+            continue;
+        }
+
+        let wrong: Vec<String> = (1..)
+            .zip(typed.iter().zip(&words))
+            .filter(|&(_, (&typed, &word))| language.complete(typed) != Some(word))
+            .map(|(position, _)| position.to_string())
+            .collect();
+        if wrong.is_empty() {
+            say!("Your backup matches.");
+            return;
+        }
+        let (noun, verb) = if wrong.len() == 1 {
+            ("Word", "doesn't")
+        } else {
+            ("Words", "don't")
+        };
+        eprintln!(
+            "Error: {} {} {} match the mnemonic.  Please check the backup and try again.",
+            noun,
+            wrong.join(", "),
+            verb
+        );
+    }
+}
+
+// '--quiz': only a few of the words, at random positions, asked for one at a time until each is
+// answered right (with the same prefixes allowed as above).
+fn quiz_written_backup(mnemonic: &str, language: Language, words: usize) {
+    // (it was only just made, in 'language')
+    let mnemonic = Mnemonic::parse(mnemonic, language).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    });
+    let challenge = bip_39::confirmation_challenge(&mnemonic, words);
+    clear_for_backup_check();
+
+    for &position in challenge.positions() {
+        loop {
+            say!("What is word #{} of your backup?", position);
+            let mut answer = String::new();
+            let read = io::stdin()
+                .read_line(&mut answer)
+                .expect("Failed to read response.");
+            if read == 0 {
+                eprintln!("Abort.");
+                process::exit(1)
+            }
+            if challenge.check(position, &answer) {
+                break;
+            }
+            eprintln!(
+                "Error: That isn't word #{}.  Please check the backup and try again.",
+                position
+            );
+        }
+    }
+    say!("Your backup matches.");
+}
+
+fn print_seed_and_root_key(config: Config) {
+    use std::io::Write;
+
+    // The seed stays as bytes all the way to the root key; it only becomes hex when printed:
+    let seed = config.to_seed();
+
+    let root_key = seed.to_root_key(network()).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    });
+    let mut out = secret_output();
+    if output() == Output::Json {
+        let written = print_json(&mut out, &config, &seed, &root_key);
+        return finish_secret_output(out, written);
+    }
+
+    let written = (|| {
+        write!(out, "your BIP-39 seed: ")?;
+        seed.write_to(&mut out, encoding())?;
+        write!(out, "\nyour BIP-32 root key: ")?;
+        root_key.write_to(&mut out)?;
+        writeln!(
+            out,
+            "\nyour BIP-32 root xpub: {}",
+            root_key.to_extended_pub_key()
+        )
+    })();
+    finish_secret_output(out, written)
+}
+
+// '--json': what the lines above say, and the entropy and fingerprint they leave out, in one
+// versioned document, so scripts needn't pick them out of the text.
+fn print_json(
+    out: &mut impl io::Write,
+    config: &Config,
+    seed: &Seed,
+    root_key: &ExtendedPrivKey,
+) -> io::Result<()> {
+    // (the phrase has already been checked, in whichever wordlist it's in)
+    let mnemonic = config.mnemonic.parse::<Mnemonic>().unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    });
+    let document = serde_json::json!({
+        "mnemonic": config.mnemonic.as_str(),
+        "language": mnemonic.language().to_string(),
+        "entropy": hex::encode(mnemonic.entropy()),
+        "seed": encoded_seed(seed),
+        "network": network().to_string(),
+        "fingerprint": hex::encode(root_key.fingerprint()),
+        "xprv": root_key.to_string(),
+        "xpub": root_key.to_extended_pub_key().to_string(),
+    });
+    writeln!(out, "{}", schema::versioned(document))
+}
+
+// The seed as '--encoding' has it, for JSON (where binary was refused):
+fn encoded_seed(seed: &Seed) -> String {
+    let mut encoded = Vec::new();
+    let _ = seed.write_to(&mut encoded, encoding());
+    String::from_utf8_lossy(&encoded).into_owned()
+}
+
+fn print_preview(config: Config) {
+    let result = config
+        .to_seed()
+        .to_root_key(network())
+        .map_err(|err| err.message())
+        .and_then(|root| preview::preview(&root));
+    match result {
+        Ok(preview) => {
+            println!("master fingerprint: {}", hex::encode(preview.fingerprint));
+            println!("account xpub: {}", preview.account.key_expression());
+            println!("first address: {}", preview.first_address);
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+// Recreate the root key from previously generated terms and print the chosen export format.
+fn export(
+    format: &str,
+    account: u32,
+    script_types: &[ScriptType],
+    accounts: u32,
+    path: Option<&DerivationPath>,
+) {
+    let root = get_existing_root_key("export");
+
+    let output = Registry::builtin().export(
+        format,
+        &ExportRequest {
+            root: &root,
+            account,
+            script_types,
+            accounts,
+            path,
+        },
+    );
+
+    match output {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+fn multisig(
+    threshold: usize,
+    cosigners: &[String],
+    account: u32,
+    format: MultisigFormat,
+    name: &str,
+) {
+    let root = get_existing_root_key("multisig key");
+
+    let result = Cosigner::derive(&root, account).and_then(|own| {
+        println!("This wallet's key, for the other cosigners: {}", own);
+        let mut keys = vec![own];
+        for cosigner in cosigners {
+            keys.push(cosigner.parse()?);
+        }
+        let wallet = Multisig::new(threshold, keys)?;
+
+        match format {
+            MultisigFormat::Descriptors => Ok(format!(
+                "receive: {}\nchange: {}\nboth: {}\n",
+                wallet.descriptor(false),
+                wallet.descriptor(true),
+                wallet.multipath_descriptor()
+            )),
+            MultisigFormat::Bip388 => {
+                let policy = WalletPolicy::multisig(name, &wallet).to_json();
+                Ok(format!(
+                    "{}\n",
+                    serde_json::to_string_pretty(&policy).unwrap()
+                ))
+            }
+            MultisigFormat::Coldcard => wallet.setup_file(name),
+        }
+    });
+
+    match result {
+        Ok(output) => print!("{}", output),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+// The empty passphrase wallet is the decoy; every passphrase entered after it opens another wallet.
+fn decoy(labels: &[String], keys: bool) {
+    let mnemonic = get_mnemonic_terms();
+
+    say!("Enter each passphrase to compare against the empty one; leave it empty when done.");
+    let mut passphrases = Vec::new();
+    loop {
+        let passphrase = get_passphrase();
+        if passphrase.is_empty() {
+            break;
+        }
+        passphrases.push(passphrase);
+    }
+
+    let wallets = decoy::wallets(&mnemonic, &passphrases).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    });
+
+    let name = |i: usize| match (i, labels.get(i.wrapping_sub(1))) {
+        (0, _) => "no passphrase".to_string(),
+        (_, Some(label)) => label.clone(),
+        (_, None) => format!("passphrase #{}", i),
+    };
+    if output() == Output::Json {
+        let wallets: Vec<serde_json::Value> = wallets
+            .iter()
+            .enumerate()
+            .map(|(i, wallet)| {
+                let mut json = serde_json::json!({
+                    "label": name(i),
+                    "fingerprint": hex::encode(wallet.fingerprint),
+                    "first_address": wallet.first_address,
+                });
+                if keys {
+                    json["seed"] = wallet.seed.to_hex().into();
+                    json["root_key"] = wallet.root_key.to_string().into();
+                }
+                json
+            })
+            .collect();
+        println!(
+            "{}",
+            schema::versioned(serde_json::json!({ "wallets": wallets }))
+        );
+    } else {
+        for (i, wallet) in wallets.iter().enumerate() {
+            println!(
+                "{}: fingerprint {}, first address {}",
+                name(i),
+                hex::encode(wallet.fingerprint),
+                wallet.first_address
+            );
+            if keys {
+                println!("  its BIP-39 seed: {}", wallet.seed);
+                println!("  its BIP-32 root key: {}", wallet.root_key);
+            }
+        }
+    }
+    if labels.len() >= wallets.len() {
+        eprintln!("Warning: there are more labels than passphrases.");
+    }
+
+    // A repeated fingerprint means two passphrases open the same wallet:
+    for (i, wallet) in wallets.iter().enumerate() {
+        if wallets[..i]
+            .iter()
+            .any(|other| other.fingerprint == wallet.fingerprint)
+        {
+            eprintln!("Warning: {} opens a wallet listed above it.", name(i));
+        }
+    }
+}
+
+fn bip85(application: Bip85Application) {
+    let root = get_existing_root_key("BIP-85 master");
+
+    let result = match application {
+        Bip85Application::Mnemonic { words, index } => MnemonicListSize::from(words)
+            .and_then(|size| bip85::mnemonic(&root, language(), size, index))
+            .map(|mnemonic| println!("your BIP-85 mnemonic #{}: {}", index, mnemonic.phrase())),
+        Bip85Application::Wif { index } => {
+            bip85::wif(&root, index).map(|wif| println!("your BIP-85 WIF key #{}: {}", index, wif))
+        }
+        Bip85Application::Xprv { index } => bip85::xprv(&root, index)
+            .map(|xprv| println!("your BIP-85 extended private key #{}: {}", index, xprv)),
+        Bip85Application::Hex { num_bytes, index } => bip85::hex(&root, num_bytes, index)
+            .map(|entropy| println!("your BIP-85 entropy #{}: {}", index, entropy)),
+        Bip85Application::Password {
+            length,
+            base85,
+            index,
+        } => {
+            let password = if base85 {
+                bip85::password_base85(&root, length, index)
+            } else {
+                bip85::password_base64(&root, length, index)
+            };
+            password.map(|password| println!("your BIP-85 password #{}: {}", index, password))
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+// 'wif' is whether to show the WIF too, and if so, whether it's flagged compressed.
+fn derive(
+    key: Option<&str>,
+    path: &str,
+    script_type: ScriptType,
+    count: Option<u32>,
+    wif: Option<bool>,
+) {
+    let result = derive_key(key, path).and_then(|(xprv, xpub)| {
+        let count = match count {
+            Some(count) => count,
+            None => {
+                match &xprv {
+                    Some(xprv) => println!("your derived key: {}", xprv),
+                    None => println!("your derived key: {}", xpub),
+                }
+                if let Some(compressed) = wif {
+                    let xprv = xprv
+                        .as_ref()
+                        .ok_or("A WIF needs a private key, not an xpub.")?;
+                    println!("its WIF: {}", xprv.to_wif(xprv.network, compressed));
+                }
+                println!("its public key: {}", hex::encode(xpub.public_key()));
+                let address =
+                    address::for_script_type(script_type, &xpub.public_key(), xpub.network)?;
+                println!("its {} address: {}", script_type, address);
+                return Ok(());
+            }
+        };
+
+        // (normal children, so those of an xpub work too)
+        let path = path.trim_end_matches('/');
+        for index in 0..count {
+            let child = xpub.derive_child(ChildNumber::normal(index)?)?;
+            let address =
+                address::for_script_type(script_type, &child.public_key(), child.network)?;
+            if path.is_empty() {
+                println!("{}: {}", index, address);
+            } else {
+                println!("{}/{}: {}", path, index, address);
+            }
+        }
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+// The key at 'path' (its xprv, unless it's derived from an xpub) and its xpub: relative to 'key'
+// when there is one, or from the mnemonic's root key (or '--xprv''s) down.  Private keys derive
+// to private keys; public keys only along normal steps.
+fn derive_key(
+    key: Option<&str>,
+    path: &str,
+) -> Result<(Option<ExtendedPrivKey>, ExtendedPubKey), &'static str> {
+    let xprv = match key {
+        Some(key) => {
+            let path = DerivationPath::relative(path)?;
+            match key.parse::<ExtendedPrivKey>() {
+                Ok(xprv) => xprv.derive_path(&path)?,
+                Err(_) => {
+                    let child = key.parse::<ExtendedPubKey>()?.derive_path(&path)?;
+                    return Ok((None, child));
+                }
+            }
+        }
+        None => {
+            // (checked before the mnemonic is asked for)
+            let path = path.parse::<DerivationPath>()?;
+            get_existing_root_key("derived key").derive_path(&path)?
+        }
+    };
+    let xpub = xprv.to_extended_pub_key();
+    Ok((Some(xprv), xpub))
+}
+
+// Nothing secret is printed: lines are reported by number, with fingerprints and addresses only.
+fn bulk_audit(file: &Path, script_type: Option<ScriptType>) {
+    let text = std::fs::read_to_string(file).unwrap_or_else(|_| {
+        eprintln!("Error: Unable to read the mnemonics file.");
+        process::exit(1)
+    });
+    println!("The same passphrase is tried with every mnemonic.");
+    let passphrase = get_passphrase();
+
+    let audits = bulk_audit::audit(&text, &passphrase, network(), script_type);
+    let mut valid = 0;
+    for audit in &audits {
+        match &audit.result {
+            Ok(wallet) => {
+                valid += 1;
+                print!(
+                    "line {}: {} ({})",
+                    audit.line,
+                    hex::encode(wallet.fingerprint),
+                    wallet.language
+                );
+                match &wallet.address {
+                    Some(address) => println!(" {}", address),
+                    None => println!(),
+                }
+            }
+            Err(err) => println!("line {}: invalid: {}", audit.line, err),
+        }
+    }
+    println!("{} of {} mnemonics are valid.", valid, audits.len());
+}
+
+// The vectors are public, so a failure is shown in full, for a bug report.
+fn self_test() {
+    let version = env!("CARGO_PKG_VERSION");
+    let result = bip_39::verify_implementation();
+    if output() == Output::Json {
+        let report = match &result {
+            Ok(count) => {
+                serde_json::json!({ "version": version, "passed": true, "vectors": count })
+            }
+            Err(mismatch) => {
+                let mut failure = serde_json::json!({
+                    "spec": mismatch.spec,
+                    "input": mismatch.input,
+                    "step": mismatch.step,
+                    "expected": mismatch.expected,
+                    "got": mismatch.got,
+                });
+                // (what BIP-39's input was called before BIP-32's vectors were checked too)
+                if mismatch.spec == "BIP-39" {
+                    failure["entropy"] = serde_json::json!(mismatch.input);
+                }
+                serde_json::json!({ "version": version, "passed": false, "failure": failure })
+            }
+        };
+        println!("{}", schema::versioned(report));
+        if result.is_err() {
+            process::exit(1)
+        }
+        return;
+    }
+    match result {
+        Ok(count) => say!(
+            "bip-39 {}: all {} BIP-39 and BIP-32 test vectors pass (mnemonic, entropy, seed, root key and derived keys).",
+            version,
+            count
+        ),
+        Err(mismatch) => {
+            eprintln!("Error: bip-39 {} got {}", version, mismatch);
+            process::exit(1)
+        }
+    }
+}
+
+fn vectors(entropy: &[String], count: usize, words: usize, passphrase: &str) {
+    let result = MnemonicListSize::from(words)
+        .map_err(|err| err.message())
+        .and_then(|_| {
+            let mut entropies = entropy
+                .iter()
+                .map(|hex| hex::decode(hex).map_err(|_| "Entropy must be hex."))
+                .collect::<Result<Vec<_>, _>>()?;
+            // Four bytes for every three words:
+            entropies.extend((0..count).map(|_| entropy::random::<32>()[..words * 4 / 3].to_vec()));
+            if entropies.is_empty() {
+                return Err("Give some --entropy, or a --count of random ones.");
+            }
+
+            let mut generated = Vec::new();
+            for entropy in &entropies {
+                generated.extend(vectors::generate(entropy, passphrase)?);
+            }
+            Ok(vectors::to_json(&generated, passphrase))
+        });
+
+    match result {
+        Ok(json) => println!("{}", serde_json::to_string_pretty(&json).unwrap()),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+// Steel plates and recovery sheets mostly number the words from 1; SeedQR and other encodings of
+// the 11-bit values count from 0, so that's an option rather than a guess.
+fn wordlist(query: Option<&str>, prefix: bool, zero_based: bool, language: Language) {
+    let first = if zero_based { 0 } else { 1 };
+    let print = |index: usize, word: &str| println!("{:>4}. {}", index + first, word);
+
+    let query = match query {
+        Some(query) => query.trim(),
+        None => {
+            for (index, word) in language.word_list().iter().enumerate() {
+                print(index, word);
+            }
+            return;
+        }
+    };
+    if let Ok(number) = query.parse::<usize>() {
+        match number
+            .checked_sub(first)
+            .and_then(|index| language.word_list().get(index))
+        {
+            Some(word) => print(number - first, word),
+            None => {
+                eprintln!("Error: Words are numbered {} to {}.", first, 2047 + first);
+                process::exit(1)
+            }
+        }
+        return;
+    }
+
+    let matches = language.search(&bip_39::normalize_mnemonic(query), prefix);
+    if matches.is_empty() {
+        eprintln!("Error: No word matches \"{}\".", query);
+        process::exit(1)
+    }
+    for (index, word) in matches {
+        print(index, word);
+    }
+}
+
+// Pasted one or more to a line (from a block explorer or an old wallet's history), up to an
+// empty line:
+fn get_addresses() -> Vec<String> {
+    println!("Paste the addresses to look for, then an empty line:");
+    let mut addresses = Vec::new();
+    loop {
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            return addresses;
+        }
+        addresses.extend(line.split_whitespace().map(str::to_string));
+    }
+}
+
+fn check_addresses(addresses: Vec<String>, accounts: u32, gap_limit: u32) {
+    // A mnemonic's root key is made for whichever network the addresses turn out to be on; an
+    // xprv comes with its own.
+    enum Existing {
+        Seed(Seed),
+        Xprv(ExtendedPrivKey),
+    }
+    let existing = if FROM_XPRV.load(Ordering::Relaxed) {
+        Existing::Xprv(get_existing_xprv("address check"))
+    } else {
+        Existing::Seed(get_existing_seed("address check"))
+    };
+    let addresses = if addresses.is_empty() {
+        get_addresses()
+    } else {
+        addresses
+    };
+    if addresses.is_empty() {
+        eprintln!("Error: No addresses to look for.");
+        process::exit(1)
+    }
+
+    // The addresses say which network to derive for:
+    let network = match address::network_of(&addresses[0]) {
+        Some(network) => network,
+        None => {
+            eprintln!("Error: {} isn't an address this tool knows.", addresses[0]);
+            process::exit(1)
+        }
+    };
+    if let Some(other) = addresses
+        .iter()
+        .find(|a| address::network_of(a) != Some(network))
+    {
+        eprintln!(
+            "Error: {} is for another network than {}.",
+            other, addresses[0]
+        );
+        process::exit(1)
+    }
+
+    let known: Vec<&str> = addresses.iter().map(String::as_str).collect();
+    let root = match existing {
+        Existing::Seed(seed) => seed.to_root_key(network).map_err(|err| err.message()),
+        Existing::Xprv(root) if root.network == network => Ok(root),
+        Existing::Xprv(_) => Err("The xprv is for another network than the addresses."),
+    };
+    let found = root.and_then(|root| address::find_known(&root, &known, accounts, gap_limit));
+    let found = match found {
+        Ok(found) => found,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    };
+
+    let mut matched = 0;
+    for address in &addresses {
+        match found
+            .iter()
+            .find(|record| record.address.eq_ignore_ascii_case(address))
+        {
+            Some(record) => {
+                matched += 1;
+                println!(
+                    "yes {} ({} at {})",
+                    address, record.script_type, record.path
+                )
+            }
+            None => println!("no  {}", address),
+        }
+    }
+    if matched == 0 {
+        println!(
+            "None of these addresses are this mnemonic's (with this passphrase) in the first {} \
+             addresses of {} account(s) of each script type.",
+            gap_limit, accounts
+        );
+        process::exit(1)
+    }
+    println!(
+        "{} of {} addresses are this mnemonic's: it's the right one.",
+        matched,
+        addresses.len()
+    );
+}
+
+fn contains_address(xpub: &str, address: &str, gap_limit: u32) {
+    let found = xpub
+        .parse::<ExtendedPubKey>()
+        .and_then(|xpub| xpub.contains_address(address, gap_limit));
+
+    match found {
+        Ok(Some(found)) => println!("{} is yours: {}", address, found),
+        Ok(None) => {
+            println!(
+                "{} isn't in the first {} receive or change addresses.",
+                address, gap_limit
+            );
+            process::exit(1)
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+fn watch_only(xpub: &str, script_type: ScriptType, origin: Option<&str>, action: WatchOnlyAction) {
+    let result = WatchOnlyWallet::import(xpub, script_type, origin).and_then(|wallet| {
+        match action {
+            WatchOnlyAction::Addresses {
+                change,
+                start,
+                count,
+            } => {
+                let end = start.checked_add(count).ok_or("Too many addresses.")?;
+                let records: Vec<_> = wallet
+                    .addresses(change, start..end)?
+                    .iter()
+                    .map(|record| record.to_json())
+                    .collect();
+                let output = schema::versioned(serde_json::json!({ "addresses": records }));
+                println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            }
+            WatchOnlyAction::Descriptors => {
+                println!("receive: {}", wallet.descriptor(false));
+                println!("change: {}", wallet.descriptor(true));
+                println!("both: {}", wallet.multipath_descriptor());
+            }
+            WatchOnlyAction::Sparrow => println!("{}", wallet.sparrow_wallet()),
+        }
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+fn sign(
+    path: &DerivationPath,
+    script_type: ScriptType,
+    format: Option<SignatureFormat>,
+    message: Option<String>,
+    file: Option<&Path>,
+) {
+    let format = format.unwrap_or(match script_type {
+        ScriptType::P2tr => SignatureFormat::Bip322,
+        _ => SignatureFormat::Bip137,
+    });
+    let message = match (message, file) {
+        (None, None) => Ok(get_message_to_sign().into_bytes()),
+        (message, file) => read_message(message, file),
+    };
+
+    let result = message.and_then(|message| {
+        let root = get_existing_root_key("signing key");
+        let key = root.derive_path(path)?;
+        let address = address::for_script_type(script_type, &key.public_key(), key.network)?;
+
+        // The private key is about to be used, so say exactly what for and let them back out:
+        println!(
+            "Signing {} bytes as {} ({}), with the {} key at {}.",
+            message.len(),
+            address,
+            format,
+            script_type,
+            path
+        );
+        if !get_confirmation("Type 'yes' to sign with this private key:") {
+            eprintln!("Abort.");
+            process::exit(1)
+        }
+
+        let signature = message::sign(&key.secret_key_bytes(), script_type, format, &message)?;
+        println!("your {} signature: {}", format, signature);
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+fn verify_message(address: &str, signature: &str, message: Option<String>, file: Option<&Path>) {
+    let verified = read_message(message, file).and_then(|message| {
+        let format = SignatureFormat::of(signature)?;
+        message::verify(address, &message, signature).map(|verified| (format, verified))
+    });
+
+    match verified {
+        Ok((format, true)) => println!("Good {} signature by {}.", format, address),
+        Ok((format, false)) => {
+            println!(
+                "The {} signature doesn't match {} and the message.",
+                format, address
+            );
+            process::exit(1)
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+// A message given on the command line, in a file, or else on standard input, byte for byte:
+fn read_message(message: Option<String>, file: Option<&Path>) -> Result<Vec<u8>, &'static str> {
+    use std::io::Read;
+
+    match (message, file) {
+        (Some(message), _) => Ok(message.into_bytes()),
+        (None, Some(file)) => std::fs::read(file).map_err(|_| "Unable to read the message file."),
+        (None, None) => {
+            let mut message = Vec::new();
+            io::stdin()
+                .read_to_end(&mut message)
+                .map_err(|_| "Unable to read the message.")?;
+            Ok(message)
+        }
+    }
+}
+
+fn payment_code(account: u32) {
+    let root = get_existing_root_key("payment code");
+
+    match bip47::payment_code(&root, account) {
+        Ok(payment_code) => println!("your BIP-47 payment code: {}", payment_code),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+fn lightning_keys(index: u32) {
+    let root = get_existing_root_key("node keys");
+
+    let result = lightning::node_id(&root).and_then(|node_id| {
+        println!("your LND node id: {}", hex::encode(node_id));
+        for family in KeyFamily::all().iter() {
+            let path = family.path(&root, index)?;
+            let key = root.derive_path(&path)?;
+            println!("{} ({}): {}", family, path, hex::encode(key.public_key()));
+        }
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+fn ssh(identity: &str, index: u32, output: Option<&Path>) {
+    let seed = get_existing_seed("SSH key");
+
+    let result = SshKey::derive(&seed, identity, index).and_then(|key| {
+        let public_key = key.to_openssh_public(identity);
+        let private_key = key.to_openssh_private(identity);
 
-    // 'let' declares variables:
-    let mnemonic_list_size = get_mnemonic_list_size();
+        match output {
+            Some(output) => {
+                write_private_file(output, &private_key)?;
+                let mut public_path = output.as_os_str().to_owned();
+                public_path.push(".pub");
+                std::fs::write(public_path, format!("{}\n", public_key))
+                    .map_err(|_| "Unable to write the public key file.")?;
+                println!("your SSH public key: {}", public_key);
+            }
+            None => {
+                println!("your SSH public key: {}", public_key);
+                print!("{}", private_key);
+            }
+        }
+        Ok(())
+    });
 
-    let is_new_seed = get_is_new_seed();
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
 
-    let passphrase = get_passphrase();
+fn pgp(user_id: &str, created: u32, output: Option<&Path>) {
+    let seed = get_existing_seed("PGP key");
 
-    // Just another way to declare a local variable; notice no use of parenthesis:
-    let config = if is_new_seed {
-        // Our first struct - 'Config'
-        let config = Config::generate_from(mnemonic_list_size, passphrase);
-        println!("your BIP-39 mnemonic: {}", config.mnemonic);
-        config
-    } else {
-        Config::new(get_mnemonic_terms(), mnemonic_list_size, passphrase)
+    let result = PgpKey::derive(&seed, created).and_then(|key| {
+        let private_key = key.to_armored_private(user_id);
+        println!(
+            "your PGP key fingerprint: {}",
+            hex::encode_upper(key.fingerprint())
+        );
+
+        match output {
+            Some(output) => write_private_file(output, &private_key),
+            None => {
+                print!("{}", private_key);
+                Ok(())
+            }
+        }
+    });
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+fn write_private_file(path: &Path, contents: &str) -> Result<(), &'static str> {
+    use std::io::Write;
+
+    create_private_file(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|_| "Unable to write the private key file.")
+}
+
+// ssh refuses private keys other users can read, and nobody else has any business reading a seed
+// or mnemonic either, so create the file as 0600 to begin with:
+fn create_private_file(path: &Path) -> io::Result<std::fs::File> {
+    use std::fs::OpenOptions;
+    #[cfg(unix)]
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options.open(path)
+}
+
+// Where the seed and root key are written: '--output''s file, or standard output.
+fn secret_output() -> Box<dyn io::Write> {
+    match OUTPUT_FILE.get().cloned().flatten() {
+        Some(path) => Box::new(create_private_file(&path).unwrap_or_else(|_| {
+            eprintln!("Error: Unable to create {}.", path.display());
+            process::exit(1)
+        })),
+        None => Box::new(io::stdout()),
+    }
+}
+
+// Flushed, so a full disk shows up here rather than as a file cut short without a word:
+fn finish_secret_output(mut out: Box<dyn io::Write>, written: io::Result<()>) {
+    if written.and_then(|()| out.flush()).is_err() {
+        eprintln!("Error: Unable to write the seed.");
+        process::exit(1)
+    }
+    if let Some(path) = OUTPUT_FILE.get().cloned().flatten() {
+        say!("(written to {})", path.display());
+    }
+}
+
+fn nostr(account: u32) {
+    let root = get_existing_root_key("Nostr key");
+
+    match nostr::Keys::derive(&root, account) {
+        Ok(keys) => {
+            println!("your Nostr public key: {}", keys.npub());
+            println!("your Nostr private key: {}", keys.nsec());
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+fn tezos(account: u32, fundraiser: Option<String>) {
+    let key = match fundraiser {
+        // (the email and password together are the BIP-39 passphrase)
+        Some(email) => {
+            println!("The fundraiser key will be derived from its 15 word mnemonic.");
+            let passphrase = email + &read_secret("Fundraiser password: ");
+            Config::from_phrase(get_mnemonic_terms(), passphrase)
+                .map(|config| TezosKey::fundraiser(&config.to_seed()))
+                .map_err(|err| err.message())
+        }
+        None => TezosKey::derive(&get_existing_seed("Tezos key"), account),
+    };
+
+    match key {
+        Ok(key) => {
+            println!("your Tezos address: {}", key.address());
+            println!("your Tezos public key: {}", key.public_key_string());
+            println!("your Tezos secret key: {}", key.secret_key_string());
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+// One JSON request per line in, one JSON response per line out, one connection at a time, until
+// a {"method": "close"} request (or Ctrl-C).  Nothing that comes back is secret, but which
+// addresses and xpubs belong together is, so the socket has to live in a directory only this user
+// can get into: checking that up front closes the gap between binding the socket and restricting
+// it.
+#[cfg(unix)]
+fn daemon(socket: Option<PathBuf>) {
+    use bip_39::session::Session;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let fail = |err: &str| -> ! {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    };
+
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR");
+    let socket = socket
+        .or_else(|| runtime_dir.map(|dir| Path::new(&dir).join("bip-39.sock")))
+        .unwrap_or_else(|| fail("No --socket given, and XDG_RUNTIME_DIR isn't set."));
+    let dir = match socket.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    match fs::metadata(dir) {
+        Ok(metadata) if metadata.permissions().mode() & 0o077 == 0 => {}
+        Ok(_) => fail("The socket's directory is open to other users; use a private one (700)."),
+        Err(_) => fail("Unable to read the socket's directory."),
+    }
+    // A socket left behind by a session that was killed is removed; a live one is left alone:
+    if let Ok(metadata) = fs::symlink_metadata(&socket) {
+        if !metadata.file_type().is_socket() || UnixStream::connect(&socket).is_ok() {
+            fail("The socket path is already in use.");
+        }
+        let _ = fs::remove_file(&socket);
+    }
+
+    let session = Session::new(get_existing_root_key("session"));
+    let listener =
+        UnixListener::bind(&socket).unwrap_or_else(|_| fail("Unable to create the socket."));
+    if fs::set_permissions(&socket, fs::Permissions::from_mode(0o600)).is_err() {
+        let _ = fs::remove_file(&socket);
+        fail("Unable to restrict the socket's permissions.");
+    }
+    println!(
+        "Listening on {}; send {{\"method\": \"close\"}} or press Ctrl-C to end the session.",
+        socket.display()
+    );
+
+    'session: for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let reader = BufReader::new(stream.try_clone().unwrap_or_else(|_| fail("Socket error.")));
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            let response = match serde_json::from_str::<serde_json::Value>(&line) {
+                Ok(ref request) if request["method"] == "close" => break 'session,
+                Ok(request) => session.respond(&request),
+                Err(_) => schema::versioned(
+                    serde_json::json!({ "error": "Requests are one JSON object per line." }),
+                ),
+            };
+            if writeln!(stream, "{}", response).is_err() {
+                break;
+            }
+        }
+    }
+
+    let _ = fs::remove_file(&socket);
+    println!("Session closed.");
+}
+
+#[cfg(feature = "keyring")]
+fn keyring(action: KeyringAction, label: &str, format: &MnemonicFormat) {
+    use bip_39::os_keyring;
+
+    let result = match action {
+        KeyringAction::Store => {
+            // Config::from_phrase checks the word count for us:
+            let config =
+                Config::from_phrase(get_mnemonic_terms(), String::new()).unwrap_or_else(|err| {
+                    eprintln!("Error: {}", err);
+                    process::exit(1)
+                });
+
+            say!("Storing a mnemonic on this machine turns it into a hot wallet; only do this for small test wallets.");
+            if !get_confirmation("Type 'yes' to store the mnemonic in the keyring:") {
+                eprintln!("Abort.");
+                process::exit(1)
+            }
+
+            os_keyring::store(
+                label,
+                &config.mnemonic,
+                &prompt_new_password("keyring password"),
+            )
+        }
+        KeyringAction::Load => os_keyring::retrieve(label, &read_secret("Keyring password: "))
+            .map(|mnemonic| print_mnemonic(&mnemonic, format)),
+        KeyringAction::Delete => {
+            if !get_confirmation("Type 'yes' to delete the mnemonic from the keyring:") {
+                eprintln!("Abort.");
+                process::exit(1)
+            }
+            os_keyring::delete(label)
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+#[cfg(feature = "age")]
+fn backup(action: BackupAction, format: &MnemonicFormat) {
+    use bip_39::age_backup;
+    use std::fs;
+
+    let result = match action {
+        BackupAction::Export { recipient, output } => {
+            Config::from_phrase(get_mnemonic_terms(), String::new())
+                .map_err(|err| err.message())
+                .and_then(|config| {
+                    if recipient.is_empty() {
+                        let passphrase = prompt_new_password("backup passphrase");
+                        age_backup::encrypt_with_passphrase(&config.mnemonic, &passphrase)
+                    } else {
+                        age_backup::encrypt_to_recipients(&config.mnemonic, &recipient)
+                    }
+                })
+                .and_then(|backup| {
+                    fs::write(&output, backup).map_err(|_| "Unable to write the backup file.")
+                })
+        }
+        BackupAction::Recover { input, identity } => {
+            fs::read(&input)
+                .map_err(|_| "Unable to read the backup file.")
+                .and_then(|backup| match identity {
+                    Some(identity) => fs::read_to_string(identity)
+                        .map_err(|_| "Unable to read the identity file.")
+                        .and_then(|identity| {
+                            age_backup::decrypt_with_identities(&backup, &identity)
+                        }),
+                    None => age_backup::decrypt_with_passphrase(
+                        &backup,
+                        &read_secret("Backup passphrase: "),
+                    ),
+                })
+                .and_then(|mnemonic| {
+                    // (checking the word count before the passphrase is asked for)
+                    let config = Config::from_phrase(mnemonic, "")?;
+                    print_mnemonic(&config.mnemonic, format);
+                    let passphrase = get_passphrase();
+                    print_seed_and_root_key(Config::from_phrase(
+                        config.mnemonic.clone(),
+                        passphrase,
+                    )?);
+                    Ok(())
+                })
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+#[cfg(feature = "vault")]
+fn vault(action: VaultAction, format: &MnemonicFormat) {
+    use bip_39::vault;
+    use std::fs;
+
+    let result = match action {
+        VaultAction::Create { output } => Config::from_phrase(get_mnemonic_terms(), String::new())
+            .map_err(|err| err.message())
+            .and_then(|config| {
+                vault::create(&config.mnemonic, &prompt_new_password("backup passphrase"))
+            })
+            .and_then(|file| {
+                fs::write(&output, file).map_err(|_| "Unable to write the vault file.")
+            }),
+        VaultAction::Open { input } => fs::read(&input)
+            .map_err(|_| "Unable to read the vault file.")
+            .and_then(|file| vault::open(&file, &read_secret("Backup passphrase: ")))
+            .and_then(|mnemonic| {
+                // (as for an age backup, the word count is checked before the passphrase is asked)
+                let config = Config::from_phrase(mnemonic, "")?;
+                print_mnemonic(&config.mnemonic, format);
+                let passphrase = get_passphrase();
+                print_seed_and_root_key(Config::from_phrase(config.mnemonic.clone(), passphrase)?);
+                Ok(())
+            }),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+fn keystore(action: KeystoreAction, format: &MnemonicFormat) {
+    use bip_39::keystore::{self, Contents};
+    use bip_39::Error;
+    use std::fs;
+
+    let result = match action {
+        KeystoreAction::Export { output, seed, kdf } => get_mnemonic_terms()
+            .parse::<Mnemonic>()
+            .map(|mnemonic| {
+                if seed {
+                    Contents::Seed(mnemonic.to_seed(get_passphrase()))
+                } else {
+                    Contents::Mnemonic(mnemonic)
+                }
+            })
+            .and_then(|contents| {
+                keystore::export_with(&contents, &prompt_new_password("backup passphrase"), &kdf)
+            })
+            .and_then(|file| {
+                fs::write(&output, file)
+                    .map_err(|_| Error::Other("Unable to write the keystore file."))
+            }),
+        KeystoreAction::Import { input } => fs::read_to_string(&input)
+            .map_err(|_| Error::Other("Unable to read the keystore file."))
+            .and_then(|file| keystore::import(&file, &read_secret("Backup passphrase: ")))
+            .and_then(|contents| match contents {
+                Contents::Mnemonic(mnemonic) => {
+                    print_mnemonic(&mnemonic.phrase(), format);
+                    let passphrase = get_passphrase();
+                    print_seed_and_root_key(Config::from_phrase(mnemonic.phrase(), passphrase)?);
+                    Ok(())
+                }
+                // (there's no mnemonic to print, nor a passphrase to ask for)
+                Contents::Seed(seed) => {
+                    let root_key = seed.to_root_key(network())?;
+                    println!("your BIP-39 seed: {}", seed);
+                    println!("your BIP-32 root key: {}", root_key);
+                    println!("your BIP-32 root xpub: {}", root_key.to_extended_pub_key());
+                    Ok(())
+                }
+            }),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
+
+// Standard input is where the mnemonic comes from too, so a message typed there is one line.
+fn get_message_to_sign() -> String {
+    println!("In one line, please enter the message to sign:");
+    let mut message = String::new();
+    io::stdin()
+        .read_line(&mut message)
+        .expect("Failed to read message.");
+
+    message.trim_end_matches(&['\r', '\n'][..]).to_string()
+}
+
+fn get_confirmation(question: &str) -> bool {
+    println!("{}", question);
+    let mut response = String::new();
+    io::stdin()
+        .read_line(&mut response)
+        .expect("Failed to read response.");
+
+    response.trim() == "yes"
+}
+
+fn bip38(action: Bip38Action) {
+    let result = match action {
+        Bip38Action::Encrypt { path, uncompressed } => {
+            let root = get_existing_root_key("key");
+            root.derive_path(&path)
+                .map_err(|err| err.message())
+                .and_then(|key| {
+                    let password = prompt_new_password("BIP-38 password");
+                    let encrypted =
+                        bip38::encrypt(&key.secret_key_bytes(), !uncompressed, &password)?;
+                    println!("your BIP-38 encrypted key for {}: {}", path, encrypted);
+                    Ok(())
+                })
+        }
+        Bip38Action::Decrypt { key } => bip38::decrypt(&key, &read_secret("BIP-38 password: "))
+            .map(|(secret_key, compressed)| {
+                println!("your private key: {}", hex::encode(secret_key));
+                println!("compressed public key: {}", compressed);
+            }),
     };
 
-    let seed = bip_39::generate_seed(config);
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    }
+}
 
-    println!("your BIP-39 seed: {}", seed);
+// Prompt for a previously generated mnemonic (and its passphrase) and recreate its seed.
+fn get_existing_seed(purpose: &str) -> Seed {
+    require_mnemonic();
+    say!("The {} will be derived from an existing mnemonic.", purpose);
+    let passphrase = get_passphrase();
+    let terms = get_mnemonic_terms();
+    let mnemonic = terms.parse::<Mnemonic>().unwrap_or_else(|err| {
+        eprintln!(
+            "Error: {}",
+            i18n::localize(err.message(), phrase_language(&terms))
+        );
+        process::exit(1)
+    });
+    mnemonic.to_seed(passphrase)
+}
 
-    let data = hex::decode(seed).unwrap();
+// The same, on to the BIP-32 root key, or with '--xprv' the root key as it was backed up.
+fn get_existing_root_key(purpose: &str) -> ExtendedPrivKey {
+    if FROM_XPRV.load(Ordering::Relaxed) {
+        return get_existing_xprv(purpose);
+    }
+    get_existing_seed(purpose)
+        .to_root_key(network())
+        .unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        })
+}
 
-    let root_key = bip_39::generate_root_key(&data);
+// Only a root key (depth 0) will do: everything derived from here starts at m, and an account
+// key taken for one would give a wallet that looks fine and matches nothing.  Keys below the root
+// go on with 'derive' instead.  (The network is the key's own: a tprv is a testnet wallet, unless
+// '--network' says it's signet's or regtest's, which share its version bytes.)
+fn get_existing_xprv(purpose: &str) -> ExtendedPrivKey {
+    say!(
+        "The {} will be derived from an existing root xprv.",
+        purpose
+    );
+    let mut root = read_secret("Root extended private key: ")
+        .trim()
+        .parse::<ExtendedPrivKey>()
+        .unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1)
+        });
+    if root.depth != 0 {
+        eprintln!(
+            "Error: That key is {} levels below the root; use 'derive --key' to go on from it.",
+            root.depth
+        );
+        process::exit(1)
+    }
+    if let Some(network) = NETWORK.get().cloned().flatten() {
+        if network.xprv_version() != root.network.xprv_version() {
+            eprintln!("Error: That key is for {}, not {}.", root.network, network);
+            process::exit(1)
+        }
+        root.network = network;
+    }
+    root
+}
 
-    println!("your BIP-32 root key: {}", root_key)
+// Seeds (for SLIP-0010 keys, say) and mnemonics (to store or check) can't be had from an xprv:
+// it's the end of a one-way derivation.
+fn require_mnemonic() {
+    if FROM_XPRV.load(Ordering::Relaxed) {
+        eprintln!(
+            "Error: This needs the mnemonic itself; an xprv doesn't have what it's made from."
+        );
+        process::exit(1)
+    }
 }
 
 fn get_mnemonic_terms() -> String {
-    println!("In one line, separated by single spaces, please enter your ordered mnemonic terms:");
+    require_mnemonic();
+    #[cfg(feature = "camera")]
+    if let Some(device) = SCAN.get().cloned().flatten() {
+        return scan_mnemonic_terms(&device);
+    }
+    say!("In one line, separated by spaces, please enter your ordered mnemonic terms:");
     let mut terms = String::new();
 
     io::stdin()
         .read_line(&mut terms)
         .expect("Failed to read mnemonic terms");
 
-    terms.trim().to_string()
+    // Pasted terms can carry invisible or full-width characters along with them:
+    let terms = correct_unknown_words(bip_39::normalize_mnemonic(&terms));
+    check_weakness(&terms);
+    terms
+}
+
+// '--scan': the phrase off the camera, in '--language''s list, or else English's, as SeedSigner
+// reads a SeedQR's indices.  A scanned code has been checked for misreads, so only the weakness
+// check a typed phrase gets is left.
+#[cfg(feature = "camera")]
+fn scan_mnemonic_terms(device: &Path) -> String {
+    use std::time::Duration;
+
+    let language = LANGUAGE
+        .get()
+        .cloned()
+        .flatten()
+        .unwrap_or(Language::English);
+    say!("Hold the SeedQR or crypto-seed code up to the camera (a minute at most)...");
+    match bip_39::scan::scan(device, language, Duration::from_secs(60)) {
+        Ok(mnemonic) => {
+            let terms = mnemonic.phrase();
+            check_weakness(&terms);
+            terms
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err.message());
+            process::exit(1)
+        }
+    }
+}
+
+// A typo would otherwise only come back as an error further on, or (should the checksum happen to
+// match) as somebody else's wallet; so each word the wordlist doesn't have is pointed out, with the
+// closest ones to pick from, before going on.  A phrase some embedded list takes as it is, or with
+// the wrong number of words, is left to be checked wherever it's used.
+fn correct_unknown_words(mut terms: String) -> String {
+    use bip_39::Error;
+
+    let options = ParseOptions {
+        checksum: Checksum::WarnOnly,
+        ..ParseOptions::strict()
+    };
+    let parses = |terms: &str, language| Mnemonic::parse_with(terms, language, &options);
+    if Language::available()
+        .into_iter()
+        .any(|language| parses(&terms, language).is_ok())
+    {
+        return terms;
+    }
+    let language = phrase_language(&terms);
+    loop {
+        let (err, word, index) = match parses(&terms, language) {
+            Err(err) => match err {
+                Error::UnknownWord { ref word, index } => {
+                    let word = word.clone();
+                    (err, word, index)
+                }
+                _ => return terms,
+            },
+            _ => return terms,
+        };
+        let replacement = get_replacement_word(&word, index, language).unwrap_or_else(|| {
+            eprintln!(
+                "Error: {} (word {})",
+                i18n::localize(err.message(), language),
+                index + 1
+            );
+            process::exit(1)
+        });
+        let mut words: Vec<&str> = terms.split(' ').collect();
+        words[index] = &replacement;
+        terms = words.join(" ");
+    }
+}
+
+// Up to five of 'Language::suggest''s candidates by number, or any word typed in (which is looked
+// up again like the rest); 'None' once the input runs out:
+fn get_replacement_word(word: &str, index: usize, language: Language) -> Option<String> {
+    let candidates: Vec<&str> = language.suggest(word).into_iter().take(5).collect();
+    if candidates.is_empty() {
+        say!(
+            "Word {}, \"{}\", isn't in the {} wordlist, nor close to any of its words.",
+            index + 1,
+            word,
+            language.native_name()
+        );
+    } else {
+        say!(
+            "Word {}, \"{}\", isn't in the {} wordlist.  Did you mean:",
+            index + 1,
+            word,
+            language.native_name()
+        );
+        for (i, candidate) in candidates.iter().enumerate() {
+            say!("  {}. {}", i + 1, candidate);
+        }
+    }
+    loop {
+        if candidates.is_empty() {
+            say!("Please enter the word as it should be:");
+        } else {
+            say!("Please enter a number, or the word as it should be:");
+        }
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return None;
+        }
+        let answer = bip_39::normalize_mnemonic(&answer);
+        if let Some(&candidate) = answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .and_then(|i| candidates.get(i))
+        {
+            return Some(candidate.to_string());
+        }
+        if !answer.is_empty() && !answer.contains(' ') {
+            return Some(answer);
+        }
+    }
+}
+
+// Refuses (or with '--force', warns about) a mnemonic whose funds anyone could take.  Its
+// checksum and words are left to be checked wherever it's used.
+fn check_weakness(terms: &str) {
+    let options = ParseOptions {
+        checksum: Checksum::WarnOnly,
+        ..ParseOptions::strict()
+    };
+    let weakness = match Mnemonic::parse_with(terms, phrase_language(terms), &options) {
+        Ok(mnemonic) => weak::check(&mnemonic),
+        Err(_) => None,
+    };
+    if let Some(weakness) = weakness {
+        if FORCE.load(Ordering::Relaxed) {
+            eprintln!(
+                "Warning: {} Anyone can take what's sent to its addresses.",
+                weakness
+            );
+        } else {
+            eprintln!(
+                "Error: {} Anyone can take what's sent to its addresses; use --force to go ahead \
+                 with it anyway.",
+                weakness
+            );
+            process::exit(1)
+        }
+    }
 }
 
 // 'fn' is a "function pointer":
@@ -98,10 +3560,94 @@ fn get_mnemonic_list_size() -> MnemonicListSize {
     mnemonic_list_size
 }
 
+// '--language', the menu's choice, or the build's default wordlist, the one an existing mnemonic
+// is most likely written in:
+fn language() -> Language {
+    LANGUAGE
+        .get()
+        .cloned()
+        .flatten()
+        .or_else(|| CHOSEN_LANGUAGE.get().cloned())
+        .unwrap_or_else(Language::compiled)
+}
+
+// The wordlist an existing mnemonic is in: '--language', or else whichever list it's written in
+// ('Language::detect'), or failing that the one with the most of its words, whose words any
+// mistyped ones are most likely meant to be:
+fn phrase_language(terms: &str) -> Language {
+    LANGUAGE
+        .get()
+        .cloned()
+        .flatten()
+        .unwrap_or_else(|| Language::detect(terms).unwrap_or_else(|_| Language::closest(terms)))
+}
+
+// '--network', or bitcoin's:
+fn network() -> Network {
+    NETWORK.get().cloned().flatten().unwrap_or(Network::Mainnet)
+}
+
+// '--json', '--quiet', or neither:
+fn output() -> Output {
+    OUTPUT.get().copied().unwrap_or(Output::Text)
+}
+
+fn encoding() -> Encoding {
+    ENCODING.get().copied().unwrap_or(Encoding::Hex)
+}
+
+// A numbered menu of the wordlists by their native names; Enter keeps the compiled in default.
+// Asked for only when '--language' didn't already say, and only once:
+fn get_language() -> Language {
+    if let Some(language) = LANGUAGE
+        .get()
+        .cloned()
+        .flatten()
+        .or_else(|| CHOSEN_LANGUAGE.get().cloned())
+    {
+        return language;
+    }
+    let language = pick_language();
+    let _ = CHOSEN_LANGUAGE.set(language);
+    language
+}
+
+fn pick_language() -> Language {
+    let default = Language::compiled();
+    say!("Which language should the mnemonic be in?");
+    let languages = Language::available();
+    for (i, language) in languages.iter().enumerate() {
+        let marker = if *language == default {
+            " (default)"
+        } else {
+            ""
+        };
+        say!("  {:>2}. {}{}", i + 1, language.native_name(), marker);
+    }
+
+    loop {
+        let mut choice = String::new();
+        io::stdin()
+            .read_line(&mut choice)
+            .expect("Failed to read language.");
+
+        let choice = choice.trim();
+        if choice.is_empty() {
+            return default;
+        }
+        match choice.parse::<usize>() {
+            Ok(n) if (1..=languages.len()).contains(&n) => return languages[n - 1],
+            _ => {
+                eprintln!("Error: Please enter a number from the list, or nothing for the default.")
+            }
+        }
+    }
+}
+
 // Pop quiz! Describe what's going on here:
 fn get_is_new_seed() -> bool {
     let mut new_seed_response = String::new();
-    println!("Are you creating a new seed? [Y/n]");
+    say!("Are you creating a new seed? [Y/n]");
     io::stdin()
         .read_line(&mut new_seed_response)
         .expect("Failed to read response.");
@@ -118,19 +3664,334 @@ fn get_is_new_seed() -> bool {
     }
 }
 
+// The passphrase is only worth having if it can't be guessed: anyone holding the mnemonic can try
+// passphrases offline as fast as they can run PBKDF2.
+fn print_passphrase_strength(passphrase: &str) {
+    if passphrase.is_empty() {
+        return;
+    }
+    let strength = bip_39::estimate_passphrase_strength(passphrase);
+    say!(
+        "passphrase strength: {}/4 (estimated offline guessing time: {})",
+        strength.score,
+        strength.crack_time
+    );
+    check_passphrase_strength(&strength);
+}
+
+// A new wallet's passphrase can still be changed, so a weak one is refused (unless
+// '--allow-weak-passphrase' says to go ahead, with a warning); an existing wallet's is what it is,
+// and isn't checked.  Going without one ('--no-passphrase', or Enter) is a choice of its own.
+fn check_passphrase_strength(strength: &PassphraseStrength) {
+    if !strength.is_weak() {
+        return;
+    }
+    let allowed = ALLOW_WEAK_PASSPHRASE.load(Ordering::Relaxed);
+    if allowed {
+        eprintln!("Warning: This passphrase is weak; whoever finds the mnemonic could guess it.");
+    } else {
+        eprintln!(
+            "Error: This passphrase is weak; whoever finds the mnemonic could guess it.  Use \
+             --allow-weak-passphrase to go ahead with it anyway."
+        );
+    }
+    for advice in strength.warning.iter().chain(&strength.suggestions) {
+        eprintln!("  {}", advice);
+    }
+    if !allowed {
+        process::exit(1)
+    }
+}
+
+// Every passphrase and password prompt.  Input that runs out, or Ctrl-C, ends the program there,
+// rather than carrying on with an empty secret nobody typed:
+fn read_secret(prompt: &str) -> String {
+    prompt::read_secret(prompt, ASTERISKS.load(Ordering::Relaxed)).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        // (130 being what shells report for a program Ctrl-C ended)
+        process::exit(if err == PromptError::Interrupted {
+            130
+        } else {
+            1
+        })
+    })
+}
+
+// A new password for a backup file, the keyring or a BIP-38 key, asked for twice until the two
+// match.  These protect only what they're set on; none of them is the BIP-39 passphrase.
+// ('label' is as it reads mid-sentence: "backup passphrase".)
+fn prompt_new_password(label: &str) -> String {
+    let mut capitalized = label.to_string();
+    if let Some(first) = capitalized.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    loop {
+        let password = read_secret(&format!("{}: ", capitalized));
+        let confirmation = read_secret(&format!("Confirm {}: ", label));
+        if password.is_empty() {
+            eprintln!("Error: The {} may not be empty.  Please try again.", label);
+        } else if password == confirmation {
+            return password;
+        } else {
+            eprintln!(
+                "Error: {} and confirmation did not match.  Please try again.",
+                capitalized
+            );
+        }
+    }
+}
+
 fn get_passphrase() -> String {
+    // (set by 'main' from the command line before anything asks)
+    let policy = match PASSPHRASE_POLICY.get_or_init(|| Some(PassphrasePolicy::default())) {
+        Some(policy) => policy,
+        // '--no-passphrase':
+        None => return String::new(),
+    };
+    if let Some(path) = PASSPHRASE_FILE.get().cloned().flatten() {
+        return read_passphrase_file(&path, policy);
+    }
+    let prompt = if policy.allow_empty {
+        "Passphrase (Optional): "
+    } else {
+        "Passphrase: "
+    };
+
+    let mut attempts = 0;
     // 'loop' here does exactly what you think it would:
     loop {
-        // 'rpassword' is an external "crate" we're using for capturing passwords:
-        let passphrase = rpassword::prompt_password_stdout("Passphrase (Optional): ").unwrap();
-        let confirmation = rpassword::prompt_password_stdout("Confirm passphrase: ").unwrap();
+        // 'read_secret' keeps what's typed off the screen (see the library's 'prompt' module):
+        let passphrase = read_secret(prompt);
+        let result = policy.check(&passphrase).and_then(|()| {
+            // '!=' is a shorthand for the PartialEq "trait" on String; traits basically resemble
+            // Java interfaces.
+            if policy.confirm && read_secret("Confirm passphrase: ") != passphrase {
+                return Err("Passphrase and confirmation did not match.");
+            }
+            Ok(())
+        });
 
-        // '==' is a shorthand for the partialEq "trait" on String; traits basically resemble Java interfaces.
-        if passphrase == confirmation {
-            return passphrase;
-        } else {
-            eprintln!("Error: Passphrase and confirmation did not match.  Please try again.");
-            continue;
+        match result {
+            Ok(()) => return passphrase,
+            Err(err) => {
+                attempts += 1;
+                if policy.max_attempts.is_some_and(|max| attempts >= max) {
+                    eprintln!("Error: {}  Giving up.", err);
+                    process::exit(1);
+                }
+                eprintln!("Error: {}  Please try again.", err);
+            }
         }
     }
 }
+
+// '--passphrase-file': its first line (a file written with 'echo' ends in a newline that isn't
+// part of the passphrase), held to the same rules as a passphrase typed in.
+fn read_passphrase_file(path: &Path, policy: &PassphrasePolicy) -> String {
+    let passphrase = std::fs::read_to_string(path)
+        .map_err(|_| "Unable to read the passphrase file.")
+        .and_then(|text| {
+            let passphrase = text.lines().next().unwrap_or("").to_string();
+            policy.check(&passphrase)?;
+            Ok(passphrase)
+        });
+    passphrase.unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(["bip-39"].iter().chain(args)).unwrap()
+    }
+
+    fn shows_secrets(args: &[&str]) -> bool {
+        parse(args).command.as_ref().unwrap().shows_secrets()
+    }
+
+    // Which runs leave a mnemonic or private key up for the countdown to clear:
+    #[test]
+    fn commands_that_show_secrets() {
+        assert!(shows_secrets(&["seed"]));
+        assert!(shows_secrets(&["nostr"]));
+        assert!(shows_secrets(&["derive", "--path", "m/84'/0'/0'/0/0"]));
+        assert!(shows_secrets(&[
+            "ssh",
+            "--identity",
+            "ssh://git@github.com"
+        ]));
+
+        // Public keys and addresses, or secrets written to a file instead:
+        assert!(!shows_secrets(&[
+            "derive",
+            "--path",
+            "m/84'/0'/0'/0",
+            "--count",
+            "20"
+        ]));
+        assert!(!shows_secrets(&["derive", "--key", "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V", "--path", "0/0"]));
+        assert!(!shows_secrets(&[
+            "ssh",
+            "--identity",
+            "ssh://git@github.com",
+            "--output",
+            "id"
+        ]));
+        assert!(!shows_secrets(&[
+            "verify-message",
+            "--address",
+            "1",
+            "--signature",
+            "x",
+            "--message",
+            "m"
+        ]));
+    }
+
+    #[test]
+    fn clear_after() {
+        assert_eq!(parse(&["seed"]).clear_after, 60);
+        // It's global, so it goes after the subcommand as well as before:
+        assert_eq!(parse(&["seed", "--clear-after", "0"]).clear_after, 0);
+        assert_eq!(parse(&["--clear-after", "5", "nostr"]).clear_after, 5);
+        // and 0 is no countdown at all:
+        clear_screen_after(0);
+    }
+
+    #[cfg(feature = "camera")]
+    #[test]
+    fn scan_device() {
+        assert_eq!(parse(&["validate"]).scan, None);
+        assert_eq!(
+            parse(&["validate", "--scan"]).scan,
+            Some(PathBuf::from("/dev/video0"))
+        );
+        assert_eq!(
+            parse(&["recover", "--scan", "/dev/video2"]).scan,
+            Some(PathBuf::from("/dev/video2"))
+        );
+    }
+
+    #[test]
+    fn redirected_secrets() {
+        assert_eq!(Redirection::of(true, true), Redirection::None);
+        // ('bip-39 seed 2>log' still shows them on screen)
+        assert_eq!(Redirection::of(true, false), Redirection::None);
+        assert_eq!(Redirection::of(false, true), Redirection::Watched);
+        assert_eq!(Redirection::of(false, false), Redirection::Unwatched);
+
+        assert!(!parse(&["seed"]).allow_redirect);
+        assert!(parse(&["seed", "--allow-redirect"]).allow_redirect);
+    }
+
+    #[test]
+    fn language_option() {
+        // Global, in the wordlists' feature names:
+        assert_eq!(
+            parse(&["seed", "--language", "japanese"]).language,
+            Some(Language::Japanese)
+        );
+        assert_eq!(
+            parse(&["--language", "chinese_simplified", "wordlist"]).language,
+            Some(Language::ChineseSimplified)
+        );
+        assert!(Cli::try_parse_from(["bip-39", "--language", "klingon", "seed"]).is_err());
+
+        // Without it, existing mnemonics are read in the build's default wordlist:
+        assert_eq!(parse(&["seed"]).language, None);
+        assert_eq!(language(), Language::compiled());
+    }
+
+    // BIP-32's first vector, from its m/0' keys down to m/0'/1/2':
+    #[test]
+    fn derive_from_a_key() {
+        let chains = bip_39::test_vectors::BIP32[0].chains;
+        let (xprv, xpub) = derive_key(Some(chains[1].xprv), "1/2'").unwrap();
+        assert_eq!(xprv.unwrap().to_string(), chains[3].xprv);
+        assert_eq!(xpub.to_string(), chains[3].xpub);
+
+        // From an xpub there's no private key, and no hardened step:
+        let (xprv, xpub) = derive_key(Some(chains[3].xpub), "2").unwrap();
+        assert!(xprv.is_none());
+        assert_eq!(xpub.to_string(), chains[4].xpub);
+        assert!(derive_key(Some(chains[1].xpub), "1/2'").is_err());
+
+        // A path from the mnemonic is checked before it's asked for:
+        assert!(derive_key(None, "0/1").is_err());
+
+        assert!(Cli::try_parse_from([
+            "bip-39", "derive", "--path", "m/0", "--count", "5", "--wif"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn passphrase_file() {
+        // Only the first line, without its line ending:
+        let path = std::env::temp_dir().join(format!("bip-39-passphrase-{}", process::id()));
+        std::fs::write(&path, "TREZOR\r\nnot this\n").unwrap();
+        let passphrase = read_passphrase_file(&path, &PassphrasePolicy::default());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(passphrase, "TREZOR");
+
+        assert!(parse(&["seed", "--passphrase-file", "p.txt"])
+            .passphrase_file
+            .is_some());
+        assert!(Cli::try_parse_from([
+            "bip-39",
+            "seed",
+            "--passphrase-file",
+            "p.txt",
+            "--no-passphrase"
+        ])
+        .is_err());
+        // The word count or the entropy it'd come from, not both:
+        assert!(
+            Cli::try_parse_from(["bip-39", "generate", "--words", "12", "--entropy", "00"])
+                .is_err()
+        );
+        assert!(matches!(
+            parse(&["generate", "--words", "24"]).command,
+            Some(Command::Generate {
+                words: Some(24),
+                ..
+            })
+        ));
+    }
+
+    // The '--json' document for the first Trezor vector:
+    #[test]
+    fn json_output() {
+        let (entropy, mnemonic, seed, xprv) = bip_39::test_vectors::BIP39[0];
+        let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+        let seed_bytes = config.to_seed();
+        let root_key = seed_bytes.to_root_key(Network::Mainnet).unwrap();
+
+        let mut out = Vec::new();
+        print_json(&mut out, &config, &seed_bytes, &root_key).unwrap();
+        let document: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            document,
+            serde_json::json!({
+                "schema_version": schema::VERSION,
+                "mnemonic": mnemonic,
+                "language": "english",
+                "entropy": entropy,
+                "seed": seed,
+                "network": "mainnet",
+                "fingerprint": hex::encode(root_key.fingerprint()),
+                "xprv": xprv,
+                "xpub": root_key.to_extended_pub_key().to_string(),
+            })
+        );
+        // One line, for scripts reading them one at a time:
+        assert_eq!(out.iter().filter(|&&byte| byte == b'\n').count(), 1);
+
+        assert!(Cli::try_parse_from(["bip-39", "--json", "--quiet", "seed"]).is_err());
+        assert!(Cli::try_parse_from(["bip-39", "--json", "--preview"]).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+// Checking a signed message needs nothing but the address, the message and the signature, so
+// this is safe to run on an online machine with no seed anywhere near it.  BIP-137 signatures
+// are always 65 bytes, and BIP-322 witnesses never are, which is how the two are told apart.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::fmt;
+use std::str::FromStr;
+
+use address;
+use bip137;
+use bip322;
+use descriptor::ScriptType;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureFormat {
+    Bip137,
+    Bip322,
+}
+
+impl SignatureFormat {
+    pub fn of(signature: &str) -> Result<SignatureFormat, &'static str> {
+        let data = STANDARD
+            .decode(signature.trim())
+            .map_err(|_| "Signatures are base64 encoded.")?;
+        Ok(if data.len() == 65 {
+            SignatureFormat::Bip137
+        } else {
+            SignatureFormat::Bip322
+        })
+    }
+}
+
+impl FromStr for SignatureFormat {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<SignatureFormat, &'static str> {
+        match name.to_lowercase().as_str() {
+            "bip137" => Ok(SignatureFormat::Bip137),
+            "bip322" => Ok(SignatureFormat::Bip322),
+            _ => Err("Unknown signature format."),
+        }
+    }
+}
+
+impl fmt::Display for SignatureFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SignatureFormat::Bip137 => "BIP-137",
+            SignatureFormat::Bip322 => "BIP-322",
+        })
+    }
+}
+
+// The base64 signature of 'message' by 'secret_key', for its 'script_type' address:
+pub fn sign(
+    secret_key: &[u8; 32],
+    script_type: ScriptType,
+    format: SignatureFormat,
+    message: &[u8],
+) -> Result<String, &'static str> {
+    match format {
+        SignatureFormat::Bip137 => bip137::sign(secret_key, script_type, message),
+        SignatureFormat::Bip322 => bip322::sign(secret_key, script_type, message),
+    }
+}
+
+// Whether 'signature' (in either format) proves control of 'address':
+pub fn verify(address: &str, message: &[u8], signature: &str) -> Result<bool, &'static str> {
+    let network = address::network_of(address).ok_or("Unrecognized address.")?;
+    match SignatureFormat::of(signature)? {
+        SignatureFormat::Bip137 => bip137::verify(address, message, signature, network),
+        SignatureFormat::Bip322 => bip322::verify(address, message, signature, network),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base58;
+    use bip32::ExtendedPrivKey;
+    use network::Network;
+    use test_vectors;
+
+    // What the sign command does, for each kind of address and signature: whatever it signs has to
+    // verify against the address it showed.
+    #[test]
+    fn signatures_verify_in_either_format() {
+        let wif = base58::decode_check(test_vectors::BIP322.wif).unwrap();
+        let mut secret_key = [0; 32];
+        secret_key.copy_from_slice(&wif[1..33]);
+        let public_key = ExtendedPrivKey::from_parts(Network::Mainnet, [0; 32], &secret_key)
+            .unwrap()
+            .public_key();
+
+        for &script_type in ScriptType::all().iter() {
+            let address =
+                address::for_script_type(script_type, &public_key, Network::Mainnet).unwrap();
+            for &format in [SignatureFormat::Bip137, SignatureFormat::Bip322].iter() {
+                let signed = sign(&secret_key, script_type, format, b"Hello World");
+                // BIP-137 predates taproot, and BIP-322's simple signatures only cover native
+                // segwit:
+                match (script_type, format) {
+                    (ScriptType::P2tr, SignatureFormat::Bip137) => {
+                        assert_eq!(signed, Err("BIP-137 doesn't cover taproot addresses."));
+                        continue;
+                    }
+                    (ScriptType::P2pkh, SignatureFormat::Bip322)
+                    | (ScriptType::P2shP2wpkh, SignatureFormat::Bip322) => {
+                        assert!(signed.is_err());
+                        continue;
+                    }
+                    _ => {}
+                }
+                let signed = signed.unwrap();
+                assert_eq!(SignatureFormat::of(&signed), Ok(format));
+                assert_eq!(verify(&address, b"Hello World", &signed), Ok(true), "{} {}", script_type, format);
+                assert_eq!(verify(&address, b"Goodbye World", &signed), Ok(false));
+            }
+        }
+    }
+
+    #[test]
+    fn format_names() {
+        for &format in [SignatureFormat::Bip137, SignatureFormat::Bip322].iter() {
+            assert_eq!(format.to_string().replace('-', "").parse(), Ok(format));
+        }
+        assert_eq!("bip-137".parse::<SignatureFormat>(), Err("Unknown signature format."));
+        assert_eq!(SignatureFormat::of("not base64!"), Err("Signatures are base64 encoded."));
+    }
+}
@@ -0,0 +1,454 @@
+// A mnemonic checked against its wordlist: every word looked up, the count one of the
+// MnemonicListSize lengths, and the checksum verified.  How picky to be about all that is the
+// caller's call, by way of 'ParseOptions': a wallet restoring funds wants the strict defaults,
+// while a tool digging through an old "just words" backup may need to get past a bad checksum.
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+use alloc_prelude::*;
+use entropy::{Entropy, EntropySource};
+use error::Error;
+use fault;
+use hash::sha256;
+use language::Language;
+use zeroize::{Zeroize, Zeroizing};
+use {
+    append_term, edit_distance, get_mnemonic_from_entropy, nfkd, word_index, Config,
+    MnemonicListSize, Seed, MAX_PHRASE_LEN,
+};
+
+// What to do with a word that isn't in the wordlist:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownWords {
+    Reject,
+    // Swap in its completion (for abbreviated backups) or its one nearest word (for typos), and
+    // note the swap in 'Mnemonic::corrections'; the checksum then double checks the guess.
+    Suggest,
+}
+
+// What to do with a mnemonic whose last word doesn't match its checksum:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    Required,
+    // Parse it anyway, leaving 'Mnemonic::checksum_valid' to say it's off.
+    WarnOnly,
+}
+
+pub struct ParseOptions {
+    pub unknown_words: UnknownWords,
+    pub checksum: Checksum,
+    // The characters allowed between words; runs of them count as one.
+    pub separators: Vec<char>,
+}
+
+impl ParseOptions {
+    // BIP-39 as written: known words, a valid checksum, and the spaces the spec joins words with
+    // (Japanese uses the ideographic one).
+    pub fn strict() -> ParseOptions {
+        ParseOptions {
+            unknown_words: UnknownWords::Reject,
+            checksum: Checksum::Required,
+            separators: vec![' ', '\u{3000}'],
+        }
+    }
+
+    // Whatever can still be made sense of: suggestions for unknown words, a checksum warning
+    // rather than an error, and any whitespace or commas between the words.
+    pub fn lenient() -> ParseOptions {
+        ParseOptions {
+            unknown_words: UnknownWords::Suggest,
+            checksum: Checksum::WarnOnly,
+            separators: vec![' ', '\u{3000}', '\u{A0}', '\t', '\r', '\n', ','],
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions::strict()
+    }
+}
+
+// An unknown word 'UnknownWords::Suggest' replaced, at 'position' (counting from 0):
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Correction {
+    pub position: usize,
+    pub written: String,
+    pub word: &'static str,
+}
+
+pub struct Mnemonic {
+    language: Language,
+    words: Vec<&'static str>,
+    indices: Vec<usize>,
+    corrections: Vec<Correction>,
+    checksum_valid: bool,
+}
+
+impl Mnemonic {
+    pub fn parse(phrase: &str, language: Language) -> Result<Mnemonic, Error> {
+        Mnemonic::parse_with(phrase, language, &ParseOptions::default())
+    }
+
+    pub fn parse_with(
+        phrase: &str,
+        language: Language,
+        options: &ParseOptions,
+    ) -> Result<Mnemonic, Error> {
+        trace_span!("validate", language = %language);
+        if !language.is_embedded() {
+            return Err(Error::LanguageNotEmbedded);
+        }
+        // The wordlists are in NFKD, so "é" typed as one character has to be taken apart first:
+        let phrase = nfkd(phrase);
+        let written: Vec<&str> = phrase
+            .split(|c| options.separators.contains(&c))
+            .filter(|word| !word.is_empty())
+            .collect();
+        trace_event!(word_count = written.len(), "split the phrase");
+        MnemonicListSize::from(written.len())?;
+
+        let word_list = language.word_list();
+        let mut words = Vec::new();
+        let mut indices = Vec::new();
+        let mut corrections = Vec::new();
+        for (position, &word) in written.iter().enumerate() {
+            let index = match word_index(word, language) {
+                Some(index) => index,
+                None if options.unknown_words == UnknownWords::Suggest => {
+                    let suggestion = suggest(language, word).ok_or_else(|| Error::NoCloseWord {
+                        word: word.to_string(),
+                        index: position,
+                    })?;
+                    corrections.push(Correction {
+                        position,
+                        written: word.to_string(),
+                        word: suggestion,
+                    });
+                    word_index(suggestion, language).unwrap()
+                }
+                None => {
+                    return Err(Error::UnknownWord {
+                        word: word.to_string(),
+                        index: position,
+                    })
+                }
+            };
+            words.push(word_list[index]);
+            indices.push(index);
+        }
+
+        let mut mnemonic = Mnemonic {
+            language,
+            words,
+            indices,
+            corrections,
+            checksum_valid: false,
+        };
+        mnemonic.checksum_valid = fault::twice(
+            || mnemonic.checksum() == mnemonic.expected_checksum(),
+            PartialEq::eq,
+        );
+        trace_event!(
+            checksum_valid = mnemonic.checksum_valid,
+            corrections = mnemonic.corrections.len(),
+            "checked the words"
+        );
+        if !mnemonic.checksum_valid && options.checksum == Checksum::Required {
+            return Err(Error::ChecksumMismatch);
+        }
+
+        Ok(mnemonic)
+    }
+
+    // The words for 'entropy' ('Entropy::random()' for a new mnemonic):
+    pub fn from_entropy<const N: usize>(entropy: &Entropy<N>, language: Language) -> Mnemonic {
+        let mut buffer = [0; MAX_PHRASE_LEN];
+        Mnemonic::parse(entropy.write_phrase(language, &mut buffer), language)
+            .expect("Generated an invalid mnemonic.")
+    }
+
+    // The same from bytes whose length is only known at run time (a hardware RNG's output, a test
+    // vector read from a file), so it's checked here instead of by the compiler:
+    pub fn from_entropy_bytes(entropy: &[u8], language: Language) -> Result<Mnemonic, Error> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(Error::InvalidEntropyLength(entropy.len()));
+        }
+        Mnemonic::parse(&get_mnemonic_from_entropy(entropy, language), language)
+    }
+
+    // The mnemonic whose words are at these (0-based) places in the list, as steel plates and
+    // SeedQR codes store them, checked like a phrase is: its length, and its checksum.
+    pub fn from_indices(indices: &[usize], language: Language) -> Result<Mnemonic, Error> {
+        if !language.is_embedded() {
+            return Err(Error::LanguageNotEmbedded);
+        }
+        MnemonicListSize::from(indices.len())?;
+        let word_list = language.word_list();
+        let words = indices
+            .iter()
+            .map(|&index| word_list.get(index).copied())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::Other("A word's index must be under 2,048."))?;
+
+        let mnemonic = Mnemonic {
+            language,
+            words,
+            indices: indices.to_vec(),
+            corrections: Vec::new(),
+            checksum_valid: true,
+        };
+        if !fault::twice(
+            || mnemonic.checksum() == mnemonic.expected_checksum(),
+            PartialEq::eq,
+        ) {
+            return Err(Error::ChecksumMismatch);
+        }
+        Ok(mnemonic)
+    }
+
+    // The words that finish 'partial' (the first 11, 14, 17, 20 or 23 words) with a valid checksum,
+    // in list order, for a phrase drawn by hand (dice, a shuffled deck) that needs a last word to
+    // make it a mnemonic.  The last word carries the rest of the entropy and then the checksum,
+    // so there's one for each value of its entropy bits: 128 of them after 11 words, 8 after 23.
+    pub fn valid_last_words(
+        partial: &[&str],
+        language: Language,
+    ) -> Result<Vec<&'static str>, Error> {
+        if !language.is_embedded() {
+            return Err(Error::LanguageNotEmbedded);
+        }
+        MnemonicListSize::from(partial.len() + 1)
+            .map_err(|_| Error::InvalidWordCount(partial.len()))?;
+        let mut indices = Vec::new();
+        for (index, &word) in partial.iter().enumerate() {
+            let word = nfkd(word);
+            indices.push(
+                word_index(&word, language).ok_or_else(|| Error::UnknownWord {
+                    word: word.to_string(),
+                    index,
+                })?,
+            );
+        }
+
+        // (the last word's checksum bits are left out of the entropy, so any will do here)
+        indices.push(0);
+        let mut candidate = Mnemonic {
+            language,
+            words: Vec::new(),
+            indices,
+            corrections: Vec::new(),
+            checksum_valid: true,
+        };
+        let checksum_len = candidate.checksum_len();
+        let word_list = language.word_list();
+        Ok((0..1 << (11 - checksum_len))
+            .map(|rest: usize| {
+                *candidate.indices.last_mut().unwrap() = rest << checksum_len;
+                word_list[rest << checksum_len | candidate.expected_checksum() as usize]
+            })
+            .collect())
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn words(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    pub fn word_count(&self) -> MnemonicListSize {
+        // (parsing already checked the count)
+        MnemonicListSize::from(self.words.len()).unwrap()
+    }
+
+    // The words joined the way PBKDF2 expects them, by single plain spaces:
+    pub fn phrase(&self) -> String {
+        self.words.join(" ")
+    }
+
+    // The same, written into 'buffer' rather than allocated, as 'Entropy::write_phrase' does:
+    pub fn write_phrase<'a>(&self, buffer: &'a mut [u8; MAX_PHRASE_LEN]) -> &'a str {
+        let mut len = 0;
+        for word in &self.words {
+            append_term(word, buffer, &mut len);
+        }
+        // Only ever whole words (and no longer than the longest built in ones; see 'Wordlist'):
+        core::str::from_utf8(&buffer[..len]).unwrap()
+    }
+
+    pub fn checksum_valid(&self) -> bool {
+        self.checksum_valid
+    }
+
+    pub fn corrections(&self) -> &[Correction] {
+        &self.corrections
+    }
+
+    // On to the rest of the pipeline, e.g. 'mnemonic.to_seed(passphrase).to_root_key(...)':
+    pub fn to_seed<P: AsRef<str>>(&self, passphrase: P) -> Seed {
+        Seed::new(self, passphrase)
+    }
+
+    // The seed as hex, for scripts that want nothing else:
+    pub fn to_seed_hex<P: AsRef<str>>(&self, passphrase: P) -> String {
+        self.to_seed(passphrase).to_string()
+    }
+
+    // The entropy the words encode: every word's 11 bits, less the checksum bits at the end.
+    // It's the wallet itself, so it wipes itself when dropped, as the indices it came from do.
+    pub fn entropy(&self) -> Zeroizing<Vec<u8>> {
+        let bits = self.bits();
+        Zeroizing::new(
+            bits[..bits.len() - self.checksum_len()]
+                .chunks(8)
+                .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+                .collect(),
+        )
+    }
+
+    // The same entropy in another wordlist's words, for a Japanese phrase backed up in English,
+    // say.  Every word's index carries over, so the checksum does too.  It's a different wallet,
+    // though, not a translation of this one: the seed comes from the words themselves, not the
+    // entropy under them (and a mnemonic let through with a bad checksum comes out put right).
+    // 'to' has to be embedded (or a custom list).
+    pub fn translate(&self, to: Language) -> Result<Mnemonic, Error> {
+        Mnemonic::from_entropy_bytes(&self.entropy(), to)
+    }
+
+    // The same, for a language known to be embedded:
+    pub fn to_language(&self, language: Language) -> Mnemonic {
+        self.translate(language)
+            .expect("That wordlist isn't built in.")
+    }
+
+    fn bits(&self) -> Zeroizing<Vec<bool>> {
+        Zeroizing::new(
+            self.indices
+                .iter()
+                .flat_map(|index| (0..11).rev().map(move |i| index >> i & 1 == 1))
+                .collect(),
+        )
+    }
+
+    // One checksum bit per 32 bits of entropy, i.e. one in every 33 bits of words:
+    fn checksum_len(&self) -> usize {
+        self.indices.len() * 11 / 33
+    }
+
+    fn checksum(&self) -> u8 {
+        let bits = self.bits();
+        bits[bits.len() - self.checksum_len()..]
+            .iter()
+            .fold(0, |acc, &bit| acc << 1 | bit as u8)
+    }
+
+    fn expected_checksum(&self) -> u8 {
+        sha256(&self.entropy())[0] >> (8 - self.checksum_len())
+    }
+}
+
+// So phrases in the compiled in language parse with '"...".try_into()?' (strictly):
+impl<'a> TryFrom<&'a str> for Mnemonic {
+    type Error = Error;
+
+    fn try_from(phrase: &'a str) -> Result<Mnemonic, Error> {
+        Mnemonic::parse(phrase, Language::compiled())
+    }
+}
+
+// '"...".parse()?' is less particular about the language: whichever embedded list takes the
+// phrase ('Language::detect', which says which wins when two do, and which list's error comes
+// back when none do).
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    fn from_str(phrase: &str) -> Result<Mnemonic, Error> {
+        Mnemonic::parse(phrase, Language::detect(phrase)?)
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.phrase())
+    }
+}
+
+// The indices are the entropy, so they go the way 'Config''s phrase and 'Seed''s bytes do (the
+// words are the wordlist's own, and give nothing away once their indices are gone):
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.indices.zeroize();
+    }
+}
+
+// New mnemonics one after another, each from entropy of its own, for provisioning a batch of
+// devices; they're only made as they're asked for, so '.take(n)' can go on to whatever writes
+// them out without all 'n' sitting in memory at once:
+//
+//   let mut random = OsRandom;
+//   for mnemonic in MnemonicGenerator::new(&mut random, Language::English, TwentyFour).take(100) {
+//       let mnemonic = mnemonic?;
+//       ...
+//   }
+//
+// It never ends of itself; an 'Err' is the source failing (which, for 'OsRandom', it doesn't).
+pub struct MnemonicGenerator<'a> {
+    source: &'a mut dyn EntropySource,
+    language: Language,
+    size: MnemonicListSize,
+}
+
+impl<'a> MnemonicGenerator<'a> {
+    pub fn new(
+        source: &'a mut dyn EntropySource,
+        language: Language,
+        size: MnemonicListSize,
+    ) -> MnemonicGenerator<'a> {
+        MnemonicGenerator {
+            source,
+            language,
+            size,
+        }
+    }
+}
+
+impl<'a> Iterator for MnemonicGenerator<'a> {
+    type Item = Result<Mnemonic, Error>;
+
+    fn next(&mut self) -> Option<Result<Mnemonic, Error>> {
+        let generated = Config::generate_using(self.source, self.language, self.size, "")
+            .and_then(|config| Mnemonic::parse(&config.mnemonic, self.language));
+        Some(generated)
+    }
+}
+
+// The completion of an abbreviated word, or else the one word a typo or two away from it:
+fn suggest(language: Language, written: &str) -> Option<&'static str> {
+    if let Some(word) = language.complete(written) {
+        return Some(word);
+    }
+
+    let mut nearest = None;
+    let mut nearest_distance = 3;
+    let mut tied = false;
+    for &word in language.word_list().iter() {
+        let distance = edit_distance(written, word);
+        if distance < nearest_distance {
+            nearest = Some(word);
+            nearest_distance = distance;
+            tied = false;
+        } else if distance == nearest_distance {
+            tied = true;
+        }
+    }
+
+    if tied {
+        None
+    } else {
+        nearest
+    }
+}
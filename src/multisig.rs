@@ -0,0 +1,195 @@
+// Multisig wallets from several cosigners' account keys: each cosigner (a hardware wallet, this
+// tool, ...) hands over its BIP-48 account xpub along with its origin, and the wallet's
+// 'wsh(sortedmulti(...))' descriptors spend with any 'threshold' of them.  'sortedmulti' sorts
+// the keys in every script itself, so the cosigners can be listed in any order and every
+// coordinator still arrives at the same addresses.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, KeyOrigin};
+use descriptor;
+
+// How the 'multisig' command writes the wallet out:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultisigFormat {
+    // Receive, change and multipath descriptors:
+    Descriptors,
+    // A BIP-388 wallet policy:
+    Bip388,
+    // The setup file Coldcard and Specter import:
+    Coldcard,
+}
+
+impl FromStr for MultisigFormat {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<MultisigFormat, &'static str> {
+        match name.to_lowercase().as_str() {
+            "descriptors" => Ok(MultisigFormat::Descriptors),
+            "bip388" => Ok(MultisigFormat::Bip388),
+            "coldcard" => Ok(MultisigFormat::Coldcard),
+            _ => Err("Unknown multisig format."),
+        }
+    }
+}
+
+impl fmt::Display for MultisigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MultisigFormat::Descriptors => "descriptors",
+            MultisigFormat::Bip388 => "bip388",
+            MultisigFormat::Coldcard => "coldcard",
+        })
+    }
+}
+
+// BIP-48's script type level for native segwit multisig (P2WSH):
+const P2WSH_SCRIPT_TYPE: u32 = 2;
+// The most keys Bitcoin Core puts in one 'sortedmulti':
+const MAX_COSIGNERS: usize = 20;
+// The longest wallet name a Coldcard takes in a setup file:
+const MAX_SETUP_NAME_LEN: usize = 20;
+
+// One cosigner's account key.  The origin isn't optional: a signer has to find its own key in the
+// wallet by fingerprint and path before it will sign for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cosigner {
+    pub origin: KeyOrigin,
+    pub xpub: ExtendedPubKey,
+}
+
+impl Cosigner {
+    // This wallet's own key for multisig account 'account': m/48'/coin_type'/account'/2'.
+    pub fn derive(root: &ExtendedPrivKey, account: u32) -> Result<Cosigner, &'static str> {
+        let path = DerivationPath::from(vec![
+            ChildNumber::hardened(48)?,
+            ChildNumber::hardened(root.network.coin_type())?,
+            ChildNumber::hardened(account)?,
+            ChildNumber::hardened(P2WSH_SCRIPT_TYPE)?,
+        ]);
+
+        Ok(Cosigner {
+            xpub: root.derive_path(&path)?.to_extended_pub_key(),
+            origin: KeyOrigin::new(root.fingerprint(), path),
+        })
+    }
+}
+
+// The "[73c5da0a/48h/0h/0h/2h]xpub..." key expressions coordinators pass around:
+impl FromStr for Cosigner {
+    type Err = &'static str;
+
+    fn from_str(key: &str) -> Result<Cosigner, &'static str> {
+        let end = key
+            .find(']')
+            .ok_or("A cosigner key starts with its origin: \"[fingerprint/path]xpub...\".")?;
+        let (origin, xpub) = key.split_at(end + 1);
+        Ok(Cosigner {
+            origin: origin.parse()?,
+            xpub: xpub.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for Cosigner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#}{}", self.origin, self.xpub)
+    }
+}
+
+pub struct Multisig {
+    pub threshold: usize,
+    pub cosigners: Vec<Cosigner>,
+}
+
+impl Multisig {
+    pub fn new(threshold: usize, cosigners: Vec<Cosigner>) -> Result<Multisig, &'static str> {
+        if cosigners.len() > MAX_COSIGNERS {
+            return Err("A multisig wallet can have at most 20 cosigners.");
+        }
+        if threshold == 0 || threshold > cosigners.len() {
+            return Err("The threshold must be between 1 and the number of cosigners.");
+        }
+        if cosigners
+            .iter()
+            .any(|cosigner| cosigner.xpub.network != cosigners[0].xpub.network)
+        {
+            return Err("The cosigners' keys are for different networks.");
+        }
+        for (i, cosigner) in cosigners.iter().enumerate() {
+            if cosigners[..i].iter().any(|other| other.xpub == cosigner.xpub) {
+                return Err("The same cosigner key is in there twice.");
+            }
+        }
+
+        Ok(Multisig {
+            threshold,
+            cosigners,
+        })
+    }
+
+    // The ranged descriptor, with checksum, for the receive (0) or change (1) chain:
+    pub fn descriptor(&self, change: bool) -> String {
+        self.ranged_descriptor(if change { "1" } else { "0" })
+    }
+
+    // Both chains at once ("/<0;1>/*"), as Bitcoin Core and Sparrow import them:
+    pub fn multipath_descriptor(&self) -> String {
+        self.ranged_descriptor("<0;1>")
+    }
+
+    // The plain text setup file Coldcard imports from its SD card (and Specter from a file), so a
+    // device can check every later PSBT against the same keys.  The derivation goes in the header
+    // when the cosigners share it, and before each key when they don't:
+    //
+    //   Name: Multisig
+    //   Policy: 2 of 3
+    //   Derivation: m/48'/0'/0'/2'
+    //   Format: P2WSH
+    //
+    //   73C5DA0A: xpub6DkFAXWQ2dHxq2vatrt9qyA3bXYU4ToWQwCHbf5XB2mSTexcHZCeKS1VZYcPoBd5X8yVcbX...
+    pub fn setup_file(&self, name: &str) -> Result<String, &'static str> {
+        if name.is_empty() || name.len() > MAX_SETUP_NAME_LEN || !name.is_ascii() {
+            return Err("A Coldcard wallet name is 1 to 20 ASCII characters.");
+        }
+
+        let path = &self.cosigners[0].origin.path;
+        let shared = self.cosigners.iter().all(|cosigner| &cosigner.origin.path == path);
+        let mut file = format!(
+            "# Multisig setup file, for Coldcard and Specter\n#\nName: {}\nPolicy: {} of {}\n",
+            name,
+            self.threshold,
+            self.cosigners.len()
+        );
+        if shared {
+            file.push_str(&format!("Derivation: {}\n", path));
+        }
+        file.push_str("Format: P2WSH\n");
+
+        for cosigner in &self.cosigners {
+            file.push('\n');
+            if !shared {
+                file.push_str(&format!("Derivation: {}\n", cosigner.origin.path));
+            }
+            // Coldcard writes fingerprints in upper case:
+            file.push_str(&format!(
+                "{}: {}\n",
+                hex::encode_upper(cosigner.origin.fingerprint),
+                cosigner.xpub
+            ));
+        }
+        Ok(file)
+    }
+
+    fn ranged_descriptor(&self, chain: &str) -> String {
+        let keys: Vec<String> = self
+            .cosigners
+            .iter()
+            .map(|cosigner| format!("{}/{}/*", cosigner, chain))
+            .collect();
+        let descriptor = format!("wsh(sortedmulti({},{}))", self.threshold, keys.join(","));
+        descriptor::with_checksum(&descriptor)
+            .expect("Descriptors built from keys only contain valid characters.")
+    }
+}
@@ -0,0 +1,328 @@
+// Which network keys are for; it decides the version bytes extended keys serialize with
+// ("xprv" vs "tprv"), the BIP-44 coin type accounts are derived under, and how addresses and WIF
+// keys look.  Everything comes from the parameter table at the bottom, one row per network.
+//
+// Signet and regtest share testnet's extended key versions and coin type.  Liquid (and its
+// testnet) reuse bitcoin's extended key versions but have their own coin type and address
+// prefixes; only unconfidential ("ex1...") addresses are produced, since confidential ones need a
+// blinding key and Elements' blech32 encoding.  The altcoins are the handful of bitcoin-derived
+// coins whose keys and addresses only differ by these parameters; any other such coin is a
+// 'Custom' network, with a table row of the caller's own:
+//
+//   static VERTCOIN: Params = Params { xprv_version: [4, 136, 173, 228], ... };
+//   let root = seed.to_root_key(Network::Custom(&VERTCOIN))?;
+//
+// Keys read back in (an "xprv..." string) only ever come out as one of the built in networks.
+
+use core::fmt;
+use core::str::FromStr;
+
+use alloc_prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+    Liquid,
+    LiquidTestnet,
+    Litecoin,
+    Dogecoin,
+    Dash,
+    Custom(&'static Params),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Params {
+    pub xprv_version: [u8; 4],
+    pub xpub_version: [u8; 4],
+    // SLIP-44 registered coin type:
+    pub coin_type: u32,
+    // The leading bytes of WIF private keys and of base58 P2PKH and P2SH addresses:
+    pub wif_prefix: u8,
+    pub p2pkh_prefix: u8,
+    pub p2sh_prefix: u8,
+    // The human readable part in front of segwit addresses, for networks that have them:
+    pub bech32_hrp: Option<&'static str>,
+}
+
+impl Network {
+    pub fn all() -> [Network; 9] {
+        [
+            Network::Mainnet,
+            Network::Testnet,
+            Network::Signet,
+            Network::Regtest,
+            Network::Liquid,
+            Network::LiquidTestnet,
+            Network::Litecoin,
+            Network::Dogecoin,
+            Network::Dash,
+        ]
+    }
+
+    // Several networks share version bytes, so a parsed "xprv" comes back as Mainnet and a "tprv"
+    // as Testnet; callers who know better can set the network afterwards.
+    pub fn from_xprv_version(version: [u8; 4]) -> Option<Network> {
+        Network::all()
+            .iter()
+            .find(|network| network.xprv_version() == version)
+            .cloned()
+    }
+
+    pub fn from_xpub_version(version: [u8; 4]) -> Option<Network> {
+        Network::all()
+            .iter()
+            .find(|network| network.xpub_version() == version)
+            .cloned()
+    }
+
+    pub fn params(&self) -> &'static Params {
+        match self {
+            Network::Mainnet => &MAINNET,
+            Network::Testnet | Network::Signet => &TESTNET,
+            Network::Regtest => &REGTEST,
+            Network::Liquid => &LIQUID,
+            Network::LiquidTestnet => &LIQUID_TESTNET,
+            Network::Litecoin => &LITECOIN,
+            Network::Dogecoin => &DOGECOIN,
+            Network::Dash => &DASH,
+            Network::Custom(params) => params,
+        }
+    }
+
+    pub fn xprv_version(&self) -> [u8; 4] {
+        self.params().xprv_version
+    }
+
+    pub fn xpub_version(&self) -> [u8; 4] {
+        self.params().xpub_version
+    }
+
+    pub fn coin_type(&self) -> u32 {
+        self.params().coin_type
+    }
+
+    pub fn wif_prefix(&self) -> u8 {
+        self.params().wif_prefix
+    }
+
+    pub fn p2pkh_prefix(&self) -> u8 {
+        self.params().p2pkh_prefix
+    }
+
+    pub fn p2sh_prefix(&self) -> u8 {
+        self.params().p2sh_prefix
+    }
+
+    pub fn bech32_hrp(&self) -> Option<&'static str> {
+        self.params().bech32_hrp
+    }
+}
+
+// The names '--network' takes:
+impl FromStr for Network {
+    type Err = &'static str;
+
+    fn from_str(name: &str) -> Result<Network, &'static str> {
+        Network::all()
+            .iter()
+            .find(|network| network.to_string() == name.to_lowercase())
+            .cloned()
+            .ok_or("Unknown network.")
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Signet => "signet",
+            Network::Regtest => "regtest",
+            Network::Liquid => "liquid",
+            Network::LiquidTestnet => "liquid-testnet",
+            Network::Litecoin => "litecoin",
+            Network::Dogecoin => "dogecoin",
+            Network::Dash => "dash",
+            Network::Custom(_) => "custom",
+        })
+    }
+}
+
+const XPRV: [u8; 4] = [4, 136, 173, 228];
+const XPUB: [u8; 4] = [4, 136, 178, 30];
+const TPRV: [u8; 4] = [4, 53, 131, 148];
+const TPUB: [u8; 4] = [4, 53, 135, 207];
+
+const MAINNET: Params = Params {
+    xprv_version: XPRV,
+    xpub_version: XPUB,
+    coin_type: 0,
+    wif_prefix: 128,
+    p2pkh_prefix: 0,
+    p2sh_prefix: 5,
+    bech32_hrp: Some("bc"),
+};
+
+const TESTNET: Params = Params {
+    xprv_version: TPRV,
+    xpub_version: TPUB,
+    coin_type: 1,
+    wif_prefix: 239,
+    p2pkh_prefix: 111,
+    p2sh_prefix: 196,
+    bech32_hrp: Some("tb"),
+};
+
+const REGTEST: Params = Params {
+    bech32_hrp: Some("bcrt"),
+    ..TESTNET
+};
+
+const LIQUID: Params = Params {
+    xprv_version: XPRV,
+    xpub_version: XPUB,
+    coin_type: 1776,
+    wif_prefix: 128,
+    p2pkh_prefix: 57,
+    p2sh_prefix: 39,
+    bech32_hrp: Some("ex"),
+};
+
+const LIQUID_TESTNET: Params = Params {
+    xprv_version: TPRV,
+    xpub_version: TPUB,
+    coin_type: 1,
+    wif_prefix: 239,
+    p2pkh_prefix: 36,
+    p2sh_prefix: 19,
+    bech32_hrp: Some("tex"),
+};
+
+// Litecoin Core and Dash Core serialize extended keys as "xprv"/"xpub" like bitcoin does:
+const LITECOIN: Params = Params {
+    xprv_version: XPRV,
+    xpub_version: XPUB,
+    coin_type: 2,
+    wif_prefix: 176,
+    p2pkh_prefix: 48,
+    p2sh_prefix: 50,
+    bech32_hrp: Some("ltc"),
+};
+
+// Dogecoin's are "dgpv"/"dgub":
+const DOGECOIN: Params = Params {
+    xprv_version: [2, 250, 195, 152],
+    xpub_version: [2, 250, 202, 253],
+    coin_type: 3,
+    wif_prefix: 158,
+    p2pkh_prefix: 30,
+    p2sh_prefix: 22,
+    bech32_hrp: None,
+};
+
+const DASH: Params = Params {
+    xprv_version: XPRV,
+    xpub_version: XPUB,
+    coin_type: 5,
+    wif_prefix: 204,
+    p2pkh_prefix: 76,
+    p2sh_prefix: 16,
+    bech32_hrp: None,
+};
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use address;
+    use Config;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // The first receive address of the account 'path' (down to its chain) leads to:
+    fn first_address(network: Network, path: &str, segwit: bool) -> String {
+        let config = Config::from_phrase(MNEMONIC, "").unwrap();
+        let root = config.to_seed().to_root_key(network).unwrap();
+        let key = root
+            .derive_path(&path.parse().unwrap())
+            .unwrap()
+            .public_key();
+        if segwit {
+            address::p2wpkh(&key, network).unwrap()
+        } else {
+            address::p2pkh(&key, network)
+        }
+    }
+
+    // Elements' unconfidential addresses: "Q..."/"ex1..." on Liquid, "F..."/"tex1..." on its testnet.
+    #[test]
+    fn liquid_addresses() {
+        assert_eq!(
+            first_address(Network::Liquid, "m/44'/1776'/0'/0/0", false),
+            "QED6hgAQPU9UtS67ce3uBPzcVT7EDkvWYd"
+        );
+        assert_eq!(
+            first_address(Network::Liquid, "m/84'/1776'/0'/0/0", true),
+            "ex1qyuh42lps6t6jpdk54cwmmhd27zrs3yulrc7t5a"
+        );
+        assert_eq!(
+            first_address(Network::LiquidTestnet, "m/44'/1'/0'/0/0", false),
+            "FaUKrQXheW6QmoQYykjbvpMPgohd9XnECy"
+        );
+        assert_eq!(
+            first_address(Network::LiquidTestnet, "m/84'/1'/0'/0/0", true),
+            "tex1q6rz28mcfaxtmd6v789l9rrlrusdprr9p634wu8"
+        );
+
+        assert_eq!("liquid-testnet".parse(), Ok(Network::LiquidTestnet));
+        assert_eq!(Network::Liquid.coin_type(), 1776);
+        // Its keys serialize as bitcoin's do, so read back they're bitcoin's:
+        assert_eq!(
+            Network::from_xpub_version(Network::Liquid.xpub_version()),
+            Some(Network::Mainnet)
+        );
+    }
+
+    // The BIP-44 and BIP-84 addresses other wallets show for the test mnemonic:
+    #[test]
+    fn altcoin_addresses() {
+        assert_eq!(
+            first_address(Network::Litecoin, "m/44'/2'/0'/0/0", false),
+            "LUWPbpM43E2p7ZSh8cyTBEkvpHmr3cB8Ez"
+        );
+        assert_eq!(
+            first_address(Network::Litecoin, "m/84'/2'/0'/0/0", true),
+            "ltc1qjmxnz78nmc8nq77wuxh25n2es7rzm5c2rkk4wh"
+        );
+        assert_eq!(
+            first_address(Network::Dogecoin, "m/44'/3'/0'/0/0", false),
+            "DBus3bamQjgJULBJtYXpEzDWQRwF5iwxgC"
+        );
+        assert_eq!(
+            first_address(Network::Dash, "m/44'/5'/0'/0/0", false),
+            "XoJA8qE3N2Y3jMLEtZ3vcN42qseZ8LvFf5"
+        );
+
+        let config = Config::from_phrase(MNEMONIC, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Dogecoin).unwrap();
+        assert!(root.to_string().starts_with("dgpv"));
+        assert!(root.to_extended_pub_key().to_string().starts_with("dgub"));
+        assert!(address::p2wpkh(&root.public_key(), Network::Dogecoin).is_err());
+    }
+
+    // A custom row is used as it is: here, Litecoin's under another name.
+    #[test]
+    fn custom_network() {
+        static LITECOIN_AGAIN: Params = LITECOIN;
+        let custom = Network::Custom(&LITECOIN_AGAIN);
+        assert_eq!(custom.params(), Network::Litecoin.params());
+        assert_eq!(
+            first_address(custom, "m/44'/2'/0'/0/0", false),
+            "LUWPbpM43E2p7ZSh8cyTBEkvpHmr3cB8Ez"
+        );
+        assert_eq!(custom.to_string(), "custom");
+        assert_eq!("custom".parse::<Network>(), Err("Unknown network."));
+    }
+}
@@ -0,0 +1,84 @@
+// Nostr identity keys from the same mnemonic (NIP-06): the secp256k1 key at
+// m/44'/1237'/account'/0/0, shown the NIP-19 way as bech32 "npub1..." and "nsec1..." strings.
+
+use bech32::{self, Variant};
+use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+
+// SLIP-44's coin type for Nostr:
+const COIN_TYPE: u32 = 1237;
+
+pub struct Keys {
+    // Nostr public keys are x-only (BIP-340): the compressed key without its parity byte.
+    pub public_key: [u8; 32],
+    secret_key: [u8; 32],
+}
+
+impl Keys {
+    pub fn derive(root: &ExtendedPrivKey, account: u32) -> Result<Keys, &'static str> {
+        let path = DerivationPath::from(vec![
+            ChildNumber::hardened(44)?,
+            ChildNumber::hardened(COIN_TYPE)?,
+            ChildNumber::hardened(account)?,
+            ChildNumber::normal(0)?,
+            ChildNumber::normal(0)?,
+        ]);
+        let key = root.derive_path(&path)?;
+
+        let mut public_key = [0; 32];
+        public_key.copy_from_slice(&key.public_key()[1..]);
+        Ok(Keys {
+            public_key,
+            secret_key: key.secret_key_bytes(),
+        })
+    }
+
+    pub fn npub(&self) -> String {
+        bech32::encode("npub", &bech32::to_base32(&self.public_key), Variant::Bech32)
+    }
+
+    pub fn nsec(&self) -> String {
+        bech32::encode("nsec", &bech32::to_base32(&self.secret_key), Variant::Bech32)
+    }
+
+    pub fn secret_key_bytes(&self) -> [u8; 32] {
+        self.secret_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+    use Config;
+
+    // NIP-06's test vectors (account 0, no passphrase):
+    #[test]
+    fn spec_vectors() {
+        let vectors = [
+            (
+                "leader monkey parrot ring guide accident before fence cannon height naive bean",
+                "7f7ff03d123792d6ac594bfa67bf6d0c0ab55b6b1fdb6249303fe861f1ccba9a",
+                "nsec10allq0gjx7fddtzef0ax00mdps9t2kmtrldkyjfs8l5xruwvh2dq0lhhkp",
+                "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917",
+                "npub1zutzeysacnf9rru6zqwmxd54mud0k44tst6l70ja5mhv8jjumytsd2x7nu",
+            ),
+            (
+                "what bleak badge arrange retreat wolf trade produce cricket blur garlic valid proud rude strong choose busy staff weather area salt hollow arm fade",
+                "c15d739894c81a2fcfd3a2df85a0d2c0dbc47a280d092799f144d73d7ae78add",
+                "nsec1c9wh8xy5eqdzln7n5t0ctgxjcrdug73gp5yj0x03gntn67h83twssdfhel",
+                "d41b22899549e1f3d335a31002cfd382174006e166d3e658e3a5eecdb6463573",
+                "npub16sdj9zv4f8sl85e45vgq9n7nsgt5qphpvmf7vk8r5hhvmdjxx4es8rq74h",
+            ),
+        ];
+        for &(mnemonic, secret_key, nsec, public_key, npub) in vectors.iter() {
+            let config = Config::from_phrase(mnemonic, "").unwrap();
+            let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+            let keys = Keys::derive(&root, 0).unwrap();
+
+            assert_eq!(hex::encode(keys.secret_key_bytes()), secret_key);
+            assert_eq!(keys.nsec(), nsec);
+            assert_eq!(hex::encode(keys.public_key), public_key);
+            assert_eq!(keys.npub(), npub);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Hot storage of a mnemonic in the platform keyring (Secret Service, Keychain or Credential
+// Manager). The keyring only ever sees ciphertext: the mnemonic is encrypted with a password
+// first (scrypt for the key, AES-256-GCM for the encryption).
+//
+// This is meant for small test wallets; anything holding real funds belongs offline.
+
+use std::convert::TryFrom;
+
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce, Tag};
+use keyring::Entry;
+use scrypt::{scrypt, Params as ScryptParams};
+
+use entropy;
+
+const SERVICE: &str = "bip-39";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+pub fn store(label: &str, mnemonic: &str, password: &str) -> Result<(), &'static str> {
+    store_in(&entry(label)?, mnemonic, password)
+}
+
+fn store_in(entry: &Entry, mnemonic: &str, password: &str) -> Result<(), &'static str> {
+    let salt = entropy::random::<SALT_LEN>();
+    let nonce = entropy::random::<NONCE_LEN>();
+    let key = derive_key(password, &salt);
+
+    let mut ciphertext = mnemonic.as_bytes().to_vec();
+    let tag = Aes256Gcm::new(&key.into())
+        .encrypt_in_place_detached(&Nonce::from(nonce), SERVICE.as_bytes(), &mut ciphertext)
+        .unwrap();
+
+    // salt | nonce | ciphertext | tag, hex encoded since keyrings store text:
+    let mut stored = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len() + TAG_LEN);
+    stored.extend(&salt);
+    stored.extend(&nonce);
+    stored.extend(&ciphertext);
+    stored.extend(&tag);
+
+    entry
+        .set_password(&hex::encode(stored))
+        .map_err(|_| "Unable to write to the keyring.")
+}
+
+pub fn retrieve(label: &str, password: &str) -> Result<String, &'static str> {
+    retrieve_from(&entry(label)?, password)
+}
+
+fn retrieve_from(entry: &Entry, password: &str) -> Result<String, &'static str> {
+    let stored = entry
+        .get_password()
+        .map_err(|_| "No mnemonic found in the keyring under that label.")?;
+    let stored = hex::decode(stored).map_err(|_| "Keyring entry is not a stored mnemonic.")?;
+    if stored.len() < SALT_LEN + NONCE_LEN + TAG_LEN {
+        return Err("Keyring entry is not a stored mnemonic.");
+    }
+
+    let (salt, rest) = stored.split_at(SALT_LEN);
+    let (nonce, rest) = rest.split_at(NONCE_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+    let key = derive_key(password, salt);
+
+    let mut plaintext = ciphertext.to_vec();
+    let decrypted = Aes256Gcm::new(&key.into()).decrypt_in_place_detached(
+        &Nonce::from(<[u8; NONCE_LEN]>::try_from(nonce).unwrap()),
+        SERVICE.as_bytes(),
+        &mut plaintext,
+        &Tag::from(<[u8; TAG_LEN]>::try_from(tag).unwrap()),
+    );
+    if decrypted.is_err() {
+        return Err("Wrong password, or the keyring entry is corrupt.");
+    }
+
+    String::from_utf8(plaintext).map_err(|_| "Keyring entry is not a stored mnemonic.")
+}
+
+pub fn delete(label: &str) -> Result<(), &'static str> {
+    entry(label)?
+        .delete_credential()
+        .map_err(|_| "Unable to delete the keyring entry.")
+}
+
+fn entry(label: &str) -> Result<Entry, &'static str> {
+    Entry::new(SERVICE, label).map_err(|_| "Unable to open the platform keyring.")
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0; 32];
+    let params = ScryptParams::new(15, 8, 1, key.len()).unwrap();
+    scrypt(password.as_bytes(), salt, &params, &mut key).unwrap();
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use keyring::{mock, Entry};
+
+    use super::{retrieve_from, store_in, SERVICE};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // A mock keyring entry, which keeps what's stored in it only for as long as it lives.
+    fn mock_entry() -> Entry {
+        let credential = mock::default_credential_builder().build(None, SERVICE, "test").unwrap();
+        Entry::new_with_credential(credential)
+    }
+
+    #[test]
+    fn round_trip() {
+        let entry = mock_entry();
+        store_in(&entry, MNEMONIC, "correct horse").unwrap();
+
+        // Only ciphertext reaches the keyring:
+        assert!(!entry.get_password().unwrap().contains("abandon"));
+        assert_eq!(retrieve_from(&entry, "correct horse"), Ok(MNEMONIC.to_string()));
+    }
+
+    #[test]
+    fn wrong_password() {
+        let entry = mock_entry();
+        store_in(&entry, MNEMONIC, "correct horse").unwrap();
+
+        assert_eq!(
+            retrieve_from(&entry, "battery staple"),
+            Err("Wrong password, or the keyring entry is corrupt.")
+        );
+    }
+
+    #[test]
+    fn not_a_stored_mnemonic() {
+        let entry = mock_entry();
+        assert!(retrieve_from(&entry, "correct horse").is_err());
+
+        entry.set_password("hunter2").unwrap();
+        assert_eq!(
+            retrieve_from(&entry, "correct horse"),
+            Err("Keyring entry is not a stored mnemonic.")
+        );
+    }
+}
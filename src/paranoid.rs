@@ -0,0 +1,69 @@
+// A second opinion on the two steps every key rests on, for users who'd rather not trust a single
+// implementation with them: once 'enable'd, each BIP-39 seed and BIP-32 root key is computed
+// again with ring's PBKDF2, HMAC and SHA-512 (BoringSSL's, much of it assembly), which share no
+// code with the RustCrypto crates behind 'hash'.  A backend bug or a miscompilation would give keys no other wallet
+// derives from the same words, so if the two ever disagree the process aborts instead of handing
+// them out.
+
+use std::num::NonZeroU32;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ring::{hmac, pbkdf2};
+
+use bip32::ExtendedPrivKey;
+use {get_salt, nfkd};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+pub fn seed(mnemonic: &str, passphrase: &str) -> [u8; 64] {
+    let salt = get_salt(passphrase.to_string());
+    let mut seed = [0; 64];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA512,
+        NonZeroU32::new(2048).unwrap(),
+        salt.as_bytes(),
+        nfkd(mnemonic).as_bytes(),
+        &mut seed,
+    );
+    seed
+}
+
+// (chain code, private key), as BIP-32 splits HMAC-SHA512("Bitcoin seed", seed):
+pub fn master_key(seed: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let key = hmac::Key::new(hmac::HMAC_SHA512, b"Bitcoin seed");
+    let tag = hmac::sign(&key, seed);
+    let output = tag.as_ref();
+
+    let mut chain_code = [0; 32];
+    let mut private_key = [0; 32];
+    chain_code.copy_from_slice(&output[32..]);
+    private_key.copy_from_slice(&output[..32]);
+    (chain_code, private_key)
+}
+
+pub fn check_seed(mnemonic: &str, passphrase: &str, derived: &[u8; 64]) {
+    if is_enabled() && seed(mnemonic, passphrase) != *derived {
+        disagree("seed");
+    }
+}
+
+pub fn check_root_key(seed: &[u8], derived: &ExtendedPrivKey) {
+    if is_enabled() && master_key(seed) != (derived.chain_code, derived.secret_key_bytes()) {
+        disagree("root key");
+    }
+}
+
+// Not a panic, which could be caught and carried on from:
+fn disagree(what: &str) -> ! {
+    eprintln!("The two {} derivations disagree; aborting.", what);
+    process::abort()
+}
@@ -0,0 +1,35 @@
+// The types most callers end up needing together, so 'use bip_39::prelude::*;' covers the usual
+// mnemonic -> seed -> keys -> accounts round trip.
+
+pub use account::Account;
+pub use bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, KeyOrigin};
+pub use descriptor::ScriptType;
+pub use entropy::Entropy;
+pub use language::Language;
+pub use mnemonic::{Mnemonic, ParseOptions};
+pub use network::Network;
+pub use watch_only::WatchOnlyWallet;
+pub use wordlist::Wordlist;
+pub use {Config, Error, MnemonicListSize, Seed};
+
+#[cfg(test)]
+mod tests {
+    // Nothing but the prelude, as the README's example has it:
+    use prelude::*;
+
+    #[test]
+    fn round_trip() {
+        let mnemonic = Mnemonic::from_entropy(&Entropy::from([0; 16]), Language::English);
+        let config = Config::from_phrase(mnemonic.words().join(" "), String::new()).unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+        let account = Account::derive(&root, ScriptType::P2wpkh, 0).unwrap();
+        assert_eq!(
+            account.descriptor(false),
+            "wpkh([73c5da0a/84h/0h/0h]xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#afwvtk2s"
+        );
+        assert_eq!(
+            account.origin.path,
+            "m/84'/0'/0'".parse::<DerivationPath>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// What '--preview' shows of a wallet: nothing that can spend from it, only enough to see that a
+// backup recreates the wallet it should (against a watch-only copy, or an earlier preview).  It's
+// for typing a mnemonic in on a machine that isn't fully trusted: whatever is on the screen, or
+// reaches a log, gives away the wallet's balance and history but not its funds.
+
+use account::Account;
+use address;
+use bip32::ExtendedPrivKey;
+use descriptor::ScriptType;
+
+pub struct Preview {
+    pub fingerprint: [u8; 4],
+    // The BIP-44 account at m/44'/coin'/0', whose xpub is what watch-only wallets import:
+    pub account: Account,
+    // Its first receive address, m/44'/coin'/0'/0/0:
+    pub first_address: String,
+}
+
+pub fn preview(root: &ExtendedPrivKey) -> Result<Preview, &'static str> {
+    let account = Account::derive(root, ScriptType::P2pkh, 0)?;
+    let key = account.receive_key(0)?;
+
+    Ok(Preview {
+        fingerprint: root.fingerprint(),
+        first_address: address::p2pkh(&key.public_key(), root.network),
+        account,
+    })
+}
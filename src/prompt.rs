@@ -0,0 +1,328 @@
+// Reading passphrases and passwords without showing them.  On a terminal, echo is turned off and
+// the keys are read one at a time, so there can be an asterisk for each character typed (for
+// those who want to see that their typing registers, screen readers included), and Ctrl-C comes
+// back as an error rather than a signal, with the terminal put back the way it was; killed with
+// echo still off, it would stay that way.  Anything other than a terminal (a pipe, a file, an
+// automation tool's pseudo-input) is read a line at a time as it is.
+//
+// Either way, input that ends before a line does (Ctrl-D, an empty pipe) is an error of its own,
+// rather than an empty passphrase nobody typed.
+//
+// The prompts (and asterisks) go to standard output, or with 'prompt_on_stderr(true)' to standard
+// error, for callers whose standard output is for something else, such as a JSON document.
+
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ON_STDERR: AtomicBool = AtomicBool::new(false);
+
+pub fn prompt_on_stderr(on: bool) {
+    ON_STDERR.store(on, Ordering::Relaxed);
+}
+
+fn output() -> Box<dyn Write> {
+    if ON_STDERR.load(Ordering::Relaxed) {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    }
+}
+
+#[derive(Debug)]
+pub enum PromptError {
+    // Standard input ran out before a line was entered:
+    EndOfInput,
+    // Ctrl-C at the prompt:
+    Interrupted,
+    // Reading standard input or setting up the terminal failed, for this reason ('source()'):
+    Io(io::Error),
+}
+
+// (I/O errors are told apart by their kind, there being nothing else to compare)
+impl PartialEq for PromptError {
+    fn eq(&self, other: &PromptError) -> bool {
+        match (self, other) {
+            (PromptError::Io(err), PromptError::Io(other)) => err.kind() == other.kind(),
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PromptError::EndOfInput => "The input ended before anything was entered.",
+            PromptError::Interrupted => "Interrupted.",
+            PromptError::Io(_) => "Unable to read from the terminal.",
+        })
+    }
+}
+
+impl error::Error for PromptError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PromptError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+// How the BIP-39 passphrase is asked for, for wherever there are rules about it.  The default is
+// the tool's own behavior: any passphrase (none at all included), confirmed, asked again until
+// the two entries match.
+pub struct PassphrasePolicy {
+    // The fewest characters a passphrase may have (an empty one aside: see 'allow_empty'):
+    pub min_length: usize,
+    // Ask for it twice, and compare:
+    pub confirm: bool,
+    // Take an empty entry as "no passphrase"; without it, going without takes an explicit
+    // acknowledgment (the command line tool's '--no-passphrase') instead of just pressing Enter:
+    pub allow_empty: bool,
+    // Give up after this many refused or mismatched entries, rather than asking forever:
+    pub max_attempts: Option<usize>,
+}
+
+impl Default for PassphrasePolicy {
+    fn default() -> PassphrasePolicy {
+        PassphrasePolicy {
+            min_length: 0,
+            confirm: true,
+            allow_empty: true,
+            max_attempts: None,
+        }
+    }
+}
+
+impl PassphrasePolicy {
+    // Whether 'passphrase' may be used (confirming it is up to the prompt):
+    pub fn check(&self, passphrase: &str) -> Result<(), &'static str> {
+        if passphrase.is_empty() {
+            if self.allow_empty {
+                return Ok(());
+            }
+            return Err("Going without a passphrase takes --no-passphrase.");
+        }
+        if passphrase.chars().count() < self.min_length {
+            return Err("The passphrase is shorter than the minimum length.");
+        }
+        Ok(())
+    }
+}
+
+// 'prompt' goes to standard output (see above), the answer comes from standard input;
+// 'asterisks' only matters on a terminal.
+pub fn read_secret(prompt: &str, asterisks: bool) -> Result<String, PromptError> {
+    let mut output = output();
+    write!(output, "{}", prompt).and_then(|()| output.flush()).map_err(PromptError::Io)?;
+
+    #[cfg(unix)]
+    {
+        if unix::stdin_is_terminal() {
+            return unix::read_from_terminal(asterisks);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        // (no raw mode here: rpassword turns echo off for the line)
+        use std::io::IsTerminal;
+        let _ = asterisks;
+        if io::stdin().is_terminal() {
+            return rpassword::read_password().map_err(|err| match err.kind() {
+                io::ErrorKind::UnexpectedEof => PromptError::EndOfInput,
+                io::ErrorKind::Interrupted => PromptError::Interrupted,
+                _ => PromptError::Io(err),
+            });
+        }
+    }
+    read_line(&mut io::stdin().lock())
+}
+
+// One line, without its line ending.  A last line with no newline after it still counts, since
+// 'printf secret | bip-39 ...' is how scripts pass one.
+pub fn read_line(reader: &mut dyn BufRead) -> Result<String, PromptError> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Err(PromptError::EndOfInput),
+        Ok(_) => {
+            let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+            line.truncate(len);
+            Ok(line)
+        }
+        Err(err) => Err(PromptError::Io(err)),
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::io::{self, Read, Write};
+    use std::mem;
+
+    use super::{output, PromptError};
+
+    const CTRL_C: u8 = 0x03;
+    const CTRL_D: u8 = 0x04;
+    const BACKSPACE: u8 = 0x08;
+    const CTRL_U: u8 = 0x15;
+    const ESCAPE: u8 = 0x1b;
+    const DELETE: u8 = 0x7f;
+
+    pub fn stdin_is_terminal() -> bool {
+        unsafe { libc::isatty(libc::STDIN_FILENO) == 1 }
+    }
+
+    // The terminal's settings before the prompt, put back when this goes out of scope, however
+    // the prompt ends:
+    struct RawMode(libc::termios);
+
+    impl RawMode {
+        // No echo, no line editing (it's done below instead), and no signals from Ctrl-C or
+        // Ctrl-Z, with each key passed on as it's pressed:
+        fn enable() -> Result<RawMode, PromptError> {
+            let mut original: libc::termios = unsafe { mem::zeroed() };
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+                return Err(PromptError::Io(io::Error::last_os_error()));
+            }
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::ISIG | libc::IEXTEN);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &raw) } != 0 {
+                return Err(PromptError::Io(io::Error::last_os_error()));
+            }
+            Ok(RawMode(original))
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &self.0);
+            }
+        }
+    }
+
+    pub fn read_from_terminal(asterisks: bool) -> Result<String, PromptError> {
+        let raw_mode = RawMode::enable()?;
+        // (room enough up front that growing doesn't leave copies behind in freed memory)
+        let mut secret = Vec::with_capacity(1024);
+        let result = read_keys(&mut secret, asterisks);
+        drop(raw_mode);
+        // (the Enter that ended it wasn't echoed)
+        let _ = writeln!(output());
+
+        result?;
+        String::from_utf8(secret).map_err(|err| {
+            wipe(&mut err.into_bytes());
+            PromptError::Io(io::ErrorKind::InvalidData.into())
+        })
+    }
+
+    fn read_keys(secret: &mut Vec<u8>, asterisks: bool) -> Result<(), PromptError> {
+        let stdin = io::stdin();
+        let mut stdin = stdin.lock();
+        let mut output = output();
+        let mut key = [0; 1];
+        loop {
+            match stdin.read(&mut key) {
+                Ok(0) => return fail(secret, PromptError::EndOfInput),
+                Ok(_) => {}
+                Err(err) => return fail(secret, PromptError::Io(err)),
+            }
+            match key[0] {
+                b'\r' | b'\n' => return Ok(()),
+                CTRL_C => return fail(secret, PromptError::Interrupted),
+                CTRL_D if secret.is_empty() => return fail(secret, PromptError::EndOfInput),
+                BACKSPACE | DELETE => {
+                    if pop_char(secret) && asterisks {
+                        let _ = write!(output, "\x08 \x08");
+                    }
+                }
+                CTRL_U => {
+                    while pop_char(secret) {
+                        if asterisks {
+                            let _ = write!(output, "\x08 \x08");
+                        }
+                    }
+                }
+                // Arrow keys and the like send "ESC [" (or "ESC O") and letters up to one in
+                // '@'..='~', none of it any part of a passphrase:
+                ESCAPE => {
+                    let mut next = [0; 1];
+                    stdin.read_exact(&mut next).map_err(PromptError::Io)?;
+                    if next[0] == b'[' || next[0] == b'O' {
+                        loop {
+                            stdin.read_exact(&mut next).map_err(PromptError::Io)?;
+                            if (0x40..=0x7e).contains(&next[0]) {
+                                break;
+                            }
+                        }
+                    }
+                }
+                // Nor are the other control keys:
+                byte if byte < 0x20 => {}
+                byte => {
+                    secret.push(byte);
+                    // One asterisk per character, not per byte of UTF-8:
+                    if asterisks && byte & 0xc0 != 0x80 {
+                        let _ = write!(output, "*");
+                    }
+                }
+            }
+            output.flush().map_err(PromptError::Io)?;
+        }
+    }
+
+    // Wipe and drop the last character, however many bytes of UTF-8 it took:
+    fn pop_char(secret: &mut Vec<u8>) -> bool {
+        if secret.is_empty() {
+            return false;
+        }
+        while let Some(byte) = secret.last_mut() {
+            let first = *byte & 0xc0 != 0x80;
+            *byte = 0;
+            secret.pop();
+            if first {
+                break;
+            }
+        }
+        true
+    }
+
+    fn fail(secret: &mut Vec<u8>, err: PromptError) -> Result<(), PromptError> {
+        wipe(secret);
+        Err(err)
+    }
+
+    fn wipe(bytes: &mut Vec<u8>) {
+        for byte in bytes.iter_mut() {
+            *byte = 0;
+        }
+        bytes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    struct Broken;
+
+    impl io::Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "not allowed"))
+        }
+    }
+
+    #[test]
+    fn io_errors_are_the_source() {
+        let err = read_line(&mut io::BufReader::new(Broken)).unwrap_err();
+        assert_eq!(err, PromptError::Io(io::ErrorKind::PermissionDenied.into()));
+        assert_eq!(err.to_string(), "Unable to read from the terminal.");
+        assert_eq!(err.source().unwrap().to_string(), "not allowed");
+        assert!(PromptError::Interrupted.source().is_none());
+    }
+}
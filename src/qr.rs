@@ -0,0 +1,373 @@
+// Just enough of a QR code encoder for SeedQR: versions 1 to 3 (21 to 29 modules square), the
+// numeric and byte modes, and error correction level L, which is what SeedSigner prints and
+// scans.  It's all in one Reed-Solomon block at those sizes, which keeps the layout simple:
+//
+//   let code = QrCode::numeric("0309154321170002")?;
+//   print!("{}", code.to_terminal());
+//
+// The steps are ISO/IEC 18004's: the data bits (mode, length, payload, terminator, padding),
+// the error correction codewords after them, the finder, timing and alignment patterns, the
+// codewords in their zig-zag up and down the columns, and the mask, of the eight, that scores
+// the lowest penalty (the fewest long runs, blocks, and finder lookalikes).  Written out here,
+// with no QR crate, so the secret in it is never handed to code that might keep a copy.
+
+use zeroize::Zeroize;
+
+// Total codewords and data codewords for versions 1 to 3 at level L:
+const CAPACITY: [(usize, usize); 3] = [(26, 19), (44, 34), (70, 55)];
+
+// Where the one alignment pattern goes, in versions 2 and 3:
+const ALIGNMENT: [usize; 3] = [0, 18, 22];
+
+// Level L's two format bits:
+const LEVEL_L: u32 = 0b01;
+
+pub struct QrCode {
+    size: usize,
+    // Row by row:
+    modules: Vec<bool>,
+    // The finder, timing, alignment and format modules, which the mask leaves alone:
+    reserved: Vec<bool>,
+}
+
+impl QrCode {
+    // Digits only, three to ten bits:
+    pub fn numeric(digits: &str) -> Result<QrCode, &'static str> {
+        if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err("A numeric QR code holds digits only.");
+        }
+        let mut bits = Bits::default();
+        bits.push(0b0001, 4);
+        bits.push(digits.len() as u32, 10);
+        for group in digits.as_bytes().chunks(3) {
+            let value = group.iter().fold(0, |acc, digit| acc * 10 + (digit - b'0') as u32);
+            bits.push(value, [0, 4, 7, 10][group.len()]);
+        }
+        QrCode::new(bits)
+    }
+
+    pub fn bytes(data: &[u8]) -> Result<QrCode, &'static str> {
+        let mut bits = Bits::default();
+        bits.push(0b0100, 4);
+        bits.push(data.len() as u32, 8);
+        for &byte in data {
+            bits.push(byte as u32, 8);
+        }
+        QrCode::new(bits)
+    }
+
+    fn new(mut bits: Bits) -> Result<QrCode, &'static str> {
+        let version = (1..=3)
+            .find(|&version| bits.len() <= CAPACITY[version - 1].1 * 8)
+            .ok_or("That's too much for a version 1 to 3 QR code.")?;
+        let (total, data) = CAPACITY[version - 1];
+
+        // The terminator (up to four zero bits), zeros to the byte, then the pad bytes:
+        let terminator = (data * 8 - bits.len()).min(4);
+        bits.push(0, terminator);
+        bits.push(0, (8 - bits.len() % 8) % 8);
+        let mut codewords = bits.into_bytes();
+        for pad in [0xec, 0x11].iter().cycle() {
+            if codewords.len() == data {
+                break;
+            }
+            codewords.push(*pad);
+        }
+        let mut correction = reed_solomon(&codewords, total - data);
+        codewords.extend(&correction);
+        correction.zeroize();
+
+        let size = 17 + 4 * version;
+        let mut code = QrCode {
+            size,
+            modules: vec![false; size * size],
+            reserved: vec![false; size * size],
+        };
+        code.draw_function_patterns(version);
+        code.draw_codewords(&codewords);
+        codewords.zeroize();
+
+        let best = (0..8)
+            .min_by_key(|&mask| {
+                code.apply_mask(mask);
+                code.draw_format(mask);
+                let penalty = code.penalty();
+                code.apply_mask(mask);
+                penalty
+            })
+            .unwrap();
+        code.apply_mask(best);
+        code.draw_format(best);
+        Ok(code)
+    }
+
+    // The modules on a side, without the quiet zone around them:
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 'x' across, 'y' down, from the top left:
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    // Two rows of modules to a line, in half blocks, with a two module quiet zone (half the
+    // spec's, which phone cameras don't need).  The dark modules come out as spaces, for the usual
+    // light text on a dark background; the terminal's background is the code's dark.
+    pub fn to_terminal(&self) -> String {
+        let quiet = 2;
+        let span = self.size + 2 * quiet;
+        let light = |x: usize, y: usize| {
+            x < quiet
+                || y < quiet
+                || x >= quiet + self.size
+                || y >= quiet + self.size
+                || !self.is_dark(x - quiet, y - quiet)
+        };
+        let mut text = String::new();
+        for y in (0..span).step_by(2) {
+            for x in 0..span {
+                text.push(match (light(x, y), y + 1 < span && light(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.reserved[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+        for i in 0..size {
+            self.set(6, i, i % 2 == 0);
+            self.set(i, 6, i % 2 == 0);
+        }
+        // The finders, with their light separators around them:
+        for &(x, y) in [(3, 3), (size - 4, 3), (3, size - 4)].iter() {
+            for dy in -4i32..=4 {
+                for dx in -4i32..=4 {
+                    let (column, row) = (x as i32 + dx, y as i32 + dy);
+                    if (0..size as i32).contains(&column) && (0..size as i32).contains(&row) {
+                        let ring = dx.abs().max(dy.abs());
+                        self.set(column as usize, row as usize, ring != 2 && ring != 4);
+                    }
+                }
+            }
+        }
+        if version > 1 {
+            let center = ALIGNMENT[version - 1];
+            for dy in -2i32..=2 {
+                for dx in -2i32..=2 {
+                    let (x, y) = ((center as i32 + dx) as usize, (center as i32 + dy) as usize);
+                    self.set(x, y, dx.abs().max(dy.abs()) != 1);
+                }
+            }
+        }
+        // The format modules are drawn for each mask tried, but they're reserved from the start:
+        self.draw_format(0);
+    }
+
+    // Level L and the mask, with their BCH code and the spec's mask over them, twice: around the
+    // top left finder, and split between the other two (with the always dark module by them).
+    fn draw_format(&mut self, mask: u32) {
+        let data = LEVEL_L << 3 | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| bits >> i & 1 == 1;
+
+        let size = self.size;
+        for i in 0..6 {
+            self.set(8, i, bit(i));
+        }
+        self.set(8, 7, bit(6));
+        self.set(8, 8, bit(7));
+        self.set(7, 8, bit(8));
+        for i in 9..15 {
+            self.set(14 - i, 8, bit(i));
+        }
+        for i in 0..8 {
+            self.set(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set(8, size - 15 + i, bit(i));
+        }
+        self.set(8, size - 8, true);
+    }
+
+    // Two columns at a time from the right, up then down, skipping the vertical timing pattern;
+    // the modules left over at the end (there are 7 in versions 2 and 3) stay light.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vertical } else { vertical };
+                    if !self.reserved[y * size + x] && i < codewords.len() * 8 {
+                        self.modules[y * size + x] = codewords[i / 8] >> (7 - i % 8) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    // Masking twice takes it off again.
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let flip = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if flip && !self.reserved[y * self.size + x] {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        let lines: Vec<Vec<bool>> = (0..size)
+            .map(|y| (0..size).map(|x| self.is_dark(x, y)).collect())
+            .chain((0..size).map(|x| (0..size).map(|y| self.is_dark(x, y)).collect()))
+            .collect();
+        for line in &lines {
+            // Runs of five or more alike:
+            let mut run = 1;
+            for i in 1..=size {
+                if i < size && line[i] == line[i - 1] {
+                    run += 1;
+                    continue;
+                }
+                if run >= 5 {
+                    penalty += run - 2;
+                }
+                run = 1;
+            }
+            // Dark, light, three dark, light, dark, with four light on one side:
+            let finder = [true, false, true, true, true, false, true];
+            for window in line.windows(11) {
+                if (window[..7] == finder && window[7..].iter().all(|&dark| !dark))
+                    || (window[4..] == finder && window[..4].iter().all(|&dark| !dark))
+                {
+                    penalty += 40;
+                }
+            }
+        }
+        // Two by two blocks alike:
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.is_dark(x, y);
+                if dark == self.is_dark(x + 1, y)
+                    && dark == self.is_dark(x, y + 1)
+                    && dark == self.is_dark(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        // Ten points for every 5% the dark modules are off half:
+        let dark = self.modules.iter().filter(|&&dark| dark).count();
+        let total = size * size;
+        let off = (dark * 20).abs_diff(total * 10);
+        penalty + off.div_ceil(total).saturating_sub(1) * 10
+    }
+}
+
+// (the modules are the secret itself, drawn)
+impl Drop for QrCode {
+    fn drop(&mut self) {
+        self.modules.zeroize();
+    }
+}
+
+#[derive(Default)]
+struct Bits(Vec<bool>);
+
+impl Bits {
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            self.0.push(value >> i & 1 == 1);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        let bytes = self
+            .0
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+            .collect();
+        self.0.zeroize();
+        bytes
+    }
+}
+
+// The remainder of the data, as a polynomial over GF(256) (x^8 + x^4 + x^3 + x^2 + 1), by the
+// generator with roots 2^0 to 2^(count - 1):
+fn reed_solomon(data: &[u8], count: usize) -> Vec<u8> {
+    let mut generator = vec![0; count - 1];
+    generator.push(1);
+    let mut root = 1;
+    for _ in 0..count {
+        for i in 0..count {
+            generator[i] = multiply(generator[i], root);
+            if i + 1 < count {
+                generator[i] ^= generator[i + 1];
+            }
+        }
+        root = multiply(root, 2);
+    }
+
+    let mut remainder = vec![0; count];
+    for &byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        for (term, &coefficient) in remainder.iter_mut().zip(&generator) {
+            *term ^= multiply(coefficient, factor);
+        }
+    }
+    remainder
+}
+
+fn multiply(a: u8, b: u8) -> u8 {
+    let mut product: u32 = 0;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11d);
+        product ^= (b as u32 >> i & 1) * a as u32;
+    }
+    product as u8
+}
@@ -0,0 +1,81 @@
+// A few of a new mnemonic's words asked for by position ("what is word #7?"), so whoever was
+// shown it has to prove they wrote it down before the wallet is used.  Retyping the whole phrase
+// (the command line's backup check) proves the same thing more thoroughly; a handful of words is
+// what wallet apps usually settle for, and all a small screen has room for.
+//
+// The positions are drawn at random, so the words can't be learned from the quiz itself, and
+// answers are checked the way typed mnemonics are read: cleaned up by 'normalize_mnemonic', and
+// cut to any unambiguous prefix (four letters in English).
+
+use entropy::{EntropySource, OsRandom};
+use language::Language;
+use mnemonic::Mnemonic;
+use normalize_mnemonic;
+use zeroize::Zeroize;
+
+pub struct Challenge {
+    // The positions asked about, from 1, in the order they're to be asked:
+    positions: Vec<usize>,
+    words: Vec<&'static str>,
+    language: Language,
+}
+
+// 'n' positions (no more than there are words, and none twice) drawn with the system's random
+// numbers.  Without random numbers there's no quiz worth having, so this panics (as
+// 'entropy::fill_random' does).
+pub fn confirmation_challenge(mnemonic: &Mnemonic, n: usize) -> Challenge {
+    confirmation_challenge_using(mnemonic, n, &mut OsRandom).unwrap()
+}
+
+// The same with positions drawn from 'source', for a device's own random numbers (or a test's).
+pub fn confirmation_challenge_using<S: EntropySource>(
+    mnemonic: &Mnemonic,
+    n: usize,
+    source: &mut S,
+) -> Result<Challenge, &'static str> {
+    // The first 'n' of a shuffle: each place swapped with a later one (or itself), at random.
+    // (16 random bytes for every pick leave its bias at 2^-120, too little to matter.)
+    let mut positions: Vec<usize> = (1..=mnemonic.words().len()).collect();
+    let n = n.min(positions.len());
+    for i in 0..n {
+        let mut random = [0; 16];
+        source.fill(&mut random)?;
+        let pick = i + (u128::from_be_bytes(random) % (positions.len() - i) as u128) as usize;
+        random.zeroize();
+        positions.swap(i, pick);
+    }
+    positions.truncate(n);
+
+    Ok(Challenge {
+        words: positions.iter().map(|&position| mnemonic.words()[position - 1]).collect(),
+        positions,
+        language: mnemonic.language(),
+    })
+}
+
+impl Challenge {
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    // Whether 'answer' is the word at 'position' (from 1); false for positions not asked about.
+    pub fn check(&self, position: usize, answer: &str) -> bool {
+        let answer = normalize_mnemonic(answer);
+        self.positions
+            .iter()
+            .zip(&self.words)
+            .find(|&(&asked, _)| asked == position)
+            .is_some_and(|(_, &word)| self.language.complete(&answer) == Some(word))
+    }
+
+    // The positions whose answers are wrong, given in the order of 'positions()'; an empty list
+    // means the quiz is passed.
+    pub fn wrong_answers(&self, answers: &[&str]) -> Vec<usize> {
+        self.positions
+            .iter()
+            .enumerate()
+            .filter(|&(i, &position)| answers.get(i).is_none_or(|answer| !self.check(position, answer)))
+            .map(|(_, &position)| position)
+            .collect()
+    }
+}
@@ -0,0 +1,452 @@
+// Finding a mnemonic from a backup that's only partly legible: the words that can be read, with
+// the rest written as patterns of what they might be, to go through every mnemonic they allow.
+// Each word of the pattern is one of:
+//
+//   cable           the word itself
+//   ?               any word at all
+//   ca*le  s?ke     a smudged word: '*' for any run of letters, '?' for any one letter
+//   cable|castle    any of these (each of which can be smudged too)
+//
+// A pattern one word short of a mnemonic (23 words, say) means a word was lost without its place
+// being known, so every place is tried.  Few of the combinations carry their checksum (one in
+// 16 for 12 words, one in 256 for 24), and those are the candidates; which of them is the wallet
+// takes something known about it, an address it paid or its master fingerprint, and a PBKDF2 run
+// (and some BIP-32 derivations) per candidate to check it, which the 'parallel' feature spreads
+// across threads.
+//
+//   let search = Search::new("ability able about above absent ? ...", Language::English)?;
+//   let found = search.find(&Target::Fingerprint(fingerprint), "", &mut |_| {})?;
+//
+// Every word unaccounted for multiplies the candidates by 2,048, so two is about as many as is
+// practical: 4 million combinations, and some 16 thousand seeds to derive for 24 words.  A search
+// that long can be stopped and picked up again: 'find_from' reports a 'Checkpoint' after every
+// batch of candidates, and starts from one it's given.
+
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use address;
+use error::Error;
+use language::Language;
+use mnemonic::Mnemonic;
+use network::Network;
+use {nfkd, word_index, MnemonicListSize, Seed};
+
+// How many candidates are checked against a target between progress reports:
+const CHUNK: usize = 1024;
+
+pub struct Search {
+    language: Language,
+    // The words each place could hold (as wordlist indices), in every layout worth trying: the
+    // pattern as it is, or with the lost word at each place in turn.
+    layouts: Vec<Vec<Vec<usize>>>,
+}
+
+impl Search {
+    pub fn new(pattern: &str, language: Language) -> Result<Search, Error> {
+        if !language.is_embedded() {
+            return Err(Error::LanguageNotEmbedded);
+        }
+        let pattern = nfkd(pattern).to_lowercase();
+        let slots = pattern
+            .split_whitespace()
+            .enumerate()
+            .map(|(index, term)| slot(term, index, language))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let layouts = if MnemonicListSize::from(slots.len()).is_ok() {
+            vec![slots]
+        } else {
+            MnemonicListSize::from(slots.len() + 1)
+                .map_err(|_| Error::InvalidWordCount(slots.len()))?;
+            (0..=slots.len())
+                .map(|place| {
+                    // Had the lost word been one the next place could hold, the layout with it
+                    // one place further on has the same mnemonic, so it's only tried there:
+                    let lost = match slots.get(place) {
+                        Some(next) => (0..2048).filter(|word| !next.contains(word)).collect(),
+                        None => (0..2048).collect(),
+                    };
+                    let mut layout = slots.clone();
+                    layout.insert(place, lost);
+                    layout
+                })
+                .collect()
+        };
+
+        Ok(Search { language, layouts })
+    }
+
+    // How many combinations of words there are to go through, checksums and all:
+    pub fn combinations(&self) -> u128 {
+        self.layouts
+            .iter()
+            .map(|layout| layout.iter().map(|words| words.len() as u128).product::<u128>())
+            .sum()
+    }
+
+    // The combinations that carry their checksum, one by one, in the pattern's order (the lost
+    // word's place first, then the last word changing fastest):
+    pub fn candidates(&self) -> Candidates<'_> {
+        Candidates {
+            search: self,
+            layout: 0,
+            positions: vec![0; self.layouts[0].len()],
+            exhausted: self.layouts[0].iter().any(Vec::is_empty),
+        }
+    }
+
+    // The candidates left after 'checkpoint', which has to be one of this search's:
+    pub fn candidates_from(&self, checkpoint: &Checkpoint) -> Result<Candidates<'_>, Error> {
+        // (A finished search's layout is one past the last.)
+        let fits = checkpoint.combinations == self.combinations()
+            && match self.layouts.get(checkpoint.layout) {
+                Some(layout) => {
+                    layout.len() == checkpoint.positions.len()
+                        && layout
+                            .iter()
+                            .zip(&checkpoint.positions)
+                            .all(|(words, &position)| position < words.len().max(1))
+                }
+                None => checkpoint.layout == self.layouts.len() && checkpoint.exhausted,
+            };
+        if !fits {
+            return Err(Error::Other("The checkpoint is from a different search."));
+        }
+
+        Ok(Candidates {
+            search: self,
+            layout: checkpoint.layout,
+            positions: checkpoint.positions.clone(),
+            exhausted: checkpoint.exhausted,
+        })
+    }
+
+    // The candidates that turn out to be the wallet 'target' describes, with 'passphrase';
+    // 'progress' hears how many candidates have been checked as the search goes.
+    pub fn find(
+        &self,
+        target: &Target,
+        passphrase: &str,
+        progress: &mut dyn FnMut(u64),
+    ) -> Result<Vec<Mnemonic>, Error> {
+        self.find_from(target, passphrase, None, &mut |checkpoint, _| {
+            progress(checkpoint.checked)
+        })
+    }
+
+    // The same, starting from 'checkpoint' (or the beginning), with 'progress' handed a checkpoint
+    // after every batch, and the batch's matches, so they needn't wait for the end to be seen.
+    // What's returned is only what this run found.
+    pub fn find_from(
+        &self,
+        target: &Target,
+        passphrase: &str,
+        checkpoint: Option<&Checkpoint>,
+        progress: &mut dyn FnMut(&Checkpoint, &[Mnemonic]),
+    ) -> Result<Vec<Mnemonic>, Error> {
+        let network = match target {
+            Target::Fingerprint(_) => Network::Mainnet,
+            Target::Address { address, .. } => {
+                address::network_of(address).ok_or(Error::Other("That isn't an address of a known network."))?
+            }
+        };
+        let matches = |mnemonic: &Mnemonic| target.matches(mnemonic, passphrase, network);
+
+        let (mut candidates, mut checked, mut found_before) = match checkpoint {
+            Some(checkpoint) => (
+                self.candidates_from(checkpoint)?,
+                checkpoint.checked,
+                checkpoint.found,
+            ),
+            None => (self.candidates(), 0, 0),
+        };
+        let mut found = Vec::new();
+        loop {
+            let chunk: Vec<Mnemonic> = candidates.by_ref().take(CHUNK).collect();
+            if chunk.is_empty() {
+                return Ok(found);
+            }
+            #[cfg(feature = "parallel")]
+            let hits = chunk.par_iter().map(matches).collect::<Result<Vec<_>, _>>()?;
+            #[cfg(not(feature = "parallel"))]
+            let hits = chunk.iter().map(matches).collect::<Result<Vec<_>, _>>()?;
+
+            checked += chunk.len() as u64;
+            let start = found.len();
+            found.extend(chunk.into_iter().zip(hits).filter(|(_, hit)| *hit).map(|(m, _)| m));
+            found_before += (found.len() - start) as u64;
+            progress(&candidates.checkpoint(checked, found_before), &found[start..]);
+        }
+    }
+}
+
+// What's known of the wallet being looked for:
+pub enum Target {
+    // Its master key's fingerprint, as descriptors, PSBTs and hardware wallets show it:
+    Fingerprint([u8; 4]),
+    // An address it paid to or from: one of the first 'gap_limit' receive and change addresses of
+    // any script type's account 0, on the address's network.
+    Address { address: String, gap_limit: u32 },
+}
+
+impl Target {
+    fn matches(
+        &self,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        network: Network,
+    ) -> Result<bool, Error> {
+        let root = Seed::new(mnemonic, passphrase).to_root_key(network)?;
+        Ok(match self {
+            Target::Fingerprint(fingerprint) => root.fingerprint() == *fingerprint,
+            Target::Address { address, gap_limit } => {
+                !address::find_known(&root, &[address.as_str()], 1, *gap_limit)?.is_empty()
+            }
+        })
+    }
+}
+
+// Where a search has got to: the layout and the place in each of its word lists that the next
+// combination starts from, with how many candidates were checked, and how many of them matched,
+// before it.  Its text form ('Display', read back by 'FromStr') is a few lines of numbers, with
+// none of the words in it; it doesn't hold the matches, which 'find_from' hands over as they're
+// found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub checked: u64,
+    pub found: u64,
+    // The search's size, so a checkpoint isn't taken up by a different one:
+    combinations: u128,
+    layout: usize,
+    positions: Vec<usize>,
+    exhausted: bool,
+}
+
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let positions: Vec<String> = self.positions.iter().map(usize::to_string).collect();
+        writeln!(f, "bip-39 recover-search checkpoint 1")?;
+        writeln!(f, "combinations {}", self.combinations)?;
+        writeln!(f, "checked {}", self.checked)?;
+        writeln!(f, "found {}", self.found)?;
+        writeln!(f, "layout {}", self.layout)?;
+        writeln!(f, "positions {}", positions.join(" "))?;
+        writeln!(f, "exhausted {}", self.exhausted)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Checkpoint, Error> {
+        let mut lines = text.lines();
+        if lines.next() != Some("bip-39 recover-search checkpoint 1") {
+            return Err(Error::Other(NOT_A_CHECKPOINT));
+        }
+        // Each line after the first is a name and its value, in this order:
+        let mut field = |name: &str| match lines.next().and_then(|line| line.split_once(' ')) {
+            Some((found, value)) if found == name => Ok(value),
+            _ => Err(Error::Other(NOT_A_CHECKPOINT)),
+        };
+
+        Ok(Checkpoint {
+            combinations: parse(field("combinations")?)?,
+            checked: parse(field("checked")?)?,
+            found: parse(field("found")?)?,
+            layout: parse(field("layout")?)?,
+            positions: field("positions")?
+                .split(' ')
+                .map(parse)
+                .collect::<Result<_, _>>()?,
+            exhausted: parse(field("exhausted")?)?,
+        })
+    }
+}
+
+const NOT_A_CHECKPOINT: &str = "Not a recover-search checkpoint.";
+
+fn parse<T: FromStr>(value: &str) -> Result<T, Error> {
+    value.parse().map_err(|_| Error::Other(NOT_A_CHECKPOINT))
+}
+
+pub struct Candidates<'a> {
+    search: &'a Search,
+    layout: usize,
+    // Which of each place's words the next combination takes:
+    positions: Vec<usize>,
+    // Whether the layout has no combinations left:
+    exhausted: bool,
+}
+
+impl<'a> Candidates<'a> {
+    // Where the candidates have got to, having come to 'checked' so far and 'found' of them
+    // matching:
+    pub fn checkpoint(&self, checked: u64, found: u64) -> Checkpoint {
+        Checkpoint {
+            checked,
+            found,
+            combinations: self.search.combinations(),
+            layout: self.layout,
+            positions: self.positions.clone(),
+            exhausted: self.exhausted,
+        }
+    }
+}
+
+impl<'a> Iterator for Candidates<'a> {
+    type Item = Mnemonic;
+
+    fn next(&mut self) -> Option<Mnemonic> {
+        loop {
+            if self.exhausted {
+                self.layout += 1;
+                let layout = self.search.layouts.get(self.layout)?;
+                self.positions = vec![0; layout.len()];
+                self.exhausted = layout.iter().any(Vec::is_empty);
+                continue;
+            }
+
+            let layout = &self.search.layouts[self.layout];
+            let indices: Vec<usize> = self
+                .positions
+                .iter()
+                .zip(layout)
+                .map(|(&position, words)| words[position])
+                .collect();
+
+            // On to the next combination, the last place first, as an odometer turns:
+            self.exhausted = true;
+            for (position, words) in self.positions.iter_mut().zip(layout).rev() {
+                *position += 1;
+                if *position < words.len() {
+                    self.exhausted = false;
+                    break;
+                }
+                *position = 0;
+            }
+
+            if let Ok(mnemonic) = Mnemonic::from_indices(&indices, self.search.language) {
+                return Some(mnemonic);
+            }
+        }
+    }
+}
+
+// The words one term of the pattern allows, in list order:
+fn slot(term: &str, index: usize, language: Language) -> Result<Vec<usize>, Error> {
+    if term == "?" || term == "*" {
+        return Ok((0..2048).collect());
+    }
+    let mut words = Vec::new();
+    for alternative in term.split('|') {
+        if alternative.contains(['*', '?']) {
+            let pattern: Vec<char> = alternative.chars().collect();
+            let before = words.len();
+            words.extend(language.word_list().iter().enumerate().filter_map(|(word, text)| {
+                let text: Vec<char> = text.chars().collect();
+                glob_match(&pattern, &text).then_some(word)
+            }));
+            if words.len() == before {
+                return Err(Error::NoCloseWord {
+                    word: alternative.to_string(),
+                    index,
+                });
+            }
+        } else {
+            words.push(word_index(alternative, language).ok_or_else(|| Error::UnknownWord {
+                word: alternative.to_string(),
+                index,
+            })?);
+        }
+    }
+    words.sort_unstable();
+    words.dedup();
+    Ok(words)
+}
+
+// Whether 'text' fits 'pattern', where '*' stands for any run of characters and '?' for one:
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|skip| glob_match(rest, &text[skip..])),
+        Some((&c, rest)) => match text.split_first() {
+            Some((&t, text)) => (c == '?' || c == t) && glob_match(rest, text),
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATTERN: &str = "legal winner thank year ? sausage worth useful legal winner thank yellow";
+
+    #[test]
+    fn checkpoints_resume_where_they_were_taken() {
+        let search = Search::new(PATTERN, Language::English).unwrap();
+        let all: Vec<String> = search.candidates().map(|mnemonic| mnemonic.phrase()).collect();
+        assert_eq!(all.len(), 130);
+
+        let mut candidates = search.candidates();
+        candidates.by_ref().take(50).for_each(drop);
+        let checkpoint = candidates.checkpoint(50, 0);
+        let text = checkpoint.to_string();
+        assert!(!text.contains("legal"));
+        assert_eq!(text.parse(), Ok(checkpoint.clone()));
+
+        let rest: Vec<String> = search
+            .candidates_from(&checkpoint)
+            .unwrap()
+            .map(|mnemonic| mnemonic.phrase())
+            .collect();
+        assert_eq!(rest, all[50..]);
+
+        // and a finished search's checkpoint has nothing after it:
+        let mut candidates = search.candidates();
+        candidates.by_ref().for_each(drop);
+        let done = candidates.checkpoint(130, 0);
+        assert_eq!(search.candidates_from(&done).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn find_from_a_checkpoint() {
+        let search = Search::new(PATTERN, Language::English).unwrap();
+        let mut candidates = search.candidates();
+        candidates.by_ref().take(100).for_each(drop);
+        let checkpoint = candidates.checkpoint(100, 0);
+
+        // "legal winner thank year wave ..." is the 127th candidate, with an empty passphrase:
+        let target = Target::Fingerprint([0xb8, 0x68, 0x8d, 0xf1]);
+        let mut last = None;
+        let mut reported = Vec::new();
+        let found = search
+            .find_from(&target, "", Some(&checkpoint), &mut |checkpoint, matches| {
+                last = Some(checkpoint.clone());
+                reported.extend(matches.iter().map(Mnemonic::phrase));
+            })
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(reported, [found[0].phrase()]);
+        let last = last.unwrap();
+        assert_eq!((last.checked, last.found), (130, 1));
+    }
+
+    #[test]
+    fn foreign_checkpoints() {
+        let search = Search::new(PATTERN, Language::English).unwrap();
+        let other = Search::new(&PATTERN.replace("sausage", "?"), Language::English).unwrap();
+        let checkpoint = other.candidates().checkpoint(0, 0);
+        assert!(search.candidates_from(&checkpoint).is_err());
+
+        assert_eq!(
+            "bip-39 recover-search checkpoint 1\ncombinations 2048\n".parse::<Checkpoint>(),
+            Err(Error::Other("Not a recover-search checkpoint."))
+        );
+        assert!("".parse::<Checkpoint>().is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// A mnemonic read off a camera ('--features camera') rather than typed in: a SeedQR (either form,
+// see seedqr.rs) or a crypto-seed UR (ur.rs), held up to a webcam.
+//
+//   let mnemonic = scan::scan(Path::new("/dev/video0"), Language::English, Duration::from_secs(60))?;
+//
+// Cameras are read through Video4Linux, so scanning is for Linux only for now; 'read' (a payload
+// some other scanner decoded) works anywhere.  Frames are only ever in memory, and the copy of each
+// made here is wiped once it's been looked at, since a picture of a seed's code is as much the seed
+// as its words are; the driver's buffers, and the black and white copy rqrr searches, are out of
+// reach of that.
+
+use error::Error;
+use language::Language;
+use mnemonic::Mnemonic;
+use seedqr;
+use std::path::Path;
+use std::time::Duration;
+use ur;
+
+// A scanned code's mnemonic, whichever kind of code it is:
+pub fn read(payload: &[u8], language: Language) -> Result<Mnemonic, Error> {
+    if payload.len() > 3 && payload[..3].eq_ignore_ascii_case(b"ur:") {
+        let ur = std::str::from_utf8(payload).map_err(|_| Error::Other("That UR isn't text."))?;
+        ur::read_seed(ur, language)
+    } else {
+        seedqr::read(payload, language)
+    }
+}
+
+// The first SeedQR or crypto-seed the camera at 'device' sees, looking for up to 'timeout'.
+// Other QR codes in view are passed over.
+#[cfg(target_os = "linux")]
+pub fn scan(device: &Path, language: Language, timeout: Duration) -> Result<Mnemonic, Error> {
+    use linuxvideo::format::{PixFormat, PixelFormat};
+    use linuxvideo::Device;
+    use std::time::Instant;
+
+    let device = Device::open(device).map_err(|_| Error::Other("Unable to open the camera."))?;
+    // (the driver picks the size it has closest to this)
+    let capture = device
+        .video_capture(PixFormat::new(1280, 720, PixelFormat::YUYV))
+        .map_err(|_| Error::Other("The camera can't capture video."))?;
+    let format = capture.format();
+    if format.pixel_format() != PixelFormat::YUYV {
+        return Err(Error::Other("The camera doesn't give YUYV frames."));
+    }
+    let (width, height) = (format.width() as usize, format.height() as usize);
+    let stride = format.bytes_per_line() as usize;
+    let mut stream = capture
+        .into_stream()
+        .map_err(|_| Error::Other("The camera can't capture video."))?;
+
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        let frame = stream
+            .dequeue(|yuyv| Ok(Frame::from_yuyv(&yuyv, width, height, stride)))
+            .map_err(|_| Error::Other("The camera stopped sending frames."))?;
+        if let Some(mnemonic) = frame.decode(language) {
+            return Ok(mnemonic);
+        }
+    }
+    Err(Error::Other("No SeedQR or crypto-seed was seen in time."))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn scan(_device: &Path, _language: Language, _timeout: Duration) -> Result<Mnemonic, Error> {
+    Err(Error::Other("Scanning from a camera is only on Linux for now."))
+}
+
+// A frame's brightness, all a QR code needs, wiped when it's dropped:
+struct Frame {
+    luma: zeroize::Zeroizing<Vec<u8>>,
+    width: usize,
+    height: usize,
+}
+
+impl Frame {
+    // (YUYV has every pixel's brightness, and every other pixel's colour: Y U Y V)
+    #[cfg(target_os = "linux")]
+    fn from_yuyv(yuyv: &[u8], width: usize, height: usize, stride: usize) -> Frame {
+        let mut luma = zeroize::Zeroizing::new(vec![0; width * height]);
+        for (row, line) in luma.chunks_mut(width).zip(yuyv.chunks(stride)) {
+            for (pixel, pair) in row.iter_mut().zip(line.chunks(2)) {
+                *pixel = pair[0];
+            }
+        }
+        Frame {
+            luma,
+            width,
+            height,
+        }
+    }
+
+    fn decode(&self, language: Language) -> Option<Mnemonic> {
+        let mut image = rqrr::PreparedImage::prepare_from_greyscale(self.width, self.height, |x, y| {
+            self.luma[y * self.width + x]
+        });
+        for grid in image.detect_grids() {
+            let mut payload = zeroize::Zeroizing::new(Vec::new());
+            if grid.decode_to(&mut *payload).is_ok() {
+                if let Ok(mnemonic) = read(&payload, language) {
+                    return Some(mnemonic);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr::QrCode;
+    use test_vectors;
+
+    // A code drawn the way a camera would see it, four pixels a module with a quiet zone round it:
+    fn photograph(code: &QrCode) -> Frame {
+        let (scale, border) = (4, 4);
+        let width = (code.size() + 2 * border) * scale;
+        let mut luma = zeroize::Zeroizing::new(vec![255; width * width]);
+        for y in 0..code.size() {
+            for x in 0..code.size() {
+                if code.is_dark(x, y) {
+                    for dy in 0..scale {
+                        let start = ((y + border) * scale + dy) * width + (x + border) * scale;
+                        luma[start..start + scale].fill(0);
+                    }
+                }
+            }
+        }
+        Frame {
+            luma,
+            width,
+            height: width,
+        }
+    }
+
+    #[test]
+    fn scanned_codes() {
+        let mnemonic = Mnemonic::parse(test_vectors::BIP39[0].1, Language::English).unwrap();
+        let standard = QrCode::numeric(&seedqr::standard(&mnemonic)).unwrap();
+        let compact = QrCode::bytes(&seedqr::compact(&mnemonic)).unwrap();
+        for code in [standard, compact] {
+            let scanned = photograph(&code).decode(Language::English).unwrap();
+            assert_eq!(scanned.words(), mnemonic.words());
+        }
+
+        // Another code in view is passed over:
+        let other = QrCode::bytes(b"https://example.com").unwrap();
+        assert!(photograph(&other).decode(Language::English).is_none());
+
+        assert_eq!(
+            read(b"UR:SEED/OYADGDLBLBLBLBLBLBLBLBLBLBLBLBLBLBLBLBTKBDTAJN", Language::English)
+                .unwrap()
+                .words(),
+            Mnemonic::parse(test_vectors::BIP39[1].1, Language::English)
+                .unwrap()
+                .words()
+        );
+    }
+}
@@ -0,0 +1,23 @@
+// The version of the JSON documents this crate writes in its own formats: the 'bundle' export, the
+// watch-only address list, BIP-388 wallet policies, generated test vectors, audit log lines,
+// daemon responses, and the command line tool's '--json' output.
+// (Formats other wallets define, such as Coldcard's, Sparrow's and HWI's, are theirs to version,
+// so they're left as they are.)
+//
+// Each of those documents has a top level "schema_version": this number.  Within it, fields are
+// only ever added, never renamed, removed or given a different type, so a script that ignores
+// fields it doesn't know keeps working as fingerprints, descriptors and the like are added.  Any
+// other change bumps it.
+
+use serde_json::Value;
+
+pub const VERSION: u64 = 1;
+
+// The same document with its "schema_version" set (for a JSON object; anything else has nowhere
+// to put one, so it comes back unchanged):
+pub fn versioned(mut document: Value) -> Value {
+    if let Some(object) = document.as_object_mut() {
+        object.insert("schema_version".to_string(), json!(VERSION));
+    }
+    document
+}
@@ -0,0 +1,111 @@
+// A 'tracing' subscriber that wraps another and keeps secrets out of it: any span or event with a
+// field whose name suggests a secret (a mnemonic, passphrase, seed, private key...) is turned away
+// at its callsite, before its values are ever looked at, so the subscriber underneath never
+// records it.  This crate's own spans only carry public fields; the filter is there for the
+// application's (and its other dependencies') spans, where a 'mnemonic = %phrase' can slip in
+// while debugging.
+//
+// It works on field names, so a secret formatted into an event's message ('debug!("{}", phrase)')
+// gets past it; keep secrets out of messages the same way as out of 'println!'.
+//
+//   let subscriber = SecretFilter::new(my_subscriber);
+//   tracing::subscriber::set_global_default(subscriber).unwrap();
+
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, Subscriber};
+
+// Matched against lowercased field names, anywhere in them ("user_passphrase", "seed_hex"):
+pub const SECRET_FIELDS: [&str; 11] = [
+    "entropy",
+    "mnemonic",
+    "passphrase",
+    "password",
+    "phrase",
+    "private",
+    "salt",
+    "secret",
+    "seed",
+    "wif",
+    "xprv",
+];
+
+pub fn is_secret(field: &str) -> bool {
+    let field = field.to_lowercase();
+    SECRET_FIELDS.iter().any(|secret| field.contains(secret))
+}
+
+fn has_secret(metadata: &Metadata) -> bool {
+    metadata.fields().iter().any(|field| is_secret(field.name()))
+}
+
+pub struct SecretFilter<S> {
+    inner: S,
+}
+
+impl<S: Subscriber> SecretFilter<S> {
+    pub fn new(inner: S) -> SecretFilter<S> {
+        SecretFilter { inner }
+    }
+}
+
+impl<S: Subscriber> Subscriber for SecretFilter<S> {
+    // Callsites are registered once, so this is where most of the filtering happens:
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if has_secret(metadata) {
+            Interest::never()
+        } else {
+            self.inner.register_callsite(metadata)
+        }
+    }
+
+    // Checked again here, and for events below, since a subscriber isn't told about callsites
+    // registered before it was set:
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        !has_secret(metadata) && self.inner.enabled(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        self.inner.new_span(span)
+    }
+
+    // Only spans that got past 'enabled' have an id to record into, and they have no secret
+    // fields to record:
+    fn record(&self, span: &Id, values: &Record) {
+        self.inner.record(span, values)
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        self.inner.record_follows_from(span, follows)
+    }
+
+    fn event_enabled(&self, event: &Event) -> bool {
+        !has_secret(event.metadata()) && self.inner.event_enabled(event)
+    }
+
+    fn event(&self, event: &Event) {
+        if !has_secret(event.metadata()) {
+            self.inner.event(event)
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        self.inner.enter(span)
+    }
+
+    fn exit(&self, span: &Id) {
+        self.inner.exit(span)
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.inner.clone_span(id)
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        self.inner.try_close(id)
+    }
+}
@@ -0,0 +1,72 @@
+// Seed XOR, as Coldcard does it: a mnemonic split into parts that are mnemonics themselves, whose
+// entropies XOR back to the original's.  Each part is a valid BIP-39 mnemonic of the same length
+// (with a wallet of its own, decoy funds included if you like), and any one part, or all but one,
+// says nothing about the original: it takes all of them.  That's simpler than SLIP-39, and the
+// parts can be stamped on the same steel plates, but there's no threshold: lose one, lose it all.
+//
+//   let parts = seed_xor::split(&mnemonic, 3)?;
+//   assert_eq!(seed_xor::combine(&parts)?.phrase(), mnemonic.phrase());
+//
+// Coldcard splits 24 word mnemonics (and, in later firmware, 12 and 18); the XOR is the same for
+// any length.
+
+use zeroize::Zeroize;
+
+use entropy::{EntropySource, OsRandom};
+use error::Error;
+use mnemonic::Mnemonic;
+
+// All but the last part are random, and the last is the original's entropy XORed with them.
+pub fn split(mnemonic: &Mnemonic, parts: usize) -> Result<Vec<Mnemonic>, Error> {
+    split_using(&mut OsRandom, mnemonic, parts)
+}
+
+// The same with the random parts drawn from 'source':
+pub fn split_using(
+    source: &mut dyn EntropySource,
+    mnemonic: &Mnemonic,
+    parts: usize,
+) -> Result<Vec<Mnemonic>, Error> {
+    if parts < 2 {
+        return Err(Error::Other("Seed XOR takes at least two parts."));
+    }
+
+    let mut last = mnemonic.entropy();
+    let mut random = vec![0; last.len()];
+    let mut split = Vec::with_capacity(parts);
+    for _ in 1..parts {
+        if let Err(err) = source.fill(&mut random) {
+            random.zeroize();
+            return Err(err.into());
+        }
+        xor_into(&mut last, &random);
+        split.push(Mnemonic::from_entropy_bytes(&random, mnemonic.language())?);
+    }
+    split.push(Mnemonic::from_entropy_bytes(&last, mnemonic.language())?);
+    random.zeroize();
+    Ok(split)
+}
+
+// The parts can come in any order, but they must all be there: one missing (or one from another
+// split) makes a different mnemonic, valid as any, with nothing to tell.  In the first part's
+// language.
+pub fn combine(parts: &[Mnemonic]) -> Result<Mnemonic, Error> {
+    if parts.len() < 2 {
+        return Err(Error::Other("Seed XOR takes at least two parts."));
+    }
+    if parts.iter().any(|part| part.words().len() != parts[0].words().len()) {
+        return Err(Error::Other("The parts must all have the same number of words."));
+    }
+
+    let mut entropy = parts[0].entropy();
+    for part in &parts[1..] {
+        xor_into(&mut entropy, &part.entropy());
+    }
+    Mnemonic::from_entropy_bytes(&entropy, parts[0].language())
+}
+
+fn xor_into(into: &mut [u8], other: &[u8]) {
+    for (byte, other) in into.iter_mut().zip(other) {
+        *byte ^= other;
+    }
+}
@@ -0,0 +1,56 @@
+// SeedQR, SeedSigner's way of handing a mnemonic to a signing device as a QR code, so it's scanned
+// rather than typed in.  There are two forms:
+//
+//   standard  each word's index (0 to 2047) as four digits, in a numeric QR code: 48 digits for
+//             12 words, 96 for 24
+//   compact   the entropy itself, the words' bits without the checksum, in a byte mode QR code:
+//             16 bytes for 12 words, 32 for 24, so the code is a size smaller
+//
+//   let digits = seedqr::standard(&mnemonic);
+//   print!("{}", QrCode::numeric(&digits)?.to_terminal());
+//
+// The payloads are for embedders drawing their own codes; 'qr::QrCode' draws them here, and 'read'
+// takes a scanned one back (see scan.rs).  SeedSigner reads the indices as English words; other
+// wordlists' indices encode the same way, but only tools that know which list it was will read
+// them back as the same words.
+
+use error::Error;
+use language::Language;
+use mnemonic::Mnemonic;
+use word_index;
+use zeroize::Zeroizing;
+
+pub fn standard(mnemonic: &Mnemonic) -> String {
+    mnemonic
+        .words()
+        .iter()
+        // (the words came from the list, so they're in it)
+        .map(|word| format!("{:04}", word_index(word, mnemonic.language()).unwrap()))
+        .collect()
+}
+
+pub fn compact(mnemonic: &Mnemonic) -> Zeroizing<Vec<u8>> {
+    mnemonic.entropy()
+}
+
+// Either form back to its mnemonic, from a scanned code's payload: all digits is the standard form,
+// anything else the compact one.
+pub fn read(payload: &[u8], language: Language) -> Result<Mnemonic, Error> {
+    if payload.is_empty() || !payload.iter().all(u8::is_ascii_digit) {
+        return Mnemonic::from_entropy_bytes(payload, language);
+    }
+    if !payload.len().is_multiple_of(4) {
+        return Err(Error::Other("A standard SeedQR has four digits for each word."));
+    }
+    let indices: Zeroizing<Vec<usize>> = Zeroizing::new(
+        payload
+            .chunks(4)
+            .map(|digits| {
+                digits
+                    .iter()
+                    .fold(0, |index, digit| index * 10 + usize::from(digit - b'0'))
+            })
+            .collect(),
+    );
+    Mnemonic::from_indices(&indices, language)
+}
@@ -0,0 +1,218 @@
+// Serde support, behind the 'serde' feature, for config files, keystores and services built on
+// this crate.  What's public serializes as it is: a 'Language' as its name ("english"), a
+// 'MnemonicListSize' as its word count, an 'ExtendedPubKey' as its xpub.  Everything deserializes
+// as it is too, from the same forms a 'Mnemonic' or an xprv is parsed from ('FromStr').
+//
+// What's secret (a 'Mnemonic', a 'Seed', an 'ExtendedPrivKey') does not implement 'Serialize' at
+// all, so a '#[derive(Serialize)]' struct with one in it fails to compile rather than leaking it
+// into a log line or an API response.  Writing one out takes saying so, with the 'ExposeSecret'
+// wrapper or, inside a derive, 'expose_secret':
+//
+//   serde_json::to_string(&ExposeSecret(&mnemonic))?;
+//
+//   #[derive(Serialize, Deserialize)]
+//   struct Wallet {
+//       #[serde(serialize_with = "bip_39::serde_secret::expose_secret")]
+//       mnemonic: Mnemonic,
+//       words: MnemonicListSize,
+//   }
+//
+// A mnemonic comes out as its phrase, a seed as hex, a private key as its xprv.  The strings made
+// along the way are wiped, but what the serializer writes them into is the caller's to look after.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use zeroize::Zeroize;
+
+use alloc_prelude::*;
+use bip32::{ExtendedPrivKey, ExtendedPubKey};
+use language::Language;
+use mnemonic::Mnemonic;
+use {MnemonicListSize, Seed};
+
+// What 'ExposeSecret' can write out (as text, wiped once the serializer has had it):
+pub trait SerializeSecret {
+    fn serialize_secret<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+// The opt in: 'ExposeSecret(&mnemonic)' serializes the mnemonic; it deserializes whatever 'T'
+// does.  (Its Debug is redacted, as the secrets' own are.)
+pub struct ExposeSecret<T>(pub T);
+
+impl<T: SerializeSecret> Serialize for ExposeSecret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_secret(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ExposeSecret<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ExposeSecret<T>, D::Error> {
+        T::deserialize(deserializer).map(ExposeSecret)
+    }
+}
+
+impl<T> fmt::Debug for ExposeSecret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ExposeSecret([redacted])")
+    }
+}
+
+// For '#[serde(serialize_with = "...")]':
+pub fn expose_secret<T: SerializeSecret, S: Serializer>(
+    secret: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    secret.serialize_secret(serializer)
+}
+
+impl<T: SerializeSecret> SerializeSecret for &T {
+    fn serialize_secret<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize_secret(serializer)
+    }
+}
+
+fn serialize_wiped<S: Serializer>(mut text: String, serializer: S) -> Result<S::Ok, S::Error> {
+    let serialized = serializer.serialize_str(&text);
+    text.zeroize();
+    serialized
+}
+
+impl SerializeSecret for Mnemonic {
+    fn serialize_secret<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_wiped(self.phrase(), serializer)
+    }
+}
+
+impl SerializeSecret for Seed {
+    fn serialize_secret<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_wiped(self.to_hex(), serializer)
+    }
+}
+
+impl SerializeSecret for ExtendedPrivKey {
+    fn serialize_secret<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_wiped(self.to_string(), serializer)
+    }
+}
+
+// Parses a string with 'parse', wiping it if the deserializer handed over one of its own (a
+// borrowed one is the deserializer's input, and the caller's):
+struct Parsed<T, F> {
+    expecting: &'static str,
+    parse: F,
+    parsed: PhantomData<T>,
+}
+
+impl<'de, T, E: fmt::Display, F: FnOnce(&str) -> Result<T, E>> Visitor<'de> for Parsed<T, F> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<Err: de::Error>(self, text: &str) -> Result<T, Err> {
+        (self.parse)(text).map_err(Err::custom)
+    }
+
+    fn visit_string<Err: de::Error>(self, mut text: String) -> Result<T, Err> {
+        let parsed = self.visit_str(&text);
+        text.zeroize();
+        parsed
+    }
+}
+
+fn parse_str<'de, D, T, E, F>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: F,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    E: fmt::Display,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    deserializer.deserialize_str(Parsed {
+        expecting,
+        parse,
+        parsed: PhantomData,
+    })
+}
+
+// In any embedded wordlist, as with '"...".parse()':
+impl<'de> Deserialize<'de> for Mnemonic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Mnemonic, D::Error> {
+        parse_str(deserializer, "a BIP-39 mnemonic", Mnemonic::from_str)
+    }
+}
+
+impl<'de> Deserialize<'de> for Seed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Seed, D::Error> {
+        parse_str(deserializer, "a 64 byte seed in hex", |text| {
+            let mut bytes = hex::decode(text).map_err(|_| "A seed must be hex.")?;
+            let seed = Seed::from_bytes(&bytes).map_err(|err| err.message());
+            bytes.zeroize();
+            seed
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtendedPrivKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ExtendedPrivKey, D::Error> {
+        parse_str(
+            deserializer,
+            "a Base58Check extended private key",
+            ExtendedPrivKey::from_str,
+        )
+    }
+}
+
+impl Serialize for ExtendedPubKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtendedPubKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ExtendedPubKey, D::Error> {
+        parse_str(
+            deserializer,
+            "a Base58Check extended public key",
+            ExtendedPubKey::from_str,
+        )
+    }
+}
+
+// A custom wordlist has no name to be read back by, so it's an error rather than "custom":
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Language::Custom(_) = self {
+            return Err(ser::Error::custom(
+                "A custom wordlist can't be serialized by name.",
+            ));
+        }
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Language, D::Error> {
+        parse_str(deserializer, "a wordlist language", Language::from_str)
+    }
+}
+
+impl Serialize for MnemonicListSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for MnemonicListSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MnemonicListSize, D::Error> {
+        let words = u8::deserialize(deserializer)?;
+        MnemonicListSize::from(words as usize).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,69 @@
+// A derivation session for the daemon mode: the root key, recreated once, answering one JSON
+// request after another from other local processes, so a setup session (wallet software, scripts
+// checking addresses) doesn't need the mnemonic typed in for every step.  Only public data ever
+// goes back out: fingerprints, xpubs, addresses and verification results, never a private key.
+//
+// Requests are objects with a "method":
+//   {"method": "fingerprint"}
+//   {"method": "xpub", "path": "m/84'/0'/0'"}
+//   {"method": "address", "path": "m/84'/0'/0'/0/0", "script_type": "p2wpkh"}
+//   {"method": "verify_message", "address": "bc1...", "message": "...", "signature": "..."}
+// and every response is an object (with a "schema_version"; see 'schema'), with an "error" in it if
+// the request couldn't be answered.
+
+use serde_json::Value;
+
+use address;
+use bip32::{DerivationPath, ExtendedPrivKey};
+use descriptor::ScriptType;
+use message;
+use schema;
+
+pub struct Session {
+    root: ExtendedPrivKey,
+}
+
+impl Session {
+    pub fn new(root: ExtendedPrivKey) -> Session {
+        Session { root }
+    }
+
+    pub fn respond(&self, request: &Value) -> Value {
+        schema::versioned(self.answer(request).unwrap_or_else(|err| json!({ "error": err })))
+    }
+
+    fn answer(&self, request: &Value) -> Result<Value, &'static str> {
+        match field(request, "method")? {
+            "fingerprint" => Ok(json!({ "fingerprint": hex::encode(self.root.fingerprint()) })),
+            "xpub" => {
+                let xpub = self.derive(request)?.to_extended_pub_key();
+                Ok(json!({ "xpub": xpub.to_string() }))
+            }
+            "address" => {
+                let script_type: ScriptType = field(request, "script_type")?.parse()?;
+                let key = self.derive(request)?;
+                let address =
+                    address::for_script_type(script_type, &key.public_key(), self.root.network)?;
+                Ok(json!({ "address": address }))
+            }
+            "verify_message" => {
+                let valid = message::verify(
+                    field(request, "address")?,
+                    field(request, "message")?.as_bytes(),
+                    field(request, "signature")?,
+                )?;
+                Ok(json!({ "valid": valid }))
+            }
+            _ => Err("Unknown method."),
+        }
+    }
+
+    fn derive(&self, request: &Value) -> Result<ExtendedPrivKey, &'static str> {
+        let path: DerivationPath = field(request, "path")?.parse()?;
+        Ok(self.root.derive_path(&path)?)
+    }
+}
+
+fn field<'a>(request: &'a Value, name: &str) -> Result<&'a str, &'static str> {
+    request[name].as_str().ok_or("A request field is missing, or isn't a string.")
+}
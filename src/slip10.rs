@@ -0,0 +1,214 @@
+// SLIP-0010: BIP-32 style hierarchical keys for curves other than secp256k1.  The master key
+// comes from HMAC-SHA512 keyed with a per-curve string, and where BIP-32 gives up on an invalid
+// key, SLIP-0010 hashes again until it finds a valid one.  For secp256k1 the result is exactly
+// BIP-32's, which is why bip32.rs builds its master key here too.
+//
+// Ed25519 only has hardened derivation: there's no way to derive its child public keys without
+// the private key.
+
+use ed25519_dalek::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+
+use core::fmt;
+
+use zeroize::Zeroize;
+
+use alloc_prelude::*;
+use bip32::{ChildNumber, DerivationPath};
+use error::Error;
+use hash::hmac_sha512;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    Secp256k1,
+    NistP256,
+    Ed25519,
+}
+
+impl Curve {
+    // The HMAC key the master key is derived with:
+    fn seed_key(&self) -> &'static [u8] {
+        match self {
+            Curve::Secp256k1 => b"Bitcoin seed",
+            Curve::NistP256 => b"Nist256p1 seed",
+            Curve::Ed25519 => b"ed25519 seed",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ExtendedKey {
+    pub curve: Curve,
+    pub depth: u8,
+    pub child_number: ChildNumber,
+    pub chain_code: [u8; 32],
+    // Kept private; use 'secret_key_bytes' to get at it deliberately.
+    secret_key: [u8; 32],
+}
+
+impl ExtendedKey {
+    pub fn new_master(seed: &[u8], curve: Curve) -> Result<ExtendedKey, Error> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err(Error::SeedLengthOutOfRange(seed.len()));
+        }
+
+        let mut output = hmac_sha512(curve.seed_key(), seed);
+        loop {
+            let (il, ir) = split(&output);
+            if curve == Curve::Ed25519 || is_valid_scalar(curve, &il) {
+                output.zeroize();
+                return Ok(ExtendedKey {
+                    curve,
+                    depth: 0,
+                    child_number: ChildNumber::from(0),
+                    chain_code: ir,
+                    secret_key: il,
+                });
+            }
+            output = hmac_sha512(curve.seed_key(), &output);
+        }
+    }
+
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedKey, Error> {
+        if self.curve == Curve::Ed25519 && !child.is_hardened() {
+            return Err(Error::HardenedOnly);
+        }
+
+        let mut data = Vec::with_capacity(37);
+        if child.is_hardened() {
+            data.push(0);
+            data.extend(&self.secret_key);
+        } else {
+            data.extend(&self.public_key());
+        }
+        data.extend(&u32::from(child).to_be_bytes());
+
+        let mut output = hmac_sha512(&self.chain_code, &data);
+        loop {
+            let (il, ir) = split(&output);
+            let secret_key = match self.curve {
+                Curve::Ed25519 => Some(il),
+                curve => add_scalars(curve, &il, &self.secret_key),
+            };
+
+            if let Some(secret_key) = secret_key {
+                // (for a hardened child, 'data' has the parent's private key in it)
+                data.zeroize();
+                output.zeroize();
+                return Ok(ExtendedKey {
+                    curve: self.curve,
+                    depth: self.depth.checked_add(1).ok_or(Error::MaxDepthExceeded)?,
+                    child_number: child,
+                    chain_code: ir,
+                    secret_key,
+                });
+            }
+
+            // An invalid child: try again with 0x01 || IR || index.
+            data.clear();
+            data.push(1);
+            data.extend(&ir);
+            data.extend(&u32::from(child).to_be_bytes());
+            output = hmac_sha512(&self.chain_code, &data);
+        }
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedKey, Error> {
+        let mut key = self.clone();
+        for child in path.children() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+
+    // The 33 byte public key: compressed SEC1 for the ECDSA curves, and a 0x00 byte followed by
+    // the 32 byte key for Ed25519, as SLIP-0010 writes it.
+    pub fn public_key(&self) -> [u8; 33] {
+        let mut public_key = [0; 33];
+        match self.curve {
+            Curve::Secp256k1 => {
+                let secret_key = k256::SecretKey::from_slice(&self.secret_key)
+                    .expect("SLIP-0010 only produces valid secp256k1 keys.");
+                public_key
+                    .copy_from_slice(secret_key.public_key().to_encoded_point(true).as_bytes());
+            }
+            Curve::NistP256 => {
+                let secret_key = p256::SecretKey::from_slice(&self.secret_key)
+                    .expect("SLIP-0010 only produces valid P-256 keys.");
+                public_key
+                    .copy_from_slice(secret_key.public_key().to_encoded_point(true).as_bytes());
+            }
+            Curve::Ed25519 => {
+                let signing_key = SigningKey::from_bytes(&self.secret_key);
+                public_key[1..].copy_from_slice(signing_key.verifying_key().as_bytes());
+            }
+        }
+        public_key
+    }
+
+    pub fn secret_key_bytes(&self) -> [u8; 32] {
+        self.secret_key
+    }
+}
+
+// The private key is wiped when the key goes, and left out of '{:?}' (as is the chain code, which
+// with any child's private key gives this one's):
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.secret_key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl fmt::Debug for ExtendedKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedKey")
+            .field("curve", &self.curve)
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .field("chain_code", &"[redacted]")
+            .field("secret_key", &"[redacted]")
+            .finish()
+    }
+}
+
+fn split(output: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut il = [0; 32];
+    let mut ir = [0; 32];
+    il.copy_from_slice(&output[..32]);
+    ir.copy_from_slice(&output[32..]);
+    (il, ir)
+}
+
+// Whether 'bytes' is a usable private key, i.e. in 1..n for the curve's order n:
+fn is_valid_scalar(curve: Curve, bytes: &[u8; 32]) -> bool {
+    match curve {
+        Curve::Secp256k1 => k256::SecretKey::from_slice(bytes).is_ok(),
+        Curve::NistP256 => p256::SecretKey::from_slice(bytes).is_ok(),
+        Curve::Ed25519 => true,
+    }
+}
+
+// (tweak + key) mod n, or None if 'tweak' is out of range or the sum is zero:
+fn add_scalars(curve: Curve, tweak: &[u8; 32], key: &[u8; 32]) -> Option<[u8; 32]> {
+    let sum: [u8; 32] = match curve {
+        Curve::Secp256k1 => {
+            let tweak = Option::<k256::Scalar>::from(k256::Scalar::from_repr((*tweak).into()))?;
+            let key = Option::<k256::Scalar>::from(k256::Scalar::from_repr((*key).into()))?;
+            (tweak + key).to_bytes().into()
+        }
+        Curve::NistP256 => {
+            let tweak = Option::<p256::Scalar>::from(p256::Scalar::from_repr((*tweak).into()))?;
+            let key = Option::<p256::Scalar>::from(p256::Scalar::from_repr((*key).into()))?;
+            (tweak + key).to_bytes().into()
+        }
+        Curve::Ed25519 => return None,
+    };
+
+    if is_valid_scalar(curve, &sum) {
+        Some(sum)
+    } else {
+        None
+    }
+}
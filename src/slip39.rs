@@ -0,0 +1,555 @@
+// SLIP-39 (Shamir's secret sharing, as Trezor writes it): a secret split into share mnemonics,
+// any 'threshold' of which put it back together, while fewer say nothing about it at all.  Shares
+// can come in groups, each with a threshold of its own ("2 of the 3 family shares, or 3 of the 5
+// friends'"), and a threshold of groups on top.
+//
+// What's split here is a BIP-39 mnemonic's entropy, so combining the shares gives back the same
+// words (and with the BIP-39 passphrase, the same wallet):
+//
+//   let groups = [Group { threshold: 3, count: 5 }];
+//   let shares = slip39::split(&mnemonic.entropy(), "", 1, &groups, 1)?;
+//   let entropy = slip39::combine(&shares[0][..3], "")?;
+//
+// The steps are the spec's: the secret is encrypted (a four round Feistel network over
+// PBKDF2-HMAC-SHA256, under the SLIP-39 passphrase, empty by default), split into group shares,
+// and each of those into member shares, with a digest share among them so a wrong share is caught
+// when they're combined rather than making a wrong secret.  Each share is a mnemonic of 10 bit
+// words from SLIP-39's own list of 1,024: an identifier and the thresholds, the share itself, and
+// a Reed-Solomon checksum that catches any typo in up to three words.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use zeroize::Zeroize;
+
+use entropy;
+use error::Error;
+use hash::{hmac_sha256, pbkdf2_hmac_sha256};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Group {
+    // How many of the group's shares it takes:
+    pub threshold: u8,
+    // How many there are:
+    pub count: u8,
+}
+
+// "3-of-5":
+impl FromStr for Group {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Group, Error> {
+        let (threshold, count) = text.split_once("-of-").ok_or(Error::Other("A group is written as T-of-N."))?;
+        Ok(Group {
+            threshold: threshold.parse().map_err(|_| Error::Other("A group is written as T-of-N."))?,
+            count: count.parse().map_err(|_| Error::Other("A group is written as T-of-N."))?,
+        })
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-of-{}", self.threshold, self.count)
+    }
+}
+
+// One share mnemonic, taken apart.  Everything but the share's value is the same for (at least)
+// every share in its group, and is what 'combine' checks they agree on.
+pub struct Share {
+    // Random, the same for every share of one split:
+    pub identifier: u16,
+    // Whether the encryption leaves the identifier out, so more splits of the same secret can be
+    // made with it later ("shamir_extendable", rather than "shamir", customizes the checksum):
+    pub extendable: bool,
+    // PBKDF2 runs 10,000 << iteration_exponent rounds in all:
+    pub iteration_exponent: u8,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+    value: Vec<u8>,
+}
+
+// The most groups, and the most shares in a group, the 4 bit fields leave room for:
+const MAX_SHARES: u8 = 16;
+// The header's 40 bits and the checksum's 30, in words:
+const HEADER_WORDS: usize = 4;
+const CHECKSUM_WORDS: usize = 3;
+// A 128 bit secret's shares, the shortest there are:
+const MIN_WORDS: usize = HEADER_WORDS + 13 + CHECKSUM_WORDS;
+// Where 'split_secret' puts the digest share and the secret itself, out of the way of the shares:
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+
+impl Share {
+    pub fn to_mnemonic(&self) -> String {
+        let mut bits = Vec::new();
+        push_bits(&mut bits, self.identifier as usize, 15);
+        push_bits(&mut bits, self.extendable as usize, 1);
+        push_bits(&mut bits, self.iteration_exponent as usize, 4);
+        push_bits(&mut bits, self.group_index as usize, 4);
+        push_bits(&mut bits, self.group_threshold as usize - 1, 4);
+        push_bits(&mut bits, self.group_count as usize - 1, 4);
+        push_bits(&mut bits, self.member_index as usize, 4);
+        push_bits(&mut bits, self.member_threshold as usize - 1, 4);
+        // The value is padded on the left, with zeros, to a whole number of words:
+        let value_bits = self.value.len() * 8;
+        push_bits(&mut bits, 0, value_bits.div_ceil(10) * 10 - value_bits);
+        for &byte in &self.value {
+            push_bits(&mut bits, byte as usize, 8);
+        }
+
+        let mut indices: Vec<u16> = bits
+            .chunks(10)
+            .map(|word| word.iter().fold(0, |acc, &bit| acc << 1 | bit as u16))
+            .collect();
+        bits.zeroize();
+        let checksum = create_checksum(customization(self.extendable), &indices);
+        indices.extend_from_slice(&checksum);
+        let mnemonic = indices.iter().map(|&index| words()[index as usize]).collect::<Vec<_>>();
+        indices.zeroize();
+        mnemonic.join(" ")
+    }
+
+    fn from_indices(indices: &[u16]) -> Result<Share, Error> {
+        if indices.len() < MIN_WORDS {
+            return Err(Error::Other("A SLIP-39 share has at least 20 words."));
+        }
+        let mut bits: Vec<bool> = indices
+            .iter()
+            .flat_map(|&index| (0..10).rev().map(move |i| index >> i & 1 == 1))
+            .collect();
+        let field = |start: usize, len: usize| {
+            bits[start..start + len].iter().fold(0, |acc, &bit| acc << 1 | bit as usize)
+        };
+        let extendable = field(15, 1) == 1;
+        if !verify_checksum(customization(extendable), indices) {
+            bits.zeroize();
+            return Err(Error::Other("Invalid SLIP-39 share checksum."));
+        }
+
+        // What's between the header and the checksum is the value, at most 8 bits (less than a
+        // byte) of padding in front of a whole number of 16 bit halves:
+        let value_bits = (indices.len() - HEADER_WORDS - CHECKSUM_WORDS) * 10;
+        let padding = value_bits % 16;
+        let start = HEADER_WORDS * 10 + padding;
+        if padding > 8 || bits[HEADER_WORDS * 10..start].iter().any(|&bit| bit) {
+            bits.zeroize();
+            return Err(Error::Other("Invalid SLIP-39 share padding."));
+        }
+        let value = bits[start..start + value_bits - padding]
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+            .collect();
+
+        let share = Share {
+            identifier: field(0, 15) as u16,
+            extendable,
+            iteration_exponent: field(16, 4) as u8,
+            group_index: field(20, 4) as u8,
+            group_threshold: field(24, 4) as u8 + 1,
+            group_count: field(28, 4) as u8 + 1,
+            member_index: field(32, 4) as u8,
+            member_threshold: field(36, 4) as u8 + 1,
+            value,
+        };
+        bits.zeroize();
+        if share.group_threshold > share.group_count {
+            return Err(Error::Other("A SLIP-39 share's group threshold is more than its groups."));
+        }
+        Ok(share)
+    }
+}
+
+impl FromStr for Share {
+    type Err = Error;
+
+    fn from_str(mnemonic: &str) -> Result<Share, Error> {
+        let mut indices = Vec::new();
+        for word in mnemonic.split_whitespace() {
+            let word = word.to_lowercase();
+            let index = words().binary_search(&word.as_str()).map_err(|_| {
+                indices.zeroize();
+                "A word isn't in the SLIP-39 wordlist."
+            })?;
+            indices.push(index as u16);
+        }
+        let share = Share::from_indices(&indices);
+        indices.zeroize();
+        share
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("identifier", &self.identifier)
+            .field("group_index", &self.group_index)
+            .field("member_index", &self.member_index)
+            .field("value", &"[redacted]")
+            .finish()
+    }
+}
+
+// The shares for each group, in order.  'secret' is 16 bytes or more, an even number of them (any
+// BIP-39 entropy is); 'passphrase' is SLIP-39's own, printable ASCII, and needed again to combine.
+pub fn split(
+    secret: &[u8],
+    passphrase: &str,
+    group_threshold: u8,
+    groups: &[Group],
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<Share>>, Error> {
+    if secret.len() < 16 || !secret.len().is_multiple_of(2) {
+        return Err(Error::Other("A SLIP-39 secret is at least 16 bytes, and an even number of them."));
+    }
+    check_passphrase(passphrase)?;
+    if iteration_exponent > 15 {
+        return Err(Error::Other("The iteration exponent is at most 15."));
+    }
+    if groups.is_empty() || groups.len() > MAX_SHARES as usize {
+        return Err(Error::Other("There have to be 1 to 16 groups."));
+    }
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(Error::Other("The group threshold has to be 1 up to the number of groups."));
+    }
+    for group in groups {
+        if group.threshold == 0 || group.count > MAX_SHARES || group.threshold > group.count {
+            return Err(Error::Other("A group's threshold has to be 1 up to its count, of at most 16."));
+        }
+        // (a share that's a whole group's secret would be that secret, copied)
+        if group.threshold == 1 && group.count > 1 {
+            return Err(Error::Other("A group of more than one share needs a threshold of at least 2."));
+        }
+    }
+
+    let identifier = u16::from_be_bytes(entropy::random()) & 0x7fff;
+    let mut encrypted = encrypt(secret, passphrase, iteration_exponent, identifier, false);
+    let group_secrets = split_secret(group_threshold, groups.len() as u8, &encrypted);
+    encrypted.zeroize();
+
+    Ok(group_secrets
+        .into_iter()
+        .zip(groups)
+        .map(|((group_index, mut group_secret), group)| {
+            let members = split_secret(group.threshold, group.count, &group_secret);
+            group_secret.zeroize();
+            members
+                .into_iter()
+                .map(|(member_index, value)| Share {
+                    identifier,
+                    extendable: false,
+                    iteration_exponent,
+                    group_index,
+                    group_threshold,
+                    group_count: groups.len() as u8,
+                    member_index,
+                    member_threshold: group.threshold,
+                    value,
+                })
+                .collect()
+        })
+        .collect())
+}
+
+// The secret back from enough shares: a threshold's worth from each of a threshold's worth of
+// groups, all from the same split.  Extra shares are fine, and ignored.
+pub fn combine(shares: &[Share], passphrase: &str) -> Result<Vec<u8>, Error> {
+    check_passphrase(passphrase)?;
+    let first = shares.first().ok_or(Error::Other("No shares."))?;
+    if shares.iter().any(|share| {
+        share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exponent != first.iteration_exponent
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.len() != first.value.len()
+    }) {
+        return Err(Error::Other("The shares aren't all from the same split."));
+    }
+
+    // Each group's member shares, by group index, without repeats:
+    let mut groups: Vec<(u8, Vec<&Share>)> = Vec::new();
+    for share in shares {
+        let members = match groups.iter().position(|&(index, _)| index == share.group_index) {
+            Some(position) => &mut groups[position].1,
+            None => {
+                groups.push((share.group_index, Vec::new()));
+                &mut groups.last_mut().unwrap().1
+            }
+        };
+        if members.iter().any(|member| member.member_threshold != share.member_threshold) {
+            return Err(Error::Other("The shares aren't all from the same split."));
+        }
+        match members.iter().find(|member| member.member_index == share.member_index) {
+            Some(member) if member.value != share.value => {
+                return Err(Error::Other("Two shares have the same index, but not the same value."))
+            }
+            Some(_) => {}
+            None => members.push(share),
+        }
+    }
+
+    let mut group_secrets: Vec<(u8, Vec<u8>)> = Vec::new();
+    for (group_index, members) in groups {
+        let threshold = members[0].member_threshold;
+        if members.len() < threshold as usize {
+            continue;
+        }
+        let members: Vec<(u8, &[u8])> = members
+            .iter()
+            .take(threshold as usize)
+            .map(|member| (member.member_index, &member.value[..]))
+            .collect();
+        match recover_secret(threshold, &members) {
+            Ok(secret) => group_secrets.push((group_index, secret)),
+            Err(err) => {
+                wipe(&mut group_secrets);
+                return Err(err);
+            }
+        }
+    }
+    let encrypted = if group_secrets.len() < first.group_threshold as usize {
+        Err(Error::Other("There aren't enough shares to combine."))
+    } else {
+        let parts: Vec<(u8, &[u8])> = group_secrets
+            .iter()
+            .take(first.group_threshold as usize)
+            .map(|(index, secret)| (*index, &secret[..]))
+            .collect();
+        recover_secret(first.group_threshold, &parts)
+    };
+    wipe(&mut group_secrets);
+
+    let mut encrypted = encrypted?;
+    let secret = decrypt(
+        &encrypted,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+    );
+    encrypted.zeroize();
+    Ok(secret)
+}
+
+fn wipe(secrets: &mut [(u8, Vec<u8>)]) {
+    for (_, secret) in secrets.iter_mut() {
+        secret.zeroize();
+    }
+}
+
+fn check_passphrase(passphrase: &str) -> Result<(), Error> {
+    if passphrase.bytes().all(|byte| (0x20..=0x7e).contains(&byte)) {
+        Ok(())
+    } else {
+        Err(Error::Other("A SLIP-39 passphrase can only have printable ASCII characters."))
+    }
+}
+
+// SLIP-39's wordlist, sorted, so it's binary searched:
+fn words() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&str>> = OnceLock::new();
+
+    WORDS.get_or_init(|| include_str!("../wordlists/slip39.txt").lines().collect())
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    }
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: usize, len: usize) {
+    bits.extend((0..len).rev().map(|i| value >> i & 1 == 1));
+}
+
+// The spec's checksum, a Reed-Solomon code over GF(1024) by way of its polymod over the
+// customization string's bytes and the words:
+fn polymod(values: impl Iterator<Item = u32>) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+        0x21b1f890, 0x3f3f120,
+    ];
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 20;
+        (0..10)
+            .filter(|i| top >> i & 1 == 1)
+            .fold((checksum & 0xfffff) << 10 ^ value, |checksum, i| checksum ^ GENERATOR[i])
+    })
+}
+
+fn verify_checksum(customization: &[u8], indices: &[u16]) -> bool {
+    let values = customization.iter().map(|&byte| byte as u32);
+    polymod(values.chain(indices.iter().map(|&index| index as u32))) == 1
+}
+
+fn create_checksum(customization: &[u8], indices: &[u16]) -> [u16; 3] {
+    let values = customization.iter().map(|&byte| byte as u32);
+    let values = values.chain(indices.iter().map(|&index| index as u32)).chain([0; 3]);
+    let checksum = polymod(values) ^ 1;
+    [2, 1, 0].map(|i| (checksum >> (10 * i) & 1023) as u16)
+}
+
+// GF(256) as AES has it (x^8 + x^4 + x^3 + x + 1), by way of exponent and logarithm tables over
+// the generator 3:
+struct Tables {
+    exp: [u8; 255],
+    log: [u8; 256],
+}
+
+const TABLES: Tables = {
+    let mut tables = Tables { exp: [0; 255], log: [0; 256] };
+    let mut power: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        tables.exp[i] = power as u8;
+        tables.log[power as usize] = i as u8;
+        // (times 3 is times 2, plus itself, with the x^8 term reduced)
+        power ^= power << 1;
+        if power & 0x100 != 0 {
+            power ^= 0x11b;
+        }
+        i += 1;
+    }
+    tables
+};
+
+// The polynomial through 'points' (x, and a y for each byte of the secret), at 'x', one byte at
+// a time by Lagrange's formula, in logarithms: adding is XOR, so is subtracting.
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some(&(_, y)) = points.iter().find(|&&(xi, _)| xi == x) {
+        return y.to_vec();
+    }
+    let log = |value: u8| TABLES.log[value as usize] as usize;
+    let log_product: usize = points.iter().map(|&(xi, _)| log(xi ^ x)).sum();
+    let mut result = vec![0; points[0].1.len()];
+    for &(xi, y) in points {
+        let log_denominator: usize =
+            points.iter().filter(|&&(xj, _)| xj != xi).map(|&(xj, _)| log(xj ^ xi)).sum();
+        let log_basis = (log_product + 255 * points.len() - log(xi ^ x) - log_denominator) % 255;
+        for (byte, &value) in result.iter_mut().zip(y) {
+            if value != 0 {
+                *byte ^= TABLES.exp[(log(value) + log_basis) % 255];
+            }
+        }
+    }
+    result
+}
+
+// 'count' shares of 'secret', any 'threshold' of which make it back; the first threshold - 2 are
+// random, and the rest are the polynomial through them, the digest share and the secret.
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    if threshold == 1 {
+        return (0..count).map(|index| (index, secret.to_vec())).collect();
+    }
+    let random = |len: usize| {
+        let mut bytes = vec![0; len];
+        entropy::fill_random(&mut bytes);
+        bytes
+    };
+    let mut shares: Vec<(u8, Vec<u8>)> =
+        (0..threshold - 2).map(|index| (index, random(secret.len()))).collect();
+    let mut digest_share = random(secret.len());
+    let digest = hmac_sha256(&digest_share[4..], secret);
+    digest_share[..4].copy_from_slice(&digest[..4]);
+
+    let mut points: Vec<(u8, &[u8])> =
+        shares.iter().map(|(index, value)| (*index, &value[..])).collect();
+    points.push((DIGEST_INDEX, &digest_share));
+    points.push((SECRET_INDEX, secret));
+    let rest: Vec<(u8, Vec<u8>)> =
+        (threshold - 2..count).map(|index| (index, interpolate(&points, index))).collect();
+    digest_share.zeroize();
+    shares.extend(rest);
+    shares
+}
+
+// The secret at SECRET_INDEX, if the digest share agrees with it:
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> Result<Vec<u8>, Error> {
+    if threshold == 1 {
+        return Ok(shares[0].1.to_vec());
+    }
+    let mut secret = interpolate(shares, SECRET_INDEX);
+    let mut digest_share = interpolate(shares, DIGEST_INDEX);
+    let digest = hmac_sha256(&digest_share[4..], &secret);
+    let matches = digest[..4] == digest_share[..4];
+    digest_share.zeroize();
+    if !matches {
+        secret.zeroize();
+        return Err(Error::Other("The shares don't go together: their digest doesn't match."));
+    }
+    Ok(secret)
+}
+
+fn encrypt(
+    secret: &[u8],
+    passphrase: &str,
+    exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    feistel(secret, passphrase, exponent, identifier, extendable, [0, 1, 2, 3])
+}
+
+fn decrypt(
+    secret: &[u8],
+    passphrase: &str,
+    exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    feistel(secret, passphrase, exponent, identifier, extendable, [3, 2, 1, 0])
+}
+
+// Four rounds, each XORing one half with PBKDF2 of the other (salted with "shamir" and the
+// identifier, unless the shares are extendable), and swapping them; decrypting is the same rounds
+// backwards.
+fn feistel(
+    secret: &[u8],
+    passphrase: &str,
+    exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: [u8; 4],
+) -> Vec<u8> {
+    let half = secret.len() / 2;
+    let mut left = secret[..half].to_vec();
+    let mut right = secret[half..].to_vec();
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(b"shamir");
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let salt_len = salt.len();
+
+    for round in rounds {
+        let mut password = vec![round];
+        password.extend_from_slice(passphrase.as_bytes());
+        salt.truncate(salt_len);
+        salt.extend_from_slice(&right);
+        let mut stream = vec![0; half];
+        pbkdf2_hmac_sha256(&password, &salt, 2500 << exponent, &mut stream);
+        for (byte, key) in left.iter_mut().zip(&stream) {
+            *byte ^= key;
+        }
+        password.zeroize();
+        stream.zeroize();
+        std::mem::swap(&mut left, &mut right);
+    }
+    salt.zeroize();
+
+    right.extend_from_slice(&left);
+    left.zeroize();
+    right
+}
@@ -0,0 +1,96 @@
+// How guessable a BIP-39 passphrase is.  Whoever finds the mnemonic can try passphrases offline,
+// as fast as they can run PBKDF2's 2,048 rounds, so a passphrase is only worth having if it takes
+// more guesses than that makes practical.  The estimate is zxcvbn's: dictionary words, names,
+// keyboard walks, dates, repeats, sequences and l33t spellings are each guessed the way a cracker
+// would, and whatever's left is charged for its length and character classes.  The words of
+// wallets themselves ("bitcoin", "satoshi", "trezor"...) are a dictionary of their own here, since
+// they're the first guesses of anyone trying a found mnemonic.
+//
+//   let strength = bip_39::estimate_passphrase_strength(&passphrase);
+//   if strength.is_weak() {
+//       println!("Guessed in {}: {:?}", strength.crack_time, strength.warning);
+//   }
+
+use zxcvbn::zxcvbn;
+
+// The score of a passphrase that takes more than 10^8 guesses (more than three hours at 10^4 a
+// second); anything less is weak:
+pub const MIN_SCORE: u8 = 3;
+
+const WALLET_WORDS: [&str; 16] = [
+    "bip39",
+    "bitcoin",
+    "btc",
+    "coldcard",
+    "crypto",
+    "electrum",
+    "ethereum",
+    "hodl",
+    "ledger",
+    "mnemonic",
+    "nakamoto",
+    "passphrase",
+    "satoshi",
+    "seed",
+    "trezor",
+    "wallet",
+];
+
+#[derive(Clone, Debug)]
+pub struct PassphraseStrength {
+    // zxcvbn's 0 to 4: guessed within 10^3 guesses, 10^6, 10^8, 10^10, or not:
+    pub score: u8,
+    pub guesses_log10: f64,
+    // How long the guesses take at 10^4 a second, a slow hash's rate, as PBKDF2's is: "3 hours",
+    // "centuries".
+    pub crack_time: String,
+    // What makes it guessable ("This is a top-10 common password."), and what would help:
+    pub warning: Option<String>,
+    pub suggestions: Vec<String>,
+}
+
+impl PassphraseStrength {
+    pub fn is_weak(&self) -> bool {
+        self.score < MIN_SCORE
+    }
+}
+
+// (An empty passphrase scores 0, which is as much as it protects.)
+pub fn estimate_passphrase_strength(passphrase: &str) -> PassphraseStrength {
+    let estimate = zxcvbn(passphrase, &WALLET_WORDS);
+    let feedback = estimate.feedback();
+    PassphraseStrength {
+        score: estimate.score().into(),
+        guesses_log10: estimate.guesses_log10(),
+        crack_time: estimate.crack_times().offline_slow_hashing_1e4_per_second().to_string(),
+        warning: feedback.and_then(|feedback| feedback.warning()).map(|warning| warning.to_string()),
+        suggestions: feedback.map_or_else(Vec::new, |feedback| {
+            feedback.suggestions().iter().map(ToString::to_string).collect()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wallet_words_and_crack_times() {
+        // Each of the wallet words is a first guess, however it's capitalized:
+        for word in WALLET_WORDS.iter() {
+            let strength = estimate_passphrase_strength(&word.to_uppercase());
+            assert!(strength.is_weak(), "{}", word);
+            assert!(strength.guesses_log10 < 2.0, "{}", word);
+        }
+
+        // Times are at a slow hash's rate of 10^4 guesses a second:
+        let weak = estimate_passphrase_strength("trezor");
+        assert_eq!(weak.score, 0);
+        assert_eq!(weak.crack_time, "less than a second");
+        assert!(!weak.suggestions.is_empty());
+
+        let strong = estimate_passphrase_strength("quartz pelican 47 drizzle unmoored ferry");
+        assert_eq!(strong.crack_time, "centuries");
+        assert_eq!(strong.warning, None);
+    }
+}
@@ -0,0 +1,65 @@
+// BIP-341's key tweak: a taproot output key is the internal key plus a tweak that commits to
+// the (optional) script tree, so key path spends and any scripts share one output.  With no
+// scripts (BIP-86), the commitment is to the key alone.
+//
+// The merkle root is that of whatever script tree is being built on a derived key; the parity
+// that comes back with the output key goes into the control block of script path spends.
+
+use k256::elliptic_curve::ops::MulByGenerator;
+use k256::elliptic_curve::point::AffineCoordinates;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{schnorr, AffinePoint, ProjectivePoint, Scalar, SecretKey};
+
+use hash::tagged_hash;
+
+// The x-only output key for an x-only internal key, and whether its y coordinate is odd:
+pub fn taproot_tweak(
+    internal_key: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<([u8; 32], bool), &'static str> {
+    // BIP-340 x-only keys stand for the point with an even y coordinate:
+    let internal = schnorr::VerifyingKey::from_bytes(internal_key)
+        .map_err(|_| "Invalid taproot internal key.")?;
+    let tweak = tweak(internal_key, merkle_root)?;
+
+    let output = (ProjectivePoint::mul_by_generator(&tweak)
+        + ProjectivePoint::from(*internal.as_affine()))
+    .to_affine();
+    if output == AffinePoint::IDENTITY {
+        return Err("Invalid taproot tweak.");
+    }
+    Ok((output.x().into(), output.y_is_odd().into()))
+}
+
+// The private key for the output key, to sign key path spends with:
+pub fn tweak_secret_key(
+    secret_key: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<[u8; 32], &'static str> {
+    let secret_key = SecretKey::from_slice(secret_key).map_err(|_| "Invalid private key.")?;
+    let public_key = secret_key.public_key().to_encoded_point(true);
+    let mut internal_key = [0; 32];
+    internal_key.copy_from_slice(&public_key.as_bytes()[1..]);
+
+    // The internal key has to be the even one, so an odd key's secret is negated first:
+    let mut scalar = *secret_key.to_nonzero_scalar();
+    if public_key.as_bytes()[0] == 0x03 {
+        scalar = -scalar;
+    }
+    let tweaked = scalar + tweak(&internal_key, merkle_root)?;
+    if bool::from(tweaked.is_zero()) {
+        return Err("Invalid taproot tweak.");
+    }
+    Ok(tweaked.to_bytes().into())
+}
+
+// t = hash_TapTweak(internal key || merkle root), which has to be below the curve order:
+fn tweak(internal_key: &[u8; 32], merkle_root: Option<&[u8; 32]>) -> Result<Scalar, &'static str> {
+    let mut data = internal_key.to_vec();
+    if let Some(merkle_root) = merkle_root {
+        data.extend(merkle_root);
+    }
+    let hash = tagged_hash("TapTweak", &data);
+    Option::from(Scalar::from_repr(hash.into())).ok_or("Invalid taproot tweak.")
+}
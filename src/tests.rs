@@ -0,0 +1,3782 @@
+// Reference vectors guarding the entropy -> mnemonic bit slicing (and, for English, the rest of
+// the pipeline). The English set is the upstream Trezor one, 'test_vectors::BIP39'. There are no
+// upstream vectors for the other wordlists, so those are (entropy, mnemonic) pairs for the same
+// entropy values, produced by an independent implementation (rust-bitcoin's bip39 crate) with the
+// same official wordlists.
+//
+// Only one wordlist is compiled in at a time, so 'cargo test -F japanese' and friends check the
+// other languages.
+
+use super::*;
+use std::convert::{TryFrom, TryInto};
+
+const ENGLISH: [(&str, &str, &str, &str); 24] = test_vectors::BIP39;
+
+const CHINESE_SIMPLIFIED: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "的 的 的 的 的 的 的 的 的 的 的 在",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "枪 疫 霉 尝 俩 闹 饿 贤 枪 疫 霉 卿",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "壤 对 据 人 三 谈 我 表 壤 对 据 不",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 逻",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 动",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "枪 疫 霉 尝 俩 闹 饿 贤 枪 疫 霉 尝 俩 闹 饿 贤 枪 殿",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "壤 对 据 人 三 谈 我 表 壤 对 据 人 三 谈 我 表 壤 民",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 裕",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 性",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "枪 疫 霉 尝 俩 闹 饿 贤 枪 疫 霉 尝 俩 闹 饿 贤 枪 疫 霉 尝 俩 闹 饿 搭",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "壤 对 据 人 三 谈 我 表 壤 对 据 人 三 谈 我 表 壤 对 据 人 三 谈 我 五",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 佳",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "蒙 台 脱 纪 构 硫 浆 霉 感 仅 鱼 汤",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "父 泥 炼 胁 鞋 控 载 政 惨 逐 整 碗 环 惯 案 棒 订 移",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "宁 照 违 材 交 养 违 野 悉 偷 梅 设 贵 帝 鲜 仰 圈 首 荷 钩 隙 抓 养 熟",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "伐 旱 泡 口 线 揭 县 杨 断 芳 额 件",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "福 惜 怀 叔 筋 酵 货 科 牙 冒 辈 罩 悬 耕 浇 呵 连 级",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "仪 未 九 茶 队 梯 妇 孤 托 病 泉 贺 产 绘 吹 测 局 碳 征 墨 晶 帮 息 延",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "济 扶 块 言 穗 定 万 绘 姻 逃 颗 焰",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "虑 铺 目 祸 英 钩 尤 添 醇 嘛 触 独 起 赋 连 剪 邦 中",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "而 怕 夏 客 盖 古 松 面 解 谓 鲜 唯 障 烯 共 吴 永 丁 赤 副 醒 分 猛 埔",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "昏 途 所 够 请 乃 风 一 雕 缺 垫 阀",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "瓶 顾 床 圈 倡 励 炭 柄 且 招 价 紧 折 将 乎 硬 且 空",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "柄 需 固 姆 色 斥 霍 握 宾 琴 况 团 抵 经 摸 郭 沙 鸣 拖 妙 阳 辈 掉 迁",
+    ),
+];
+
+const CHINESE_TRADITIONAL: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "的 的 的 的 的 的 的 的 的 的 的 在",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "槍 疫 黴 嘗 倆 鬧 餓 賢 槍 疫 黴 卿",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "壤 對 據 人 三 談 我 表 壤 對 據 不",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 邏",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 動",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "槍 疫 黴 嘗 倆 鬧 餓 賢 槍 疫 黴 嘗 倆 鬧 餓 賢 槍 殿",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "壤 對 據 人 三 談 我 表 壤 對 據 人 三 談 我 表 壤 民",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 裕",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 的 性",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "槍 疫 黴 嘗 倆 鬧 餓 賢 槍 疫 黴 嘗 倆 鬧 餓 賢 槍 疫 黴 嘗 倆 鬧 餓 搭",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "壤 對 據 人 三 談 我 表 壤 對 據 人 三 談 我 表 壤 對 據 人 三 談 我 五",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 歇 佳",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "蒙 台 脫 紀 構 硫 漿 黴 感 僅 魚 湯",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "父 泥 煉 脅 鞋 控 載 政 慘 逐 整 碗 環 慣 案 棒 訂 移",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "寧 照 違 材 交 養 違 野 悉 偷 梅 設 貴 帝 鮮 仰 圈 首 荷 鉤 隙 抓 養 熟",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "伐 旱 泡 口 線 揭 縣 楊 斷 芳 額 件",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "福 惜 懷 叔 筋 酵 貨 科 牙 冒 輩 罩 懸 耕 澆 呵 連 級",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "儀 未 九 茶 隊 梯 婦 孤 托 病 泉 賀 產 繪 吹 測 局 碳 徵 墨 晶 幫 息 延",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "濟 扶 塊 言 穗 定 萬 繪 姻 逃 顆 焰",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "慮 鋪 目 禍 英 鉤 尤 添 醇 嘛 觸 獨 起 賦 連 剪 邦 中",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "而 怕 夏 客 蓋 古 松 面 解 謂 鮮 唯 障 烯 共 吳 永 丁 赤 副 醒 分 猛 埔",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "昏 途 所 夠 請 乃 風 一 雕 缺 墊 閥",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "瓶 顧 床 圈 倡 勵 炭 柄 且 招 價 緊 折 將 乎 硬 且 空",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "柄 需 固 姆 色 斥 霍 握 賓 琴 況 團 抵 經 摸 郭 沙 鳴 拖 妙 陽 輩 掉 遷",
+    ),
+];
+
+const CZECH: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace agrese",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec znak uznat zubr",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma akce",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zticha",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace balonek",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec zmije",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma amputace bidlo jedle arogance butik obvinit bezinka",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zlehka",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace branka",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec znak uznat zubovina zeman skupina zrcadlo veskrze",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma amputace bidlo jedle arogance cihla",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zavolat",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "pokoj jogurt malovat kroupa holub malvice rachot uznat hnout kasa karamel potupa",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "masakr odtok pijavice tajga upravit krmelec krvinka buditel zavinit lakomec flirt traktor kresba plevel kapitola tlupa paseka ladnost",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "migrace iluze pukavec kaktus drogerie hrobka pukavec onehdy suchar veterina rorejs cukr mihule koza makovice uvozovka oklika inzerce odhadce zprudka spousta namluvit hrobka obsluha",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "sledovat ticho potkan dotaz carevna pahorek ihned nikterak hematom pokrok neochota cvik",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "most uvolnit novota usmrtit terapie tehdy litovat filozof radon svrab surovina zdivo stanice pejsek ukrojit vymizet helma decibel",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "poloha koruna dobytek makak domluvit svatba paluba utahovat orlice jakost sypat podvod barva technika pastelka kurt ikona obvod mokro recept napnout kabel lord nasadit",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "drak uniforma kuna kapka tmel bedna evoluce technika vypustit popadat rychlost vodstvo",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "ochladit silnice exkurze zrnitost jiskra zprudka pstruh tlukot vytasit valoun najisto kralovat bobek uklidnit helma ubytovna pysk andulka",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "bavlna mozaika ofsajd kukla obliba kormidlo monarcha batoh chmura mdloba makovice obilnice popel pohnutka duchovno panika neuron okupant rukavice kouzlo stehno badatel sklenice rozchod",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "zajet nutrie beton kobyla kriket sranda elektron abeceda uboze lump vzorek potvora",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "slezina navzdory odjinud oklika ucho ropucha rohovka zavalit graf panenka invalida katedra odebrat deska metoda ohryzek graf flotila",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "zavalit genetika kapusta tvrdost dopad ujmout zdobit mistr splav ptactvo fosfor hoch pocit beztak slon poplach mazivo tancovat pravda uvalit konkurs surovina nazvat vypadat",
+    ),
+];
+
+const FRENCH: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "implorer visage sonnette voyage véloce pourpre volaille tribunal implorer visage sonnette voyelle",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "indexer acompte bolide abrasif agréable dédale abusif appuyer indexer acompte bolide abolir",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie voter",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser adéquat",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "implorer visage sonnette voyage véloce pourpre volaille tribunal implorer visage sonnette voyage véloce pourpre volaille tribunal implorer vinaigre",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "indexer acompte bolide abrasif agréable dédale abusif appuyer indexer acompte bolide abrasif agréable dédale abusif appuyer indexer agencer",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie viande",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser anaphore",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "implorer visage sonnette voyage véloce pourpre volaille tribunal implorer visage sonnette voyage véloce pourpre volaille tribunal implorer visage sonnette voyage véloce pourpre volaille studieux",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "indexer acompte bolide abrasif agréable dédale abusif appuyer indexer acompte bolide abrasif agréable dédale abusif appuyer indexer acompte bolide abrasif agréable dédale abusif axiome",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie zoologie valable",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "monument dépenser féroce entasser comédie ferveur optique sonnette codifier discuter dioxyde nerveux",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "fiasco ivoire mardi révulsif signal enlever envahir anormal vaisseau essayer céleste sagesse engager mener différer ruisseau lutter esprit",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "flatteur cultiver oisillon destrier brusque crainte oisillon labourer remède substrat parfumer banquier flèche enclave fémur sombre jongler damier insigne voguer rasage gomme crainte incendie",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "prélude routine négation brasier arlequin logique cuivre hiberner cirque moqueur halte barque",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "froid soluble horde sinistre rouge rocheux exiler causer orbite résineux renfort vaste récolter maison serrure tonique cirer bélier",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "mouche embryon bison femme bondir renvoi louer social largeur déborder rétablir miracle adresse rivière machine époque culminer indice frégate ouvrage gourmand déposer exulter grappin",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "brochure sextuple épisode digérer ruser affecter cantine rivière torse muscle permuter talisman",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "informer poivre capable volcan dénicher voguer offenser ruiner tragique sortir glace enduire allouer serein cirer semaine opportun abriter",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "adverbe fuite jaune épaule imbiber éluder frémir adulte attentif filou fémur idylle muséum mobile bureau loyal hélium jugement péplum encadrer rédiger acier posséder pavillon",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "ultrason hublot agacer éclore englober ravin caféine abandon séduire farfelu tropical nettoyer",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "prétexte grogner instinct jongler sembler paresse papier vaillant chenille louve cynique dissiper inoculer besogne flairer jeunesse chenille cellule",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "vaillant chance dimanche sécable bonus séparer vecteur forcer raideur officier censurer cohésion meuble agiter prison mutation filière rincer novice solitude élargir renfort gronder tornade",
+    ),
+];
+
+const ITALIAN: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abete",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "mimosa vita sussurro zinco vero saltare zattera ulisse mimosa vita sussurro zircone",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "misurare afoso bravura accadere alogeno dottore acrilico arazzo misurare afoso bravura abisso",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zerbino",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco agitare",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "mimosa vita sussurro zinco vero saltare zattera ulisse mimosa vita sussurro zinco vero saltare zattera ulisse mimosa virulento",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "misurare afoso bravura accadere alogeno dottore acrilico arazzo misurare afoso bravura accadere alogeno dottore acrilico arazzo misurare allievo",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa vile",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco angelo",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "mimosa vita sussurro zinco vero saltare zattera ulisse mimosa vita sussurro zinco vero saltare zattera ulisse mimosa vita sussurro zinco vero saltare zattera tarpare",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "misurare afoso bravura accadere alogeno dottore acrilico arazzo misurare afoso bravura accadere alogeno dottore acrilico arazzo misurare afoso bravura accadere alogeno dottore acrilico baco",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa zuppa vedetta",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "pesista educare imballo formica curvo imbevuto raddoppio sussurro croce eppure epilogo poligono",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "immolato mummia oviparo sigla stirpe fonetico fosso appetito vasca galoppo cigno solubile foderato pargolo enduro sociale ormeggio galateo",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "infatti dire pudica elica camola deposito pudica nobile servire taverna restauro baritono inflitto flacone ilare suonare nastrare dito montato vulcano scrutinio lisca deposito mirtillo",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "sarto smottato podismo burlone aria omissione dipolo marmo coricato peso malto basso",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "italia sultano meccanico strappo smeraldo sipario gommone chimera raffica sforzato sfamato vendemmia segnalato oscurare staffa trio cordata benda",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "piacere feudo bisonte ignorato brevetto sfida onorevole stufo nulla docente sfuso perbene albo sinusoide orologio fulmine diradare mitezza iride rata londra egoismo gravoso luce",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "calmo statuto fucsia energia sodale aliante cedibile sinusoide trovare pila rinnovo tiro",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "modulo rubizzo cefalo zavorra economia vulcano prudente soccorso tuta svedese limitare fluente amico srotolato cordata sportivo querela accusato",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "alcolico lacrima muto frigo michele fessura irrigato alce ateismo incendio ilare metallo pilifero pergamena canotto opposto manovra nemmeno rimorchio fisico selettivo aforisma sabotato riciclato",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "utopia melodia allegro evoluto folata scuola carisma abbaglio spillato guanto unificato pollice",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "satira lusinga mordere nastrare sposo responso replica varcato colza opinione distanza erario monetario bici india narice colza cilindro",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "varcato codice enzima spessore brillante squillo vento insieme scoprire prugna circa cruciale peccato allusivo savio pilota inarcare simulato precluso sugo fegato sfamato lusso trono",
+    ),
+];
+
+const JAPANESE: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あおぞら",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "そつう れきだい ほんやく わかす りくつ ばいか ろせん やちん そつう れきだい ほんやく わかめ",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "そとづら あまど おおう あこがれる いくぶん けいけん あたえる いよく そとづら あまど おおう あかちゃん",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "われる われる われる われる われる われる われる われる われる われる われる ろんぶん",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あらいぐま",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "そつう れきだい ほんやく わかす りくつ ばいか ろせん やちん そつう れきだい ほんやく わかす りくつ ばいか ろせん やちん そつう れいぎ",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "そとづら あまど おおう あこがれる いくぶん けいけん あたえる いよく そとづら あまど おおう あこがれる いくぶん けいけん あたえる いよく そとづら いきなり",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる りんご",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん いってい",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "そつう れきだい ほんやく わかす りくつ ばいか ろせん やちん そつう れきだい ほんやく わかす りくつ ばいか ろせん やちん そつう れきだい ほんやく わかす りくつ ばいか ろせん まんきつ",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "そとづら あまど おおう あこがれる いくぶん けいけん あたえる いよく そとづら あまど おおう あこがれる いくぶん けいけん あたえる いよく そとづら あまど おおう あこがれる いくぶん けいけん あたえる うめる",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる われる らいう",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "ておくれ げざん しねま こりる きぼう しねん ななおし ほんやく きない けむり けまり てんない",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "しはつ たいちょう ちめいど ひりつ ほくろ こやく こんかい いひん よろしい さくら がはく ふっかつ こまる つごう けぬき ふすま ちから さくし",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "しやくしょ くちこみ どんぶり けつじょ おとしもの くうぐん どんぶり たずさわる ひたむき みうち にほん うわさ しゃけん このよ じどう ほめる たいよう くふう そんちょう ろくが はんこ せあぶら くうぐん そっこう",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "はいち ふかい てんすう おさない いろえんぴつ だんち くださる せんちょう きさらぎ てきとう せもたれ うんどう",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "すいえい ほとんど せんやく ほしい ふうふ ひんそう ざんしょ がちょう なにわ ひはん ひつじゅひん られつ はんぼうき ちそう ほいく めだつ きさま えがお",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "てそう こつこつ えんちょう じてん おおや ぴっちり だんねつ ほそく たなばた くらべる ひまん ていき あんい ひんしゅ ちきん ざいげん くたびれる そなえる しんか にいがた せきむ けしょう しあさって せたい",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "おたく ほうりつ さいかい げねつ ふせい いいだす かいてん ひんしゅ もえる てのひら ねいき むいか",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "そむく のぞく かいふく ろてん げきやく ろくが ともだち ふじみ やおや まかせる すらすら こぼれる いぜん へんたい きさま へきが なたでここ あさひ",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "あんぜん すうじつ たいふう こんぽん そこそこ こたつ しんせいじ あんこ うしなう しまる じどう そうり てはい ていし おめでとう たんまつ せんげん たおる ぬめり このまま ひいき あまい のらねこ にんそう",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "ようきゅう そあく いきおい こうつう こもじ はんだん おんしゃ あいさつ へいたく しすう ゆうびんきょく てんぷら",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "はえる せっさたくま そんみん たいよう へこむ になう にっさん よゆう きあつ だんぼう くねくね けらい そんけい えほうまき しゃうん たいむ きあつ かぶか",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "よゆう かんけい けぶかい へいこう おかず べんごし りえき じゆう はんい ともる かほご きぬごし つみき いきる はかる てふだ しほう ひろう とくてん ほったん こさめ ひつじゅひん せつぞく めんどう",
+    ),
+];
+
+const KOREAN: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가능",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "실장 활동 큰절 흔적 형제 제대로 훈련 한글 실장 활동 큰절 흔히",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "실현 감소 기법 가상 걱정 무슨 가족 공간 실현 감소 기법 가득",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 흑백",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 강도",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "실장 활동 큰절 흔적 형제 제대로 훈련 한글 실장 활동 큰절 흔적 형제 제대로 훈련 한글 실장 환갑",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "실현 감소 기법 가상 걱정 무슨 가족 공간 실현 감소 기법 가상 걱정 무슨 가족 공간 실현 거액",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 화살",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 계단",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "실장 활동 큰절 흔적 형제 제대로 훈련 한글 실장 활동 큰절 흔적 형제 제대로 훈련 한글 실장 활동 큰절 흔적 형제 제대로 훈련 통로",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "실현 감소 기법 가상 걱정 무슨 가족 공간 실현 감소 기법 가상 걱정 무슨 가족 공간 실현 감소 기법 가상 걱정 무슨 가족 구속",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 힘껏 허용",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "원고 물질 생일 부산 마요네즈 생활 일찍 큰절 동화책 반성 반드시 의식",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "서비스 알코올 오로지 착각 카운터 부근 부정 고양이 허락 비디오 단맛 체온 본인 완전 바람 철학 영하 비닐",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "설렁탕 모범 일곱 민간 낙엽 매스컴 일곱 언어 지진 통장 잠시 국제 설문 복도 생방송 큰딸 약수 목소리 아직 횡단보도 중독 수필 매스컴 실컷",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "제주도 처음 의견 김밥 공부 연출 모델 시장 대합실 원래 시금치 군인",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "소음 큰길 식당 커튼 책임 창구 사흘 다양성 일행 질병 진급 혈액 증가 예산 치약 하룻밤 대한민국 그날",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "월급 보너스 금고 생물 기분 진리 열매 콘서트 에어컨 몸무게 집중 우체국 강제 창고 영혼 분필 모든 심리 소나기 자랑 순서 미디어 삼십 술집",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "나들이 침대 분야 바이러스 첫날 개선 논문 창고 하필 윗사람 저고리 팩스",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "씨름 정도 놀이 훨씬 물결 횡단보도 인천 철저히 학비 킬로 수염 본격적 결심 취업 대한민국 출연 일정 가운데",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "개구리 속담 액수 북한 실내 병아리 소망 같이 관찰 선물 생방송 신용 유난히 운반 남대문 열차 시설 양주 재판 보편적 증세 감기 정오 장미",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "해결 식초 거실 백성 볼펜 중세 냄새 가끔 출근 상인 한번 의심",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "제한 스위치 아프리카 약수 출입 잠수함 잔디 향상 당장 열정 목록 반장 아시아 그토록 선풍기 약간 당장 단순",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "향상 담배 박수 추측 기술 충분히 협력 성적 줄무늬 인체 단위 딸아이 왼손 거짓 조깅 유명 석사 참석 이야기 크림 변동 진급 스케이트 하지만",
+    ),
+];
+
+const PORTUGUESE: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abater",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador vinheta sogro xingar",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abduzir",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido xeque",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate acumular",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador viga",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador afastar",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido viaduto",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate alinhar",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador vinheta sogro xerife veleiro pomar volumoso sucata",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abotoar afivelar coruja abutre asilado",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido validade",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "mexicano crosta farpa empolgar chatice fartura olaria sogro centeio defesa dedal multar",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "fazenda invasor magreza repolho separado emenda enchente amassar valente enxerto cachorro roteiro eliminar marinho deboche rodovia locutor envolver",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "filho comentar obrigado cunhado biologia clone obrigado julgador rebolar sufixo pacote atingir filtrar edital fardado siri janta conhecer inibido vogal quiosque genoma clone impor",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "pote ringue muda benzer andaime levitar comando guloso carreira micro grilo atracar",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "frieza sirene hibernar setembro rigidez retalho exagero cabana oliveira refugiar recrutar vazar ramal lousa segmento tocha carpete autoria",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "mimado drible baioneta fantoche bastante redonda ligeiro simpatia lamber copeiro reitor mensagem adeus resumir lombo enraizar combinar inapto fosco orfanato gincana cubano exibir global",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "bifocal semanal enlatar debulhar roedor adquirir branco resumir tora modular patrono tangente",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "indeciso pires braveza vontade criada vogal nutrir rodeio toxina soletrar gelo elaborar ajudar sediado carpete sarjeta oficina abreviar",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "adjetivo fugir irritado engenho iluminar dourado fralda aditivo aprovar ferrugem fardado ignorado moeda mesada bisneto linda guache jejum pastel ecologia rapel acionado pneu palmada",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "turbo hoje aeronave diagrama embargo rachar bochecha abaixo sanidade extinto tridente mundial",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "povoar gorjeta injetar janta saturar paciente ovelha vaidoso cancelar limpador confuso degelo inflamar avisar ficheiro italiano cancelar cacique",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "vaidoso calota decote sambar batida seda vazio flora queda nuvem cadeado certeiro matinal afetivo praxe moinho feno resgatar nervoso sintonia dobrador recrutar gorro tonel",
+    ),
+];
+
+const SPANISH: [(&str, &str); 24] = [
+    (
+        "00000000000000000000000000000000",
+        "ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco abierto",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "ligero vista talar yogur venta queso yacer trozo ligero vista talar zafiro",
+    ),
+    (
+        "80808080808080808080808080808080",
+        "lino admitir bolero abrir álbum dejar acelga aprender lino admitir bolero abogado",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffff",
+        "zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo yodo",
+    ),
+    (
+        "000000000000000000000000000000000000000000000000",
+        "ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco afición",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "ligero vista talar yogur venta queso yacer trozo ligero vista talar yogur venta queso yacer trozo ligero violín",
+    ),
+    (
+        "808080808080808080808080808080808080808080808080",
+        "lino admitir bolero abrir álbum dejar acelga aprender lino admitir bolero abrir álbum dejar acelga aprender lino alacrán",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo viejo",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ancla",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "ligero vista talar yogur venta queso yacer trozo ligero vista talar yogur venta queso yacer trozo ligero vista talar yogur venta queso yacer teatro",
+    ),
+    (
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "lino admitir bolero abrir álbum dejar acelga aprender lino admitir bolero abrir álbum dejar acelga aprender lino admitir bolero abrir álbum dejar acelga aumento",
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo zurdo varón",
+    ),
+    (
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "obra diadema gorila farmacia colgar gorra pausa talar cocina duda dragón optar",
+    ),
+    (
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "gráfico madera muro rutina suelo falso favor añadir variar firma casco semana fácil neón don sección morder fingir",
+    ),
+    (
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "guion culebra parcela diluir buitre crecer parcela marzo roce tejado picar azafrán guitarra exilio goloso tabla mando curar loco voto reparto insecto crecer lince",
+    ),
+    (
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "ración sapo opción brinco árbol mismo cueva lamer cigarro obrero júpiter azufre",
+    ),
+    (
+        "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+        "honor tabique lata sultán sanidad salón gafas carga payaso rostro rojizo vena retrato móvil soplar trabajo cifra balde",
+    ),
+    (
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+        "océano eterno bestia golfo bomba ron moda sur médula danza rueda núcleo agrio salmón morir ficha cuidar linterna higiene pensar iris diario ganso jamón",
+    ),
+    (
+        "23db8160a31d3e0dca3688ed941adbf3",
+        "bucle sótano fibra donar seco aire campo salmón trato odio poco tierra",
+    ),
+    (
+        "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+        "llaga pudor candil yate detalle voto papá saxofón tribu talla infiel exponer altivo sonoro cifra solapa pata abuso",
+    ),
+    (
+        "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+        "águila hoyo maldad fértil libertad estilo historia agudo asilo grosor goloso leopardo odisea nueve butaca molde lacio mañana plomo exento rey adicto puño piña",
+    ),
+    (
+        "f30f8c1da665478f49b001d94c5fc452",
+        "urbe lección ajuste enero faena reptil caimán abdomen sobre genio túnel óptica",
+    ),
+    (
+        "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+        "rama jeringa logro mando soldado pezuña pésimo vampiro cerrar mojar cupón dueño llover barro guerra mambo cerrar casero",
+    ),
+    (
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+        "vampiro célula dos simio bono sondeo vencer haz remar papel castor codo nivel alarma rapaz ofensa gripe sagaz otro tabaco esfuerzo rojizo jinete traje",
+    ),
+];
+
+// The same precedence Language::compiled uses to pick the default wordlist:
+fn get_active_vectors() -> Vec<(&'static str, &'static str)> {
+    let vectors: &[(&str, &str)] = if cfg!(feature = "chinese_traditional") {
+        &CHINESE_TRADITIONAL
+    } else if cfg!(feature = "czech") {
+        &CZECH
+    } else if cfg!(feature = "japanese") {
+        &JAPANESE
+    } else if cfg!(feature = "korean") {
+        &KOREAN
+    } else if cfg!(feature = "spanish") {
+        &SPANISH
+    } else if cfg!(feature = "chinese_simplified") {
+        &CHINESE_SIMPLIFIED
+    } else if cfg!(feature = "french") {
+        &FRENCH
+    } else if cfg!(feature = "italian") {
+        &ITALIAN
+    } else if cfg!(feature = "portuguese") {
+        &PORTUGUESE
+    } else {
+        return ENGLISH
+            .iter()
+            .map(|&(entropy, mnemonic, _, _)| (entropy, mnemonic))
+            .collect();
+    };
+    vectors.to_vec()
+}
+
+fn is_english() -> bool {
+    Language::compiled() == Language::English
+}
+
+#[test]
+fn mnemonic_from_entropy() {
+    for (entropy, mnemonic) in get_active_vectors() {
+        let entropy = hex::decode(entropy).unwrap();
+        assert_eq!(
+            get_mnemonic_from_entropy(&entropy, Language::compiled()),
+            mnemonic
+        );
+    }
+}
+
+// Every wordlist is there at runtime, whichever one is the default:
+#[test]
+fn mnemonic_from_entropy_in_every_language() {
+    let english: Vec<_> = ENGLISH
+        .iter()
+        .map(|&(entropy, mnemonic, _, _)| (entropy, mnemonic))
+        .collect();
+    for &language in Language::all().iter() {
+        let vectors: &[(&str, &str)] = match language {
+            Language::English => &english,
+            Language::ChineseSimplified => &CHINESE_SIMPLIFIED,
+            Language::ChineseTraditional => &CHINESE_TRADITIONAL,
+            Language::Czech => &CZECH,
+            Language::French => &FRENCH,
+            Language::Italian => &ITALIAN,
+            Language::Japanese => &JAPANESE,
+            Language::Korean => &KOREAN,
+            Language::Portuguese => &PORTUGUESE,
+            Language::Spanish => &SPANISH,
+            Language::Custom(_) => unreachable!(),
+        };
+        for &(entropy, mnemonic) in vectors {
+            let entropy = hex::decode(entropy).unwrap();
+            assert_eq!(get_mnemonic_from_entropy(&entropy, language), mnemonic);
+            assert_eq!(
+                mnemonic::Mnemonic::parse(mnemonic, language)
+                    .unwrap()
+                    .entropy()[..],
+                entropy
+            );
+            if let Ok(bytes) = <[u8; 16]>::try_from(&entropy[..]) {
+                let entropy = entropy::Entropy::from(bytes);
+                assert_eq!(entropy.word_count() as usize, 12);
+                assert_eq!(
+                    mnemonic::Mnemonic::from_entropy(&entropy, language).phrase(),
+                    mnemonic
+                );
+            }
+        }
+        assert_eq!(language.to_string().parse(), Ok(language));
+    }
+
+    assert_eq!(word_list(Language::Japanese)[0], "あいこくしん");
+    assert_eq!(word_list(Language::English)[2047], "zoo");
+    assert_eq!(word_index("zoo", Language::English), Some(2047));
+    assert_eq!(word_index("abandon", Language::English), Some(0));
+    assert_eq!(word_index("zoo", Language::French), None);
+
+    // Four letters are enough for any English word; "act" starts "action", "actor", ... as well:
+    assert_eq!(Language::English.complete("aban"), Some("abandon"));
+    assert_eq!(Language::English.complete("act"), Some("act"));
+    assert_eq!(Language::English.complete("ac"), None);
+    assert_eq!(Language::English.complete("abandonx"), None);
+}
+
+#[test]
+fn mnemonic_formatting() {
+    let mnemonic = ENGLISH[2].1;
+    assert_eq!(
+        display::MnemonicFormat::default().render(mnemonic),
+        mnemonic
+    );
+
+    let format = display::MnemonicFormat {
+        separator: "-".to_string(),
+        words_per_line: Some(6),
+        group_size: Some(3),
+        numbered: false,
+        highlight_checksum: false,
+        paged: false,
+    };
+    assert_eq!(
+        format.render(mnemonic),
+        "letter-advice-cage   absurd-amount-doctor\nacoustic-avoid-letter   advice-cage-above"
+    );
+
+    let numbered = display::MnemonicFormat {
+        separator: "  ".to_string(),
+        words_per_line: Some(4),
+        group_size: None,
+        numbered: true,
+        highlight_checksum: true,
+        paged: false,
+    };
+    assert_eq!(
+        numbered.render(mnemonic).lines().next(),
+        Some(" 1. letter     2. advice     3. cage       4. absurd")
+    );
+    assert!(numbered
+        .render(mnemonic)
+        .ends_with("12. \x1B[1;4mabove\x1B[0m"));
+
+    // Double-width CJK, with the wordlists' decomposed accents, voicing marks and jamo taking none:
+    assert_eq!(display::width("abandon"), 7);
+    assert_eq!(display::width("a\u{301}baco"), 5);
+    assert_eq!(
+        display::width("\u{3051}\u{3099}\u{3064}\u{308C}\u{3044}"),
+        8
+    );
+    assert_eq!(display::width(Language::Korean.word_list()[0]), 4);
+    assert_eq!(
+        display::width(Language::ChineseSimplified.word_list()[0]),
+        2
+    );
+    assert_eq!(display::pad("的", 4), "的  ");
+}
+
+#[test]
+fn explain_trace() {
+    let (_, mnemonic, seed, root) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+    let trace = explain::trace(&config, Language::English).unwrap();
+
+    assert!(trace.contains("entropy (128 bits): 00000000000000000000000000000000"));
+    assert!(trace.contains("last 4 bits are 0011 (valid)"));
+    assert!(trace.contains(&format!("seed: {}", seed)));
+    assert!(trace.ends_with(root));
+}
+
+#[test]
+fn pasted_mnemonic_normalization() {
+    assert_eq!(
+        normalize_mnemonic(concat!(
+            "\u{FEFF}Abandon\u{200B} ａｂａｎｄｏｎ\u{00A0}\u{00A0}",
+            "aban\u{00AD}don\u{3000}about \n"
+        )),
+        "abandon abandon abandon about"
+    );
+    assert_eq!(normalize_mnemonic(" \u{200D} "), "");
+}
+
+#[test]
+fn ideographic_space_separators() {
+    let (_, mnemonic) = JAPANESE[0];
+    let seed = |mnemonic: &str| {
+        Config::from_phrase(mnemonic, "")
+            .unwrap()
+            .to_seed()
+            .to_string()
+    };
+    assert_eq!(seed(&mnemonic.replace(' ', "\u{3000}")), seed(mnemonic));
+    assert_eq!(
+        seed(&mnemonic.replacen(' ', " \u{3000}\t", 3)),
+        seed(mnemonic)
+    );
+}
+
+#[test]
+fn nfkd_before_pbkdf2() {
+    // The first of the Japanese vectors (bip32JP's), whose passphrase starts with "㍍", which NFKD
+    // makes "メートル":
+    let (_, mnemonic) = JAPANESE[0];
+    let passphrase = "㍍ガバヴァぱばぐゞちぢ十人十色";
+    let seed = "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c\
+                467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55";
+    let config = Config::from_phrase(mnemonic, passphrase).unwrap();
+    assert_eq!(config.to_seed().to_hex(), seed);
+    let mut into = [0; 64];
+    config.derive_seed_into(&mut into);
+    assert_eq!(hex::encode(&into[..]), seed);
+    let spelled_out = "メートルガバヴァぱばぐゞちぢ十人十色".to_string();
+    let config = Config::from_phrase(mnemonic, spelled_out).unwrap();
+    assert_eq!(config.to_seed().to_hex(), seed);
+
+    // Accents typed as one character (NFC, as most keyboards do) still find their words:
+    let (_, mnemonic) = FRENCH[1];
+    let composed: String = mnemonic.replace("e\u{301}", "\u{e9}");
+    assert_ne!(composed, mnemonic);
+    let parsed = Mnemonic::parse(&composed, Language::French).unwrap();
+    assert_eq!(parsed.phrase(), mnemonic);
+    assert_eq!(
+        Config::from_phrase(composed, "")
+            .unwrap()
+            .to_seed()
+            .to_hex(),
+        Config::from_phrase(mnemonic, "")
+            .unwrap()
+            .to_seed()
+            .to_hex()
+    );
+}
+
+#[test]
+fn seed_from_mnemonic() {
+    if !is_english() {
+        return;
+    }
+    for &(_, mnemonic, seed, _) in ENGLISH.iter() {
+        let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+        assert_eq!(config.to_seed().to_string(), seed);
+
+        // The old constructor, size and all, still agrees:
+        let size = MnemonicListSize::from(mnemonic.split(' ').count()).unwrap();
+        #[allow(deprecated)]
+        let config = Config::new(mnemonic.to_string(), size, "TREZOR".to_string());
+        assert_eq!(config.to_seed().to_string(), seed);
+
+        // Borrowed strings make the same seeds as owned ones:
+        let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+        assert_eq!(config.mnemonic, mnemonic);
+        assert_eq!(config.to_seed().to_string(), seed);
+
+        let mnemonic = mnemonic::Mnemonic::parse(mnemonic, Language::English).unwrap();
+        assert_eq!(Seed::new(&mnemonic, "TREZOR").to_string(), seed);
+        assert_eq!(mnemonic.to_seed_hex("TREZOR"), seed);
+        assert_eq!(mnemonic.to_seed("TREZOR").to_string(), seed);
+        assert_eq!(mnemonic.words().join(" "), config.mnemonic);
+    }
+
+    assert!(Config::from_phrase("abandon about", "").is_err());
+
+    // The rest of the pipeline works just the same on a seed from some other derivation:
+    struct Electrum;
+    impl kdf::Kdf for Electrum {
+        fn derive(
+            &self,
+            mnemonic: &str,
+            passphrase: &str,
+            _: &mut dyn FnMut(u32, u32),
+        ) -> [u8; 64] {
+            let salt = [&b"electrum"[..], passphrase.as_bytes()];
+            let mut seed = [0; 64];
+            hash::pbkdf2_hmac_sha512(mnemonic.as_bytes(), &salt, 2048, &mut |_, _| {}, &mut seed);
+            seed
+        }
+    }
+    let (_, mnemonic, seed, _) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+    assert_eq!(config.to_seed_with(&kdf::Bip39).to_string(), seed);
+    let electrum = config.to_seed_with(&Electrum);
+    assert_ne!(electrum.to_string(), seed);
+    assert!(electrum.to_root_key(Network::Mainnet).is_ok());
+
+    let entropy = entropy::Entropy::<32>::random();
+    let mnemonic = mnemonic::Mnemonic::from_entropy(&entropy, Language::English);
+    assert_eq!(mnemonic.words().len(), 24);
+}
+
+#[test]
+fn root_key_from_seed() {
+    for &(_, _, seed, root_key) in ENGLISH.iter() {
+        assert_eq!(
+            generate_root_key(&hex::decode(seed).unwrap()).unwrap(),
+            root_key
+        );
+    }
+
+    // Straight from a Seed, no hex in between:
+    if is_english() {
+        let (_, mnemonic, seed_hex, root_key) = ENGLISH[0];
+        let seed = Config::from_phrase(mnemonic, "TREZOR").unwrap().to_seed();
+        assert_eq!(seed.to_hex(), seed_hex);
+        assert_eq!(seed.as_ref(), &seed.as_bytes()[..]);
+        assert_eq!(generate_root_key(&seed).unwrap(), root_key);
+    }
+}
+
+// The pipeline's network changes the root key's version bytes, and accounts' coin type:
+#[test]
+fn testnet_root_key_and_account() {
+    let seed = Seed::from_bytes(&hex::decode(ENGLISH[0].2).unwrap()).unwrap();
+    assert_eq!(
+        seed.to_root_key(Network::Testnet).unwrap().to_string(),
+        "tprv8ZgxMBicQKsPeWHBt7a68nPnvgTnuDhUgDWC8wZCgA8GahrQ3f3uWpq7wE7Uc1dLBnCe1hhCZ886K6ND37memRDWqsA9HgSKDXtwh2Qxo6J"
+    );
+
+    let root = Config::from_phrase(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "",
+    )
+    .unwrap()
+    .to_seed()
+    .to_root_key(Network::Testnet)
+    .unwrap();
+    let account = account::Account::derive(&root, descriptor::ScriptType::P2wpkh, 0).unwrap();
+    assert_eq!(account.origin.to_string(), "[73c5da0a/84'/1'/0']");
+    let key = account.receive_key(0).unwrap();
+    assert_eq!(
+        address::p2wpkh(&key.public_key(), Network::Testnet).unwrap(),
+        "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl"
+    );
+}
+
+#[test]
+fn seed_and_keys_written_out() {
+    let seed = Seed::from_bytes(&hex::decode(ENGLISH[0].2).unwrap()).unwrap();
+    let mut written = Vec::new();
+    seed.write_to(&mut written, Encoding::Hex).unwrap();
+    assert_eq!(written, seed.to_hex().into_bytes());
+
+    let mut written = Vec::new();
+    seed.write_to(&mut written, Encoding::Binary).unwrap();
+    assert_eq!(written, seed.as_bytes().to_vec());
+
+    let mut written = Vec::new();
+    seed.write_to(&mut written, Encoding::Base64).unwrap();
+    assert_eq!(written.len(), 88);
+    assert!(written.ends_with(b"=="));
+    assert_eq!("BASE64".parse::<Encoding>(), Ok(Encoding::Base64));
+    assert!("base58".parse::<Encoding>().is_err());
+
+    let root_key = seed.to_root_key(Network::Mainnet).unwrap();
+    let mut written = Vec::new();
+    root_key.write_to(&mut written).unwrap();
+    assert_eq!(written, root_key.to_string().into_bytes());
+    let mut written = Vec::new();
+    root_key
+        .to_extended_pub_key()
+        .write_to(&mut written)
+        .unwrap();
+    assert_eq!(
+        written,
+        root_key.to_extended_pub_key().to_string().into_bytes()
+    );
+}
+
+#[test]
+fn root_xpub_from_seed() {
+    // BIP-32's first test vector, m:
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivKey::new_master(&seed, Network::Mainnet).unwrap();
+    assert_eq!(
+        master.to_extended_pub_key().to_string(),
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+    );
+
+    for &(_, _, seed, root_key) in ENGLISH.iter() {
+        let (xprv, xpub) = generate_root_keys(&hex::decode(seed).unwrap()).unwrap();
+        assert_eq!(xprv, root_key);
+        let key: ExtendedPrivKey = root_key.parse().unwrap();
+        assert_eq!(xpub, key.to_extended_pub_key().to_string());
+        assert!(xpub.starts_with("xpub"));
+    }
+    assert_eq!(
+        generate_root_keys(&[0; 16]).err(),
+        Some(Error::InvalidSeedLength(16))
+    );
+}
+
+#[test]
+fn batch_derivation_matches_single_children() {
+    let vector = &test_vectors::BIP32[0];
+    let master =
+        ExtendedPrivKey::new_master(&hex::decode(vector.seed).unwrap(), Network::Mainnet).unwrap();
+    let xpub = master.to_extended_pub_key();
+
+    let private = master.derive_children(0..20).unwrap();
+    let public = xpub.derive_children(0..20).unwrap();
+    for index in 0..20 {
+        let child = bip32::ChildNumber::normal(index).unwrap();
+        assert_eq!(
+            private[index as usize].to_string(),
+            master.derive_child(child).unwrap().to_string()
+        );
+        assert_eq!(public[index as usize], xpub.derive_child(child).unwrap());
+    }
+}
+
+#[test]
+fn account_addresses() {
+    let root = Config::from_phrase(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        String::new(),
+    )
+    .unwrap()
+    .to_seed()
+    .to_root_key(Network::Mainnet)
+    .unwrap();
+
+    // The first receive address of each account type, as every BIP-44/49/84/86 wallet shows it:
+    for &(script_type, expected) in [
+        (
+            descriptor::ScriptType::P2pkh,
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+        ),
+        (
+            descriptor::ScriptType::P2shP2wpkh,
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+        ),
+        (
+            descriptor::ScriptType::P2wpkh,
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+        ),
+        (
+            descriptor::ScriptType::P2tr,
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        ),
+    ]
+    .iter()
+    {
+        let account = account::Account::derive(&root, script_type, 0).unwrap();
+        let addresses = address::derive_addresses(&account, false, 0..5).unwrap();
+        assert_eq!(addresses[0].address, expected);
+        assert_eq!(
+            addresses[4].path.to_string(),
+            format!("m/{}'/0'/0'/0/4", script_type.purpose())
+        );
+        assert_eq!(
+            account
+                .xpub
+                .contains_address(&addresses[4].address, 20)
+                .unwrap(),
+            Some(address::AddressMatch {
+                change: false,
+                index: 4,
+                script_type
+            })
+        );
+        assert_eq!(
+            account
+                .xpub
+                .contains_address(&addresses[4].address, 4)
+                .unwrap(),
+            None
+        );
+
+        // The exported xpub and origin alone give the same wallet:
+        let origin = format!("{:#}", account.origin);
+        let xpub = account.xpub.to_string();
+        let watch_only =
+            watch_only::WatchOnlyWallet::import(&xpub, script_type, Some(&origin)).unwrap();
+        assert_eq!(watch_only.address(false, 0).unwrap(), expected);
+        assert_eq!(
+            watch_only.multipath_descriptor(),
+            account.multipath_descriptor()
+        );
+        assert!(watch_only::WatchOnlyWallet::import(&root.to_string(), script_type, None).is_err());
+
+        #[cfg(feature = "parallel")]
+        for (parallel, record) in address::derive_addresses_parallel(&account, false, 0..5)
+            .unwrap()
+            .iter()
+            .zip(&addresses)
+        {
+            assert_eq!(parallel.address, record.address);
+        }
+    }
+}
+
+#[test]
+fn slip10_vectors() {
+    for vector in test_vectors::SLIP10.iter() {
+        let master =
+            slip10::ExtendedKey::new_master(&hex::decode(vector.seed).unwrap(), vector.curve)
+                .unwrap();
+
+        for chain in vector.chains {
+            let key = master.derive_path(&chain.path.parse().unwrap()).unwrap();
+            assert_eq!(
+                hex::encode(key.chain_code),
+                chain.chain_code,
+                "{}",
+                chain.path
+            );
+            assert_eq!(
+                hex::encode(key.secret_key_bytes()),
+                chain.private_key,
+                "{}",
+                chain.path
+            );
+            assert_eq!(
+                hex::encode(key.public_key()),
+                chain.public_key,
+                "{}",
+                chain.path
+            );
+        }
+    }
+}
+
+#[test]
+fn slip10_ed25519_rejects_normal_derivation() {
+    let seed = hex::decode(test_vectors::SLIP10[1].seed).unwrap();
+    let master = slip10::ExtendedKey::new_master(&seed, slip10::Curve::Ed25519).unwrap();
+    assert!(master
+        .derive_child(bip32::ChildNumber::normal(0).unwrap())
+        .is_err());
+}
+
+#[test]
+fn bip47_payment_codes() {
+    if !is_english() {
+        return;
+    }
+    // Alice's and Bob's wallets from the BIP-47 test vectors:
+    let vectors = [
+        (
+            "response seminar brave tip suit recall often sound stick owner lottery motion",
+            "PM8TJTLJbPRGxSbc8EJi42Wrr6QbNSaSSVJ5Y3E4pbCYiTHUskHg13935Ubb7q8tx9GVbh2UuRnBc3WSyJHhUrw8KhprKnn9eDznYGieTzFcwQRya4GA",
+        ),
+        (
+            "reward upper indicate eight swift arch injury crystal super wrestle already dentist",
+            "PM8TJS2JxQ5ztXUpBBRnpTbcUXbUHy2T1abfrb3KkAAtMEGNbey4oumH7Hc578WgQJhPjBxteQ5GHHToTYHE3A1w6p7tU6KSoFmWBVbFGjKPisZDbP97",
+        ),
+    ];
+
+    for &(mnemonic, payment_code) in vectors.iter() {
+        let config = Config::from_phrase(mnemonic, "").unwrap();
+        let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+        assert_eq!(bip47::payment_code(&root, 0).unwrap(), payment_code);
+    }
+}
+
+#[test]
+fn slip13_identity_path() {
+    // The SLIP-0013 example identity:
+    let path = ssh::identity_path("https://satoshi@bitcoin.org/login", 0).unwrap();
+    assert_eq!(
+        path.to_string(),
+        "m/13'/490267344'/697598796'/1613620211'/1858012177'"
+    );
+}
+
+#[test]
+fn bip85_applications() {
+    let root: ExtendedPrivKey = test_vectors::BIP85_MASTER.parse().unwrap();
+
+    for &(path, entropy) in test_vectors::BIP85_ENTROPY.iter() {
+        let derived = bip85::derive_entropy(&root, &path.parse().unwrap()).unwrap();
+        assert_eq!(hex::encode(&derived[..]), entropy);
+    }
+
+    let entropy = bip85::derive_entropy(&root, &test_vectors::BIP85_ENTROPY[0].0.parse().unwrap());
+    let mut drng = bip85::Drng::new(&entropy.unwrap());
+    let mut output = [0; 80];
+    drng.read(&mut output);
+    assert_eq!(hex::encode(&output[..]), test_vectors::BIP85_DRNG);
+
+    assert_eq!(bip85::wif(&root, 0).unwrap(), test_vectors::BIP85_WIF);
+    assert_eq!(
+        bip85::xprv(&root, 0).unwrap().to_string(),
+        test_vectors::BIP85_XPRV
+    );
+    assert_eq!(bip85::hex(&root, 64, 0).unwrap(), test_vectors::BIP85_HEX);
+    assert_eq!(
+        bip85::password_base64(&root, 21, 0).unwrap(),
+        test_vectors::BIP85_PWD_BASE64
+    );
+    assert_eq!(
+        bip85::password_base85(&root, 12, 0).unwrap(),
+        test_vectors::BIP85_PWD_BASE85
+    );
+
+    for &(words, phrase) in test_vectors::BIP85_BIP39.iter() {
+        let size = MnemonicListSize::from(words).unwrap();
+        let mnemonic = bip85::mnemonic(&root, Language::English, size, 0).unwrap();
+        assert_eq!(mnemonic.phrase(), phrase);
+    }
+    let custom = Wordlist::of(Language::English);
+    assert_eq!(
+        bip85::mnemonic(&root, Language::Custom(custom), Twelve, 0).err(),
+        Some(Error::Other("BIP-85 has no number for a custom wordlist."))
+    );
+}
+
+#[test]
+fn bip137_signatures() {
+    let (secret_key, message, signature) = test_vectors::BIP137;
+    let mut key = [0; 32];
+    key.copy_from_slice(&hex::decode(secret_key).unwrap());
+    let signed = bip137::sign(&key, descriptor::ScriptType::P2pkh, message.as_bytes()).unwrap();
+    assert_eq!(signed, signature);
+
+    let root = ExtendedPrivKey::from_parts(Network::Mainnet, [0; 32], &key).unwrap();
+    let address = address::p2pkh(&root.public_key(), Network::Mainnet);
+    assert!(bip137::verify(&address, message.as_bytes(), signature, Network::Mainnet).unwrap());
+    assert!(!bip137::verify(
+        &address,
+        b"a different message",
+        signature,
+        Network::Mainnet
+    )
+    .unwrap());
+
+    // A segwit signature says so in its header, and checks against the bc1 address:
+    let signed = bip137::sign(&key, descriptor::ScriptType::P2wpkh, message.as_bytes()).unwrap();
+    let address = address::p2wpkh(&root.public_key(), Network::Mainnet).unwrap();
+    assert!(bip137::verify(&address, message.as_bytes(), &signed, Network::Mainnet).unwrap());
+}
+
+#[test]
+fn seeds_for_several_passphrases() {
+    let (_, phrase, seed, root) = ENGLISH[1];
+    let mnemonic = mnemonic::Mnemonic::parse(phrase, Language::English).unwrap();
+    let seeds = decoy::derive_seeds(&mnemonic, &["", "TREZOR", "TREZOR "]);
+    assert_eq!(seeds.len(), 3);
+    assert_eq!(seeds[0].to_hex(), mnemonic.to_seed("").to_hex());
+    assert_eq!(seeds[1].to_hex(), seed);
+    assert_ne!(seeds[2].to_hex(), seed);
+
+    // Each wallet labelled by its fingerprint, the empty passphrase's first:
+    let wallets = decoy::wallets(phrase, &["TREZOR".to_string()]).unwrap();
+    assert_eq!(wallets.len(), 2);
+    assert_eq!(wallets[1].root_key.to_string(), root);
+    assert_eq!(
+        wallets[1].fingerprint,
+        root.parse::<ExtendedPrivKey>().unwrap().fingerprint()
+    );
+    assert_eq!(wallets[0].seed.to_hex(), seeds[0].to_hex());
+}
+
+#[test]
+fn verifying_the_implementation() {
+    // The 24 BIP-39 vectors, BIP-32's first four, and its fifth's invalid keys:
+    assert_eq!(verify_implementation(), Ok(29));
+    let vectors: Vec<_> = test_vectors().collect();
+    assert_eq!(vectors.len(), test_vectors::BIP39.len());
+    assert_eq!(vectors[1].mnemonic, ENGLISH[1].1);
+
+    // A vector that's off is reported at the step it's off at:
+    let mut wrong = vectors[0];
+    wrong.seed = ENGLISH[1].2;
+    let mismatch = wrong.verify().unwrap_err();
+    assert_eq!(
+        (mismatch.step, mismatch.got.as_str()),
+        ("seed", ENGLISH[0].2)
+    );
+    wrong.mnemonic = ENGLISH[1].1;
+    assert_eq!(wrong.verify().unwrap_err().step, "mnemonic");
+    assert!(wrong
+        .verify()
+        .unwrap_err()
+        .to_string()
+        .starts_with("the mnemonic of BIP-39 vector 0000"));
+
+    // and a BIP-32 one at the first key that comes out differently:
+    let wrong = test_vectors::Bip32Vector {
+        seed: test_vectors::BIP32[1].seed,
+        ..test_vectors::BIP32[0]
+    };
+    let mismatch = wrong.verify().unwrap_err();
+    assert_eq!(
+        (mismatch.spec, mismatch.step, mismatch.got.as_str()),
+        ("BIP-32", "m", test_vectors::BIP32[1].chains[0].xprv)
+    );
+}
+
+#[test]
+fn private_keys_in_wallet_import_format() {
+    let mut secret_key = [0; 32];
+    secret_key.copy_from_slice(&hex::decode(test_vectors::WIF_SECRET_KEY).unwrap());
+    let key = ExtendedPrivKey::from_parts(Network::Mainnet, [0; 32], &secret_key).unwrap();
+    assert_eq!(
+        key.to_wif(Network::Mainnet, true),
+        test_vectors::WIF_COMPRESSED
+    );
+    assert_eq!(
+        key.to_wif(Network::Mainnet, false),
+        test_vectors::WIF_UNCOMPRESSED
+    );
+
+    // Testnet's 0xEF prefix makes "c" keys and "9" keys of the same secret:
+    for &(compressed, first, len) in &[(true, 'c', 34), (false, '9', 33)] {
+        let wif = key.to_wif(Network::Testnet, compressed);
+        assert!(wif.starts_with(first));
+        let decoded = base58::decode_check(&wif).unwrap();
+        assert_eq!((decoded[0], decoded.len()), (0xef, len));
+        assert_eq!(&decoded[1..33], &secret_key[..]);
+    }
+
+    // BIP-322's signing key round trips:
+    let wif = base58::decode_check(test_vectors::BIP322.wif).unwrap();
+    let secret_key = <[u8; 32]>::try_from(&wif[1..33]).unwrap();
+    let key = ExtendedPrivKey::from_parts(Network::Mainnet, [0; 32], &secret_key).unwrap();
+    assert_eq!(key.to_wif(Network::Mainnet, true), test_vectors::BIP322.wif);
+}
+
+// Spread across threads, the Trezor vectors' seeds still come back in their configs' order:
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_seed_derivation() {
+    if !is_english() {
+        return;
+    }
+    let configs: Vec<Config> = ENGLISH
+        .iter()
+        .map(|&(_, mnemonic, _, _)| Config::from_phrase(mnemonic, "TREZOR").unwrap())
+        .collect();
+    let seeds: Vec<String> = derive_seeds(&configs).iter().map(Seed::to_hex).collect();
+    let expected: Vec<&str> = ENGLISH.iter().map(|&(_, _, seed, _)| seed).collect();
+    assert_eq!(seeds, expected);
+
+    // Each seed reported once, whichever thread finished it:
+    let reports = std::sync::Mutex::new(Vec::new());
+    derive_seeds_with_progress(&configs, &|done, total| {
+        reports.lock().unwrap().push((done, total))
+    });
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort();
+    let expected: Vec<(usize, usize)> = (1..=configs.len())
+        .map(|done| (done, configs.len()))
+        .collect();
+    assert_eq!(reports, expected);
+}
+
+#[test]
+fn bip322_signatures() {
+    let vectors = &test_vectors::BIP322;
+    for &(message, hash) in vectors.message_hashes.iter() {
+        assert_eq!(hex::encode(bip322::message_hash(message.as_bytes())), hash);
+    }
+
+    let wif = base58::decode_check(vectors.wif).unwrap();
+    let mut key = [0; 32];
+    key.copy_from_slice(&wif[1..33]);
+    let public_key = ExtendedPrivKey::from_parts(Network::Mainnet, [0; 32], &key)
+        .unwrap()
+        .public_key();
+
+    for &(script_type, address, message, signature) in vectors.signatures.iter() {
+        let derived = address::for_script_type(script_type, &public_key, Network::Mainnet);
+        assert_eq!(derived.unwrap(), address);
+        assert!(bip322::verify(address, message.as_bytes(), signature, Network::Mainnet).unwrap());
+        assert!(!bip322::verify(address, b"Goodbye World", signature, Network::Mainnet).unwrap());
+
+        // ECDSA nonces and BIP-340 auxiliary randomness differ between signers, so new
+        // signatures needn't match the published ones, only verify:
+        let signed = bip322::sign(&key, script_type, message.as_bytes()).unwrap();
+        assert!(bip322::verify(address, message.as_bytes(), &signed, Network::Mainnet).unwrap());
+    }
+}
+
+#[test]
+fn bip341_output_keys() {
+    let bytes = |hex: &str| {
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    };
+    for &(internal_key, merkle_root, output_key) in test_vectors::BIP341_TWEAKS.iter() {
+        let merkle_root = merkle_root.map(bytes);
+        let (tweaked, _) =
+            taproot::taproot_tweak(&bytes(internal_key), merkle_root.as_ref()).unwrap();
+        assert_eq!(hex::encode(tweaked), output_key);
+    }
+}
+
+#[test]
+fn message_verification_without_a_network() {
+    let (secret_key, text, signature) = test_vectors::BIP137;
+    let mut key = [0; 32];
+    key.copy_from_slice(&hex::decode(secret_key).unwrap());
+    let root = ExtendedPrivKey::from_parts(Network::Mainnet, [0; 32], &key).unwrap();
+    let address = address::p2pkh(&root.public_key(), Network::Mainnet);
+    assert_eq!(
+        message::SignatureFormat::of(signature),
+        Ok(message::SignatureFormat::Bip137)
+    );
+    assert!(message::verify(&address, text.as_bytes(), signature).unwrap());
+
+    for &(_, address, text, signature) in test_vectors::BIP322.signatures.iter() {
+        assert_eq!(
+            message::SignatureFormat::of(signature),
+            Ok(message::SignatureFormat::Bip322)
+        );
+        assert!(message::verify(address, text.as_bytes(), signature).unwrap());
+        assert!(!message::verify(address, b"Goodbye World", signature).unwrap());
+    }
+
+    let testnet = address::p2wpkh(&root.public_key(), Network::Testnet).unwrap();
+    assert_eq!(address::network_of(&testnet), Some(Network::Testnet));
+    assert_eq!(address::network_of("not an address"), None);
+}
+
+#[test]
+fn mnemonic_parse_options() {
+    use mnemonic::{Checksum, Correction, Mnemonic, ParseOptions, UnknownWords};
+
+    let (_, phrase, _, _) = ENGLISH[0];
+    let mnemonic = Mnemonic::parse(phrase, Language::English).unwrap();
+    assert_eq!(mnemonic.phrase(), phrase);
+    if is_english() {
+        let mnemonic: Mnemonic = phrase.try_into().unwrap();
+        assert_eq!(mnemonic.words().len(), 12);
+        assert!(Mnemonic::try_from("abandon about").is_err());
+    }
+    assert!(mnemonic.checksum_valid() && mnemonic.corrections().is_empty());
+
+    // Strict by default: unknown words, bad checksums, wrong counts and commas are all errors.
+    let misspelled = phrase.replace("about", "abuot");
+    let bad_checksum = phrase.replace("about", "abandon");
+    assert!(Mnemonic::parse(&misspelled, Language::English).is_err());
+    assert!(Mnemonic::parse(&bad_checksum, Language::English).is_err());
+    assert!(Mnemonic::parse("abandon about", Language::English).is_err());
+    assert!(Mnemonic::parse(&phrase.replace(' ', ", "), Language::English).is_err());
+
+    // Lenient: the typo and the abbreviations are suggested (and noted), commas are fine, and a
+    // bad checksum only clears 'checksum_valid'.
+    let lenient = ParseOptions::lenient();
+    let written = misspelled
+        .replacen("abandon", "aban", 2)
+        .replace(' ', ",\n");
+    let mnemonic = Mnemonic::parse_with(&written, Language::English, &lenient).unwrap();
+    assert_eq!(mnemonic.phrase(), phrase);
+    assert_eq!(mnemonic.corrections().len(), 3);
+    assert_eq!(
+        mnemonic.corrections()[2],
+        Correction {
+            position: 11,
+            written: "abuot".to_string(),
+            word: "about"
+        }
+    );
+    let mnemonic = Mnemonic::parse_with(&bad_checksum, Language::English, &lenient).unwrap();
+    assert!(!mnemonic.checksum_valid());
+    let gibberish = "zzzzzz ".repeat(12);
+    assert!(Mnemonic::parse_with(&gibberish, Language::English, &lenient).is_err());
+
+    // Options mix and match:
+    let options = ParseOptions {
+        unknown_words: UnknownWords::Reject,
+        checksum: Checksum::WarnOnly,
+        separators: vec!['-'],
+    };
+    let parse =
+        |phrase: &str| Mnemonic::parse_with(&phrase.replace(' ', "-"), Language::English, &options);
+    assert!(parse(&bad_checksum).is_ok());
+    assert!(parse(&misspelled).is_err());
+}
+
+#[test]
+fn session_requests() {
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "").unwrap();
+    let session = session::Session::new(config.to_seed().to_root_key(Network::Mainnet).unwrap());
+
+    let respond = |request: &str| session.respond(&serde_json::from_str(request).unwrap());
+    assert_eq!(
+        respond(r#"{"method": "fingerprint"}"#)["fingerprint"],
+        "73c5da0a"
+    );
+    assert_eq!(
+        respond(r#"{"method": "address", "path": "m/84'/0'/0'/0/0", "script_type": "p2wpkh"}"#)
+            ["address"],
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+    assert!(respond(r#"{"method": "xpub", "path": "m/84"}"#)["xpub"]
+        .as_str()
+        .unwrap()
+        .starts_with("xpub"));
+
+    // Nothing private is on offer, and bad requests get an error back rather than a panic:
+    assert!(respond(r#"{"method": "xprv", "path": "m"}"#)["error"].is_string());
+    assert!(respond(r#"{"method": "address", "path": "m/0"}"#)["error"].is_string());
+    assert!(respond(r#"[]"#)["error"].is_string());
+    assert_eq!(respond(r#"[]"#)["schema_version"], schema::VERSION);
+}
+
+#[test]
+fn exporter_registry() {
+    use export::{ExportRequest, Exporter, Registry};
+
+    struct Fingerprint;
+    impl Exporter for Fingerprint {
+        fn name(&self) -> &'static str {
+            "fingerprint"
+        }
+        fn description(&self) -> &'static str {
+            "Just the master fingerprint"
+        }
+        fn export(&self, request: &ExportRequest) -> Result<String, &'static str> {
+            Ok(hex::encode(request.root.fingerprint()))
+        }
+    }
+
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "").unwrap();
+    let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+    let request = ExportRequest {
+        root: &root,
+        account: 0,
+        script_types: &[descriptor::ScriptType::P2wpkh],
+        accounts: 1,
+        path: None,
+    };
+
+    let mut registry = Registry::builtin();
+    assert_eq!(registry.exporters().count(), 10);
+    let descriptor = registry
+        .get("descriptor")
+        .unwrap()
+        .export(&request)
+        .unwrap();
+    assert!(descriptor.starts_with("wpkh([73c5da0a/84h/0h/0h]"));
+    registry.register(Box::new(Fingerprint));
+    assert_eq!(
+        registry
+            .get("fingerprint")
+            .unwrap()
+            .export(&request)
+            .unwrap(),
+        "73c5da0a"
+    );
+    assert!(registry.get("nope").is_none());
+
+    // Our own format says which version of itself it is; the others are defined elsewhere:
+    let bundle: serde_json::Value =
+        serde_json::from_str(&registry.export("bundle", &request).unwrap()).unwrap();
+    assert_eq!(bundle["schema_version"], schema::VERSION);
+    let sparrow: serde_json::Value =
+        serde_json::from_str(&registry.export("sparrow", &request).unwrap()).unwrap();
+    assert!(sparrow.get("schema_version").is_none());
+}
+
+#[test]
+fn audit_events() {
+    use audit::Event;
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let heard = events.clone();
+    audit::set_hook(Box::new(move |event| {
+        heard.lock().unwrap().push(event.clone())
+    }));
+
+    let config = Config::generate_in(Language::Czech, MnemonicListSize::Fifteen, String::new());
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    Config::from_phrase(mnemonic, "").unwrap().to_seed();
+    audit::clear_hook();
+    config.to_seed();
+
+    // (other tests run alongside this one, so there may be more events than these)
+    let events = events.lock().unwrap();
+    let generated = Event::MnemonicGenerated {
+        language: Language::Czech,
+        words: 15,
+    };
+    assert!(events.contains(&generated));
+    assert!(events.contains(&Event::SeedDerived {
+        fingerprint: [0x73, 0xc5, 0xda, 0x0a]
+    }));
+    assert_eq!(generated.to_json()["event"], "mnemonic_generated");
+
+    // Nothing in any of them is secret:
+    for event in events.iter() {
+        let json = event.to_json().to_string();
+        assert!(!json.contains(config.mnemonic.as_str()) && !json.contains("abandon"));
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn secret_filter() {
+    use secret_filter::{is_secret, SecretFilter};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata};
+
+    // Writes down the name of every span and event, and every field name, it's given:
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl tracing::Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut heard = self.0.lock().unwrap();
+            heard.push(span.metadata().name().to_string());
+            heard.extend(span.fields().iter().map(|field| field.name().to_string()));
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event) {
+            let fields = event.fields().map(|field| field.name().to_string());
+            self.0.lock().unwrap().extend(fields);
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    assert!(is_secret("mnemonic") && is_secret("User_Passphrase") && is_secret("seed_hex"));
+    assert!(!is_secret("language") && !is_secret("word_count"));
+
+    let heard = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = SecretFilter::new(Recorder(heard.clone()));
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    tracing::subscriber::with_default(subscriber, || {
+        Mnemonic::parse(mnemonic, Language::English).unwrap();
+        Config::from_phrase(mnemonic, "").unwrap().to_seed();
+        // What an application might write while debugging:
+        tracing::debug!(mnemonic, "recovering");
+        let _span = tracing::debug_span!("recover", passphrase = "TREZOR").entered();
+    });
+
+    let heard = heard.lock().unwrap();
+    for name in [
+        "validate",
+        "language",
+        "checksum_valid",
+        "derive",
+        "word_count",
+    ] {
+        assert!(heard.iter().any(|heard| heard == name));
+    }
+    assert!(!heard
+        .iter()
+        .any(|heard| is_secret(heard) || heard == "recover"));
+}
+
+#[test]
+fn localized_errors() {
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let phrase = mnemonic.replace("about", "abandon");
+    let err = Mnemonic::parse(&phrase, Language::English).err().unwrap();
+    assert_eq!(
+        i18n::localize(err.message(), Language::English),
+        err.to_string()
+    );
+    assert_eq!(
+        i18n::localize(err.message(), Language::Spanish),
+        "Suma de verificación de la frase mnemotécnica no válida."
+    );
+    let err = MnemonicListSize::from(13).err().unwrap();
+    assert_eq!(
+        i18n::localize(err.message(), Language::Japanese),
+        "ニーモニックの単語数が正しくありません。"
+    );
+    // (nothing to translate it from)
+    assert_eq!(
+        i18n::localize("Unknown method.", Language::French),
+        "Unknown method."
+    );
+}
+
+#[test]
+fn typed_errors() {
+    use bip32::{ChildNumber, DerivationPath};
+    use mnemonic::ParseOptions;
+
+    assert_eq!(
+        MnemonicListSize::from(13).err(),
+        Some(Error::InvalidWordCount(13))
+    );
+    assert_eq!(
+        Mnemonic::parse("abandon abandon", Language::English).err(),
+        Some(Error::InvalidWordCount(2))
+    );
+    let phrase = "abandon ".repeat(11) + "qqqqqqqq";
+    assert_eq!(
+        Mnemonic::parse_with(&phrase, Language::English, &ParseOptions::lenient()).err(),
+        Some(Error::NoCloseWord {
+            word: "qqqqqqqq".to_string(),
+            index: 11
+        })
+    );
+    assert_eq!(
+        generate_root_key(&[0; 32]).err(),
+        Some(Error::InvalidSeedLength(32))
+    );
+
+    // The word stays out of the message:
+    let err = Mnemonic::parse(&phrase, Language::English).err().unwrap();
+    assert_eq!(err.to_string(), "A mnemonic word isn't in the word list.");
+    assert!(!format!("{:?}", err).contains("qqqqqqqq"));
+    let err: Box<dyn std::error::Error> = Box::new(err);
+    assert!(!err.to_string().contains("qqqqqqqq"));
+
+    // Derivation paths, keys and keystores have variants of their own:
+    assert_eq!(
+        "44'/0'".parse::<DerivationPath>(),
+        Err(Error::PathWithoutMaster)
+    );
+    assert_eq!(
+        "m/2147483648".parse::<DerivationPath>(),
+        Err(Error::ChildIndexOutOfRange)
+    );
+    let xpub = ENGLISH[0]
+        .3
+        .parse::<ExtendedPrivKey>()
+        .unwrap()
+        .to_extended_pub_key();
+    assert_eq!(
+        xpub.derive_child(ChildNumber::hardened(0).unwrap()).err(),
+        Some(Error::HardenedFromPublicKey)
+    );
+    assert_eq!(
+        ExtendedPrivKey::from_bytes(&[0; 77]).err(),
+        Some(Error::InvalidExtendedKeyLength(77))
+    );
+
+    // And back and forth with the modules that still speak '&'static str':
+    let message: &str = Error::ChecksumMismatch.into();
+    assert_eq!(message, "Invalid mnemonic checksum.");
+    assert_eq!(Error::from("Invalid path."), Error::Other("Invalid path."));
+}
+
+#[cfg(feature = "paranoid")]
+#[test]
+fn paranoid_cross_check() {
+    // The second implementation agrees with the vectors on its own...
+    for &(_, mnemonic, seed, root_key) in ENGLISH.iter().take(4) {
+        let second = paranoid::seed(mnemonic, "TREZOR");
+        assert_eq!(hex::encode(second), seed);
+        let root: ExtendedPrivKey = root_key.parse().unwrap();
+        assert_eq!(
+            paranoid::master_key(&second),
+            (root.chain_code, root.secret_key_bytes())
+        );
+    }
+
+    // ...and with the first, once it's checking (a disagreement would abort the test run):
+    paranoid::enable();
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "").unwrap();
+    let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+    assert_eq!(root.fingerprint(), [0x73, 0xc5, 0xda, 0x0a]);
+}
+
+#[cfg(feature = "double-check")]
+#[test]
+fn double_check() {
+    let mut runs = 0;
+    assert_eq!(
+        fault::twice(
+            || {
+                runs += 1;
+                7
+            },
+            PartialEq::eq
+        ),
+        7
+    );
+    assert_eq!(runs, 2);
+
+    // Everything still comes out as it should, just computed twice over:
+    let (entropy, mnemonic, seed, root_key) = ENGLISH[1];
+    let entropy: [u8; 16] = hex::decode(entropy).unwrap().try_into().unwrap();
+    let parsed = Mnemonic::from_entropy(&entropy::Entropy::from(entropy), Language::English);
+    assert_eq!(parsed.phrase(), mnemonic);
+    assert!(parsed.checksum_valid());
+    let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+    let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+    assert_eq!(config.to_seed().to_string(), seed);
+    assert_eq!(root.to_string(), root_key);
+    assert!(root.derive_path(&"m/84'/0'/0'".parse().unwrap()).is_ok());
+}
+
+// Counts this thread's heap allocations, so tests can check a path makes none:
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn heap_free_generation_and_derivation() {
+    use kdf::Kdf;
+
+    struct Zeros;
+    impl EntropySource for Zeros {
+        fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+            entropy.iter_mut().for_each(|byte| *byte = 0);
+            Ok(())
+        }
+    }
+
+    let (entropy, mnemonic, seed, _) = ENGLISH[3];
+    let entropy: [u8; 16] = hex::decode(entropy).unwrap().try_into().unwrap();
+    let entropy = entropy::Entropy::from(entropy);
+    // (unpacking the wordlist the first time is the one allocation, kept for good)
+    Language::English.word_list();
+    let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+    let parsed = Mnemonic::parse(mnemonic, Language::English).unwrap();
+
+    let before = ALLOCATIONS.with(|count| count.get());
+    let mut buffer = [0; MAX_PHRASE_LEN];
+    let phrase = entropy.write_phrase(Language::English, &mut buffer);
+    let derived = kdf::Bip39.derive(phrase, "TREZOR", &mut |_, _| {});
+    let from_config = config.to_seed();
+    let mut into = [0; 64];
+    config.derive_seed_into(&mut into);
+    let from_mnemonic = parsed.to_seed("TREZOR");
+    let generated = Config::generate_using(&mut Zeros, Language::English, Twelve, "").unwrap();
+    assert_eq!(ALLOCATIONS.with(|count| count.get()), before);
+
+    assert_eq!(phrase, mnemonic);
+    assert_eq!(hex::encode(derived), seed);
+    assert_eq!(from_config.as_bytes()[..], derived[..]);
+    assert_eq!(into[..], derived[..]);
+    assert_eq!(from_mnemonic.as_bytes()[..], derived[..]);
+    assert_eq!(generated.mnemonic, ENGLISH[0].1);
+}
+
+#[test]
+fn multisig_descriptors() {
+    use multisig::{Cosigner, Multisig};
+
+    let cosigners: Vec<Cosigner> = ENGLISH[..3]
+        .iter()
+        .map(|&(_, mnemonic, _, _)| {
+            let config = Config::from_phrase(mnemonic, "").unwrap();
+            let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+            Cosigner::derive(&root, 0).unwrap()
+        })
+        .collect();
+    let own = &cosigners[0];
+    assert!(own.to_string().starts_with("[73c5da0a/48h/0h/0h/2h]xpub"));
+    assert_eq!(&own.to_string().parse::<Cosigner>().unwrap(), own);
+
+    let wallet = Multisig::new(2, cosigners.clone()).unwrap();
+    let receive = wallet.descriptor(false);
+    let (body, checksum) = receive.split_at(receive.len() - 9);
+    assert!(body.starts_with("wsh(sortedmulti(2,[73c5da0a/48h/0h/0h/2h]xpub"));
+    assert!(body.ends_with("/0/*))"));
+    assert_eq!(body.matches("/0/*").count(), 3);
+    assert_eq!(
+        checksum,
+        format!("#{}", descriptor::checksum(body).unwrap())
+    );
+    assert!(wallet.multipath_descriptor().contains("/<0;1>/*,"));
+
+    assert!(Multisig::new(4, cosigners.clone()).is_err());
+    assert!(Multisig::new(0, cosigners.clone()).is_err());
+    assert!(Multisig::new(2, vec![own.clone(), own.clone()]).is_err());
+    assert!(own.xpub.to_string().parse::<Cosigner>().is_err());
+}
+
+#[test]
+fn wallet_policies() {
+    use multisig::{Cosigner, Multisig};
+    use wallet_policy::WalletPolicy;
+
+    let roots: Vec<ExtendedPrivKey> = ENGLISH[..3]
+        .iter()
+        .map(|&(_, mnemonic, _, _)| {
+            let config = Config::from_phrase(mnemonic, "").unwrap();
+            config.to_seed().to_root_key(Network::Mainnet).unwrap()
+        })
+        .collect();
+
+    let account = account::Account::derive(&roots[0], descriptor::ScriptType::P2wpkh, 0).unwrap();
+    let policy = WalletPolicy::single_sig("Savings", &account);
+    assert_eq!(policy.template, "wpkh(@0/**)");
+    assert!(policy.keys[0].starts_with("[73c5da0a/84'/0'/0']xpub"));
+    let json = policy.to_json();
+    assert_eq!(json["name"], "Savings");
+    assert_eq!(json["keys_info"][0], policy.keys[0].as_str());
+
+    // The template filled in with the keys is the wallet's own descriptor, "'"s and all:
+    let cosigners = roots
+        .iter()
+        .map(|root| Cosigner::derive(root, 0).unwrap())
+        .collect();
+    let wallet = Multisig::new(2, cosigners).unwrap();
+    let policy = WalletPolicy::multisig("Vault", &wallet);
+    assert_eq!(policy.template, "wsh(sortedmulti(2,@0/**,@1/**,@2/**))");
+    let multipath = wallet.multipath_descriptor();
+    assert_eq!(
+        policy.descriptor().replace('\'', "h"),
+        multipath[..multipath.len() - 9]
+    );
+}
+
+#[test]
+fn multisig_setup_file() {
+    use multisig::{Cosigner, Multisig};
+
+    let mut cosigners: Vec<Cosigner> = ENGLISH[..2]
+        .iter()
+        .map(|&(_, mnemonic, _, _)| {
+            let config = Config::from_phrase(mnemonic, "").unwrap();
+            let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+            Cosigner::derive(&root, 0).unwrap()
+        })
+        .collect();
+    let wallet = Multisig::new(2, cosigners.clone()).unwrap();
+    let file = wallet.setup_file("Vault").unwrap();
+    let header = "\nName: Vault\nPolicy: 2 of 2\nDerivation: m/48'/0'/0'/2'\nFormat: P2WSH\n";
+    assert!(file.contains(header));
+    assert!(file.contains(&format!("\n73C5DA0A: {}\n", cosigners[0].xpub)));
+    assert!(file.contains("\nB8688DF1: xpub6FQya7zGhR92"));
+    assert!(wallet.setup_file("A name too long for a Coldcard").is_err());
+
+    // Cosigners on different accounts each get their own derivation line:
+    cosigners[1].origin.path = "m/48'/0'/1'/2'".parse().unwrap();
+    let file = Multisig::new(1, cosigners)
+        .unwrap()
+        .setup_file("Vault")
+        .unwrap();
+    assert!(file.contains("Format: P2WSH\n\nDerivation: m/48'/0'/0'/2'\n73C5DA0A: "));
+    assert!(file.contains("\nDerivation: m/48'/0'/1'/2'\nB8688DF1: "));
+}
+
+#[test]
+fn ur_exports() {
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "").unwrap();
+    let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+
+    // A map of the master fingerprint ("JKSKTNBK" is 73c5da0a) and four descriptors, the first
+    // pkh(hdkey{key data, chain code, ...}):
+    let account = ur::crypto_account(&root, 0).unwrap();
+    assert!(account.starts_with("UR:CRYPTO-ACCOUNT/OEADCYJKSKTNBKAOLRTAADMUTAADDLOXAXHDCLAX"));
+
+    let p2wpkh = account::Account::derive(&root, descriptor::ScriptType::P2wpkh, 0).unwrap();
+    let hdkey = ur::crypto_hdkey(&p2wpkh).unwrap();
+    assert!(hdkey.starts_with("UR:CRYPTO-HDKEY/OXAXHDCLAOJOKNIDZCPSSAJTPT"));
+    assert!(account.contains(&hdkey["UR:CRYPTO-HDKEY/".len()..hdkey.len() - 8]));
+
+    let testnet = config.to_seed().to_root_key(Network::Testnet).unwrap();
+    let p2wpkh = account::Account::derive(&testnet, descriptor::ScriptType::P2wpkh, 0).unwrap();
+    assert!(ur::crypto_hdkey(&p2wpkh)
+        .unwrap()
+        .starts_with("UR:CRYPTO-HDKEY/ONAXHDCL"));
+    let litecoin = config.to_seed().to_root_key(Network::Litecoin).unwrap();
+    assert!(ur::crypto_account(&litecoin, 0).is_err());
+}
+
+#[test]
+fn ur_seeds() {
+    // seedtool's (bc-components') URs for the seed 7f7f...7f, the second Trezor vector's entropy,
+    // alone and with a birthday, name and note:
+    let legal = Mnemonic::parse(test_vectors::BIP39[1].1, Language::English).unwrap();
+    for seed in [
+        "ur:seed/oyadgdlblblblblblblblblblblblblblblblbtkbdtajn",
+        "ur:seed/oxadgdlblblblblblblblblblblblblblblblbaosecyhnecmsaeaxiyhghsjzjzihjyaajejkjyihihjz\
+         cxjojzhsjyihrkbsurlb",
+        "UR:CRYPTO-SEED/OYADGDLBLBLBLBLBLBLBLBLBLBLBLBLBLBLBLBTKBDTAJN",
+    ] {
+        let read = ur::read_seed(seed, Language::English).unwrap();
+        assert_eq!(read.words(), legal.words());
+    }
+
+    // A misread letter fails the checksum; other types, and multi-part URs, aren't seeds:
+    assert_eq!(
+        ur::read_seed(
+            "ur:seed/oyadgdlblblblblblflblblblblblblblblblbtkbdtajn",
+            Language::English
+        )
+        .err(),
+        Some(Error::Other(
+            "The UR's checksum doesn't match; it was misread."
+        ))
+    );
+    let p2wpkh = account::Account::derive(
+        &Config::from_phrase(test_vectors::BIP39[1].1, "")
+            .unwrap()
+            .to_seed()
+            .to_root_key(Network::Mainnet)
+            .unwrap(),
+        descriptor::ScriptType::P2wpkh,
+        0,
+    )
+    .unwrap();
+    assert!(ur::read_seed(&ur::crypto_hdkey(&p2wpkh).unwrap(), Language::English).is_err());
+    assert_eq!(
+        ur::read_seed("ur:seed/1-2/lpadaobgaeoehlchyhrl", Language::English).err(),
+        Some(Error::Other("Only single-part URs are read."))
+    );
+}
+
+#[test]
+fn network_version_bytes() {
+    use network::Params;
+
+    for network in Network::all().iter() {
+        assert_eq!(network.to_string().parse::<Network>(), Ok(*network));
+    }
+    assert_eq!("Testnet".parse::<Network>(), Ok(Network::Testnet));
+    assert!("bitcoin-cash".parse::<Network>().is_err());
+
+    let (_, _, seed, root_key) = ENGLISH[0];
+    let seed = hex::decode(seed).unwrap();
+    let mainnet = ExtendedPrivKey::new_master(&seed, Network::Mainnet).unwrap();
+    assert_eq!(mainnet.to_string(), root_key);
+    let testnet = ExtendedPrivKey::new_master(&seed, Network::Testnet).unwrap();
+    assert!(testnet.to_string().starts_with("tprv"));
+    assert!(testnet
+        .to_extended_pub_key()
+        .to_string()
+        .starts_with("tpub"));
+    assert_eq!(testnet.to_bytes()[4..], mainnet.to_bytes()[4..]);
+
+    // Version bytes of the caller's own (these are SLIP-132's "zprv"/"zpub"):
+    static ZPRV: Params = Params {
+        xprv_version: [0x04, 0xb2, 0x43, 0x0c],
+        xpub_version: [0x04, 0xb2, 0x47, 0x46],
+        coin_type: 0,
+        wif_prefix: 128,
+        p2pkh_prefix: 0,
+        p2sh_prefix: 5,
+        bech32_hrp: Some("bc"),
+    };
+    let custom = ExtendedPrivKey::new_master(&seed, Network::Custom(&ZPRV)).unwrap();
+    assert!(custom.to_string().starts_with("zprv"));
+    assert!(custom.to_extended_pub_key().to_string().starts_with("zpub"));
+    assert_eq!(custom.to_bytes()[4..], mainnet.to_bytes()[4..]);
+    assert_eq!(custom.network.coin_type(), 0);
+}
+
+#[test]
+fn known_addresses() {
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let config = Config::from_phrase(mnemonic, "").unwrap();
+    let root = config.to_seed().to_root_key(Network::Mainnet).unwrap();
+
+    let own = address::find_known(
+        &root,
+        &[
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            "BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU",
+        ],
+        1,
+        5,
+    )
+    .unwrap();
+    assert_eq!(own.len(), 2);
+    assert_eq!(own[0].path.to_string(), "m/44'/0'/0'/0/0");
+    assert_eq!(own[1].script_type, descriptor::ScriptType::P2wpkh);
+
+    // Someone else's:
+    let other = ["3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"];
+    assert!(address::find_known(&root, &other, 1, 5).unwrap().is_empty());
+}
+
+#[test]
+fn bulk_audits() {
+    let (_, first, _, _) = ENGLISH[0];
+    let (_, second, _, _) = ENGLISH[1];
+    let text = format!(
+        "# backups\n{}\n\nabandon abandon abandon\n  {}  \n",
+        first, second
+    );
+
+    let p2wpkh = Some(descriptor::ScriptType::P2wpkh);
+    let audits = bulk_audit::audit(&text, "", Network::Mainnet, p2wpkh);
+    assert_eq!(
+        audits.iter().map(|audit| audit.line).collect::<Vec<_>>(),
+        vec![2, 4, 5]
+    );
+    let wallet = audits[0].result.as_ref().unwrap();
+    assert_eq!(hex::encode(wallet.fingerprint), "73c5da0a");
+    assert_eq!(
+        wallet.address.as_deref(),
+        Some("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu")
+    );
+    assert_eq!(
+        audits[1].result.as_ref().err(),
+        Some(&"Invalid mnemonic word list size.")
+    );
+    assert_eq!(
+        hex::encode(audits[2].result.as_ref().unwrap().fingerprint),
+        "b8688df1"
+    );
+}
+
+#[test]
+fn weak_mnemonics() {
+    use mnemonic::{Checksum, Mnemonic, ParseOptions};
+    use weak::Weakness;
+
+    // (made up phrases rarely have a valid checksum)
+    let options = ParseOptions {
+        checksum: Checksum::WarnOnly,
+        ..ParseOptions::strict()
+    };
+    let check = |phrase: &str| {
+        weak::check(&Mnemonic::parse_with(phrase, Language::English, &options).unwrap())
+    };
+    let (_, vector, _, _) = ENGLISH[5];
+    assert_eq!(check(vector), Some(Weakness::TestVector));
+    let ganache = "candy maple cake sugar pudding cream honey rich smooth crumble sweet treat";
+    assert_eq!(check(ganache), Some(Weakness::DevelopmentDefault));
+    let hardhat = "test test test test test test test test test test test junk";
+    assert_eq!(check(hardhat), Some(Weakness::RepeatedWord));
+    let run = "ability able about above absent absorb abstract absurd abuse access accident zoo";
+    assert_eq!(check(run), Some(Weakness::Sequential));
+
+    let entropy = hex::decode("3b1bc5e4c9f8a1d2e7f60918273645aa").unwrap();
+    let entropy: [u8; 16] = entropy.try_into().unwrap();
+    let random = Mnemonic::from_entropy(&entropy::Entropy::from(entropy), Language::English);
+    assert_eq!(weak::check(&random), None);
+}
+
+#[test]
+fn confirmation_quiz() {
+    use entropy::EntropySource;
+    use quiz;
+
+    let (_, phrase, _, _) = ENGLISH[5];
+    let mnemonic = Mnemonic::parse(phrase, Language::English).unwrap();
+    let words = mnemonic.words();
+
+    let challenge = confirmation_challenge(&mnemonic, 4);
+    let positions = challenge.positions().to_vec();
+    assert_eq!(positions.len(), 4);
+    assert!(positions
+        .iter()
+        .all(|&position| (1..=words.len()).contains(&position)));
+    let mut distinct = positions.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), 4);
+
+    let answers: Vec<&str> = positions
+        .iter()
+        .map(|&position| words[position - 1])
+        .collect();
+    assert!(challenge.wrong_answers(&answers).is_empty());
+    // (the same prefixes, case and stray characters as a typed mnemonic)
+    let first = positions[0];
+    assert!(challenge.check(first, &words[first - 1][..4].to_uppercase()));
+    assert!(!challenge.check(first, "zoo"));
+    let unasked = (1..=words.len())
+        .find(|position| !positions.contains(position))
+        .unwrap();
+    assert!(!challenge.check(unasked, words[unasked - 1]));
+    assert_eq!(challenge.wrong_answers(&answers[..3]), vec![positions[3]]);
+
+    // All the words at most, in an order set by the source:
+    struct Zeros;
+    impl EntropySource for Zeros {
+        fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+            entropy.iter_mut().for_each(|byte| *byte = 0);
+            Ok(())
+        }
+    }
+    let challenge = quiz::confirmation_challenge_using(&mnemonic, 30, &mut Zeros).unwrap();
+    assert_eq!(
+        challenge.positions(),
+        &(1..=words.len()).collect::<Vec<_>>()[..]
+    );
+}
+
+#[test]
+fn pbkdf2_lengths() {
+    let (_, mnemonic, seed, _) = ENGLISH[0];
+
+    // Longer outputs start with the shorter ones, BIP-39's 64 bytes included:
+    let stretched = kdf::Pbkdf2 {
+        length: 96,
+        ..kdf::Pbkdf2::default()
+    };
+    let derived = stretched.derive(mnemonic, "TREZOR").unwrap();
+    assert_eq!(hex::encode(&derived[..64]), seed);
+    assert_eq!(
+        hex::encode(&derived[64..]),
+        "3b08b59df9c6f29e33a24601a545b2edc4b404b5985ab51c5502ab234c757cf8"
+    );
+
+    let short = kdf::Pbkdf2 {
+        salt_prefix: "electrum".to_string(),
+        rounds: 2048,
+        length: 32,
+    };
+    assert_eq!(
+        hex::encode(short.derive(mnemonic, "").unwrap()),
+        "b24af40d049942cb7e7ea70ce919f5e665782a751ee51fb372bb13b76b1bbca3"
+    );
+    let no_rounds = kdf::Pbkdf2 {
+        rounds: 0,
+        ..kdf::Pbkdf2::default()
+    };
+    assert!(no_rounds.derive(mnemonic, "").is_err());
+}
+
+#[cfg(feature = "vault")]
+#[test]
+fn vaults() {
+    use argon2::{argon2id, Params};
+
+    // RFC 9106's Argon2id test vector:
+    let params = Params {
+        memory: 32,
+        iterations: 3,
+        lanes: 4,
+    };
+    let mut tag = [0; 32];
+    argon2id(&[1; 32], &[2; 16], &[3; 8], &[4; 12], &params, &mut tag).unwrap();
+    assert_eq!(
+        hex::encode(tag),
+        "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+    );
+
+    // (small parameters, to keep the test quick; they're read back from the header)
+    let (_, mnemonic, _, _) = ENGLISH[1];
+    let params = Params {
+        memory: 64,
+        iterations: 1,
+        lanes: 1,
+    };
+    let mut file = vault::create_with(mnemonic, "correct horse", &params).unwrap();
+    assert_eq!(&file[..9], b"BIP39VLT\x01");
+    assert_eq!(vault::open(&file, "correct horse").unwrap(), mnemonic);
+    assert_eq!(
+        vault::open(&file, "battery staple"),
+        Err("Wrong passphrase, or the vault file is corrupt.")
+    );
+
+    // The parameters are authenticated along with the mnemonic:
+    file[9] = 128;
+    assert!(vault::open(&file, "correct horse").is_err());
+    assert_eq!(
+        vault::open(b"BIP39VLT", "correct horse"),
+        Err("Not a vault file.")
+    );
+}
+
+#[test]
+fn generated_vectors() {
+    use vectors;
+
+    // English reproduces the published vectors:
+    for &(entropy, mnemonic, seed, root_key) in ENGLISH.iter().take(6) {
+        let generated = vectors::generate(&hex::decode(entropy).unwrap(), "TREZOR").unwrap();
+        assert_eq!(generated.len(), Language::available().len());
+        let english = generated
+            .iter()
+            .find(|v| v.language == Language::English)
+            .unwrap();
+        assert_eq!(english.mnemonic, mnemonic);
+        assert_eq!(english.seed, seed);
+        assert_eq!(english.root_key, root_key);
+    }
+
+    // Every other language's phrase gives back the same entropy:
+    let entropy = [0x7f; 16];
+    for vector in vectors::generate(&entropy, "").unwrap() {
+        let mnemonic = Mnemonic::parse(&vector.mnemonic, vector.language).unwrap();
+        assert_eq!(mnemonic.entropy()[..], entropy);
+        assert_eq!(mnemonic.to_seed_hex(""), vector.seed);
+    }
+
+    let json = vectors::to_json(&vectors::generate(&entropy, "").unwrap(), "");
+    assert_eq!(
+        json["vectors"][0]["entropy"],
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"
+    );
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(
+        vectors::generate(&[0; 17], "").err(),
+        Some("Entropy must be 16, 20, 24, 28, or 32 bytes.")
+    );
+}
+
+#[test]
+fn paged_display() {
+    let mnemonic = ENGLISH[2].1;
+    let format = display::MnemonicFormat::default();
+    let pages = format.pages(mnemonic);
+    assert_eq!(pages.len(), 12);
+    assert_eq!(pages[0], " 1. letter");
+    assert_eq!(pages[11], "12. above");
+
+    let rows = display::MnemonicFormat {
+        words_per_line: Some(4),
+        ..display::MnemonicFormat::default()
+    };
+    let pages = rows.pages(mnemonic);
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[2], " 9. letter   10. advice   11. cage     12. above");
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn piped_secrets() {
+    use prompt::{self, PromptError};
+    use std::io::Cursor;
+
+    let mut input = Cursor::new(&b"correct horse\r\n\nbattery staple"[..]);
+    assert_eq!(
+        prompt::read_line(&mut input),
+        Ok("correct horse".to_string())
+    );
+    // An empty line is an empty passphrase, and a last line needs no newline...
+    assert_eq!(prompt::read_line(&mut input), Ok(String::new()));
+    assert_eq!(
+        prompt::read_line(&mut input),
+        Ok("battery staple".to_string())
+    );
+    // ...but nothing at all is the end of the input:
+    assert_eq!(prompt::read_line(&mut input), Err(PromptError::EndOfInput));
+}
+
+#[test]
+fn tezos_keys() {
+    use tezos::TezosKey;
+
+    // Flextesa's "alice" sandbox account:
+    let alice = TezosKey::from_secret_key(*b"alicealicealicealicealicealiceal");
+    assert_eq!(alice.address(), "tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjb");
+    assert_eq!(
+        alice.public_key_string(),
+        "edpkvGfYw3LyB1UcCahKQk4rF2tvbMUk8GFiTuMjL75uGXrpvKXhjn"
+    );
+    assert_eq!(
+        alice.secret_key_string(),
+        "edsk3QoqBuvdamxouPhin7swCvkQNgq4jP5KZPbwWNnwdZpSpJiEbq"
+    );
+
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let seed = Config::from_phrase(mnemonic, "").unwrap().to_seed();
+    let key = TezosKey::derive(&seed, 0).unwrap();
+    assert_eq!(key.address(), "tz1VQA4RP4fLjEEMW2FR4pE9kAg5abb5h5GL");
+    assert_ne!(TezosKey::derive(&seed, 1).unwrap().address(), key.address());
+
+    let fundraiser = TezosKey::fundraiser(&seed);
+    assert_eq!(fundraiser.secret_key_bytes()[..], seed.as_bytes()[..32]);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn passphrase_policy() {
+    use prompt::PassphrasePolicy;
+
+    let default = PassphrasePolicy::default();
+    assert!(default.confirm);
+    assert_eq!(default.check(""), Ok(()));
+    assert_eq!(default.check("x"), Ok(()));
+
+    let strict = PassphrasePolicy {
+        min_length: 4,
+        allow_empty: false,
+        ..PassphrasePolicy::default()
+    };
+    assert!(strict.check("").is_err());
+    // Characters, not bytes:
+    assert!(strict.check("äöü").is_err());
+    assert_eq!(strict.check("äöüß"), Ok(()));
+
+    // The minimum leaves "no passphrase" alone when that's allowed:
+    let lenient = PassphrasePolicy {
+        allow_empty: true,
+        ..strict
+    };
+    assert_eq!(lenient.check(""), Ok(()));
+    assert!(lenient.check("abc").is_err());
+}
+
+#[test]
+fn previews() {
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let root = Config::from_phrase(mnemonic, "")
+        .unwrap()
+        .to_seed()
+        .to_root_key(Network::Mainnet)
+        .unwrap();
+    let preview = preview::preview(&root).unwrap();
+    assert_eq!(hex::encode(preview.fingerprint), "73c5da0a");
+    assert_eq!(
+        preview.account.xpub.to_string(),
+        "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj"
+    );
+    assert_eq!(preview.first_address, "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA");
+}
+
+#[test]
+fn wordlist_search() {
+    if !Language::English.is_embedded() {
+        return;
+    }
+    let english = Language::English;
+    assert_eq!(
+        english.search("zo", true),
+        vec![(2046, "zone"), (2047, "zoo")]
+    );
+    let containing = english.search("ount", false);
+    assert!(containing.contains(&(12, "account")));
+    assert!(containing
+        .iter()
+        .all(|(index, word)| english.word_list()[*index] == *word));
+    assert!(english.search("xyzq", false).is_empty());
+}
+
+#[test]
+fn xpub_at_any_depth() {
+    use export::xpub;
+
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let root = Config::from_phrase(mnemonic, "")
+        .unwrap()
+        .to_seed()
+        .to_root_key(Network::Mainnet)
+        .unwrap();
+
+    let purpose: serde_json::Value =
+        serde_json::from_str(&xpub::at_path(&root, &"m/84'/0'".parse().unwrap()).unwrap()).unwrap();
+    assert_eq!(purpose["depth"], 2);
+    assert_eq!(purpose["child_number"], "0'");
+    assert_eq!(purpose["parent_fingerprint"], "7ead67a7");
+    assert_eq!(
+        purpose["xpub"],
+        "xpub6Ahdv22doho7XqPh2pkqidemQe8RW9GunTBew4qLdzFbzoQSXuvRoPgrK8EAsWkJAAkUAWPXxxziuJoURzPsMqmuZ7PUbAn3gyuYPvDov7r"
+    );
+    assert!(purpose["key_expression"]
+        .as_str()
+        .unwrap()
+        .starts_with("[73c5da0a/84h/0h]xpub"));
+
+    let master: serde_json::Value =
+        serde_json::from_str(&xpub::at_path(&root, &bip32::DerivationPath::master()).unwrap())
+            .unwrap();
+    assert_eq!(master["depth"], 0);
+    assert!(master["child_number"].is_null());
+    assert_eq!(master["parent_fingerprint"], "00000000");
+}
+
+#[test]
+fn root_xprv_as_backup() {
+    use account::Account;
+    use descriptor::ScriptType;
+
+    // A root key read back from its xprv gives the same wallet as the mnemonic it came from:
+    let (_, mnemonic, _, _) = ENGLISH[0];
+    let from_mnemonic = Config::from_phrase(mnemonic, "")
+        .unwrap()
+        .to_seed()
+        .to_root_key(Network::Mainnet)
+        .unwrap();
+    let from_xprv: ExtendedPrivKey = from_mnemonic.to_string().parse().unwrap();
+    assert_eq!(from_xprv.depth, 0);
+    assert_eq!(from_xprv.fingerprint(), from_mnemonic.fingerprint());
+    for &script_type in ScriptType::all().iter() {
+        assert_eq!(
+            Account::derive(&from_xprv, script_type, 0)
+                .unwrap()
+                .key_expression(),
+            Account::derive(&from_mnemonic, script_type, 0)
+                .unwrap()
+                .key_expression()
+        );
+    }
+}
+
+#[test]
+fn wordlist_analytics() {
+    for language in Language::available() {
+        let length = language.unique_prefix_length();
+        // Every word is told apart by at most that many of its characters:
+        for word in language.word_list().iter() {
+            let prefix = language.shortest_prefix(word).unwrap();
+            assert!(prefix.chars().count() <= length, "{} {}", language, word);
+            assert_eq!(language.complete(prefix), Some(*word));
+        }
+        assert!(language.shortest_prefix("not a word").is_none());
+    }
+
+    if Language::English.is_embedded() {
+        assert_eq!(Language::English.unique_prefix_length(), 4);
+        assert_eq!(Language::English.shortest_prefix("abandon"), Some("aba"));
+        assert_eq!(Language::English.shortest_prefix("zoo"), Some("zoo"));
+    }
+    if Language::English.is_embedded() && Language::French.is_embedded() {
+        let shared = Language::English.shared_words(Language::French);
+        assert_eq!(shared.len(), 100);
+        assert_eq!(shared, Language::French.shared_words(Language::English));
+        let both = Language::containing(shared[0]);
+        assert!(both.contains(&Language::English) && both.contains(&Language::French));
+    }
+}
+
+#[test]
+fn recreating_checks_the_phrase() {
+    if is_english() {
+        let (_, mnemonic, _, _) = ENGLISH[0];
+        let mut words: Vec<&str> = mnemonic.split(' ').collect();
+
+        // A word swapped for another from the list breaks the checksum...
+        let last = words.len() - 1;
+        words[last] = "zoo";
+        assert_eq!(
+            Config::from_phrase(words.join(" "), String::new()).err(),
+            Some(Error::ChecksumMismatch)
+        );
+        // ...and a typo isn't a word at all:
+        words[last] = "abuot";
+        assert_eq!(
+            Config::from_phrase(words.join(" "), String::new()).err(),
+            Some(Error::UnknownWord {
+                word: "abuot".to_string(),
+                index: last
+            })
+        );
+    }
+
+    // A phrase from another embedded list than the compiled in one is fine:
+    for language in Language::available() {
+        let phrase = get_mnemonic_from_entropy(&[0x7f; 16], language);
+        assert!(Config::from_phrase(phrase, "").is_ok(), "{}", language);
+    }
+}
+
+#[test]
+fn typed_mnemonics() {
+    use mnemonic::Mnemonic;
+
+    for language in Language::available() {
+        let phrase = get_mnemonic_from_entropy(&[0x7f; 16], language);
+        let mnemonic: Mnemonic = phrase.parse().unwrap();
+        assert_eq!(mnemonic.language(), language);
+        assert_eq!(mnemonic.entropy()[..], vec![0x7f; 16]);
+        assert_eq!(mnemonic.to_string(), phrase);
+        assert_eq!(mnemonic.words().len(), 12);
+        assert_eq!(
+            mnemonic.to_seed("TREZOR").as_bytes()[..],
+            Config::from_phrase(phrase, "TREZOR")
+                .unwrap()
+                .to_seed()
+                .as_bytes()[..]
+        );
+    }
+    assert!("abandon about".parse::<Mnemonic>().is_err());
+}
+
+#[test]
+fn mnemonic_from_entropy_bytes() {
+    use mnemonic::Mnemonic;
+
+    for &(entropy, mnemonic, _, _) in test_vectors::BIP39.iter() {
+        let generated =
+            Mnemonic::from_entropy_bytes(&hex::decode(entropy).unwrap(), Language::English)
+                .unwrap();
+        assert_eq!(generated.phrase(), mnemonic);
+    }
+    for &len in [0, 15, 17, 31, 33, 64].iter() {
+        assert_eq!(
+            Mnemonic::from_entropy_bytes(&vec![0; len], Language::English).err(),
+            Some(Error::InvalidEntropyLength(len))
+        );
+    }
+}
+
+#[test]
+fn mnemonics_in_another_language() {
+    use mnemonic::{Mnemonic, ParseOptions};
+
+    let phrase = get_mnemonic_from_entropy(&[0x80; 16], Language::English);
+    let english = Mnemonic::parse(&phrase, Language::English).unwrap();
+    for language in Language::available() {
+        let other = english.to_language(language);
+        assert_eq!(other.language(), language);
+        assert_eq!(other.entropy()[..], vec![0x80; 16]);
+        // (the same indices, checksum and all)
+        let translated = english.translate(language).unwrap();
+        assert_eq!(translated.phrase(), other.phrase());
+        let indices = |mnemonic: &Mnemonic| -> Vec<Option<usize>> {
+            let words = mnemonic.words().iter();
+            words
+                .map(|word| word_index(word, mnemonic.language()))
+                .collect()
+        };
+        assert_eq!(indices(&translated), indices(&english));
+        assert_eq!(
+            other.to_language(Language::English).phrase(),
+            english.phrase()
+        );
+    }
+
+    // The checksum is put right:
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                  abandon abandon";
+    let lenient =
+        Mnemonic::parse_with(phrase, Language::English, &ParseOptions::lenient()).unwrap();
+    assert!(!lenient.checksum_valid());
+    let fixed = lenient.to_language(Language::English);
+    assert!(fixed.checksum_valid());
+    assert_eq!(fixed.words()[11], "about");
+}
+
+#[test]
+fn secrets_left_out_of_debug() {
+    use slip10::Curve;
+
+    let (_, mnemonic, _, _) = ENGLISH[1];
+    let config = Config::from_phrase(mnemonic, "TREZOR").unwrap();
+    let debug = format!("{:?}", config);
+    assert!(!debug.contains("legal") && !debug.contains("TREZOR"));
+
+    let seed = config.to_seed();
+    assert!(!format!("{:?}", seed).contains(&seed.to_hex()[..8]));
+
+    let root = seed.to_root_key(Network::Mainnet).unwrap();
+    let debug = format!("{:?}", root);
+    assert!(debug.contains("depth: 0") && debug.contains("[redacted]"));
+    assert!(!debug.contains(&hex::encode(root.secret_key_bytes())));
+    assert!(!debug.contains(&hex::encode(root.chain_code)));
+
+    let ed25519 = seed.to_slip10_key(Curve::Ed25519).unwrap();
+    assert!(!format!("{:?}", ed25519).contains(&format!("{:?}", ed25519.secret_key_bytes())));
+}
+
+// The primitives 'hash' wraps, against their specs' own vectors (FIPS 180's "abc", RFC 4231's
+// HMAC-SHA512 cases 1 and 6, for a short and a long key), and HASH160 against the compressed
+// public key of private key 1, whose legacy address is 1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH:
+#[test]
+fn hash_known_answers() {
+    assert_eq!(
+        hex::encode(hash::sha256(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex::encode(hash::sha1(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(
+        hex::encode(hash::hmac_sha512(&[0x0b; 20], b"Hi There")),
+        "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+         daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+    );
+    assert_eq!(
+        hex::encode(hash::hmac_sha512(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        )),
+        "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+         6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+    );
+    // One keyed HMAC, reused for messages in parts, against a fresh one for each whole message:
+    let mac = hash::HmacSha512::new(b"key");
+    for len in [0, 111, 112, 127, 128, 240, 300].iter() {
+        let data = vec![0x61; *len];
+        assert_eq!(
+            mac.mac_parts(data.chunks(7)),
+            hash::hmac_sha512(b"key", &data)
+        );
+    }
+    let public_key =
+        hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    assert_eq!(
+        hex::encode(hash::hash160(&public_key)),
+        "751e76e8199196d454941c45d1b3a323f1433bd6"
+    );
+}
+
+#[test]
+fn wordlist_lookups() {
+    use wordlist::Wordlist;
+
+    for language in Language::available() {
+        let words = Wordlist::of(language);
+        for (index, &word) in language.word_list().iter().enumerate() {
+            assert_eq!(words.get(index), Some(word));
+            assert_eq!(words.index_of(word), Some(index));
+        }
+        assert_eq!(words.get(2048), None);
+    }
+
+    let english = Wordlist::of(Language::English);
+    assert_eq!(english.index_of("zoo"), Some(2047));
+    assert_eq!(english.index_of("zo"), None);
+    assert_eq!(english.words_starting_with("zo"), ["zone", "zoo"]);
+    assert_eq!(english.words_starting_with("abando"), ["abandon"]);
+    assert!(english.words_starting_with("xylo").is_empty());
+    assert_eq!(english.words_starting_with("").len(), 2048);
+    // Unsorted lists give theirs in list order too:
+    let chinese = Wordlist::of(Language::ChineseSimplified);
+    let found = chinese.words_starting_with("的");
+    assert_eq!(found, ["的"]);
+    // (as NFKD has it, an "a" and a combining accent)
+    let spanish = Wordlist::of(Language::Spanish);
+    assert_eq!(spanish.words_starting_with("a\u{301}ba"), ["a\u{301}baco"]);
+    assert_eq!(
+        Language::Spanish.complete("a\u{301}ba"),
+        Some("a\u{301}baco")
+    );
+}
+
+#[test]
+fn custom_wordlists() {
+    use wordlist::Wordlist;
+
+    // English upper-cased, so that no embedded list has the words:
+    let upper: Vec<String> = word_list(Language::English)
+        .iter()
+        .map(|word| word.to_uppercase())
+        .collect();
+    let list = Wordlist::from_lines(&upper.join("\n")).unwrap();
+    let custom = Language::Custom(list);
+    assert_eq!(list.index_of("ZOO"), Some(2047));
+    assert_eq!(custom.complete("ABAN"), Some("ABANDON"));
+    assert_eq!(custom.unique_prefix_length(), 4);
+    assert_eq!(custom.to_string(), "custom");
+
+    let mnemonic = Mnemonic::from_entropy_bytes(&[0x7f; 16], custom).unwrap();
+    assert_eq!(mnemonic.phrase(), ENGLISH[1].1.to_uppercase());
+    assert_eq!(
+        Mnemonic::parse(&mnemonic.phrase(), custom)
+            .unwrap()
+            .entropy()[..],
+        [0x7f; 16]
+    );
+    let config = Config::from_phrase_in(mnemonic.phrase(), "", custom).unwrap();
+    assert_eq!(config.mnemonic, mnemonic.phrase());
+    assert_eq!(
+        Config::from_phrase(mnemonic.phrase(), "").unwrap_err(),
+        Error::UnknownWord {
+            word: "LEGAL".to_string(),
+            index: 0
+        }
+    );
+    let generated = Config::generate_in(custom, MnemonicListSize::Twelve, "");
+    assert!(Mnemonic::parse(&generated.mnemonic, custom)
+        .unwrap()
+        .checksum_valid());
+
+    // Blank lines and spacing don't count, but the words do:
+    let spaced = format!("\n  {}  \n\n", upper.join("\n"));
+    assert_eq!(*Wordlist::from_lines(&spaced).unwrap(), *list);
+    assert_eq!(
+        Wordlist::from_lines(&upper[1..].join("\n")).unwrap_err(),
+        "A wordlist must have 2,048 words."
+    );
+    let mut changed = upper.clone();
+    changed[1] = "ZOO".to_string();
+    assert_eq!(
+        Wordlist::from_lines(&changed.join("\n")).unwrap_err(),
+        "A wordlist's words must all be different."
+    );
+    changed[1] = "ABANDONS".to_string();
+    assert_eq!(
+        Wordlist::from_lines(&changed.join("\n")).unwrap_err(),
+        "A wordlist's words must differ in their first four letters."
+    );
+    changed[1] = "ICE CREAM".to_string();
+    assert_eq!(
+        Wordlist::from_lines(&changed.join("\n")).unwrap_err(),
+        "A wordlist's words can't have spaces in them."
+    );
+
+    // Words as long as the longest built in one fill a 24 word phrase's buffer, and no more:
+    let padded = |len: usize| -> Vec<String> {
+        upper
+            .iter()
+            .map(|word| format!("{:X<1$}", word, len))
+            .collect()
+    };
+    let longest = Wordlist::from_lines(&padded(wordlists::MAX_WORD_LEN).join("\n")).unwrap();
+    let mnemonic = Mnemonic::from_entropy_bytes(&[7; 32], Language::Custom(longest)).unwrap();
+    assert_eq!(mnemonic.phrase().len(), MAX_PHRASE_LEN);
+    assert_eq!(
+        Wordlist::from_lines(&padded(wordlists::MAX_WORD_LEN + 1).join("\n")).unwrap_err(),
+        "A wordlist's words can't be longer than the built in lists' longest."
+    );
+}
+
+#[test]
+fn suggestions_for_typos() {
+    let english = Language::English;
+    assert_eq!(english.suggest("yellow"), ["yellow"]);
+    assert_eq!(english.suggest("YELLOW"), Vec::<&str>::new());
+    assert_eq!(english.suggest(""), Vec::<&str>::new());
+    assert_eq!(english.suggest("qqqqqq"), Vec::<&str>::new());
+    // The words it starts, then the nearest, those starting the same way first:
+    assert_eq!(english.suggest("zo")[..2], ["zone", "zoo"]);
+    assert_eq!(english.suggest("yelow")[..2], ["yellow", "below"]);
+    assert_eq!(english.suggest("winnr")[0], "winner");
+    assert_eq!(english.suggest("abandno")[0], "abandon");
+    // (typed with the accent as one character, and looked up in NFKD)
+    assert_eq!(Language::Spanish.suggest("\u{e1}baco"), ["a\u{301}baco"]);
+}
+
+#[test]
+fn last_word_candidates() {
+    for &(_, phrase, _, _) in ENGLISH.iter() {
+        let words: Vec<&str> = phrase.split(' ').collect();
+        let (last, partial) = words.split_last().unwrap();
+        let candidates = mnemonic::Mnemonic::valid_last_words(partial, Language::English).unwrap();
+        // 11 bits, less one checksum bit per three words:
+        assert_eq!(candidates.len(), 1 << (11 - words.len() / 3));
+        assert!(candidates.contains(last));
+        for candidate in candidates {
+            let phrase = format!("{} {}", partial.join(" "), candidate);
+            assert!(mnemonic::Mnemonic::parse(&phrase, Language::English).is_ok());
+        }
+    }
+
+    let abandon = ["abandon"; 23];
+    let candidates = mnemonic::Mnemonic::valid_last_words(&abandon, Language::English).unwrap();
+    assert_eq!(
+        candidates,
+        ["art", "diesel", "false", "kite", "organ", "ready", "surface", "trouble"]
+    );
+    assert_eq!(
+        mnemonic::Mnemonic::valid_last_words(&abandon[..10], Language::English).unwrap_err(),
+        Error::InvalidWordCount(10)
+    );
+    let mut typo = abandon;
+    typo[3] = "abandn";
+    assert_eq!(
+        mnemonic::Mnemonic::valid_last_words(&typo, Language::English).unwrap_err(),
+        Error::UnknownWord {
+            word: "abandn".to_string(),
+            index: 3
+        }
+    );
+}
+
+#[test]
+fn searching_partial_mnemonics() {
+    use recover::{Search, Target};
+
+    let (_, phrase, _, root) = ENGLISH[1];
+    let words: Vec<&str> = phrase.split(' ').collect();
+    let found = |pattern: &[&str]| -> Vec<String> {
+        let search = Search::new(&pattern.join(" "), Language::English).unwrap();
+        search
+            .candidates()
+            .map(|mnemonic| mnemonic.phrase())
+            .collect()
+    };
+
+    // A lost word, a smudged one, and alternatives:
+    let mut pattern = words.clone();
+    pattern[4] = "?";
+    assert!(found(&pattern).contains(&phrase.to_string()));
+    pattern[4] = "w*e";
+    pattern[7] = "usual|useful";
+    let candidates = found(&pattern);
+    assert!(candidates.contains(&phrase.to_string()));
+    assert!(candidates
+        .iter()
+        .all(|candidate| candidate.parse::<mnemonic::Mnemonic>().is_ok()));
+
+    // A word lost from somewhere, found once, though "legal" and "winner" come twice:
+    let mut pattern = words.clone();
+    pattern.remove(8);
+    let candidates = found(&pattern);
+    assert_eq!(
+        candidates
+            .iter()
+            .filter(|candidate| *candidate == phrase)
+            .count(),
+        1
+    );
+
+    // The candidates narrowed down to the wallet:
+    let mut pattern = words.clone();
+    pattern[5] = "sa*";
+    let search = Search::new(&pattern.join(" "), Language::English).unwrap();
+    assert_eq!(search.combinations(), 19);
+    let fingerprint = root.parse::<ExtendedPrivKey>().unwrap().fingerprint();
+    let mut checked = 0;
+    let wallets = search
+        .find(&Target::Fingerprint(fingerprint), "TREZOR", &mut |done| {
+            checked = done
+        })
+        .unwrap();
+    assert_eq!(wallets.len(), 1);
+    assert_eq!(wallets[0].phrase(), phrase);
+    assert_eq!(checked, search.candidates().count() as u64);
+
+    assert_eq!(
+        Search::new(&words[..10].join(" "), Language::English).err(),
+        Some(Error::InvalidWordCount(10))
+    );
+    assert_eq!(
+        Search::new("zz* legal", Language::English).err(),
+        Some(Error::NoCloseWord {
+            word: "zz*".to_string(),
+            index: 0
+        })
+    );
+}
+
+#[test]
+fn entropy_from_rolls() {
+    use entropy::{Conditioning, Rolls, Source};
+
+    // Hashed: 50 rolls hold the 128 bits of 12 words, 100 the 256 of 24:
+    let mut rolls = Rolls::new(Source::Dice, Conditioning::Sha256, MnemonicListSize::Twelve);
+    assert_eq!(rolls.remaining(), 50);
+    rolls.add("1 2 3 4 5 6, 6 5 4 3 2 1").unwrap();
+    assert_eq!((rolls.count(), rolls.remaining()), (12, 38));
+    assert_eq!(rolls.add("1 2 7").unwrap_err(), "Dice rolls are 1 to 6.");
+    assert_eq!(rolls.count(), 12);
+    assert_eq!(rolls.entropy(), None);
+    rolls.add(&"3".repeat(38)).unwrap();
+    let digits = format!("123456654321{}", "3".repeat(38));
+    assert_eq!(
+        rolls.entropy().unwrap(),
+        hash::sha256(digits.as_bytes())[..16]
+    );
+    let rolls = Rolls::new(
+        Source::Dice,
+        Conditioning::Sha256,
+        MnemonicListSize::TwentyFour,
+    );
+    assert_eq!(rolls.remaining(), 100);
+
+    // Pairs: different flips are a bit (heads first a 1), the same are thrown away:
+    let mut flips = Rolls::new(
+        Source::Coin,
+        Conditioning::VonNeumann,
+        MnemonicListSize::Twelve,
+    );
+    assert_eq!(flips.remaining(), 512);
+    flips.add("hh tt").unwrap();
+    assert_eq!(flips.remaining(), 512);
+    flips.add("h").unwrap();
+    assert_eq!(flips.remaining(), 511);
+    flips.add(&format!("t{}", "thht".repeat(63))).unwrap();
+    assert_eq!(flips.remaining(), 4);
+    flips.add("TH").unwrap();
+    assert_eq!(flips.remaining(), 0);
+    let entropy = flips.entropy().unwrap();
+    assert_eq!(entropy, [0xaa; 16]);
+    assert!(mnemonic::Mnemonic::from_entropy_bytes(&entropy, Language::English).is_ok());
+    assert_eq!(
+        flips.add("x").unwrap_err(),
+        "Coin flips are h or t (or 1 or 0)."
+    );
+}
+
+#[test]
+fn entropy_sources() {
+    use entropy::{self, Conditioning, EntropySource, Mixed, OsRandom, Rolls, Source};
+
+    // A source of one's own, here a test vector's entropy:
+    struct Fixed(u8);
+    impl EntropySource for Fixed {
+        fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+            entropy.iter_mut().for_each(|byte| *byte = self.0);
+            Ok(())
+        }
+    }
+    let config = Config::generate_using(
+        &mut Fixed(0x7f),
+        Language::English,
+        MnemonicListSize::Twelve,
+        "",
+    )
+    .unwrap();
+    assert_eq!(config.mnemonic, ENGLISH[1].1);
+
+    // The system's numbers, any length of them:
+    assert_ne!(entropy::random::<32>(), entropy::random::<32>());
+    let mut odd = [0; 5];
+    OsRandom.fill(&mut odd).unwrap();
+
+    // Mixed, the same material makes different mnemonics, since the system's numbers differ:
+    let mut mixed = Mixed::new(b"keys mashed at random");
+    let (mut first, mut second) = ([0; 32], [0; 32]);
+    mixed.fill(&mut first).unwrap();
+    mixed.fill(&mut second).unwrap();
+    assert_ne!(first, second);
+    let size = MnemonicListSize::TwentyFour;
+    let config = Config::generate_using(&mut mixed, Language::English, size, "").unwrap();
+    assert!(config
+        .mnemonic
+        .parse::<Mnemonic>()
+        .unwrap()
+        .checksum_valid());
+
+    // Rolls, once there are enough for as many words as they're asked for:
+    let mut rolls = Rolls::new(Source::Coin, Conditioning::Sha256, MnemonicListSize::Twelve);
+    let generate = |rolls: &mut Rolls, size| {
+        Config::generate_using(rolls, Language::English, size, "").map(|_| ())
+    };
+    assert_eq!(
+        generate(&mut rolls, MnemonicListSize::Twelve),
+        Err(Error::Other("There aren't enough rolls yet."))
+    );
+    rolls.add(&"h".repeat(128)).unwrap();
+    assert_eq!(generate(&mut rolls, MnemonicListSize::Twelve), Ok(()));
+    assert_eq!(
+        generate(&mut rolls, MnemonicListSize::TwentyFour),
+        Err(Error::Other("The rolls were for another number of words."))
+    );
+}
+
+#[test]
+fn slip39_shares() {
+    use slip39::{Group, Share};
+
+    let parse = |mnemonics: &[&str]| -> Vec<Share> {
+        mnemonics
+            .iter()
+            .map(|mnemonic| mnemonic.parse().unwrap())
+            .collect()
+    };
+    // The spec's vectors (a 1-of-1 share, and 2 of a 2-of-3), under their "TREZOR" passphrase:
+    let single = "duckling enlarge academic academic agency result length solution fridge kidney \
+                  coal piece deal husband erode duke ajar critical decision keyboard";
+    let shares = parse(&[single]);
+    assert_eq!(shares[0].to_mnemonic(), single);
+    assert_eq!(
+        hex::encode(slip39::combine(&shares, "TREZOR").unwrap()),
+        "bb54aac4b89dc868ba37d9cc21b2cece"
+    );
+    let shares = parse(&[
+        "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist \
+         rescue view short owner flip making coding armed",
+        "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip \
+         twice unkind craft early superior advocate guest smoking",
+    ]);
+    assert_eq!(
+        hex::encode(slip39::combine(&shares, "TREZOR").unwrap()),
+        "b43ceb7e57a0ea8766221624d01b0864"
+    );
+    assert_eq!(
+        slip39::combine(&shares[..1], "TREZOR").unwrap_err(),
+        Error::Other("There aren't enough shares to combine.")
+    );
+    let typo = single.replace("keyboard", "kidney");
+    assert_eq!(
+        typo.parse::<Share>().unwrap_err(),
+        Error::Other("Invalid SLIP-39 share checksum.")
+    );
+
+    // Two of three groups, from a 24 word mnemonic's entropy:
+    let entropy = hex::decode(ENGLISH[11].0).unwrap();
+    let groups: Vec<Group> = ["1-of-1", "2-of-3", "3-of-5"]
+        .iter()
+        .map(|group| group.parse().unwrap())
+        .collect();
+    let split = slip39::split(&entropy, "", 2, &groups, 0).unwrap();
+    assert_eq!(split.iter().map(Vec::len).collect::<Vec<_>>(), [1, 3, 5]);
+    let again = |shares: &[&Share]| -> Vec<Share> {
+        shares
+            .iter()
+            .map(|share| share.to_mnemonic().parse().unwrap())
+            .collect()
+    };
+    let chosen = again(&[&split[0][0], &split[2][4], &split[2][0], &split[2][2]]);
+    assert_eq!(split[0][0].to_mnemonic().split(' ').count(), 33);
+    assert_eq!(slip39::combine(&chosen, "").unwrap(), entropy);
+    let chosen = again(&[
+        &split[1][2],
+        &split[2][1],
+        &split[1][0],
+        &split[2][3],
+        &split[2][4],
+    ]);
+    assert_eq!(slip39::combine(&chosen, "").unwrap(), entropy);
+    // (a group short of its threshold counts for nothing)
+    let chosen = again(&[&split[0][0], &split[1][1], &split[2][1], &split[2][3]]);
+    assert_eq!(
+        slip39::combine(&chosen, "").unwrap_err(),
+        Error::Other("There aren't enough shares to combine.")
+    );
+    // Another passphrase is another secret, not an error, as in BIP-39:
+    let chosen = again(&[&split[0][0], &split[1][0], &split[1][1]]);
+    assert_ne!(slip39::combine(&chosen, "TREZOR").unwrap(), entropy);
+    assert_eq!(
+        slip39::split(&entropy, "", 1, &["1-of-3".parse().unwrap()], 0).unwrap_err(),
+        Error::Other("A group of more than one share needs a threshold of at least 2.")
+    );
+}
+
+#[test]
+fn seed_xor_parts() {
+    let parse = |phrase: &str| Mnemonic::parse(phrase, Language::English).unwrap();
+    // Coldcard's own example, three 24 word parts:
+    let parts = [
+        parse(
+            "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss \
+             spice fitness easy legal release recall obey exchange recycle dragon room",
+        ),
+        parse(
+            "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager \
+             fringe sick camera series noodle toy crowd jeans select depth lounge",
+        ),
+        parse(
+            "vault nominee cradle silk own frown throw leg cactus recall talent worry gadget \
+             surface shy planet purpose coffee drip few seven term squeeze educate",
+        ),
+    ];
+    let original = parse(
+        "silent toe meat possible chair blossom wait occur this worth option bag nurse find fish \
+         scene bench asthma bike wage world quit primary indoor",
+    );
+    assert_eq!(
+        seed_xor::combine(&parts).unwrap().phrase(),
+        original.phrase()
+    );
+
+    let split = seed_xor::split(&original, 4).unwrap();
+    assert_eq!(split.len(), 4);
+    assert!(split
+        .iter()
+        .all(|part| part.words().len() == 24 && part.phrase() != original.phrase()));
+    assert_eq!(
+        seed_xor::combine(&split).unwrap().phrase(),
+        original.phrase()
+    );
+    assert_ne!(
+        seed_xor::combine(&split[..3]).unwrap().phrase(),
+        original.phrase()
+    );
+
+    assert_eq!(
+        seed_xor::split(&original, 1).err(),
+        Some(Error::Other("Seed XOR takes at least two parts."))
+    );
+    let short = parse(test_vectors::BIP39[0].1);
+    assert_eq!(
+        seed_xor::combine(&[parse(&parts[0].phrase()), short]).err(),
+        Some(Error::Other(
+            "The parts must all have the same number of words."
+        ))
+    );
+}
+
+#[test]
+fn keystores() {
+    use keystore::{Contents, KeyDerivation};
+
+    // (parameters far too weak for real use, so the test is quick)
+    let scrypt = KeyDerivation::Scrypt {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+    let argon2id = KeyDerivation::Argon2id(argon2::Params {
+        memory: 32,
+        iterations: 1,
+        lanes: 1,
+    });
+    let phrase = test_vectors::BIP39[1].1;
+    let mnemonic = Mnemonic::parse(phrase, Language::English).unwrap();
+    let file = keystore::export_with(&Contents::Mnemonic(mnemonic), "hunter2", &scrypt).unwrap();
+    match keystore::import(&file, "hunter2") {
+        Ok(Contents::Mnemonic(mnemonic)) => assert_eq!(mnemonic.phrase(), phrase),
+        _ => panic!("The mnemonic didn't come back."),
+    }
+    assert!(matches!(
+        keystore::import(&file, "hunter3"),
+        Err(Error::WrongPassword)
+    ));
+
+    let seed = Mnemonic::parse(phrase, Language::English)
+        .unwrap()
+        .to_seed("TREZOR");
+    let file = keystore::export_with(&Contents::Seed(seed), "hunter2", &argon2id).unwrap();
+    match keystore::import(&file, "hunter2") {
+        Ok(Contents::Seed(seed)) => assert_eq!(seed.to_hex(), test_vectors::BIP39[1].2),
+        _ => panic!("The seed didn't come back."),
+    }
+    // The header is authenticated, so it can't be changed, not even to another valid one:
+    let tampered = file.replace("\"iterations\": 1", "\"iterations\": 2");
+    assert_ne!(tampered, file);
+    assert!(keystore::import(&tampered, "hunter2").is_err());
+    assert!(keystore::import("{}", "hunter2").is_err());
+
+    // A file written by an earlier build (with rust-crypto's scrypt and AES-GCM), which has to go
+    // on opening:
+    let file = r#"{
+      "ciphertext": "df77cdab59d745e62eeb0c60421ef5b1e7d1232ae1cdd32b1a1be613fde885744c4c57ddda72a2b42564a87ce21267b3af679da6bf66a5e654ad92c86ea6d4b425f02c6fe40f708f45d3b7",
+      "header": {
+        "cipher": { "name": "aes-256-gcm", "nonce": "574fe8479cc8d54e8015435f" },
+        "contents": "mnemonic",
+        "kdf": { "log_n": 4, "name": "scrypt", "p": 1, "r": 8, "salt": "1e19126b8d75ebbd356ceaf37bfee0ea" },
+        "version": 1
+      },
+      "tag": "2f745812085591c76386ad45bcf8c117"
+    }"#;
+    match keystore::import(file, "hunter2") {
+        Ok(Contents::Mnemonic(mnemonic)) => assert_eq!(mnemonic.phrase(), phrase),
+        _ => panic!("The earlier keystore didn't open."),
+    }
+}
+
+#[test]
+fn seedqr_payloads() {
+    use qr::QrCode;
+
+    // SeedSigner's own example:
+    let mnemonic = Mnemonic::parse(
+        "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy \
+         pony ranch winter theme error hybrid van cereal salon goddess expire",
+        Language::English,
+    )
+    .unwrap();
+    let digits = seedqr::standard(&mnemonic);
+    assert_eq!(
+        digits,
+        "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408\
+         010643"
+    );
+    assert_eq!(seedqr::compact(&mnemonic), mnemonic.entropy());
+
+    // 24 words take a 29 module code as digits and a 25 module one compact; 12 words, 25 and 21:
+    assert_eq!(QrCode::numeric(&digits).unwrap().size(), 29);
+    assert_eq!(
+        QrCode::bytes(&seedqr::compact(&mnemonic)).unwrap().size(),
+        25
+    );
+    let twelve = Mnemonic::parse(test_vectors::BIP39[0].1, Language::English).unwrap();
+    assert_eq!(
+        QrCode::numeric(&seedqr::standard(&twelve)).unwrap().size(),
+        25
+    );
+    let code = QrCode::bytes(&seedqr::compact(&twelve)).unwrap();
+    assert_eq!(code.size(), 21);
+    // (the finders' corners, and the module that's always dark)
+    assert!(
+        code.is_dark(0, 0) && code.is_dark(20, 0) && code.is_dark(0, 20) && code.is_dark(8, 13)
+    );
+    assert!(!code.is_dark(7, 7));
+
+    assert!(QrCode::numeric("12ab").is_err());
+    assert!(QrCode::bytes(&[0; 54]).is_err());
+
+    // And back, as scanned:
+    let read = seedqr::read(digits.as_bytes(), Language::English).unwrap();
+    assert_eq!(read.words(), mnemonic.words());
+    let read = seedqr::read(&seedqr::compact(&twelve), Language::English).unwrap();
+    assert_eq!(read.words(), twelve.words());
+    assert_eq!(
+        seedqr::read(&digits.as_bytes()[..47], Language::English).err(),
+        Some(Error::Other(
+            "A standard SeedQR has four digits for each word."
+        ))
+    );
+    // (2048 is past the list's end, and the wrong last word fails the checksum)
+    let past_end = format!("2048{}", &digits[4..]);
+    assert!(seedqr::read(past_end.as_bytes(), Language::English).is_err());
+    let wrong_checksum = format!("{}0000", &digits[..92]);
+    assert_eq!(
+        seedqr::read(wrong_checksum.as_bytes(), Language::English).err(),
+        Some(Error::ChecksumMismatch)
+    );
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn c_interface() {
+    use ffi::*;
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+
+    let c_str = |string: &str| CString::new(string).unwrap();
+    let last_error = || unsafe { CStr::from_ptr(bip39_last_error()).to_str().unwrap() };
+    unsafe {
+        let mnemonic = bip39_generate_mnemonic(24, c_str("english").as_ptr());
+        assert_eq!(
+            CStr::from_ptr(mnemonic)
+                .to_str()
+                .unwrap()
+                .split(' ')
+                .count(),
+            24
+        );
+        assert_eq!(bip39_validate(mnemonic), 0);
+        bip39_string_free(mnemonic);
+        assert!(bip39_generate_mnemonic(13, ptr::null()).is_null());
+        assert_eq!(last_error(), "Invalid mnemonic word list size.");
+
+        if !is_english() {
+            return;
+        }
+        for &(_, mnemonic, seed, xprv) in ENGLISH.iter() {
+            let mut bytes = [0; 64];
+            let phrase = c_str(mnemonic);
+            let passphrase = c_str("TREZOR");
+            assert_eq!(
+                bip39_mnemonic_to_seed(phrase.as_ptr(), passphrase.as_ptr(), &mut bytes[0]),
+                0
+            );
+            assert_eq!(hex::encode(&bytes[..]), seed);
+            let root = bip39_seed_to_xprv(&bytes[0], ptr::null());
+            assert_eq!(CStr::from_ptr(root).to_str().unwrap(), xprv);
+            bip39_string_free(root);
+        }
+
+        assert_eq!(
+            bip39_validate(c_str("abandon ".repeat(12).trim_end()).as_ptr()),
+            -1
+        );
+        assert_eq!(last_error(), "Invalid mnemonic checksum.");
+        assert_eq!(
+            bip39_mnemonic_to_seed(ptr::null(), ptr::null(), ptr::null_mut()),
+            -1
+        );
+        assert!(bip39_seed_to_xprv([0; 64].as_ptr(), c_str("nowhere").as_ptr()).is_null());
+        assert_eq!(last_error(), "Unknown network.");
+        bip39_string_free(ptr::null_mut());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_needs_secrets_exposed() {
+    use bip32::ExtendedPubKey;
+    use serde::{Deserialize, Serialize};
+    use serde_secret::{expose_secret, ExposeSecret};
+
+    #[derive(Serialize, Deserialize)]
+    struct Wallet {
+        #[serde(serialize_with = "expose_secret")]
+        mnemonic: Mnemonic,
+        language: Language,
+        words: MnemonicListSize,
+    }
+
+    let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+    let json = format!(
+        r#"{{"mnemonic":"{}","language":"english","words":12}}"#,
+        phrase
+    );
+    let wallet: Wallet = serde_json::from_str(&json).unwrap();
+    assert_eq!(wallet.mnemonic.phrase(), phrase);
+    assert_eq!(serde_json::to_string(&wallet).unwrap(), json);
+
+    let seed = Seed::new(&wallet.mnemonic, "TREZOR");
+    let hex = serde_json::to_string(&ExposeSecret(&seed)).unwrap();
+    assert_eq!(hex, format!("\"{}\"", seed.to_hex()));
+    let ExposeSecret(read): ExposeSecret<Seed> = serde_json::from_str(&hex).unwrap();
+    assert_eq!(read.as_bytes(), seed.as_bytes());
+
+    let root = seed.to_root_key(Network::Mainnet).unwrap();
+    let xprv = serde_json::to_string(&ExposeSecret(&root)).unwrap();
+    assert_eq!(xprv, format!("\"{}\"", root));
+    let xpub = serde_json::to_string(&root.to_extended_pub_key()).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ExtendedPubKey>(&xpub).unwrap(),
+        root.to_extended_pub_key()
+    );
+
+    assert!(serde_json::from_str::<MnemonicListSize>("13").is_err());
+    assert!(serde_json::from_str::<Mnemonic>(r#""abandon abandon""#).is_err());
+    assert!(serde_json::from_str::<Seed>(r#""00ff""#).is_err());
+}
+
+#[test]
+fn detecting_the_language() {
+    for language in Language::available() {
+        let mnemonic = Mnemonic::from_entropy_bytes(&[0x5a; 16], language).unwrap();
+        assert!(Language::detect(&mnemonic.phrase()) == Ok(language));
+        assert_eq!(
+            mnemonic.phrase().parse::<Mnemonic>().unwrap().language(),
+            language
+        );
+    }
+    assert_eq!(
+        Language::detect("abandon abandon"),
+        Err(Error::InvalidWordCount(2))
+    );
+
+    if !(is_english() && Language::French.is_embedded()) {
+        return;
+    }
+    // A phrase of words English and French share is in whichever list takes its checksum, and
+    // in English (the compiled in language) when both do:
+    let shared = Language::English.shared_words(Language::French);
+    let mut french_only = false;
+    for &last in shared.iter() {
+        let phrase = format!("{} {}", shared[..11].join(" "), last);
+        let english = Mnemonic::parse(&phrase, Language::English).is_ok();
+        let french = Mnemonic::parse(&phrase, Language::French).is_ok();
+        match Language::detect(&phrase) {
+            Ok(Language::English) => assert!(english),
+            Ok(Language::French) => assert!(french && !english),
+            Ok(_) => panic!("Detected another language."),
+            Err(err) => assert!(!english && !french && err == Error::ChecksumMismatch),
+        }
+        french_only |= french && !english;
+    }
+    assert!(french_only);
+
+    // Otherwise the error is the one from the list with the most of the words:
+    let mut words: Vec<String> = Mnemonic::from_entropy_bytes(&[0x5a; 16], Language::French)
+        .unwrap()
+        .words()
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    words[3] = "zzz".to_string();
+    assert_eq!(Language::closest(&words.join(" ")), Language::French);
+    match Language::detect(&words.join(" ")) {
+        Err(Error::UnknownWord { index, .. }) => assert_eq!(index, 3),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn generating_a_batch() {
+    use mnemonic::MnemonicGenerator;
+
+    // Each mnemonic from the next of the source's bytes, and then a source that gives out:
+    struct Counting(u8);
+    impl EntropySource for Counting {
+        fn fill(&mut self, entropy: &mut [u8]) -> Result<(), &'static str> {
+            if self.0 == 3 {
+                return Err("Out of entropy.");
+            }
+            entropy.iter_mut().for_each(|byte| *byte = self.0);
+            self.0 += 1;
+            Ok(())
+        }
+    }
+    let mut source = Counting(0);
+    let batch: Vec<_> =
+        MnemonicGenerator::new(&mut source, Language::English, MnemonicListSize::Fifteen)
+            .take(4)
+            .collect();
+    for (byte, mnemonic) in batch[..3].iter().enumerate() {
+        let mnemonic = mnemonic.as_ref().unwrap();
+        assert_eq!(mnemonic.words().len(), 15);
+        assert_eq!(mnemonic.entropy()[..], vec![byte as u8; 20]);
+    }
+    assert_eq!(
+        batch[3].as_ref().err(),
+        Some(&Error::Other("Out of entropy."))
+    );
+
+    let mut random = OsRandom;
+    let mut generator =
+        MnemonicGenerator::new(&mut random, Language::English, MnemonicListSize::Twelve);
+    let first = generator.next().unwrap().unwrap();
+    assert_ne!(first.phrase(), generator.next().unwrap().unwrap().phrase());
+}
+
+#[test]
+fn passphrase_strength() {
+    let weak = estimate_passphrase_strength("password");
+    assert!(weak.is_weak());
+    assert_eq!(weak.score, 0);
+    assert!(weak.warning.is_some());
+    // The words of wallets are as good as dictionary words to whoever found the mnemonic:
+    assert!(estimate_passphrase_strength("Satoshi1").is_weak());
+    assert!(estimate_passphrase_strength("").is_weak());
+
+    let strong = estimate_passphrase_strength("quartz pelican 47 drizzle unmoored ferry");
+    assert!(!strong.is_weak());
+    assert_eq!(strong.score, 4);
+    assert!(strong.guesses_log10 > weak.guesses_log10);
+}
@@ -0,0 +1,78 @@
+// Tezos keys from the same mnemonic, for checking a backup offline.  Wallets from Ledger's on
+// (Kukai, Temple, Umami) derive an ed25519 key by SLIP-0010 at m/44'/1729'/account'/0', every
+// step hardened, as ed25519 needs.  The 2017 fundraiser wallets came before any of that: their
+// key is simply the first 32 bytes of the BIP-39 seed of a 15 word mnemonic, with the email
+// address and password the fundraiser issued, run together, as the passphrase.
+//
+// Keys and addresses are Base58Check behind prefixes that make them start with fixed letters:
+// "tz1..." for the address (BLAKE2b-160 of the public key), "edpk..." for the public key, and
+// "edsk..." for the secret key, in the 32 byte form wallets import.
+
+use base58;
+use bip32::{ChildNumber, DerivationPath};
+use ed25519_dalek::SigningKey;
+use hash::blake2b_160;
+use slip10::{Curve, ExtendedKey};
+use Seed;
+
+// SLIP-44's coin type for Tezos:
+const COIN_TYPE: u32 = 1729;
+
+const TZ1_PREFIX: [u8; 3] = [6, 161, 159];
+const EDPK_PREFIX: [u8; 4] = [13, 15, 37, 217];
+const EDSK_PREFIX: [u8; 4] = [13, 15, 58, 7];
+
+pub struct TezosKey {
+    pub public_key: [u8; 32],
+    secret_key: [u8; 32],
+}
+
+impl TezosKey {
+    pub fn derive(seed: &Seed, account: u32) -> Result<TezosKey, &'static str> {
+        let path = DerivationPath::from(vec![
+            ChildNumber::hardened(44)?,
+            ChildNumber::hardened(COIN_TYPE)?,
+            ChildNumber::hardened(account)?,
+            ChildNumber::hardened(0)?,
+        ]);
+        let key = ExtendedKey::new_master(seed.as_bytes(), Curve::Ed25519)?.derive_path(&path)?;
+        Ok(TezosKey::from_secret_key(key.secret_key_bytes()))
+    }
+
+    // A fundraiser wallet's key, from the seed of its mnemonic with 'email' + 'password' as the
+    // passphrase:
+    pub fn fundraiser(seed: &Seed) -> TezosKey {
+        let mut secret_key = [0; 32];
+        secret_key.copy_from_slice(&seed.as_bytes()[..32]);
+        TezosKey::from_secret_key(secret_key)
+    }
+
+    pub fn from_secret_key(secret_key: [u8; 32]) -> TezosKey {
+        TezosKey {
+            public_key: SigningKey::from_bytes(&secret_key).verifying_key().to_bytes(),
+            secret_key,
+        }
+    }
+
+    pub fn address(&self) -> String {
+        with_prefix(&TZ1_PREFIX, &blake2b_160(&self.public_key))
+    }
+
+    pub fn public_key_string(&self) -> String {
+        with_prefix(&EDPK_PREFIX, &self.public_key)
+    }
+
+    pub fn secret_key_string(&self) -> String {
+        with_prefix(&EDSK_PREFIX, &self.secret_key)
+    }
+
+    pub fn secret_key_bytes(&self) -> [u8; 32] {
+        self.secret_key
+    }
+}
+
+fn with_prefix(prefix: &[u8], data: &[u8]) -> String {
+    let mut payload = prefix.to_vec();
+    payload.extend(data);
+    base58::encode_check(&payload)
+}
@@ -0,0 +1,57 @@
+// The few pieces of Bitcoin's transaction serialization that message signing needs: the
+// "CompactSize" variable length integers and length-prefixed byte strings.
+
+pub fn push_varint(data: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0xFC => data.push(value as u8),
+        0xFD..=0xFFFF => {
+            data.push(0xFD);
+            data.extend(&(value as u16).to_le_bytes());
+        }
+        0x10000..=0xFFFF_FFFF => {
+            data.push(0xFE);
+            data.extend(&(value as u32).to_le_bytes());
+        }
+        _ => {
+            data.push(0xFF);
+            data.extend(&value.to_le_bytes());
+        }
+    }
+}
+
+pub fn push_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    push_varint(data, bytes.len() as u64);
+    data.extend(bytes);
+}
+
+// Reads a varint from the front of 'data', moving it along:
+pub fn read_varint(data: &mut &[u8]) -> Result<u64, &'static str> {
+    let (&first, rest) = data.split_first().ok_or("Unexpected end of data.")?;
+    let len = match first {
+        0xFD => 2,
+        0xFE => 4,
+        0xFF => 8,
+        _ => {
+            *data = rest;
+            return Ok(u64::from(first));
+        }
+    };
+    if rest.len() < len {
+        return Err("Unexpected end of data.");
+    }
+
+    let mut bytes = [0; 8];
+    bytes[..len].copy_from_slice(&rest[..len]);
+    *data = &rest[len..];
+    Ok(u64::from_le_bytes(bytes))
+}
+
+pub fn read_bytes<'a>(data: &mut &'a [u8]) -> Result<&'a [u8], &'static str> {
+    let len = read_varint(data)?;
+    if (data.len() as u64) < len {
+        return Err("Unexpected end of data.");
+    }
+    let (bytes, rest) = data.split_at(len as usize);
+    *data = rest;
+    Ok(bytes)
+}
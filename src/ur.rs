@@ -0,0 +1,338 @@
+// Uniform Resources (Blockchain Commons' BCR-2020-005), which Keystone, Passport and the
+// coordinators that pair with them read account keys from: the key is CBOR, as the BC registry
+// lays it out, with a CRC-32 after it, spelled in 'bytewords' (two letters a byte) behind a
+// "ur:<type>/" prefix.  Two types are written here:
+//
+//   ur:crypto-hdkey/...    one account xpub with its origin (BCR-2020-007)
+//   ur:crypto-account/...  the master fingerprint and an output descriptor for each script type
+//                          (BCR-2020-015), for registering a whole wallet at once
+//
+// Only single-part URs are written; they're short enough for one QR code, which reads them best
+// in upper case (the QR alphanumeric mode), so that's how they come out.
+//
+// One type is read back, for a mnemonic scanned from a camera (see scan.rs):
+//
+//   ur:crypto-seed/...     a seed's bytes, with its birthday, name and note (BCR-2020-006), which
+//                          'ur:seed' is in the newer registry
+
+use account::Account;
+use bip32::{ExtendedPrivKey, KeyOrigin};
+use descriptor::ScriptType;
+use error::Error;
+use language::Language;
+use mnemonic::Mnemonic;
+use network::Network;
+use std::convert::TryFrom;
+use zeroize::Zeroizing;
+
+// The 256 bytewords; a UR keeps only the first and last letter of each:
+const BYTEWORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt", "away", "axis", "back", "bald",
+    "barn", "belt", "beta", "bias", "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash",
+    "cats", "chef", "city", "claw", "code", "cola", "cook", "cost", "crux", "curl", "cusp", "cyan",
+    "dark", "data", "days", "deli", "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam", "exit", "eyes", "fact", "fair",
+    "fern", "figs", "film", "fish", "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel",
+    "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow", "good", "gray", "grim", "guru",
+    "gush", "gyro", "half", "hang", "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into", "iris", "iron", "item", "jade",
+    "jazz", "join", "jolt", "jowl", "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept",
+    "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb", "lava", "lazy", "leaf", "legs",
+    "liar", "limp", "lion", "list", "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss", "monk", "nail", "navy", "need",
+    "news", "next", "noon", "note", "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls",
+    "paid", "part", "peck", "play", "plus", "poem", "pool", "pose", "puff", "puma", "purr", "quad",
+    "quiz", "race", "ramp", "real", "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot", "soap", "solo", "song", "stub",
+    "surf", "swan", "taco", "task", "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys",
+    "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user", "vast", "very", "veto", "vial",
+    "vibe", "view", "visa", "void", "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell", "yoga", "yurt", "zaps", "zero",
+    "zest", "zinc", "zone", "zoom",
+];
+
+// The registry's CBOR tags:
+const TAG_HDKEY: u64 = 303;
+const TAG_KEYPATH: u64 = 304;
+const TAG_COIN_INFO: u64 = 305;
+const TAG_SH: u64 = 400;
+const TAG_PKH: u64 = 403;
+const TAG_WPKH: u64 = 404;
+const TAG_TR: u64 = 409;
+
+// Just the CBOR the registry types need: map keys are always small integers.
+enum Cbor {
+    Unsigned(u64),
+    Bytes(Vec<u8>),
+    Array(Vec<Cbor>),
+    Map(Vec<(u64, Cbor)>),
+    Tag(u64, Box<Cbor>),
+    Bool(bool),
+}
+
+impl Cbor {
+    fn tagged(tag: u64, item: Cbor) -> Cbor {
+        Cbor::Tag(tag, Box::new(item))
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Cbor::Unsigned(value) => head(out, 0, *value),
+            Cbor::Bytes(bytes) => {
+                head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Cbor::Array(items) => {
+                head(out, 4, items.len() as u64);
+                for item in items {
+                    item.encode(out);
+                }
+            }
+            Cbor::Map(entries) => {
+                head(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    head(out, 0, *key);
+                    value.encode(out);
+                }
+            }
+            Cbor::Tag(tag, item) => {
+                head(out, 6, *tag);
+                item.encode(out);
+            }
+            Cbor::Bool(value) => out.push(if *value { 0xf5 } else { 0xf4 }),
+        }
+    }
+}
+
+// A major type and its argument, in the shortest form (as the registry's canonical CBOR wants):
+fn head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    if value < 24 {
+        out.push(major | value as u8);
+    } else if value <= u64::from(u8::MAX) {
+        out.extend_from_slice(&[major | 24, value as u8]);
+    } else if value <= u64::from(u16::MAX) {
+        out.push(major | 25);
+        out.extend_from_slice(&(value as u16).to_be_bytes());
+    } else if value <= u64::from(u32::MAX) {
+        out.push(major | 26);
+        out.extend_from_slice(&(value as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+// CRC-32 (the IEEE polynomial, as in zip and PNG), which bytewords append to catch misreads:
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+// "ur:<type>/<bytewords>", upper cased for a QR code:
+fn encode(ur_type: &str, item: &Cbor) -> String {
+    let mut payload = Vec::new();
+    item.encode(&mut payload);
+    let checksum = crc32(&payload);
+    payload.extend_from_slice(&checksum.to_be_bytes());
+
+    let mut ur = format!("ur:{}/", ur_type);
+    for byte in payload {
+        let word = BYTEWORDS[byte as usize].as_bytes();
+        ur.push(word[0] as char);
+        ur.push(word[3] as char);
+    }
+    ur.to_uppercase()
+}
+
+// Bytewords back to bytes, the CRC-32 checked and taken off:
+fn decode(body: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let letters = body.as_bytes();
+    if !letters.len().is_multiple_of(2) {
+        return Err(Error::Other("A UR's bytewords are two letters each."));
+    }
+    let mut bytes = Zeroizing::new(Vec::with_capacity(letters.len() / 2));
+    for pair in letters.chunks(2) {
+        let byte = BYTEWORDS
+            .iter()
+            .position(|word| word.as_bytes()[0] == pair[0] && word.as_bytes()[3] == pair[1])
+            .ok_or(Error::Other("A UR has a letter pair that isn't a byteword."))?;
+        bytes.push(byte as u8);
+    }
+    if bytes.len() < 4 {
+        return Err(Error::Other("A UR is too short for its checksum."));
+    }
+    let end = bytes.len() - 4;
+    let checksum = u32::from_be_bytes([bytes[end], bytes[end + 1], bytes[end + 2], bytes[end + 3]]);
+    bytes.truncate(end);
+    if crc32(&bytes) != checksum {
+        return Err(Error::Other("The UR's checksum doesn't match; it was misread."));
+    }
+    Ok(bytes)
+}
+
+// Just enough of a CBOR reader to find one map entry: each item's major type and argument, and
+// whole items skipped over.  'None' is CBOR that's cut short or isn't the shortest form.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if count > self.bytes.len() {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Some(taken)
+    }
+
+    fn head(&mut self) -> Option<(u8, u64)> {
+        let initial = self.take(1)?[0];
+        let size = match initial & 0x1f {
+            value @ 0..=23 => return Some((initial >> 5, u64::from(value))),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return None,
+        };
+        let value = self
+            .take(size)?
+            .iter()
+            .fold(0, |value, byte| value << 8 | u64::from(*byte));
+        Some((initial >> 5, value))
+    }
+
+    fn skip(&mut self) -> Option<()> {
+        let (major, value) = self.head()?;
+        match major {
+            2 | 3 => {
+                self.take(usize::try_from(value).ok()?)?;
+            }
+            4 => {
+                for _ in 0..value {
+                    self.skip()?;
+                }
+            }
+            5 => {
+                for _ in 0..value {
+                    self.skip()?;
+                    self.skip()?;
+                }
+            }
+            6 => self.skip()?,
+            _ => {}
+        }
+        Some(())
+    }
+}
+
+// A crypto-seed's bytes, its map's key 1:
+fn seed_bytes(cbor: &[u8]) -> Option<&[u8]> {
+    let mut reader = Reader { bytes: cbor };
+    let (major, entries) = reader.head()?;
+    if major != 5 {
+        return None;
+    }
+    for _ in 0..entries {
+        if reader.head()? == (0, 1) {
+            return match reader.head()? {
+                (2, length) => reader.take(usize::try_from(length).ok()?),
+                _ => None,
+            };
+        }
+        reader.skip()?;
+    }
+    None
+}
+
+fn fingerprint(bytes: [u8; 4]) -> Cbor {
+    Cbor::Unsigned(u64::from(u32::from_be_bytes(bytes)))
+}
+
+// crypto-keypath: each step as its index and whether it's hardened, then the master fingerprint.
+fn keypath(origin: &KeyOrigin) -> Cbor {
+    let mut components = Vec::new();
+    for child in origin.path.children() {
+        components.push(Cbor::Unsigned(u64::from(child.index())));
+        components.push(Cbor::Bool(child.is_hardened()));
+    }
+    Cbor::tagged(
+        TAG_KEYPATH,
+        Cbor::Map(vec![
+            (1, Cbor::Array(components)),
+            (2, fingerprint(origin.fingerprint)),
+        ]),
+    )
+}
+
+// crypto-hdkey for an account xpub.  The coin info is left out on mainnet, which is what a
+// reader assumes without it.
+fn hdkey(account: &Account) -> Result<Cbor, &'static str> {
+    let mut entries = vec![
+        (3, Cbor::Bytes(account.xpub.public_key().to_vec())),
+        (4, Cbor::Bytes(account.xpub.chain_code.to_vec())),
+    ];
+    match account.xpub.network {
+        Network::Mainnet => {}
+        Network::Testnet | Network::Signet | Network::Regtest => {
+            let coin_info = Cbor::Map(vec![(1, Cbor::Unsigned(0)), (2, Cbor::Unsigned(1))]);
+            entries.push((5, Cbor::tagged(TAG_COIN_INFO, coin_info)));
+        }
+        _ => return Err("UR exports are for Bitcoin keys only."),
+    }
+    entries.push((6, keypath(&account.origin)));
+    entries.push((8, fingerprint(account.xpub.parent_fingerprint)));
+    Ok(Cbor::Map(entries))
+}
+
+// The output descriptor, as nested script expression tags around the key:
+fn output(account: &Account) -> Result<Cbor, &'static str> {
+    let key = Cbor::tagged(TAG_HDKEY, hdkey(account)?);
+    Ok(match account.script_type {
+        ScriptType::P2pkh => Cbor::tagged(TAG_PKH, key),
+        ScriptType::P2shP2wpkh => Cbor::tagged(TAG_SH, Cbor::tagged(TAG_WPKH, key)),
+        ScriptType::P2wpkh => Cbor::tagged(TAG_WPKH, key),
+        ScriptType::P2tr => Cbor::tagged(TAG_TR, key),
+    })
+}
+
+pub fn crypto_hdkey(account: &Account) -> Result<String, &'static str> {
+    Ok(encode("crypto-hdkey", &hdkey(account)?))
+}
+
+// Account 'index' of every script type, as one crypto-account:
+pub fn crypto_account(root: &ExtendedPrivKey, index: u32) -> Result<String, &'static str> {
+    let mut outputs = Vec::new();
+    for script_type in ScriptType::all().iter() {
+        outputs.push(output(&Account::derive(root, *script_type, index)?)?);
+    }
+    let account = Cbor::Map(vec![
+        (1, fingerprint(root.fingerprint())),
+        (2, Cbor::Array(outputs)),
+    ]);
+    Ok(encode("crypto-account", &account))
+}
+
+// A scanned crypto-seed (or seed) UR's mnemonic.  The seed's bytes are its entropy, as seedtool
+// and the wallets that show one as words take them:
+pub fn read_seed(ur: &str, language: Language) -> Result<Mnemonic, Error> {
+    let ur = Zeroizing::new(ur.trim().to_lowercase());
+    let body = ur
+        .strip_prefix("ur:crypto-seed/")
+        .or_else(|| ur.strip_prefix("ur:seed/"))
+        .ok_or(Error::Other("That UR isn't a crypto-seed."))?;
+    if body.contains('/') {
+        return Err(Error::Other("Only single-part URs are read."));
+    }
+    let cbor = decode(body)?;
+    let entropy = seed_bytes(&cbor).ok_or(Error::Other("That crypto-seed has no seed in it."))?;
+    Mnemonic::from_entropy_bytes(entropy, language)
+}
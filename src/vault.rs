@@ -0,0 +1,135 @@
+// A vault file of our own: the mnemonic encrypted with a passphrase, for when neither age nor a
+// keyring is at hand.  The key is derived with Argon2id and the mnemonic sealed with
+// ChaCha20-Poly1305, behind a header that says how, so a vault written today still opens after
+// the defaults change:
+//
+//   magic      "BIP39VLT"
+//   version    1 byte (1)
+//   memory     4 bytes, little endian, in KiB   \
+//   iterations 4 bytes, little endian            | Argon2id's parameters
+//   lanes      4 bytes, little endian           /
+//   salt       16 bytes
+//   nonce      12 bytes
+//   ciphertext the mnemonic, with Poly1305's 16 byte tag after it
+//
+// The whole header is the cipher's associated data, so it can't be altered (say, to weaker
+// parameters) without the vault failing to open.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use argon2;
+use entropy;
+// (so library users can pick their own)
+pub use argon2::Params;
+
+const MAGIC: &[u8; 8] = b"BIP39VLT";
+const VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+// RFC 9106's second recommended setting, for machines without 2 GiB to spare: 64 MiB, three
+// passes, four lanes.
+pub const DEFAULT_MEMORY: u32 = 64 * 1024;
+pub const DEFAULT_ITERATIONS: u32 = 3;
+pub const DEFAULT_LANES: u32 = 4;
+
+// The most a vault may ask for, so a doctored header can't exhaust the machine opening it:
+const MAX_MEMORY: u32 = 4 * 1024 * 1024;
+const MAX_LANES: u32 = 64;
+
+pub fn create(mnemonic: &str, passphrase: &str) -> Result<Vec<u8>, &'static str> {
+    create_with(
+        mnemonic,
+        passphrase,
+        &Params {
+            memory: DEFAULT_MEMORY,
+            iterations: DEFAULT_ITERATIONS,
+            lanes: DEFAULT_LANES,
+        },
+    )
+}
+
+pub fn create_with(
+    mnemonic: &str,
+    passphrase: &str,
+    params: &Params,
+) -> Result<Vec<u8>, &'static str> {
+    check(params)?;
+    let mut vault = Vec::with_capacity(HEADER_LEN + mnemonic.len() + TAG_LEN);
+    vault.extend(MAGIC);
+    vault.push(VERSION);
+    for value in [params.memory, params.iterations, params.lanes].iter() {
+        vault.extend(&value.to_le_bytes());
+    }
+    let salt = entropy::random::<SALT_LEN>();
+    let nonce = entropy::random::<NONCE_LEN>();
+    vault.extend(&salt);
+    vault.extend(&nonce);
+
+    let cipher = cipher(passphrase, &salt, params)?;
+    let payload = Payload {
+        msg: mnemonic.as_bytes(),
+        aad: &vault,
+    };
+    let ciphertext = cipher
+        .encrypt(&Nonce::from(nonce), payload)
+        .map_err(|_| "Unable to encrypt the vault.")?;
+    vault.extend(ciphertext);
+    Ok(vault)
+}
+
+pub fn open(vault: &[u8], passphrase: &str) -> Result<String, &'static str> {
+    if vault.len() < HEADER_LEN + TAG_LEN || &vault[..MAGIC.len()] != MAGIC {
+        return Err("Not a vault file.");
+    }
+    if vault[MAGIC.len()] != VERSION {
+        return Err("Unsupported vault file version.");
+    }
+
+    let (header, ciphertext) = vault.split_at(HEADER_LEN);
+    let field = |i: usize| {
+        let start = MAGIC.len() + 1 + 4 * i;
+        let mut le = [0; 4];
+        le.copy_from_slice(&header[start..start + 4]);
+        u32::from_le_bytes(le)
+    };
+    let params = Params {
+        memory: field(0),
+        iterations: field(1),
+        lanes: field(2),
+    };
+    check(&params)?;
+    let salt = &header[HEADER_LEN - NONCE_LEN - SALT_LEN..HEADER_LEN - NONCE_LEN];
+    let mut nonce = [0; NONCE_LEN];
+    nonce.copy_from_slice(&header[HEADER_LEN - NONCE_LEN..]);
+
+    let payload = Payload {
+        msg: ciphertext,
+        aad: header,
+    };
+    let plaintext = cipher(passphrase, salt, &params)?
+        .decrypt(&Nonce::from(nonce), payload)
+        .map_err(|_| "Wrong passphrase, or the vault file is corrupt.")?;
+    String::from_utf8(plaintext).map_err(|_| "The decrypted vault is not a mnemonic.")
+}
+
+fn check(params: &Params) -> Result<(), &'static str> {
+    if params.memory > MAX_MEMORY || params.lanes > MAX_LANES {
+        return Err("The vault's Argon2 parameters ask for more than 4 GiB or 64 lanes.");
+    }
+    Ok(())
+}
+
+fn cipher(
+    passphrase: &str,
+    salt: &[u8],
+    params: &Params,
+) -> Result<ChaCha20Poly1305, &'static str> {
+    let mut key = [0; 32];
+    argon2::argon2id(passphrase.as_bytes(), salt, &[], &[], params, &mut key)?;
+    Ok(ChaCha20Poly1305::new(&Key::from(key)))
+}
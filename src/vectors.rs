@@ -0,0 +1,60 @@
+// Test vectors of our own, for other wallets' test suites: for each entropy, its mnemonic in every
+// wordlist this build embeds (the spec only publishes English ones), the seed under a passphrase,
+// and the BIP-32 root key.  Fixtures from an independent implementation catch the bugs that
+// checking code against its own output never will, such as a Japanese phrase joined with the
+// wrong space or a Korean one hashed before its letters are decomposed.
+//
+// The vectors come out as one versioned JSON document, the entropy and seed in hex:
+//
+//   {"passphrase": "TREZOR", "vectors": [{"entropy": "7f7f...", "language": "english",
+//     "mnemonic": "legal winner ...", "seed": "2e89...", "root_key": "xprv9s21..."}, ...],
+//    "schema_version": 1}
+
+use serde_json::Value;
+
+use language::Language;
+use mnemonic::Mnemonic;
+use network::Network;
+use schema;
+use Seed;
+
+pub struct Vector {
+    pub entropy: Vec<u8>,
+    pub language: Language,
+    pub mnemonic: String,
+    pub seed: String,
+    pub root_key: String,
+}
+
+// One vector per embedded language, in 'Language::all()' order:
+pub fn generate(entropy: &[u8], passphrase: &str) -> Result<Vec<Vector>, &'static str> {
+    let mut vectors = Vec::new();
+    for language in Language::available() {
+        let mnemonic = Mnemonic::from_entropy_bytes(entropy, language)?;
+        let seed = Seed::new(&mnemonic, passphrase);
+        vectors.push(Vector {
+            entropy: entropy.to_vec(),
+            language,
+            mnemonic: mnemonic.phrase(),
+            seed: seed.to_string(),
+            root_key: seed.to_root_key(Network::Mainnet)?.to_string(),
+        });
+    }
+    Ok(vectors)
+}
+
+pub fn to_json(vectors: &[Vector], passphrase: &str) -> Value {
+    let vectors: Vec<Value> = vectors
+        .iter()
+        .map(|vector| {
+            json!({
+                "entropy": hex::encode(&vector.entropy),
+                "language": vector.language.to_string(),
+                "mnemonic": vector.mnemonic,
+                "seed": vector.seed,
+                "root_key": vector.root_key,
+            })
+        })
+        .collect();
+    schema::versioned(json!({ "passphrase": passphrase, "vectors": vectors }))
+}
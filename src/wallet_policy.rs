@@ -0,0 +1,71 @@
+// BIP-388 wallet policies, which newer hardware signers (Ledger's, for one) register wallets
+// with: a descriptor template with each key replaced by a placeholder, "@0", "@1", ..., and the
+// keys themselves in a separate key information vector.  The template is what the device shows
+// for approval, and what it checks every later transaction against.
+//
+// Every placeholder here is followed by "/**", shorthand for both chains ("/<0;1>/*"), and the
+// keys are account keys with their origins, as the BIP asks.
+
+use serde_json::Value;
+
+use account::Account;
+use multisig::Multisig;
+use schema;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalletPolicy {
+    // What the device shows the wallet as once it's registered:
+    pub name: String,
+    // e.g. "wsh(sortedmulti(2,@0/**,@1/**,@2/**))":
+    pub template: String,
+    // e.g. "[73c5da0a/48'/0'/0'/2']xpub...", one for each placeholder, in order:
+    pub keys: Vec<String>,
+}
+
+impl WalletPolicy {
+    // A single-sig account: "wpkh(@0/**)" and the like.
+    pub fn single_sig(name: &str, account: &Account) -> WalletPolicy {
+        WalletPolicy {
+            name: name.to_string(),
+            template: account.script_type.wrap("@0/**"),
+            keys: vec![format!("{}{}", account.origin, account.xpub)],
+        }
+    }
+
+    pub fn multisig(name: &str, wallet: &Multisig) -> WalletPolicy {
+        let placeholders: Vec<String> = (0..wallet.cosigners.len())
+            .map(|i| format!("@{}/**", i))
+            .collect();
+        WalletPolicy {
+            name: name.to_string(),
+            template: format!(
+                "wsh(sortedmulti({},{}))",
+                wallet.threshold,
+                placeholders.join(",")
+            ),
+            keys: wallet
+                .cosigners
+                .iter()
+                .map(|cosigner| format!("{}{}", cosigner.origin, cosigner.xpub))
+                .collect(),
+        }
+    }
+
+    // The descriptor the template and keys stand for, to check a policy against a wallet's own
+    // (without a checksum):
+    pub fn descriptor(&self) -> String {
+        let mut descriptor = self.template.clone();
+        for (i, key) in self.keys.iter().enumerate() {
+            descriptor = descriptor.replace(&format!("@{}/**", i), &format!("{}/<0;1>/*", key));
+        }
+        descriptor
+    }
+
+    pub fn to_json(&self) -> Value {
+        schema::versioned(json!({
+            "name": self.name,
+            "descriptor_template": self.template,
+            "keys_info": self.keys,
+        }))
+    }
+}
@@ -0,0 +1,43 @@
+// JavaScript bindings, for a web wallet or recovery tool that wants this crate's mnemonics and
+// seeds as they are rather than a second implementation of them.  Built for wasm32-unknown-unknown
+// with the 'wasm-bindgen' feature, and without the default 'cli' (the library has no terminal or
+// standard input to speak of there):
+//
+//   wasm-pack build --target web --no-default-features --features wasm-bindgen
+//
+//   import init, { generate_mnemonic, validate, mnemonic_to_seed } from "./pkg/bip_39.js";
+//   await init();
+//   const phrase = generate_mnemonic(24, "english");
+//   validate(phrase);
+//   const seed = mnemonic_to_seed(phrase, "");
+//
+// New entropy comes from crypto.getRandomValues.  Errors are thrown as JavaScript Errors with the
+// crate's messages ("Invalid checksum." and the like), so 'validate' is a try/catch.
+
+use wasm_bindgen::prelude::*;
+
+use language::Language;
+use {Config, MnemonicListSize};
+
+// 12, 15, 18, 21 or 24 words, in "english", "japanese" and so on ('Language::from_str'):
+#[wasm_bindgen]
+pub fn generate_mnemonic(words: usize, language: &str) -> Result<String, JsError> {
+    let language = language.parse::<Language>().map_err(JsError::new)?;
+    let config = Config::generate_in(language, MnemonicListSize::from(words)?, "");
+    Ok(config.mnemonic.to_string())
+}
+
+// Throws the reason a phrase isn't a valid mnemonic (wrong word count, unknown word, checksum),
+// in any embedded wordlist:
+#[wasm_bindgen]
+pub fn validate(phrase: &str) -> Result<(), JsError> {
+    Config::from_phrase(phrase, "")?;
+    Ok(())
+}
+
+// The 64-byte BIP-39 seed, as a Uint8Array:
+#[wasm_bindgen]
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<Vec<u8>, JsError> {
+    let config = Config::from_phrase(phrase, passphrase)?;
+    Ok(config.to_seed().as_bytes().to_vec())
+}
@@ -0,0 +1,107 @@
+// A wallet built from an account xpub alone.  It has nowhere to keep a seed or private key, so
+// anything handed one of these (address lists, descriptor and wallet file exports, checking
+// addresses and signed messages) works the same on an online machine as it would with the
+// mnemonic, and can't sign or leak what it never had.
+
+use std::ops::Range;
+
+use account::Account;
+use address::{self, AddressMatch, AddressRecord};
+use bip32::{DerivationPath, ExtendedPubKey, KeyOrigin};
+use descriptor::ScriptType;
+use export::sparrow;
+use message;
+
+pub struct WatchOnlyWallet {
+    account: Account,
+}
+
+impl WatchOnlyWallet {
+    // Without the key's origin (from an export, "[fingerprint/84h/0h/0h]"), descriptors name the
+    // xpub as its own origin, which wallets accept but can't match to a hardware device's master.
+    pub fn new(
+        xpub: ExtendedPubKey,
+        script_type: ScriptType,
+        origin: Option<KeyOrigin>,
+    ) -> WatchOnlyWallet {
+        let origin = origin
+            .unwrap_or_else(|| KeyOrigin::new(xpub.fingerprint(), DerivationPath::from(vec![])));
+        WatchOnlyWallet {
+            account: Account {
+                script_type,
+                origin,
+                xpub,
+            },
+        }
+    }
+
+    // An "xprv..." doesn't parse here, on purpose:
+    pub fn import(
+        xpub: &str,
+        script_type: ScriptType,
+        origin: Option<&str>,
+    ) -> Result<WatchOnlyWallet, &'static str> {
+        let origin = match origin {
+            Some(origin) => Some(origin.parse()?),
+            None => None,
+        };
+        Ok(WatchOnlyWallet::new(xpub.parse()?, script_type, origin))
+    }
+
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    pub fn address(&self, change: bool, index: u32) -> Result<String, &'static str> {
+        let key = if change {
+            self.account.change_key(index)?
+        } else {
+            self.account.receive_key(index)?
+        };
+        address::for_script_type(self.account.script_type, &key.public_key(), key.network)
+    }
+
+    pub fn addresses(
+        &self,
+        change: bool,
+        range: Range<u32>,
+    ) -> Result<Vec<AddressRecord>, &'static str> {
+        address::derive_addresses(&self.account, change, range)
+    }
+
+    pub fn descriptor(&self, change: bool) -> String {
+        self.account.descriptor(change)
+    }
+
+    pub fn multipath_descriptor(&self) -> String {
+        self.account.multipath_descriptor()
+    }
+
+    pub fn sparrow_wallet(&self) -> String {
+        sparrow::account_wallet(&self.account)
+    }
+
+    pub fn contains_address(
+        &self,
+        address: &str,
+        gap_limit: u32,
+    ) -> Result<Option<AddressMatch>, &'static str> {
+        Ok(self.account.xpub.contains_address(address, gap_limit)?)
+    }
+
+    pub fn verify_message(
+        &self,
+        address: &str,
+        message: &[u8],
+        signature: &str,
+    ) -> Result<bool, &'static str> {
+        message::verify(address, message, signature)
+    }
+}
+
+// An account is only ever public keys, so it can be watched as it is:
+impl From<Account> for WatchOnlyWallet {
+    fn from(account: Account) -> WatchOnlyWallet {
+        WatchOnlyWallet { account }
+    }
+}
@@ -0,0 +1,71 @@
+// Mnemonics that pass every BIP-39 check but that anyone could have: the spec's test vectors
+// (starting with the famous "abandon abandon ... about"), the defaults development tools ship
+// with, and phrases made up by hand rather than from random entropy, such as one word over and
+// over or a run straight down the wordlist.  Bots sweep the funds sent to all of these within
+// seconds, so they're worth refusing before anyone relies on one.
+//
+// Known phrases are matched by their entropy, so the same phrase in another wordlist's words is
+// caught too.  The last word is left out of the pattern checks, since it carries the checksum
+// and so is whatever word it has to be ("test test ... junk").
+
+use std::fmt;
+
+use mnemonic::Mnemonic;
+use test_vectors;
+use word_index;
+
+// The default mnemonics of Ganache (and Truffle before it), whose keys are forever in use on
+// test networks by everyone:
+const DEVELOPMENT_ENTROPY: [&str; 2] = [
+    // "candy maple cake sugar pudding cream honey rich smooth crumble sweet treat"
+    "2150f0816c6ad265db4dcacce69b6ef3",
+    // "myth like bonus scare over problem client lizard pioneer submit female collect"
+    "92903465e029df56cab416a53b015396",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weakness {
+    // One of the BIP-39 test vectors, published with their seeds and keys:
+    TestVector,
+    // A development tool's default:
+    DevelopmentDefault,
+    // The same word over and over:
+    RepeatedWord,
+    // Consecutive words of the wordlist, forwards or backwards:
+    Sequential,
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Weakness::TestVector => "It's one of the published BIP-39 test vectors.",
+            Weakness::DevelopmentDefault => "It's a development tool's default mnemonic.",
+            Weakness::RepeatedWord => "It's the same word over and over.",
+            Weakness::Sequential => "It's a run of consecutive words from the wordlist.",
+        })
+    }
+}
+
+pub fn check(mnemonic: &Mnemonic) -> Option<Weakness> {
+    let entropy = hex::encode(mnemonic.entropy());
+    if test_vectors::BIP39.iter().any(|&(vector, _, _, _)| vector == entropy) {
+        return Some(Weakness::TestVector);
+    }
+    if DEVELOPMENT_ENTROPY.contains(&entropy.as_str()) {
+        return Some(Weakness::DevelopmentDefault);
+    }
+
+    let words = mnemonic.words();
+    let indices: Vec<i64> = words[..words.len() - 1]
+        .iter()
+        .map(|word| word_index(word, mnemonic.language()).unwrap() as i64)
+        .collect();
+    let steps: Vec<i64> = indices.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    if steps.iter().all(|&step| step == 0) {
+        return Some(Weakness::RepeatedWord);
+    }
+    if steps.iter().all(|&step| step == 1) || steps.iter().all(|&step| step == -1) {
+        return Some(Weakness::Sequential);
+    }
+    None
+}
@@ -0,0 +1,147 @@
+// A language's 2,048 words with the lookups generation, validation and recovery need: the word an
+// index (11 bits of a mnemonic) stands for, the index a word stands for, and the words starting
+// with some letters, for autocomplete and abbreviated backups.  The lists are in BIP-39's order,
+// which isn't the words' byte order for most of them (the Chinese lists aren't sorted at all), so
+// each comes with its indices sorted by word for binary searches.  It's built the first time a
+// language is looked up in, and kept:
+//
+//   let words = Wordlist::of(Language::English);
+//   assert_eq!(words.index_of("zoo"), Some(2047));
+//   assert_eq!(words.words_starting_with("zo"), ["zone", "zoo"]);
+//
+// A list of one's own (a community's, or a new language's draft) is read with 'from_lines', one
+// word per line, and used as 'Language::Custom(list)' wherever a language goes.  It's held to what
+// the spec asks of the lists it publishes: 2,048 different words, told apart by their first four
+// letters.  Its words can be no longer than the longest built in one, since that's what phrases'
+// fixed size buffers ('MAX_PHRASE_LEN') are made to hold.
+
+use alloc::collections::BTreeSet;
+use core::convert::TryFrom;
+use core::fmt;
+
+use once_cell::race::OnceBox;
+
+use alloc_prelude::*;
+use language::Language;
+use nfkd;
+use wordlists::MAX_WORD_LEN;
+
+pub struct Wordlist {
+    words: &'static [&'static str; 2048],
+    // The indices of 'words', in the words' byte order:
+    sorted: Vec<u16>,
+}
+
+impl Wordlist {
+    // Panics, as 'Language::word_list' does, for a wordlist the 'langs-*' features left out.
+    pub fn of(language: Language) -> &'static Wordlist {
+        static BUILT: [OnceBox<Wordlist>; 10] = [const { OnceBox::new() }; 10];
+
+        if let Language::Custom(list) = language {
+            return list;
+        }
+
+        let slot = Language::all()
+            .iter()
+            .position(|&other| other == language)
+            .unwrap();
+        BUILT[slot].get_or_init(|| Box::new(Wordlist::new(language.word_list())))
+    }
+
+    // Blank lines and the spaces around words are skipped, and the words are put in NFKD, as the
+    // embedded lists are.  Like those, the list is kept for the life of the program, so its words
+    // can be handed out as '&'static str' too.
+    pub fn from_lines(text: &str) -> Result<&'static Wordlist, &'static str> {
+        let words: Vec<String> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| nfkd(line).into_owned())
+            .collect();
+        if words.len() != 2048 {
+            return Err("A wordlist must have 2,048 words.");
+        }
+        if words.iter().any(|word| word.contains(char::is_whitespace)) {
+            return Err("A wordlist's words can't have spaces in them.");
+        }
+        if words.iter().any(|word| word.len() > MAX_WORD_LEN) {
+            return Err("A wordlist's words can't be longer than the built in lists' longest.");
+        }
+        let mut seen = BTreeSet::new();
+        if !words.iter().all(|word| seen.insert(word.as_str())) {
+            return Err("A wordlist's words must all be different.");
+        }
+        let mut seen = BTreeSet::new();
+        if !words
+            .iter()
+            .all(|word| seen.insert(word.chars().take(4).collect::<String>()))
+        {
+            return Err("A wordlist's words must differ in their first four letters.");
+        }
+
+        let text: &'static str = Box::leak(words.join("\n").into_boxed_str());
+        let words = <[&str; 2048]>::try_from(text.lines().collect::<Vec<_>>()).unwrap();
+        Ok(Box::leak(Box::new(Wordlist::new(Box::leak(Box::new(
+            words,
+        ))))))
+    }
+
+    fn new(words: &'static [&'static str; 2048]) -> Wordlist {
+        let mut sorted: Vec<u16> = (0..2048).collect();
+        sorted.sort_unstable_by_key(|&index| words[index as usize]);
+        Wordlist { words, sorted }
+    }
+
+    pub fn words(&self) -> &'static [&'static str; 2048] {
+        self.words
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'static str> {
+        self.words.get(index).cloned()
+    }
+
+    // The word's 0-based index, as written (NFKD, as the lists are):
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.sorted
+            .binary_search_by(|&index| self.words[index as usize].cmp(word))
+            .ok()
+            .map(|found| self.sorted[found] as usize)
+    }
+
+    // In the list's order.  The words starting with 'prefix' are the ones sorted from it on,
+    // up to the first that doesn't:
+    pub fn words_starting_with(&self, prefix: &str) -> Vec<&'static str> {
+        let start = self
+            .sorted
+            .partition_point(|&index| self.words[index as usize] < prefix);
+        let mut found: Vec<u16> = self.sorted[start..]
+            .iter()
+            .cloned()
+            .take_while(|&index| self.words[index as usize].starts_with(prefix))
+            .collect();
+        found.sort_unstable();
+        found
+            .into_iter()
+            .map(|index| self.words[index as usize])
+            .collect()
+    }
+}
+
+// Two lists are the same list if they have the same words in the same order:
+impl PartialEq for Wordlist {
+    fn eq(&self, other: &Wordlist) -> bool {
+        self.words == other.words
+    }
+}
+
+impl Eq for Wordlist {}
+
+// (all 2,048 words would be a lot for a log line)
+impl fmt::Debug for Wordlist {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Wordlist")
+            .field("first", &self.words[0])
+            .field("last", &self.words[2047])
+            .finish()
+    }
+}